@@ -0,0 +1,81 @@
+// Throughput benchmarks {{{1
+//
+// Run with `cargo bench`. Results are written to target/criterion.
+//
+// The ships come from tests/fixtures so the benchmarks stay small
+// enough to run in CI.
+
+use sharpie::{Ship, ReportOptions};
+
+use criterion::{criterion_group, criterion_main, Criterion, BatchSize};
+use std::hint::black_box;
+
+const FIXTURES: &str = "tests/fixtures";
+
+const SHIPS: [&str; 5] = [
+    "central_battery_1870",
+    "protected_cruiser_1895",
+    "dreadnought_1915",
+    "destroyer_1935",
+    "aa_cruiser_1942",
+];
+
+// Number of ships in the batch benchmark.
+const BATCH: usize = 1000;
+
+fn load(name: &str) -> Ship {
+    Ship::load(format!("{}/{}.ship", FIXTURES, name)).unwrap()
+}
+
+// report {{{2
+fn report(c: &mut Criterion) {
+    let mut group = c.benchmark_group("report");
+
+    for name in SHIPS {
+        let ship = load(name);
+        group.bench_function(name, |b| b.iter(|| black_box(&ship).report()));
+    }
+
+    group.finish();
+}
+
+// convert {{{2
+fn convert(c: &mut Criterion) {
+    let p = format!("{}/dreadnought_1915.sship", FIXTURES);
+
+    c.bench_function("convert", |b| b.iter(|| Ship::convert(black_box(p.clone())).unwrap()));
+}
+
+// batch {{{2
+fn batch(c: &mut Criterion) {
+    let ships: Vec<Ship> = SHIPS.iter().map(|n| load(n)).collect();
+
+    c.bench_function("batch_1000", |b| b.iter_batched(
+        || ships.iter().cycle().take(BATCH).cloned().collect::<Vec<Ship>>(),
+        |batch| batch.iter().map(|s| s.report().len()).sum::<usize>(),
+        BatchSize::LargeInput,
+    ));
+}
+
+// batch_write {{{2
+// Same as batch but streams every report into one reused buffer.
+fn batch_write(c: &mut Criterion) {
+    let ships: Vec<Ship> = SHIPS.iter().map(|n| load(n)).collect();
+    let options = ReportOptions::default();
+
+    c.bench_function("batch_1000_write", |b| b.iter_batched(
+        || ships.iter().cycle().take(BATCH).cloned().collect::<Vec<Ship>>(),
+        |batch| {
+            let mut buf = String::new();
+            batch.iter().map(|s| {
+                buf.clear();
+                s.write_report(&mut buf, &options).unwrap();
+                buf.len()
+            }).sum::<usize>()
+        },
+        BatchSize::LargeInput,
+    ));
+}
+
+criterion_group!(benches, report, convert, batch, batch_write);
+criterion_main!(benches);
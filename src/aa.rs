@@ -0,0 +1,150 @@
+use crate::{Battery, GunType, MountType};
+
+impl Battery { // {{{1
+    /// Least elevation (degrees) at which guns can engage aircraft.
+    pub const AA_ELEVATION: f64 = 45.0;
+    /// Greatest height (ft) a shell from a 1" gun of 45 calibres reaches
+    /// fired straight up. Larger and longer guns reach higher.
+    const CEILING_BASE: f64 = 14000.0;
+    /// Share of the greatest height at which fuzes and fire control still
+    /// let the guns hit.
+    const CEILING_SHARE: f64 = 2.0 / 3.0;
+
+    // is_high_angle {{{2
+    /// True if the guns are built to fire at high angles.
+    ///
+    pub fn is_high_angle(&self) -> bool {
+        matches!(self.kind, GunType::AntiAir | GunType::DualPurpose | GunType::RapidFire | GunType::MachineGun)
+    }
+
+    // elevation_max {{{2
+    /// Greatest elevation of the guns (degrees), limited by the mount and
+    /// by the era for guns not built to fire at high angles.
+    ///
+    /// Hull mounts fire through ports that limit any gun to 45 degrees, and
+    /// surface guns to 15. Surface guns elsewhere rise from 15 degrees
+    /// before 1905 to 40 degrees from 1930. High angle guns reach 85
+    /// degrees on open mounts, but in enclosed turrets only 70 degrees
+    /// before 1930, or 1935 for dual-purpose guns.
+    ///
+    pub fn elevation_max(&self) -> f64 {
+        let year = self.year;
+
+        match (&self.mount_kind, self.is_high_angle()) {
+            (MountType::Broadside | MountType::Casemate | MountType::ColesTurret, true)  => 45.0,
+            (MountType::Broadside | MountType::Casemate | MountType::ColesTurret, false) => 15.0,
+
+            (_, false) =>
+                     if year < 1905 { 15.0 }
+                else if year < 1920 { 20.0 }
+                else if year < 1930 { 30.0 }
+                else                { 40.0 },
+
+            (MountType::ClosedBarbette, true) => {
+                let modern = if self.kind == GunType::DualPurpose { 1935 } else { 1930 };
+                if year < modern { 70.0 } else { 85.0 }
+            },
+
+            (_, true) => 85.0,
+        }
+    }
+
+    // is_anti_aircraft {{{2
+    /// True if the guns can engage aircraft.
+    ///
+    pub fn is_anti_aircraft(&self) -> bool {
+        self.num > 0 && self.elevation_max() >= Self::AA_ELEVATION
+    }
+
+    // aa_ceiling {{{2
+    /// Greatest height (ft) at which the guns can usefully engage aircraft,
+    /// or zero if they cannot.
+    ///
+    /// The height a shell reaches grows with the calibre and length of the
+    /// gun, and with the square of the sine of the elevation.
+    ///
+    pub fn aa_ceiling(&self) -> f64 {
+        if !self.is_anti_aircraft() { return 0.0; }
+
+        let height = Self::CEILING_BASE * self.diam.powf(0.6) * (self.len / 45.0).sqrt();
+
+        height * self.elevation_max().to_radians().sin().powi(2) * Self::CEILING_SHARE
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod aa {
+    use super::*;
+    use crate::test_support::*;
+
+    fn battery(kind: GunType, mount_kind: MountType, year: u32, diam: f64, len: f64) -> Battery {
+        let mut b = Battery::default();
+        (b.num, b.kind, b.mount_kind, b.year, b.diam, b.len) = (4, kind, mount_kind, year, diam, len);
+
+        b
+    }
+
+    // Test elevation_max {{{2
+    macro_rules! test_elevation_max {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, kind, mount_kind, year) = $value;
+
+                    assert_eq!(expected, battery(kind, mount_kind, year, 5.0, 38.0).elevation_max());
+                }
+            )*
+        }
+    }
+
+    test_elevation_max! {
+        // name:                (degrees, kind, mount, year)
+        elevation_casemate:     (15.0, GunType::QuickFiring, MountType::Casemate, 1935),
+        elevation_casemate_aa:  (45.0, GunType::AntiAir, MountType::Casemate, 1935),
+        elevation_turret_1900:  (15.0, GunType::BreechLoading, MountType::ClosedBarbette, 1900),
+        elevation_turret_1912:  (20.0, GunType::BreechLoading, MountType::ClosedBarbette, 1912),
+        elevation_turret_1925:  (30.0, GunType::BreechLoading, MountType::ClosedBarbette, 1925),
+        elevation_turret_1940:  (40.0, GunType::BreechLoading, MountType::ClosedBarbette, 1940),
+        elevation_dp_1930:      (70.0, GunType::DualPurpose, MountType::ClosedBarbette, 1930),
+        elevation_dp_1935:      (85.0, GunType::DualPurpose, MountType::ClosedBarbette, 1935),
+        elevation_aa_1930:      (85.0, GunType::AntiAir, MountType::ClosedBarbette, 1930),
+        elevation_aa_deck:      (85.0, GunType::AntiAir, MountType::Deck, 1920),
+        elevation_mg:           (85.0, GunType::MachineGun, MountType::DeckAndHoist, 1915),
+    }
+
+    // Test aa_ceiling {{{2
+    macro_rules! test_aa_ceiling {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, kind, mount_kind, diam, len) = $value;
+
+                    assert_eq!(expected, to_place(battery(kind, mount_kind, 1940, diam, len).aa_ceiling(), 0));
+                }
+            )*
+        }
+    }
+
+    test_aa_ceiling! {
+        // name:                (ceiling, kind, mount, diam, len)
+        aa_ceiling_5in:         (22356.0, GunType::DualPurpose, MountType::ClosedBarbette, 5.0, 38.0),
+        aa_ceiling_40mm:        (13544.0, GunType::AntiAir, MountType::Deck, 1.57, 56.0),
+        aa_ceiling_surface:     (0.0, GunType::BreechLoading, MountType::ClosedBarbette, 15.0, 42.0),
+        aa_ceiling_casemate:    (11264.0, GunType::DualPurpose, MountType::Casemate, 5.0, 38.0),
+    }
+
+    // Test is_anti_aircraft {{{2
+    #[test]
+    fn is_anti_aircraft() {
+        let mut b = battery(GunType::AntiAir, MountType::Deck, 1940, 1.57, 56.0);
+        assert!(b.is_anti_aircraft());
+
+        b.num = 0;
+        assert!(!b.is_anti_aircraft());
+        assert_eq!(0.0, b.aa_ceiling());
+    }
+}
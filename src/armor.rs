@@ -1,5 +1,5 @@
 use crate::Hull;
-use crate::units::Units;
+use crate::units::{Units, Tons};
 
 use serde::{Serialize, Deserialize};
 
@@ -10,7 +10,9 @@ use std::fmt;
 ///
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Armor {
-    /// Units
+    /// Units SpringSharp flagged the armour as entered in.
+    #[deprecated(note = "only used by version 1 and 2 JSON reports, use Ship::units for how values are shown")]
+    #[serde(default, skip_serializing_if = "Units::is_imperial")]
     pub units: Units,
 
     /// Main belt armor.
@@ -19,6 +21,9 @@ pub struct Armor {
     pub end: Belt,
     /// Uppper belt armor.
     pub upper: Belt,
+    /// Bulkheads closing the ends of the upper belt into a citadel.
+    #[serde(default)]
+    pub citadel: Option<Citadel>,
     /// Incline of belt armor.
     pub incline: f64,
 
@@ -38,9 +43,15 @@ pub struct Armor {
     pub ct_fwd: CT,
     /// Aft conning tower armor.
     pub ct_aft: CT,
+
+    /// What the plates are made of. The usual material for the year the
+    /// ship was laid down if not set.
+    #[serde(default)]
+    pub material: Option<ArmorMaterial>,
 }
 
 impl Default for Armor { // {{{2
+    #[allow(deprecated)]
     fn default() -> Self {
         Armor {
             units: Units::Imperial,
@@ -50,6 +61,7 @@ impl Default for Armor { // {{{2
             upper:    Belt::new(BeltType::Upper),
             bulge:    Belt::new(BeltType::Bulge),
             bulkhead: Belt::new(BeltType::Bulkhead),
+            citadel:  None,
 
             bh_kind: BulkheadType::Additional,
             incline: 0.0,
@@ -59,35 +71,119 @@ impl Default for Armor { // {{{2
 
             ct_fwd: CT::default(),
             ct_aft: CT::default(),
+
+            material: None,
         }
     }
 }
 
 impl Armor { // {{{2
-    // XXX: I do not know what this does.
-    pub const INCH: f64 = 0.0185; 
+    /// Weight (t) of a square foot of armor one inch thick.
+    pub const INCH: f64 = 0.0185;
+
+    /// Fraction of the draught the main belt extends below the waterline.
+    pub const BELT_BELOW: f64 = 0.2;
+
+    // scale {{{3
+    /// Scale the length and height of the belts and the beam between
+    /// the bulkheads by factor.
+    ///
+    pub fn scale(&mut self, factor: f64) {
+        for belt in [&mut self.main, &mut self.end, &mut self.upper, &mut self.bulge, &mut self.bulkhead] {
+            belt.len *= factor;
+            belt.hgt *= factor;
+        }
+        self.bh_beam *= factor;
+        self.deck.box_len = self.deck.box_len.map(|l| l * factor);
+        self.deck.box_beam = self.deck.box_beam.map(|b| b * factor);
+    }
+
+    // scale_thick {{{3
+    /// Scale the thickness of every plate by factor.
+    ///
+    pub fn scale_thick(&mut self, factor: f64) {
+        for belt in [&mut self.main, &mut self.end, &mut self.upper, &mut self.bulge, &mut self.bulkhead] {
+            belt.thick *= factor;
+        }
+        if let Some(citadel) = &mut self.citadel { citadel.thick *= factor; }
+
+        self.deck.md *= factor;
+        self.deck.fc *= factor;
+        self.deck.qd *= factor;
+        if let Some(splinter) = &mut self.deck.splinter { splinter.thick *= factor; }
+
+        self.ct_fwd.thick *= factor;
+        self.ct_aft.thick *= factor;
+    }
 
     // wgt {{{3
     /// Total weight of armor.
     ///
-    pub fn wgt(&self, hull: Hull, wgt_mag: f64, wgt_engine: f64) -> f64 {
+    pub fn wgt(&self, hull: Hull, shafts: u32, wgt_mag: f64, wgt_engine: f64) -> f64 {
         let lwl = hull.lwl();
-        let cwp = hull.cwp();
+        let cwp = hull.cwp(shafts);
         let b   = hull.b;
-        let d   = hull.d();
+        let d   = hull.d().to_long();
 
         self.main    .wgt(lwl, cwp, b) +
         self.end     .wgt(lwl, cwp, b) +
-        self.upper   .wgt(lwl, cwp, b) +
+        self.upper_wgt(lwl, cwp, b) +
         self.bulge   .wgt(lwl, cwp, b) +
         self.bulkhead.wgt(lwl, cwp, b) +
+        self.citadel_wgt(lwl, cwp, b) +
 
-        self.deck    .wgt(hull.clone(), wgt_mag, wgt_engine) +
+        self.deck    .wgt(hull.clone(), shafts, wgt_mag, wgt_engine) +
 
         self.ct_fwd  .wgt(d) +
         self.ct_aft  .wgt(d)
     }
 
+    // citadel_area {{{3
+    /// Area of the bulkheads closing both ends of the upper belt, or 0
+    /// if it is not closed into a citadel.
+    ///
+    /// Each bulkhead runs across the beam at the end of the upper belt
+    /// and up its full height, lengthened by its slope. Slopes beyond
+    /// MAX_INCLINE are treated as MAX_INCLINE.
+    ///
+    pub fn citadel_area(&self, lwl: f64, cwp: f64, b: f64) -> f64 {
+        let Some(citadel) = &self.citadel else { return 0.0; };
+
+        let incline = citadel.incline.clamp(-Citadel::MAX_INCLINE, Citadel::MAX_INCLINE);
+        let hgt = self.upper.hgt / incline.to_radians().cos();
+
+        self.upper.beam_bulkhead(lwl, cwp, b) * hgt * 2.0
+    }
+
+    // citadel_wgt {{{3
+    /// Weight of the bulkheads closing both ends of the upper belt.
+    ///
+    pub fn citadel_wgt(&self, lwl: f64, cwp: f64, b: f64) -> f64 {
+        match &self.citadel {
+            Some(citadel) => self.citadel_area(lwl, cwp, b) * citadel.thick * Armor::INCH,
+            None          => 0.0,
+        }
+    }
+
+    // upper_area {{{3
+    /// Area of the upper belt, without its own bulkheads across the beam
+    /// if the citadel bulkheads replace them.
+    ///
+    pub fn upper_area(&self, lwl: f64, cwp: f64, b: f64) -> f64 {
+        match &self.citadel {
+            Some(_) => self.upper.len * self.upper.hgt * 2.0,
+            None    => self.upper.area(lwl, cwp, b),
+        }
+    }
+
+    // upper_wgt {{{3
+    /// Weight of the upper belt, without its own bulkheads across the
+    /// beam if the citadel bulkheads replace them.
+    ///
+    pub fn upper_wgt(&self, lwl: f64, cwp: f64, b: f64) -> f64 {
+        self.upper_area(lwl, cwp, b) * self.upper.thick * Armor::INCH
+    }
+
     // belt_coverage {{{3
     /// Percentage of the "vital areas" covered by the main belt.
     ///
@@ -105,10 +201,55 @@ impl Armor { // {{{2
 
         (t + dist) * (1.0 / radians.abs().cos()) + 0.02
     }
+
+    // belt_below_water {{{3
+    /// Depth of the main belt below the waterline.
+    ///
+    /// There is no way to enter where the belt sits on the hull side so
+    /// it is assumed to extend BELT_BELOW of the draught below the
+    /// waterline.
+    ///
+    pub fn belt_below_water(&self, t: f64) -> f64 {
+        f64::min(self.main.hgt, t * Self::BELT_BELOW)
+    }
+
+    // belt_above_water {{{3
+    /// Height of the main belt above the waterline, not counting any
+    /// part of the belt that will not fit on the hull side.
+    ///
+    pub fn belt_above_water(&self, t: f64, dist: f64) -> f64 {
+        f64::max(
+            f64::min(self.main.hgt, self.max_belt_hgt(t, dist)) - self.belt_below_water(t),
+            0.0
+        )
+    }
+
+    // main_fit {{{3
+    /// Fraction of the main belt that fits on the hull side.
+    ///
+    pub fn main_fit(&self, t: f64, dist: f64) -> f64 {
+        Belt::fit(self.main.hgt, self.max_belt_hgt(t, dist))
+    }
+
+    // end_fit {{{3
+    /// Fraction of the end belt that fits on the hull side.
+    ///
+    pub fn end_fit(&self, t: f64, dist: f64) -> f64 {
+        Belt::fit(self.end.hgt, self.max_belt_hgt(t, dist))
+    }
+
+    // upper_fit {{{3
+    /// Fraction of the upper belt that fits on the hull side above
+    /// the main belt.
+    ///
+    pub fn upper_fit(&self, t: f64, dist: f64) -> f64 {
+        Belt::fit(self.upper.hgt, self.max_belt_hgt(t, dist) - self.main.hgt)
+    }
 }
 
 // Testing Armor {{{2
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod armor {
     use super::*;
     use crate::test_support::*;
@@ -171,6 +312,172 @@ mod armor {
         max_belt_hgt_0: (20.02, 0.0),
         max_belt_hgt_45: (28.3, 45.0),
     }
+
+    // Test belt_above_water {{{3
+    macro_rules! test_belt_above_water {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, hgt, t, dist) = $value;
+
+                    let mut armor = Armor::default();
+                    armor.main.hgt = hgt;
+
+                    assert_eq!(expected, to_place(armor.belt_above_water(t, dist), 2));
+                }
+            )*
+        }
+    }
+    test_belt_above_water! {
+        // name:                  (above, hgt, t, dist)
+        belt_above_water_normal:  (10.0, 16.0, 30.0, 20.0),
+        belt_above_water_tall:    (44.02, 60.0, 30.0, 20.0),
+        belt_above_water_shallow: (0.0, 4.0, 30.0, 20.0),
+        belt_above_water_none:    (0.0, 0.0, 30.0, 20.0),
+    }
+
+    // Test belt fit {{{3
+    macro_rules! test_belt_fit {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, main, upper) = $value;
+
+                    let mut armor = Armor::default();
+                    armor.main.hgt = main;
+                    armor.upper.hgt = upper;
+
+                    assert_eq!(expected, to_place(armor.upper_fit(8.0, 12.0), 2));
+                }
+            )*
+        }
+    }
+    test_belt_fit! {
+        // name:            (upper_fit, main, upper)
+        belt_fit_all:       (1.0, 10.0, 8.0),
+        belt_fit_half:      (0.5, 10.0, 20.04),
+        belt_fit_none:      (0.0, 25.0, 8.0),
+        belt_fit_no_belt:   (1.0, 10.0, 0.0),
+    }
+
+    // Test citadel {{{3
+    macro_rules! test_citadel {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((area, wgt, upper), citadel) = $value;
+
+                    let mut armor = Armor::default();
+                    armor.upper.thick = 4.0;
+                    armor.upper.len = 250.0;
+                    armor.upper.hgt = 8.0;
+                    armor.citadel = citadel;
+
+                    assert_eq!(area, to_place(armor.citadel_area(500.0, 0.5, 50.0), 2));
+                    assert_eq!(wgt, to_place(armor.citadel_wgt(500.0, 0.5, 50.0), 2));
+                    assert_eq!(upper, to_place(armor.upper_wgt(500.0, 0.5, 50.0), 2));
+                }
+            )*
+        }
+    }
+    test_citadel! {
+        // name:            ((area, wgt, upper), citadel)
+        citadel_none:       ((0.0, 0.0, 337.86), None),
+        citadel_upright:    ((565.69, 62.79, 296.0), Some(Citadel { thick: 6.0, incline: 0.0 })),
+        citadel_sloped:     ((653.2, 72.5, 296.0), Some(Citadel { thick: 6.0, incline: 30.0 })),
+        citadel_steep:      ((1131.37, 125.58, 296.0), Some(Citadel { thick: 6.0, incline: 60.0 })),
+        citadel_too_steep:  ((1131.37, 125.58, 296.0), Some(Citadel { thick: 6.0, incline: 85.0 })),
+        citadel_belt:       ((565.69, 41.86, 296.0), Some(Citadel { thick: 4.0, incline: 0.0 })),
+    }
+}
+
+// Citadel {{{1
+/// Transverse bulkheads closing the ends of the upper belt into an
+/// armored box, as in the casemate citadels of armored cruisers.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Citadel {
+    /// Bulkhead thickness.
+    pub thick: f64,
+    /// Slope of the bulkheads from the vertical (degrees).
+    #[serde(default)]
+    pub incline: f64,
+}
+
+impl Citadel { // {{{2
+    /// Steepest slope of the bulkheads (degrees).
+    pub const MAX_INCLINE: f64 = 60.0;
+}
+
+// ArmorPlate {{{1
+/// Thickness, area and weight of one armored element of the ship.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArmorPlate {
+    /// Name of the element.
+    pub name: String,
+    /// Armor thickness.
+    pub thick: f64,
+    /// Area covered (sq ft).
+    pub area: f64,
+    /// Weight of armor.
+    pub wgt: Tons,
+}
+
+impl ArmorPlate { // {{{2
+    // implied {{{3
+    /// Element whose area is taken from its weight at the standard weight
+    /// of plate of its thickness.
+    ///
+    /// Gun and conning tower armor weights are not built up from an area
+    /// so this is the only area they have.
+    ///
+    pub fn implied(name: &str, thick: f64, wgt: Tons) -> ArmorPlate {
+        let area = if thick > 0.0 { wgt.to_long() / (thick * Armor::INCH) } else { 0.0 };
+
+        ArmorPlate { name: name.into(), thick, area, wgt }
+    }
+
+    // wgt_per_area {{{3
+    /// Weight of the armor per square foot (lbs/sq ft).
+    ///
+    pub fn wgt_per_area(&self) -> f64 {
+        if self.area == 0.0 { return 0.0; } // Catch divide by zero
+
+        self.wgt.to_pounds() / self.area
+    }
+}
+
+// Testing ArmorPlate {{{2
+#[cfg(test)]
+mod armor_plate {
+    use super::*;
+    use crate::test_support::*;
+
+    // Test implied {{{3
+    macro_rules! test_implied {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((area, wgt_per_area), thick, wgt) = $value;
+                    let plate = ArmorPlate::implied("test", thick, Tons::long(wgt));
+
+                    assert_eq!(area, to_place(plate.area, 2));
+                    assert_eq!(wgt_per_area, to_place(plate.wgt_per_area(), 2));
+                }
+            )*
+        }
+    }
+    test_implied! {
+        // name:           ((area, wgt_per_area), thick, wgt)
+        implied_zero:      ((0.0, 0.0), 0.0, 10.0),
+        implied_one_inch:  ((1000.0, 41.44), 1.0, 18.5),
+        implied_ten_inch:  ((100.0, 414.4), 10.0, 18.5),
+    }
 }
 
 // Belt {{{1
@@ -197,18 +504,39 @@ impl Belt { // {{{2
     /// Belt weight.
     ///
     pub fn wgt(&self, lwl: f64, cwp: f64, b: f64) -> f64 {
-        // Calculate the area of one bulkhead across the beam
-        let beam_bulkhead = match self.kind {
+        // Calculate the weight of one belt and one bulkhead across the beam
+        let wgt = (self.len + self.beam_bulkhead(lwl, cwp, b)) * self.hgt * self.thick * Armor::INCH;
+
+        // Double the weight to account for two belts and two beam bulkheads
+        wgt * 2.0
+    }
+
+    // area {{{3
+    /// Area of both belts and both bulkheads across the beam.
+    ///
+    pub fn area(&self, lwl: f64, cwp: f64, b: f64) -> f64 {
+        (self.len + self.beam_bulkhead(lwl, cwp, b)) * self.hgt * 2.0
+    }
+
+    // beam_bulkhead {{{3
+    /// Length of the bulkhead across the beam that closes each end of a
+    /// main or upper belt.
+    ///
+    fn beam_bulkhead(&self, lwl: f64, cwp: f64, b: f64) -> f64 {
+        match self.kind {
             BeltType::Main | BeltType::Upper =>
                 (1.0 - self.len / lwl).powf(1.0 - cwp) * b,
             _ => 0.0
-        };
+        }
+    }
 
-        // Calculate the weight of one belt and one bulkhead across the beam
-        let wgt = (self.len + beam_bulkhead) * self.hgt * self.thick * Armor::INCH;
+    // fit {{{3
+    /// Fraction of a belt of height hgt that fits in the room available.
+    ///
+    fn fit(hgt: f64, room: f64) -> f64 {
+        if hgt <= 0.0 { return 1.0; }
 
-        // Double the weight to account for two belts and two beam bulkheads
-        wgt * 2.0
+        (room / hgt).clamp(0.0, 1.0)
     }
 
     // new {{{3
@@ -260,6 +588,95 @@ mod belt {
     }
 }
 
+// ArmorMaterial {{{1
+/// What armor plates are made of.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum ArmorMaterial {
+    /// Wrought iron, bolted to thick timber backing.
+    WroughtIron,
+    /// Compound iron faced with steel, on lighter backing.
+    Compound,
+    /// Steel, including face hardened and cemented plates.
+    #[default]
+    Steel,
+}
+
+impl fmt::Display for ArmorMaterial { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::WroughtIron => "wrought iron",
+                Self::Compound    => "compound",
+                Self::Steel       => "steel",
+            }
+        )
+    }
+}
+
+impl ArmorMaterial { // {{{2
+    /// Year compound armor replaced wrought iron.
+    const COMPOUND_YEAR: u32 = 1877;
+    /// Year steel armor replaced compound, from when the year adjustment
+    /// no longer penalizes a design.
+    const STEEL_YEAR: u32 = 1890;
+
+    // for_year {{{3
+    /// Usual material for a ship laid down in year.
+    ///
+    pub fn for_year(year: u32) -> ArmorMaterial {
+             if year < Self::COMPOUND_YEAR { Self::WroughtIron }
+        else if year < Self::STEEL_YEAR    { Self::Compound }
+        else                               { Self::Steel }
+    }
+
+    // backing {{{3
+    /// Weight of the timber backing, inner skin and bolts the plates need,
+    /// as a share of the weight of the plates.
+    ///
+    /// Iron and compound plates are weaker than steel for their thickness
+    /// and have to be carried on heavy backing, so they weigh more for the
+    /// same protection. Steel plates need none beyond what the hull
+    /// weight allows for.
+    ///
+    pub fn backing(&self) -> f64 {
+        match self {
+            Self::WroughtIron => 0.2,
+            Self::Compound    => 0.1,
+            Self::Steel       => 0.0,
+        }
+    }
+}
+
+// Testing ArmorMaterial {{{2
+#[cfg(test)]
+mod armor_material {
+    use super::*;
+
+    // Test for_year {{{3
+    macro_rules! test_for_year {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year) = $value;
+
+                    assert_eq!(expected, ArmorMaterial::for_year(year));
+                }
+            )*
+        }
+    }
+
+    test_for_year! {
+        // name:                    (material, year laid down)
+        for_year_hercules:          (ArmorMaterial::WroughtIron, 1866),
+        for_year_alexandra:         (ArmorMaterial::WroughtIron, 1873),
+        for_year_colossus:          (ArmorMaterial::Compound, 1879),
+        for_year_royal_sovereign:   (ArmorMaterial::Compound, 1889),
+        for_year_majestic:          (ArmorMaterial::Steel, 1894),
+    }
+}
+
 // BulkheadType {{{1
 /// Values for Armor::bh_kind
 ///
@@ -349,24 +766,71 @@ pub struct Deck {
 
     /// Deck armor configuration.
     pub kind: DeckType,
+
+    /// Lower splinter deck, if any.
+    #[serde(default)]
+    pub splinter: Option<SplinterDeck>,
+
+    /// Length (ft) of the vitals an armoured box covers, if not the
+    /// length of the machinery and magazine spaces.
+    #[serde(default)]
+    pub box_len: Option<f64>,
+    /// Width (ft) of the vitals an armoured box covers, if not the beam.
+    #[serde(default)]
+    pub box_beam: Option<f64>,
 }
 
 impl Deck { // {{{2
-    // wgt {{{3
-    /// Weight of deck armor.
+    // splinter_thick {{{3
+    /// Thickness of the splinter deck, or zero if there is none.
+    ///
+    pub fn splinter_thick(&self) -> f64 {
+        self.splinter.as_ref().map_or(0.0, |s| s.thick)
+    }
+
+    // horizontal {{{3
+    /// Combined thickness of the main and splinter decks over the vitals.
     ///
-    pub fn wgt(&self, hull: Hull, wgt_mag: f64, wgt_engine: f64) -> f64 {
-        let d      = hull.d();
+    pub fn horizontal(&self) -> f64 {
+        self.md + self.splinter_thick()
+    }
+
+    // is_armored {{{3
+    /// True if any deck has armor.
+    ///
+    pub fn is_armored(&self) -> bool {
+        self.fc + self.md + self.qd + self.splinter_thick() > 0.0
+    }
+
+    // vitals_len {{{3
+    /// Length (ft) of the vitals a box deck of kind covers, or None if
+    /// it is not a box.
+    ///
+    /// Boxes cover the vitals unless their extent is given.
+    ///
+    pub fn vitals_len(&self, kind: &DeckType, d: f64, lwl: f64, wgt_engine: f64, wgt_mag: f64) -> Option<f64> {
+        kind.box_len(d, lwl, wgt_engine, wgt_mag).map(|len| self.box_len.unwrap_or(len))
+    }
+
+    // areas {{{3
+    /// Area of the main, forecastle, quarter and splinter decks that
+    /// armor would cover.
+    ///
+    pub fn areas(&self, hull: Hull, shafts: u32, wgt_mag: f64, wgt_engine: f64) -> (f64, f64, f64, f64) {
+        let d      = hull.d().to_long();
         let lwl    = hull.lwl();
         let b      = hull.b;
         let fc_len = hull.fc_len;
         let qd_len = hull.qd_len;
-        let cwp    = hull.cwp();
-        let wp     = hull.wp();
+        let cwp    = hull.cwp(shafts);
+        let wp     = hull.wp(shafts);
 
-        let main_deck = self.kind.wgt_factor(
-            d, lwl, b, fc_len, qd_len, wp, cwp, wgt_engine, wgt_mag
-        );
+        let area = |kind: &DeckType| match self.vitals_len(kind, d, lwl, wgt_engine, wgt_mag) {
+            Some(len) => DeckType::box_area(len, self.box_beam.unwrap_or(b)),
+            None      => kind.wgt_factor(d, lwl, b, fc_len, qd_len, wp, cwp, wgt_engine, wgt_mag),
+        };
+
+        let main_deck = area(&self.kind);
 
         let fc_deck = (fc_len * 2.0).powf(1.0 - cwp.powf(2.0)) *
             b * lwl * fc_len * 0.5;
@@ -374,10 +838,35 @@ impl Deck { // {{{2
         let qd_deck = qd_len.powf(1.0 - cwp) * b * lwl * qd_len / 4.0 *
             (2.0 + 2.0_f64.powf(1.0 - cwp));
 
-        (main_deck * self.md + fc_deck * self.fc + qd_deck * self.qd) * Armor::INCH
+        let splinter_deck = match &self.splinter {
+            Some(s) => area(&s.kind),
+            None    => 0.0,
+        };
+
+        (main_deck, fc_deck, qd_deck, splinter_deck)
+    }
+
+    // wgt {{{3
+    /// Weight of deck armor.
+    ///
+    pub fn wgt(&self, hull: Hull, shafts: u32, wgt_mag: f64, wgt_engine: f64) -> f64 {
+        let (main_deck, fc_deck, qd_deck, splinter_deck) = self.areas(hull, shafts, wgt_mag, wgt_engine);
+
+        (main_deck * self.md + fc_deck * self.fc + qd_deck * self.qd + splinter_deck * self.splinter_thick()) * Armor::INCH
     }
 }
 
+// SplinterDeck {{{1
+/// A lower deck that catches fragments from shells that burst above it.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SplinterDeck {
+    /// Deck thickness.
+    pub thick: f64,
+    /// Extent of the deck.
+    pub kind: DeckType,
+}
+
 // Testing Deck {{{2
 #[cfg(test)]
 mod deck {
@@ -405,8 +894,7 @@ mod deck {
 
                     let mut hull = Hull::default();
                     hull.set_lwl(100.0);
-                    hull.set_d(1000.0);
-                    hull.set_shafts(2); // hull.boxy == false
+                    hull.set_d(Tons::long(1000.0));
                     hull.b = 50.0;
                     hull.bb = hull.b;
                     hull.t = 10.0;
@@ -427,7 +915,7 @@ mod deck {
                     hull.qd_fwd = hull.fc_fwd;
                     hull.qd_aft = hull.fc_fwd;
 
-                    assert!(expected == to_place(deck.wgt(hull, wgt_mag, wgt_engine), 2));
+                    assert!(expected == to_place(deck.wgt(hull, 2, wgt_mag, wgt_engine), 2));
                 }
             )*
         }
@@ -446,6 +934,31 @@ mod deck {
         wgt_box_magazine_md:  (23.24, DeckType::BoxOverMagazine, 0.0, 1.0, 0.0),
         wgt_box_both_md:      (48.57, DeckType::BoxOverBoth, 0.0, 1.0, 0.0),
     }
+
+    // Test splinter {{{3
+    #[test]
+    fn splinter() {
+        let mut hull = Hull::default();
+        hull.set_lwl(100.0);
+        hull.set_d(Tons::long(1000.0));
+        hull.b = 50.0;
+        hull.bb = hull.b;
+        hull.t = 10.0;
+
+        let main = Deck { md: 1.0, kind: DeckType::BoxOverBoth, ..Default::default() };
+        let both = Deck {
+            splinter: Some(SplinterDeck { thick: 1.0, kind: DeckType::BoxOverBoth }),
+            ..main.clone()
+        };
+
+        assert_eq!(2.0, both.horizontal());
+        assert!(both.is_armored());
+        assert!(!Deck::default().is_armored());
+        assert_eq!(
+            to_place(main.wgt(hull.clone(), 2, 100.0, 100.0) * 2.0, 2),
+            to_place(both.wgt(hull, 2, 100.0, 100.0), 2)
+        );
+    }
 }
 
 // DeckType {{{1
@@ -467,6 +980,7 @@ impl DeckType { // {{{2
     // wgt_factor {{{3
     /// Main deck weight factor for each deck type.
     ///
+    #[allow(clippy::too_many_arguments)]
     pub fn wgt_factor(&self,
         d: f64, lwl: f64, b: f64, 
         fc_len: f64, qd_len:f64,
@@ -490,16 +1004,38 @@ impl DeckType { // {{{2
                 ) * 1.01
             },
 
-            Self::BoxOverMachinery =>
-                (wgt_engine * 3.0 / (d * 0.94) * 0.65 * lwl + 16.0) * (b + 16.0) - 256.0,
+            Self::BoxOverMachinery |
+            Self::BoxOverMagazine |
+            Self::BoxOverBoth =>
+                Self::box_area(self.box_len(d, lwl, wgt_engine, wgt_mag).unwrap_or_default(), b),
+        }
+    }
 
-            Self::BoxOverMagazine =>
-                (wgt_mag / (d * 0.94) * 0.65 * lwl + 16.0) * (b + 16.0) - 256.0,
+    // box_len {{{3
+    /// Length (ft) of the vitals a box covers, or None if the deck is
+    /// not a box.
+    ///
+    /// This is the vital space model's share of the hull the machinery,
+    /// magazines or both take up, where machinery needs three times the
+    /// room of its weight.
+    ///
+    pub fn box_len(&self, d: f64, lwl: f64, wgt_engine: f64, wgt_mag: f64) -> Option<f64> {
+        let wgt = match self {
+            Self::BoxOverMachinery => wgt_engine * 3.0,
+            Self::BoxOverMagazine  => wgt_mag,
+            Self::BoxOverBoth      => wgt_engine * 3.0 + wgt_mag,
+            _                      => return None,
+        };
 
-            Self::BoxOverBoth =>
-                ((wgt_engine * 3.0 + wgt_mag) / (d * 0.94) * 0.65 * lwl + 16.0) * (b + 16.0) - 256.0,
+        Some(wgt / (d * 0.94) * 0.65 * lwl)
+    }
 
-        }
+    // box_area {{{3
+    /// Deck area of a box over vitals of a length and width (ft),
+    /// reaching 8 ft beyond them all round.
+    ///
+    pub fn box_area(len: f64, beam: f64) -> f64 {
+        (len + 16.0) * (beam + 16.0) - 256.0
     }
 }
 
@@ -526,6 +1062,7 @@ impl From<String> for DeckType { // {{{2
 }
 
 impl From<&str> for DeckType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::SingleArmored,
@@ -0,0 +1,226 @@
+use crate::{Ship, Condition};
+
+use serde::Serialize;
+use std::fmt;
+
+// Deviation {{{1
+/// How far a ship as built is from its design in one of its figures.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Deviation {
+    /// Figure being compared.
+    pub name: &'static str,
+    /// Value for the design.
+    pub design: f64,
+    /// Value for the ship as built.
+    pub built: f64,
+}
+
+impl fmt::Display for Deviation { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {:.2} as built against {:.2} designed ({:+.2}, {:+.1}%)",
+            self.name, self.built, self.design, self.change(), self.pct())
+    }
+}
+
+impl Deviation { // {{{2
+    // change {{{3
+    /// Difference between the ship as built and the design.
+    ///
+    pub fn change(&self) -> f64 {
+        self.built - self.design
+    }
+
+    // pct {{{3
+    /// Difference as a percentage of the design value.
+    ///
+    pub fn pct(&self) -> f64 {
+        if self.design == 0.0 { return 0.0; } // catch divide by zero
+
+        self.change() / self.design * 100.0
+    }
+}
+
+// AsBuilt {{{1
+/// A ship as the yard delivered it, which differs a little from its design.
+///
+#[derive(Clone, Debug)]
+pub struct AsBuilt {
+    /// Seed the variations were drawn from.
+    pub seed: u64,
+    /// Ship as built.
+    pub ship: Ship,
+    /// How far the ship as built is from the design.
+    pub deviations: Vec<Deviation>,
+}
+
+impl fmt::Display for AsBuilt { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Seed: {}", self.seed)?;
+        for d in self.deviations.iter() {
+            writeln!(f, "{}", d)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Rng {{{1
+/// Small random number generator (SplitMix64) so that a seed gives the
+/// same ship on every platform and version.
+///
+struct Rng(u64);
+
+impl Rng { // {{{2
+    // next {{{3
+    /// Next random number.
+    ///
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        z ^ (z >> 31)
+    }
+
+    // uniform {{{3
+    /// Random number from 0 up to but not including 1.
+    ///
+    fn uniform(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // normal {{{3
+    /// Random number from a normal distribution, kept within a range.
+    ///
+    fn normal(&mut self, mean: f64, sd: f64, (min, max): (f64, f64)) -> f64 {
+        let (u1, u2) = (1.0 - self.uniform(), self.uniform());
+        let z = (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos();
+
+        (mean + z * sd).clamp(min, max)
+    }
+}
+
+impl Ship { // {{{1
+    /// Weight builders added to the normal displacement, as a share of it:
+    /// mean, standard deviation and range.
+    const OVERRUN: (f64, f64, (f64, f64)) = (0.015, 0.015, (-0.02, 0.06));
+    /// Speed lost on trials beyond that due to the overrun, as a share of
+    /// the speed: mean, standard deviation and range. Some ships beat
+    /// their design speed.
+    const SHORTFALL: (f64, f64, (f64, f64)) = (0.01, 0.015, (-0.02, 0.05));
+
+    // as_built {{{2
+    /// The ship as a yard might have delivered it, with small realistic
+    /// variations drawn from the seed. The same seed always gives the
+    /// same ship and the design itself is not changed.
+    ///
+    /// The builder's overrun is carried as miscellaneous hull weight and
+    /// sinks the hull deeper. A ship delivered light takes the saving off
+    /// its miscellaneous and then its structural hull weight. The top
+    /// speed is what the design's power drives the hull at as built, less
+    /// any trial shortfall.
+    ///
+    pub fn as_built(&self, seed: u64) -> AsBuilt {
+        let mut rng = Rng(seed);
+        let (mean, sd, range) = Self::OVERRUN;
+        let overrun = rng.normal(mean, sd, range);
+        let (mean, sd, range) = Self::SHORTFALL;
+        let shortfall = rng.normal(mean, sd, range);
+
+        let mut ship = self.clone();
+
+        // Displacement {{{3
+        let d = self.hull.d() * (1.0 + overrun);
+        let extra = (d - self.hull.d()).to_long().round() as i64;
+
+        ship.wgts.hull = (ship.wgts.hull as i64 + extra).max(0) as u32;
+        ship.hull.t = self.hull.t_calc(d, self.engine.shafts());
+        ship.hull.set_d(d);
+
+        // Speed {{{3
+        if self.engine.is_powered() && self.engine.vmax > 0.0 {
+            let hp = self.engine.hp_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws());
+            let hull = &ship.hull;
+
+            let (mut lo, mut hi) = (0.0, self.engine.vmax * 2.0);
+            while hi - lo > 0.001 {
+                let v = (lo + hi) / 2.0;
+                if self.engine.hp(v, hull.d().to_long(), hull.lwl(), hull.leff(), hull.cs(), hull.ws()) < hp { lo = v; } else { hi = v; }
+            }
+
+            ship.engine.vmax = (lo + hi) / 2.0 * (1.0 - shortfall);
+            ship.engine.vcruise = ship.engine.vcruise.min(ship.engine.vmax);
+        }
+
+        ship.notes.text.push(format!("As built from seed {}", seed));
+
+        // Deviations {{{3
+        let deviations = vec![
+            Deviation { name: "Normal displacement (t)", design: self.hull.d().to_long(), built: ship.hull.d().to_long() },
+            Deviation { name: "Standard displacement (t)", design: self.d_std().to_long(), built: ship.d_std().to_long() },
+            Deviation { name: "Draught (ft)", design: self.hull.t, built: ship.hull.t },
+            Deviation { name: "Maximum speed (kts)", design: self.engine.vmax, built: ship.engine.vmax },
+            Deviation { name: "Trial speed (kts)", design: self.trial_speed(Condition::Trial), built: ship.trial_speed(Condition::Trial) },
+            Deviation { name: "Metacentric height (ft)", design: self.metacenter(), built: ship.metacenter() },
+        ];
+
+        AsBuilt { seed, ship, deviations }
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod as_built {
+    use super::*;
+    use crate::Template;
+
+    // Test same seed {{{2
+    #[test]
+    fn same_seed() {
+        let design = Ship::from_template(Template::Dreadnought1910);
+        let (a, b) = (design.as_built(7), design.as_built(7));
+
+        assert_eq!(a.deviations, b.deviations);
+        assert_ne!(a.deviations, design.as_built(8).deviations);
+
+        // The design is untouched
+        assert_eq!(serde_json::to_value(Ship::from_template(Template::Dreadnought1910)).unwrap(), serde_json::to_value(&design).unwrap());
+    }
+
+    // Test variations {{{2
+    #[test]
+    fn variations() {
+        let design = Ship::from_template(Template::Dreadnought1910);
+
+        for seed in 0..200 {
+            let built = design.as_built(seed);
+            let d = &built.deviations;
+
+            // Overruns stay in range and are carried as hull weight
+            assert!((-2.01..=6.01).contains(&d[0].pct()), "{}: {}", seed, d[0]);
+            if d[0].change() > 0.0 {
+                assert_eq!(d[0].change().round(), (built.ship.wgts.hull - design.wgts.hull) as f64, "{}", seed);
+            }
+            assert_eq!(d[0].change() > 0.0, d[2].change() > 0.0, "{}", seed);
+
+            // Speed shortfall stays in range once the overrun is allowed for
+            assert!(d[3].pct() < 2.5 && d[3].pct() > -8.0, "{}: {}", seed, d[3]);
+            assert!(built.ship.engine.vcruise <= built.ship.engine.vmax);
+        }
+    }
+
+    // Test rng {{{2
+    #[test]
+    fn rng() {
+        let mut rng = Rng(0);
+        let n = 10_000;
+        let mean = (0..n).map(|_| rng.normal(1.0, 0.5, (-10.0, 10.0))).sum::<f64>() / n as f64;
+
+        assert!((mean - 1.0).abs() < 0.02, "{}", mean);
+        assert!((0..n).all(|_| (0.0..1.0).contains(&rng.uniform())));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{Ship, Tons};
+
+use serde::Serialize;
+use std::fmt;
+
+// Constants {{{1
+/// Largest standard displacement margin (t) a balanced design is left with.
+const TOLERANCE: f64 = 0.5;
+/// Most iterations made before giving up.
+const MAX_ITERATIONS: u32 = 50;
+
+// BalanceStep {{{1
+/// One iteration of balancing a design, recorded so the way it
+/// converged can be followed.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct BalanceStep {
+    /// Iteration, counting from 1.
+    pub iteration: u32,
+    /// Normal displacement (t).
+    pub d: f64,
+    /// Standard displacement (t).
+    pub d_std: f64,
+    /// Standard displacement still to be taken up (t).
+    pub margin: f64,
+    /// Block Coefficient.
+    pub cb: f64,
+}
+
+impl fmt::Display for BalanceStep { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:>4} {:>10.1} {:>10.1} {:>+9.1} {:>6.3}",
+            self.iteration, self.d, self.d_std, self.margin, self.cb)
+    }
+}
+
+// Balance {{{1
+/// A design balanced to a standard displacement.
+///
+#[derive(Clone, Debug)]
+pub struct Balance {
+    /// Balanced design.
+    pub ship: Ship,
+    /// Standard displacement balanced to.
+    pub target: Tons,
+    /// True if the margin was brought within tolerance.
+    pub converged: bool,
+    /// Each iteration in the order it was made.
+    pub trace: Vec<BalanceStep>,
+}
+
+impl fmt::Display for Balance { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Normal displacement: {:.0} t", self.ship.hull.d().to_long())?;
+        writeln!(f, "Block coefficient: {:.3}", self.ship.hull.cb())?;
+        writeln!(f, "Standard displacement: {:.0} t against {:.0} t ({:+.1} t)",
+            self.ship.d_std().to_long(), self.target.to_long(), (self.ship.d_std() - self.target).to_long())?;
+        if !self.converged {
+            writeln!(f, "Did not converge after {} iterations", self.trace.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Balance { // {{{2
+    // trace_table {{{3
+    /// The iterations of the balance as a table.
+    ///
+    pub fn trace_table(&self) -> String {
+        trace_table(&self.trace)
+    }
+}
+
+// trace_table {{{1
+/// Iterations of a balance as a table.
+///
+pub(crate) fn trace_table(trace: &[BalanceStep]) -> String {
+    let mut s = format!("{:>4} {:>10} {:>10} {:>9} {:>6}\n",
+        "Step", "Norm (t)", "Std (t)", "Margin", "Cb");
+
+    for step in trace.iter() {
+        s.push_str(&format!("{}\n", step));
+    }
+
+    s
+}
+
+impl Ship { // {{{1
+    // balance {{{2
+    /// Adjust the normal displacement, and with it the Block Coefficient,
+    /// until the standard displacement matches d_std.
+    ///
+    /// Bunkerage grows with the displacement, so the margin left after
+    /// each change is taken up again until it is within half a ton.
+    ///
+    pub fn balance(&self, d_std: Tons) -> Balance {
+        let mut ship = self.clone();
+        let mut trace = Vec::new();
+        let mut converged = false;
+
+        for iteration in 1..=MAX_ITERATIONS {
+            let margin = (d_std - ship.d_std()).to_long();
+            trace.push(BalanceStep {
+                iteration,
+                d: ship.hull.d().to_long(),
+                d_std: ship.d_std().to_long(),
+                margin,
+                cb: ship.hull.cb(),
+            });
+
+            if margin.abs() < TOLERANCE { converged = true; break; }
+
+            ship.hull.set_d(ship.hull.d() + Tons::long(margin));
+        }
+
+        Balance { ship, target: d_std, converged, trace }
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod balance {
+    use super::*;
+    use crate::Template;
+
+    // Test balance {{{2
+    macro_rules! test_balance {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (converged, template, scale) = $value;
+
+                    let ship = Ship::from_template(template);
+                    let balance = ship.balance(ship.d_std() * scale);
+
+                    assert_eq!(converged, balance.converged);
+                    assert!((balance.ship.d_std() - balance.target).to_long().abs() < TOLERANCE);
+                    assert_eq!(balance.ship.hull.d().to_long(), balance.trace.last().unwrap().d);
+
+                    // The margin shrinks every iteration
+                    assert!(balance.trace.windows(2).all(|s| s[1].margin.abs() < s[0].margin.abs()));
+                }
+            )*
+        }
+    }
+
+    test_balance! {
+        // name:                (converged, template, scale)
+        balance_heavier:        (true, Template::Dreadnought1910, 1.1),
+        balance_lighter:        (true, Template::Destroyer1935, 0.9),
+    }
+
+    #[test]
+    fn balance_balanced() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let balance = ship.balance(ship.d_std());
+
+        assert!(balance.converged);
+        assert_eq!(1, balance.trace.len());
+        assert_eq!(ship.hull.d(), balance.ship.hull.d());
+    }
+
+    #[test]
+    fn balance_impossible() {
+        // No displacement leaves a negative standard displacement
+        let ship = Ship::from_template(Template::Destroyer1935);
+        let balance = ship.balance(Tons::long(-100.0));
+
+        assert!(!balance.converged);
+        assert_eq!(MAX_ITERATIONS as usize, balance.trace.len());
+        assert!(balance.to_string().contains("Did not converge after 50 iterations"));
+    }
+
+    #[test]
+    fn trace_table() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let balance = ship.balance(ship.d_std() + Tons::long(1000.0));
+        let table = balance.trace_table();
+
+        assert!(table.starts_with("Step"));
+        assert_eq!(balance.trace.len() + 1, table.lines().count());
+    }
+}
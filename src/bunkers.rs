@@ -0,0 +1,110 @@
+use crate::{Ship, Hull};
+
+impl Ship { // {{{1
+    /// Largest share of the hull volume the bunkers and fuel tanks can
+    /// take alongside the machinery, magazines and crew.
+    pub const BUNKER_SPACE: f64 = 0.2;
+
+    // hull_volume {{{2
+    /// Volume of the hull up to the deck amidships (cu ft): the displaced
+    /// volume plus the waterplane raised by the average freeboard.
+    ///
+    pub fn hull_volume(&self) -> f64 {
+        self.hull.d().to_long() * Hull::FT3_PER_TON_SEA + self.wp() * self.hull.freeboard_dist()
+    }
+
+    // bunker_volume {{{2
+    /// Volume of the fuel carried at maximum displacement (cu ft).
+    ///
+    pub fn bunker_volume(&self) -> f64 {
+        self.engine.bunker_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()) *
+            self.engine.stowage()
+    }
+
+    // bunker_space {{{2
+    /// Volume of the hull that can be given over to bunkers (cu ft).
+    ///
+    pub fn bunker_space(&self) -> f64 {
+        self.hull_volume() * Self::BUNKER_SPACE
+    }
+
+    // range_max {{{2
+    /// Longest range (nm) whose fuel fits in the bunker space.
+    ///
+    /// Bunkerage grows in step with the range on top of a fixed reserve
+    /// for the displacement.
+    ///
+    pub fn range_max(&self) -> u32 {
+        let reserve = self.hull.d().to_long() * 0.005 * 1.8 * self.engine.stowage();
+        let per_range = self.bunker_volume() - reserve;
+        if per_range <= 0.0 { return self.engine.range; } // catch divide by zero
+
+        f64::max((self.bunker_space() - reserve) / per_range * self.engine.range as f64, 0.0) as u32
+    }
+
+    // bunkers_fit {{{2
+    /// True if the fuel for the range fits in the bunker space.
+    ///
+    pub fn bunkers_fit(&self) -> bool {
+        self.bunker_volume() <= self.bunker_space()
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod bunkers {
+    use super::*;
+    use crate::Template;
+    use crate::test_support::*;
+
+    // Test bunkers_fit {{{2
+    macro_rules! test_bunkers_fit {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, template, range) = $value;
+
+                    let mut ship = Ship::from_template(template);
+                    ship.engine.range = range;
+
+                    assert_eq!(expected, ship.bunkers_fit());
+                }
+            )*
+        }
+    }
+
+    test_bunkers_fit! {
+        // name:                    (fits, template, range)
+        bunkers_fit_dreadnought:    (true, Template::Dreadnought1910, 7000),
+        bunkers_fit_destroyer:      (true, Template::Destroyer1935, 5000),
+        bunkers_fit_fantasy:        (false, Template::Destroyer1935, 40000),
+    }
+
+    // Test range_max {{{2
+    #[test]
+    fn range_max() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let range = ship.range_max();
+        assert!(range > ship.engine.range);
+
+        // The longest range just fills the bunker space
+        let mut full = ship.clone();
+        full.engine.range = range;
+        assert_eq!(1.0, to_place(full.bunker_volume() / full.bunker_space(), 3));
+    }
+
+    // Test bunker_volume {{{2
+    #[test]
+    fn bunker_volume() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.engine.pct_coal = 0.0;
+        let oil = ship.bunker_volume();
+        ship.engine.pct_coal = 1.0;
+
+        // More coal is burnt for the range and it stows worse
+        assert!(ship.bunker_volume() > oil);
+        assert!(ship.hull_volume() > ship.hull.d().to_long() * Hull::FT3_PER_TON_SEA);
+    }
+}
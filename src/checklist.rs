@@ -0,0 +1,214 @@
+use crate::{Ship, DesignIssue, Severity};
+
+use serde::Serialize;
+use std::fmt;
+
+// CheckStep {{{1
+/// One step of the design review, with what fixes it if it fails.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct CheckStep {
+    /// Stable identifier for the step.
+    pub code: &'static str,
+    /// Question the step answers.
+    pub question: &'static str,
+    /// True if the design passes the step.
+    pub pass: bool,
+    /// Value the step was decided on.
+    pub detail: String,
+    /// Path to the Ship field most likely to fix the step.
+    pub field: &'static str,
+    /// What to change if the step fails.
+    pub fix: &'static str,
+    /// Design issues from validation that bear on the step.
+    pub issues: Vec<DesignIssue>,
+}
+
+impl fmt::Display for CheckStep { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[{}] {} {}",
+            if self.pass { "PASS" } else { "FAIL" }, self.question, self.detail)?;
+
+        if !self.pass {
+            write!(f, "\n       Fix: {} ({})", self.fix, self.field)?;
+        }
+        for i in self.issues.iter() {
+            write!(f, "\n       {}: {} [{}]", i.severity, i.message, i.code)?;
+        }
+
+        Ok(())
+    }
+}
+
+// Checklist {{{1
+/// The standard design review steps for a ship.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Checklist {
+    /// Each step in the order a design is usually reviewed.
+    pub steps: Vec<CheckStep>,
+}
+
+impl fmt::Display for Checklist { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for s in self.steps.iter() {
+            writeln!(f, "{}", s)?;
+        }
+        write!(f, "Passed {} of {} checks", self.passed(), self.steps.len())
+    }
+}
+
+impl Checklist { // {{{2
+    // passed {{{3
+    /// Number of steps the design passes.
+    ///
+    pub fn passed(&self) -> usize {
+        self.steps.iter().filter(|s| s.pass).count()
+    }
+
+    // pass {{{3
+    /// True if the design passes every step.
+    ///
+    pub fn pass(&self) -> bool {
+        self.passed() == self.steps.len()
+    }
+}
+
+impl Ship { // {{{1
+    /// Range of stability a well balanced design falls in.
+    pub(crate) const CHECK_STABILITY: (f64, f64) = (1.0, 1.2);
+    /// Largest hull room that leaves adequate space below.
+    const CHECK_HULL_ROOM: f64 = 1.1111112;
+    /// Smallest deck room that leaves adequate space on deck.
+    const CHECK_DECK_ROOM: f64 = 0.9;
+
+    // checklist {{{2
+    /// Walk through the standard design review steps.
+    ///
+    /// Each step is decided on one of the report's values and lists the
+    /// validation issues with the same cause, which fail the step if any
+    /// is a failure.
+    ///
+    pub fn checklist(&self) -> Checklist {
+        let issues = self.validate();
+        let step = |code, question, pass: bool, detail, field, fix, codes: &[&str]| {
+            let issues: Vec<DesignIssue> = issues.iter()
+                .filter(|i| codes.contains(&i.code))
+                .cloned()
+                .collect();
+            let pass = pass && !issues.iter().any(|i| i.severity == Severity::Failure);
+
+            CheckStep { code, question, pass, detail, field, fix, issues }
+        };
+
+        let (min, max) = Self::CHECK_STABILITY;
+        let (hull_room, deck_room) = (self.hull_room(), self.deck_room());
+        let belted = self.armor.main.thick > 0.0;
+
+        Checklist { steps: vec![
+            step("weights", "Do the weights balance?",
+                self.wgt_hull() > 0.0,
+                format!("{:.0} t is left for the hull structure", self.wgt_hull()),
+                "hull.d", "Raise the displacement or lighten the guns, armour and machinery",
+                &["displacement-impossible", "guns-too-heavy", "armor-too-heavy", "conversion-overweight"]),
+            step("strength", "Is the hull strong enough?",
+                self.str_comp() >= 1.0,
+                format!("Composite strength is {:.2}, at least 1.00 is needed", self.str_comp()),
+                "hull", "Raise the freeboard, shorten the hull or lighten the weights it carries",
+                &["hull-overloaded", "hull-beyond-calibration", "merchant-hull-weak"]),
+            step("belt", "Does the belt cover the vitals?",
+                !belted || self.armor.belt_coverage(self.hull.lwl()) >= hull_room,
+                if belted {
+                    format!("Main belt covers {:.0}% of the {:.0}% of normal length the vitals need",
+                        self.armor.belt_coverage(self.hull.lwl()) * 100.0, hull_room * 100.0)
+                } else {
+                    "No main belt is fitted".into()
+                },
+                "armor.main.len", "Lengthen the main belt to cover the magazines and machinery",
+                &["belt-too-tall", "belt-below-water"]),
+            step("stability", "Is the ship stable but steady?",
+                (min..=max).contains(&self.stability_adj()),
+                format!("Stability is {:.2}, between {:.2} and {:.2} is wanted", self.stability_adj(), min, max),
+                "hull.b", "Widen the beam or lower weights to gain stability, narrow it or trim for steadiness to lose it",
+                &["capsize"]),
+            step("space", "Is there room in the hull and on deck?",
+                hull_room < Self::CHECK_HULL_ROOM && deck_room > Self::CHECK_DECK_ROOM,
+                format!("Hull space is {}, deck space is {}", self.hull_room_quality().to_lowercase(), self.deck_room_quality().to_lowercase()),
+                "hull.lwl", "Lengthen or deepen the hull, or carry less machinery, fuel and weapons",
+                &["bunkers-too-large", "deck-saturated", "uptakes-crowded"]),
+        ]}
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod checklist {
+    use super::*;
+    use crate::{Template, Trim, BoilerType, DriveType, FuelType};
+    use crate::test_support::*;
+
+    fn failed(ship: &Ship) -> Vec<&'static str> {
+        ship.checklist().steps.iter()
+            .filter(|s| !s.pass)
+            .map(|s| s.code)
+            .collect()
+    }
+
+    fn low_decks(ship: &mut Ship) {
+        let h = &mut ship.hull;
+        (h.fd_fwd, h.fd_aft, h.ad_fwd, h.ad_aft, h.qd_fwd, h.qd_aft) = (12.0, 12.0, 12.0, 12.0, 12.0, 12.0);
+    }
+
+    // Test checklist {{{2
+    macro_rules! test_checklist {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, change) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    change(&mut ship);
+
+                    assert_eq!(expected, failed(&ship));
+                }
+            )*
+        }
+    }
+
+    test_checklist! {
+        // name:                 (failed steps, change)
+        checklist_pass:          (Vec::<&str>::new(), |_: &mut Ship| ()),
+        checklist_overweight:    (vec!["weights", "strength"], |s: &mut Ship| { s.armor.main.thick = 40.0; s.armor.deck.md = 12.0; }),
+        checklist_weak:          (vec!["strength"], |s: &mut Ship| s.wgts.hull = 4000),
+        checklist_short_belt:    (vec!["belt"], |s: &mut Ship| s.armor.main.len = 100.0),
+        checklist_unsteady:      (vec!["stability"], |s: &mut Ship| s.trim = Trim::new(0.0).unwrap()),
+        checklist_cramped:       (vec!["space"], low_decks),
+    }
+
+    #[test]
+    fn checklist_display() {
+        let mut ship = Ship { hull: get_hull(), year: 1910, ..Default::default() };
+        ship.engine.year = 1910;
+        ship.engine.fuel = FuelType::Coal;
+        ship.engine.boiler = BoilerType::Turbine;
+        ship.engine.drive = DriveType::Direct;
+        ship.engine.vmax = 21.0;
+        ship.engine.vcruise = 10.0;
+        ship.engine.range = 5000;
+        ship.engine.set_shafts(4);
+        ship.armor.main.thick = 10.0;
+        ship.armor.main.len = 100.0;
+        ship.armor.main.hgt = 8.0;
+        let checklist = ship.checklist();
+
+        assert!(!checklist.pass());
+        assert!(checklist.to_string().contains("[FAIL] Does the belt cover the vitals? Main belt covers 31% of the 35% of normal length the vitals need\n       Fix: Lengthen the main belt to cover the magazines and machinery (armor.main.len)"));
+        assert_eq!(checklist.to_string().lines().last(), Some("Passed 1 of 5 checks"));
+
+        let json = serde_json::to_value(&checklist).unwrap();
+        assert_eq!("belt", json["steps"][2]["code"]);
+        assert_eq!(false, json["steps"][2]["pass"]);
+    }
+}
@@ -0,0 +1,397 @@
+use crate::{HullMaterial, BowType, SternType, DeckType, BulkheadType, ArmorMaterial, Propulsion};
+use crate::{GunType, MountType, GunLayoutType, GunDistributionType, TrainingDrive, AmmoType};
+use crate::{TorpedoMountType, MineType, ASWType};
+use crate::{SuperstructureKind, EquipmentKind, CrewQuality, CrewModel, SeaState, StabilityStandard};
+use crate::{Template, ShipKind, Treaty, Condition, Units, ReportFormat, ReportSection};
+
+// Choice {{{1
+/// Enum whose options a user picks from, such as a mount type.
+///
+/// Frontends list variants() by their label() rather than keeping their
+/// own lists, and turn the picked label back into the option with
+/// from_label().
+///
+pub trait Choice: Sized + Clone + 'static {
+    // variants {{{2
+    /// Every option, in the order SpringSharp lists them where it does.
+    ///
+    fn variants() -> &'static [Self];
+
+    // label {{{2
+    /// Name of the option to show the user.
+    ///
+    fn label(&self) -> &'static str;
+
+    // from_label {{{2
+    /// Option with a label, ignoring case.
+    ///
+    fn from_label(label: &str) -> Option<Self> {
+        Self::variants().iter().find(|v| v.label().eq_ignore_ascii_case(label.trim())).cloned()
+    }
+
+    // labels {{{2
+    /// Label of every option, in the order of variants().
+    ///
+    fn labels() -> Vec<&'static str> {
+        Self::variants().iter().map(|v| v.label()).collect()
+    }
+}
+
+// choices {{{1
+/// Implement Choice for an enum from its variants and their labels.
+///
+/// Variants with fields give the value to list them with. The labels
+/// are matched exhaustively so a new variant without one will not build.
+///
+macro_rules! choices {
+    ($($t:ty { $($variant:ident $(($value:expr))? => $label:expr,)* })*) => {
+        $(
+            impl Choice for $t {
+                fn variants() -> &'static [Self] {
+                    &[$(Self::$variant $(($value))?,)*]
+                }
+
+                fn label(&self) -> &'static str {
+                    match self {
+                        $(Self::$variant { .. } => $label,)*
+                    }
+                }
+            }
+        )*
+    }
+}
+
+choices! {
+    // Hull {{{2
+    BowType {
+        Normal          => "Normal",
+        BulbStraight    => "Bulbous, straight",
+        BulbForward     => "Bulbous, forward",
+        Ram(0.0)        => "Ram",
+    }
+    SternType {
+        Cruiser         => "Cruiser",
+        TransomSm       => "Transom, small",
+        TransomLg       => "Transom, large",
+        Round           => "Round",
+    }
+    HullMaterial {
+        RivetedIron     => "Riveted iron",
+        RivetedSteel    => "Riveted steel",
+        PartiallyWelded => "Partially welded",
+        Welded          => "Welded",
+    }
+    SuperstructureKind {
+        Bridge          => "Bridge",
+        Funnel          => "Funnel",
+        Mast            => "Mast",
+    }
+    EquipmentKind {
+        Boats           => "Boats",
+        Paravanes       => "Paravanes",
+        SmokeGenerator  => "Smoke generator",
+        Minesweeping    => "Minesweeping gear",
+    }
+
+    // Armor {{{2
+    DeckType {
+        MultipleArmored     => "Armoured deck - multiple decks",
+        SingleArmored       => "Armoured deck - single deck",
+        MultipleProtected   => "Protected deck - multiple decks",
+        SingleProtected     => "Protected deck - single deck",
+        BoxOverMachinery    => "Box over machinery",
+        BoxOverMagazine     => "Box over magazines",
+        BoxOverBoth         => "Box over machinery & magazines",
+    }
+    BulkheadType {
+        Strengthened    => "Strengthened",
+        Additional      => "Additional",
+    }
+    ArmorMaterial {
+        WroughtIron     => "Wrought iron",
+        Compound        => "Compound",
+        Steel           => "Steel",
+    }
+
+    // Machinery {{{2
+    Propulsion {
+        None            => "None",
+        Powered         => "Powered",
+    }
+
+    // Weapons {{{2
+    GunType {
+        MuzzleLoading   => "Muzzle loading",
+        BreechLoading   => "Breech loading",
+        QuickFiring     => "Quick-firing",
+        AntiAir         => "Anti-air",
+        DualPurpose     => "Dual-purpose",
+        RapidFire       => "Automatic rapid-fire",
+        MachineGun      => "Machine gun",
+    }
+    MountType {
+        Broadside       => "Broadside",
+        ColesTurret     => "Coles/Ericsson turret",
+        OpenBarbette    => "Open barbette",
+        ClosedBarbette  => "Turret on barbette",
+        DeckAndHoist    => "Deck and hoist",
+        Deck            => "Deck",
+        Casemate        => "Casemate",
+    }
+    GunLayoutType {
+        Single          => "Single",
+        Twin2Row        => "2 row, twin",
+        Quad4Row        => "4 row, quad",
+        Twin            => "Twin",
+        TwoGun          => "2-gun",
+        Quad2Row        => "2 row, quad",
+        Triple          => "Triple",
+        ThreeGun        => "3-gun",
+        Sex2Row         => "2 row, sextuple",
+        Quad            => "Quad",
+        FourGun         => "4-gun",
+        Oct2Row         => "2 row, octuple",
+        Quint           => "Quintuple",
+        FiveGun         => "5-gun",
+        Dec2Row         => "2 row, decuple",
+    }
+    GunDistributionType {
+        CenterlineEven      => "Centerline - distributed",
+        CenterlineEndsFD    => "Centerline - ends (fore ≥ aft)",
+        CenterlineEndsAD    => "Centerline - ends (aft ≥ fore)",
+        CenterlineFDFwd     => "Centerline - foredeck forward",
+        CenterlineFD        => "Centerline - foredeck",
+        CenterlineFDAft     => "Centerline - foredeck aft",
+        CenterlineADFwd     => "Centerline - afterdeck forward",
+        CenterlineAD        => "Centerline - afterdeck",
+        CenterlineADAft     => "Centerline - afterdeck aft",
+        SidesEven           => "Sides - distributed",
+        SidesEndsFD         => "Sides - ends (fore ≥ aft)",
+        SidesEndsAD         => "Sides - ends (aft ≥ fore)",
+        SidesFDFwd          => "Sides - foredeck forward",
+        SidesFD             => "Sides - foredeck",
+        SidesFDAft          => "Sides - foredeck aft",
+        SidesADFwd          => "Sides - afterdeck forward",
+        SidesAD             => "Sides - afterdeck",
+        SidesADAft          => "Sides - afterdeck aft",
+    }
+    TrainingDrive {
+        Hand            => "Hand",
+        Hydraulic       => "Hydraulic",
+        Electric        => "Electric",
+    }
+    AmmoType {
+        AP              => "AP",
+        Common          => "Common",
+        HE              => "HE",
+    }
+    TorpedoMountType {
+        FixedTubes          => "Fixed tubes",
+        DeckSideTubes       => "Deck side tubes",
+        CenterTubes         => "Centerline tubes",
+        DeckReloads         => "Deck reloads",
+        BowTubes            => "Bow tubes",
+        SternTubes          => "Stern tubes",
+        BowAndSternTubes    => "Bow and stern tubes",
+        SubmergedSideTubes  => "Submerged side tubes",
+        SubmergedReloads    => "Submerged reloads",
+    }
+    MineType {
+        SternRails      => "Stern rails",
+        BowTubes        => "Bow tubes",
+        SternTubes      => "Stern tubes",
+        SideTubes       => "Side tubes",
+    }
+    ASWType {
+        SternRacks      => "Stern racks",
+        Throwers        => "Throwers",
+        Hedgehogs       => "Hedgehogs",
+        SquidMortars    => "Squid mortars",
+    }
+
+    // Crew and conditions {{{2
+    CrewQuality {
+        Regular         => "Regular",
+        Green           => "Green",
+        Veteran         => "Veteran",
+        Elite           => "Elite",
+    }
+    CrewModel {
+        Classic         => "Classic",
+        Adjusted        => "Era adjusted",
+        Override(0)     => "User set",
+    }
+    SeaState {
+        Calm            => "Calm",
+        Light           => "Light",
+        Moderate        => "Moderate",
+        Heavy           => "Heavy",
+    }
+    StabilityStandard {
+        SpringSharp     => "SpringSharp",
+        Simplified      => "Simplified",
+        Both            => "Both",
+    }
+    Condition {
+        Standard        => "Standard",
+        Trial           => "Trial",
+        Normal          => "Normal",
+        Full            => "Full load",
+        Overload        => "Emergency overload",
+    }
+
+    // Ships {{{2
+    Template {
+        CentralBattery1870      => "Central battery ironclad (1870)",
+        ProtectedCruiser1895    => "Protected cruiser (1895)",
+        Dreadnought1910         => "Dreadnought battleship (1910)",
+        TreatyCruiser1930       => "Treaty heavy cruiser (1930)",
+        Destroyer1935           => "Fleet destroyer (1935)",
+        FastBattleship1940      => "Fast battleship (1940)",
+    }
+    ShipKind {
+        Battleship      => "Battleship",
+        Cruiser         => "Cruiser",
+        Destroyer       => "Destroyer",
+        Other           => "Other",
+    }
+    Treaty {
+        Washington      => "Washington Naval Treaty",
+        London          => "London Naval Treaty",
+    }
+
+    // Reports {{{2
+    Units {
+        Imperial        => "Imperial",
+        Metric          => "Metric",
+    }
+    ReportFormat {
+        Text            => "Text",
+        Markdown        => "Markdown",
+        Html            => "HTML",
+        Json            => "JSON",
+        BBCode          => "BBCode",
+    }
+    ReportSection {
+        Header          => "Header",
+        Displacement    => "Displacement",
+        Dimensions      => "Dimensions",
+        Armament        => "Armament",
+        Armor           => "Armor",
+        Machinery       => "Machinery",
+        Complement      => "Complement",
+        Cost            => "Cost",
+        Weights         => "Weights",
+        Survivability   => "Survivability",
+        HullForm        => "Hull form",
+        Strength        => "Strength",
+        Notes           => "Notes",
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod choice {
+    use super::*;
+
+    // Test labels {{{2
+    macro_rules! test_labels {
+        ($($name:ident: $t:ty,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let labels = <$t>::labels();
+                    assert!(!labels.is_empty());
+
+                    for (i, label) in labels.iter().enumerate() {
+                        // Labels are unique and lead back to their option
+                        assert!(!labels[..i].contains(label), "{}", label);
+                        assert_eq!(*label, <$t>::from_label(label).unwrap().label());
+                        assert_eq!(*label, <$t>::from_label(&label.to_uppercase()).unwrap().label());
+                    }
+
+                    assert!(<$t>::from_label("No such option").is_none());
+                }
+            )*
+        }
+    }
+
+    test_labels! {
+        // name:                        type
+        labels_bow:                     BowType,
+        labels_stern:                   SternType,
+        labels_hull_material:           HullMaterial,
+        labels_superstructure:          SuperstructureKind,
+        labels_equipment:               EquipmentKind,
+        labels_deck:                    DeckType,
+        labels_bulkhead:                BulkheadType,
+        labels_armor_material:          ArmorMaterial,
+        labels_propulsion:              Propulsion,
+        labels_gun:                     GunType,
+        labels_mount:                   MountType,
+        labels_layout:                  GunLayoutType,
+        labels_distribution:            GunDistributionType,
+        labels_training:                TrainingDrive,
+        labels_ammo:                    AmmoType,
+        labels_torpedo_mount:           TorpedoMountType,
+        labels_mine:                    MineType,
+        labels_asw:                     ASWType,
+        labels_crew_quality:            CrewQuality,
+        labels_crew_model:              CrewModel,
+        labels_sea_state:               SeaState,
+        labels_stability_standard:      StabilityStandard,
+        labels_condition:               Condition,
+        labels_template:                Template,
+        labels_ship_kind:               ShipKind,
+        labels_treaty:                  Treaty,
+        labels_units:                   Units,
+        labels_report_format:           ReportFormat,
+        labels_report_section:          ReportSection,
+    }
+
+    // Test SpringSharp order {{{2
+    macro_rules! test_order {
+        ($($name:ident: $t:ty,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    // Options are listed in the order of their index in
+                    // SpringSharp files
+                    for (i, v) in <$t>::variants().iter().enumerate() {
+                        assert_eq!(v.label(), <$t>::from(i.to_string()).label());
+                    }
+                }
+            )*
+        }
+    }
+
+    test_order! {
+        // name:                    type
+        order_bow:                  BowType,
+        order_stern:                SternType,
+        order_deck:                 DeckType,
+        order_gun:                  GunType,
+        order_mount:                MountType,
+        order_layout:               GunLayoutType,
+        order_distribution:         GunDistributionType,
+        order_torpedo_mount:        TorpedoMountType,
+        order_mine:                 MineType,
+        order_asw:                  ASWType,
+        order_crew_quality:         CrewQuality,
+        order_sea_state:            SeaState,
+        order_stability_standard:   StabilityStandard,
+        order_template:             Template,
+        order_ship_kind:            ShipKind,
+        order_units:                Units,
+    }
+
+    // Test variants {{{2
+    #[test]
+    fn variants() {
+        assert_eq!(Template::ALL.to_vec(), Template::variants());
+        assert_eq!(Condition::ALL.to_vec(), Condition::variants());
+        assert_eq!(ReportSection::ALL.to_vec(), ReportSection::variants());
+        assert_eq!(Some(BowType::Ram(0.0)), BowType::from_label("ram"));
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{Ship, Hull};
+
+impl Ship { // {{{1
+    /// Share of a flooded compartment's volume taken up by water.
+    pub const PERMEABILITY: f64 = 0.85;
+    /// Freeboard (ft) left at the deck edge when flooding is just survived.
+    pub const FLOOD_MARGIN: f64 = 0.25;
+
+    // compartments {{{2
+    /// Number of main transverse watertight compartments, as given or
+    /// estimated from the length, year and type of ship.
+    ///
+    pub fn compartments(&self) -> u32 {
+        match self.compartments {
+            Some(n) => n.max(1),
+            None    => self.compartments_default(),
+        }
+    }
+
+    // compartments_default {{{2
+    /// Typical number of main compartments for the ship.
+    ///
+    /// Main transverse bulkheads were about 40 ft apart in early ironclads
+    /// and closed up to 30 ft by the dreadnought era. Merchant hulls were
+    /// built with fewer, about 60 ft apart.
+    ///
+    pub fn compartments_default(&self) -> u32 {
+        let spacing = if self.conversion.is_some() {
+            60.0
+        } else if self.year < 1890 {
+            40.0
+        } else if self.year < 1905 {
+            35.0
+        } else {
+            30.0
+        };
+
+        u32::max((self.hull.lwl() / spacing).round() as u32, 3)
+    }
+
+    // compartment_len {{{2
+    /// Average length of a main compartment (ft).
+    ///
+    pub fn compartment_len(&self) -> f64 {
+        self.hull.lwl() / self.compartments() as f64
+    }
+
+    // flood_sinkage {{{2
+    /// Parallel sinkage (ft) with num adjacent compartments amidships
+    /// flooded, by the lost buoyancy method.
+    ///
+    /// The flooded compartments no longer add to the waterplane, so the
+    /// water they let in has to be made up by the rest of it. Trim and the
+    /// loss of stability are not considered.
+    ///
+    pub fn flood_sinkage(&self, num: u32) -> f64 {
+        let len = self.compartment_len() * u32::min(num, self.compartments()) as f64;
+
+        let vol  = Self::PERMEABILITY * len * self.hull.b * self.hull.t * Hull::cm(self.hull.cb());
+        let lost = Self::PERMEABILITY * len * self.hull.b;
+
+        match self.wp() - lost {
+            wp if wp <= 0.0 => f64::INFINITY, // catch divide by zero
+            wp              => vol / wp,
+        }
+    }
+
+    // floodable_compartments {{{2
+    /// Most adjacent main compartments that can flood without the deck
+    /// edge going under.
+    ///
+    pub fn floodable_compartments(&self) -> u32 {
+        let free = self.hull.freeboard_dist() - Self::FLOOD_MARGIN;
+
+        (1..=self.compartments())
+            .take_while(|n| self.flood_sinkage(*n) <= free)
+            .last()
+            .unwrap_or(0)
+    }
+
+    // flooding_standard {{{2
+    /// Describe the subdivision against the one and two compartment
+    /// standards.
+    ///
+    pub fn flooding_standard(&self) -> String {
+        match self.floodable_compartments() {
+            0 => "Below one compartment standard".into(),
+            1 => "One compartment standard".into(),
+            n => format!("Two compartment standard ({} adjacent compartments can flood)", n),
+        }
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod compartments {
+    use super::*;
+    use crate::{Conversion, Tons};
+    use crate::test_support::*;
+
+    // Test compartments_default {{{2
+    macro_rules! test_compartments_default {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year, lwl, conversion) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull();
+                    ship.year = year;
+                    ship.hull.set_lwl(lwl);
+                    ship.conversion = conversion;
+
+                    assert_eq!(expected, ship.compartments_default());
+                }
+            )*
+        }
+    }
+
+    test_compartments_default! {
+        // name:                    (compartments, year, lwl, conversion)
+        compartments_ironclad:      (8, 1870, 320.0, None),
+        compartments_predreadnought:(11, 1895, 400.0, None),
+        compartments_dreadnought:   (18, 1910, 540.0, None),
+        compartments_merchant:      (9, 1910, 540.0, Some(Conversion::default())),
+        compartments_small:         (3, 1910, 60.0, None),
+    }
+
+    // Test compartments {{{2
+    #[test]
+    fn compartments() {
+        let mut ship = Ship { hull: get_hull(), year: 1910, ..Default::default() };
+        assert_eq!(17, ship.compartments());
+
+        ship.compartments = Some(12);
+        assert_eq!(12, ship.compartments());
+        assert_eq!(to_place(ship.hull.lwl() / 12.0, 2), to_place(ship.compartment_len(), 2));
+
+        // A hull is at least one compartment
+        ship.compartments = Some(0);
+        assert_eq!(1, ship.compartments());
+    }
+
+    // Test flood_sinkage {{{2
+    macro_rules! test_flood_sinkage {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, d, compartments, flooded) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull();
+                    ship.hull.set_d(Tons::long(d));
+                    ship.compartments = Some(compartments);
+
+                    assert_eq!(expected, to_place(ship.flood_sinkage(flooded), 2));
+                }
+            )*
+        }
+    }
+
+    test_flood_sinkage! {
+        // name:                (sinkage, d, compartments, flooded)
+        flood_sinkage_one:      (0.53, 7000.0, 17, 1),
+        flood_sinkage_two:      (1.11, 7000.0, 17, 2),
+        flood_sinkage_long:     (1.19, 7000.0, 8, 1),
+        flood_sinkage_fine:     (0.80, 3500.0, 17, 1),
+        // A fine hull flooded from end to end has no waterplane left
+        flood_sinkage_all:      (f64::INFINITY, 3500.0, 17, 17),
+    }
+
+    // Test flooding_standard {{{2
+    macro_rules! test_flooding_standard {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, compartments, freeboard) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull();
+                    ship.compartments = Some(compartments);
+                    let sinkage = |n| ship.flood_sinkage(n) + Ship::FLOOD_MARGIN;
+                    let free = freeboard(sinkage(1), sinkage(2));
+                    ship.hull.fd_fwd = free; ship.hull.fd_aft = free;
+                    ship.hull.ad_fwd = free; ship.hull.ad_aft = free;
+
+                    assert!(ship.flooding_standard().starts_with(expected), "{}", ship.flooding_standard());
+                }
+            )*
+        }
+    }
+
+    test_flooding_standard! {
+        // name:            (standard, compartments, freeboard(one, two))
+        flooding_below:     ("Below one", 18, |one: f64, _: f64| one * 0.9),
+        flooding_one:       ("One compartment", 18, |one: f64, two: f64| (one + two) / 2.0),
+        flooding_two:       ("Two compartment", 18, |_: f64, two: f64| two * 1.1),
+    }
+}
@@ -0,0 +1,245 @@
+use crate::{Ship, Units};
+
+use serde::{Serialize, Deserialize};
+
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::path::PathBuf;
+
+// Config {{{1
+/// User's preferences, kept between runs in a small JSON file in the
+/// standard configuration directory.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    /// Country of new ships.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// Kind of new ships, such as battleship.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// Year new ships are laid down.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub year: Option<u32>,
+    /// Name signed at the foot of reports.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub designer: Option<String>,
+    /// Units reports are limited to, or None for both.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<Units>,
+}
+
+impl Config { // {{{2
+    /// Every preference that can be viewed or set by name.
+    pub const KEYS: [&'static str; 5] = ["country", "kind", "year", "designer", "units"];
+    /// Name of the file in sharpie's configuration directory.
+    const FILE_NAME: &'static str = "config.json";
+
+    // path {{{3
+    /// Location of the configuration file: `sharpie/config.json` under
+    /// `$XDG_CONFIG_HOME` or `~/.config`, or `%APPDATA%` on Windows.
+    ///
+    pub fn path() -> Option<PathBuf> {
+        let dir = if cfg!(windows) {
+            env::var_os("APPDATA").map(PathBuf::from)
+        } else {
+            env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()).map(PathBuf::from)
+                .or_else(|| env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))
+        };
+
+        dir.map(|d| d.join("sharpie").join(Self::FILE_NAME))
+    }
+
+    // load {{{3
+    /// Load the user's preferences, or the defaults if none have been
+    /// saved.
+    ///
+    pub fn load() -> Result<Config, Box<dyn Error>> {
+        match Self::path() {
+            Some(path) => Self::load_from(path),
+            None       => Ok(Config::default()),
+        }
+    }
+
+    // load_from {{{3
+    /// Load preferences from a file, or the defaults if it does not exist.
+    ///
+    pub fn load_from(path: PathBuf) -> Result<Config, Box<dyn Error>> {
+        if !path.exists() { return Ok(Config::default()); }
+
+        match serde_json::from_str(&fs::read_to_string(&path)?) {
+            Ok(config) => Ok(config),
+            Err(err)   => Err(format!("{}: {}", path.display(), err).into()),
+        }
+    }
+
+    // save {{{3
+    /// Save the user's preferences.
+    ///
+    pub fn save(&self) -> Result<(), Box<dyn Error>> {
+        match Self::path() {
+            Some(path) => self.save_to(path),
+            None       => Err("No configuration directory found".into()),
+        }
+    }
+
+    // save_to {{{3
+    /// Save preferences to a file, creating its directory if need be.
+    ///
+    pub fn save_to(&self, path: PathBuf) -> Result<(), Box<dyn Error>> {
+        if let Some(dir) = path.parent() { fs::create_dir_all(dir)?; }
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+
+        Ok(())
+    }
+
+    // get {{{3
+    /// Value of a preference, or None if it is not set.
+    ///
+    pub fn get(&self, key: &str) -> Result<Option<String>, Box<dyn Error>> {
+        Ok(match key {
+            "country"  => self.country.clone(),
+            "kind"     => self.kind.clone(),
+            "year"     => self.year.map(|y| y.to_string()),
+            "designer" => self.designer.clone(),
+            "units"    => self.units.map(|u| u.to_string()),
+            _          => return Err(Self::unknown(key)),
+        })
+    }
+
+    // set {{{3
+    /// Set a preference from its text, or unset it if the text is empty.
+    ///
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+        let text = if value.is_empty() { None } else { Some(value.to_string()) };
+
+        match key {
+            "country"  => self.country = text,
+            "kind"     => self.kind = text,
+            "designer" => self.designer = text,
+            "year"     => self.year = match text {
+                Some(y) => Some(y.parse().map_err(|_| format!("year must be a whole number, not {}", y))?),
+                None    => None,
+            },
+            "units"    => self.units = match value {
+                "imperial"    => Some(Units::Imperial),
+                "metric"      => Some(Units::Metric),
+                "both" | ""   => None,
+                _             => return Err(format!("units must be imperial, metric or both, not {}", value).into()),
+            },
+            _          => return Err(Self::unknown(key)),
+        }
+
+        Ok(())
+    }
+
+    // unknown {{{3
+    /// Error for a preference that does not exist.
+    ///
+    fn unknown(key: &str) -> Box<dyn Error> {
+        format!("No preference named {}, use one of {}", key, Self::KEYS.join(", ")).into()
+    }
+}
+
+impl Ship { // {{{1
+    // new_with {{{2
+    /// Create an empty ship as new() does with the user's preferred
+    /// country, kind and year, laid down in year if no year is preferred.
+    ///
+    pub fn new_with(config: &Config, year: u32) -> Ship {
+        let mut ship = Ship::new(config.year.unwrap_or(year));
+
+        if let Some(country) = &config.country { ship.country = country.clone(); }
+        if let Some(kind) = &config.kind { ship.kind = kind.clone(); }
+
+        ship
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod config {
+    use super::*;
+
+    // Test set {{{2
+    macro_rules! test_set {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, key, value) = $value;
+
+                    let mut config = Config::default();
+                    config.set(key, value).unwrap();
+
+                    assert_eq!(expected, config.get(key).unwrap());
+                }
+            )*
+        }
+    }
+
+    test_set! {
+        // name:            (expected, key, value)
+        set_country:        (Some("Germany".to_string()), "country", "Germany"),
+        set_kind:           (Some("cruiser".to_string()), "kind", "cruiser"),
+        set_year:           (Some("1912".to_string()), "year", "1912"),
+        set_designer:       (Some("W. White".to_string()), "designer", "W. White"),
+        set_units:          (Some("metric".to_string()), "units", "metric"),
+        set_units_both:     (None, "units", "both"),
+        set_unset:          (None, "country", ""),
+    }
+
+    // Test set errors {{{2
+    macro_rules! test_set_error {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (key, value) = $value;
+
+                    assert!(Config::default().set(key, value).is_err());
+                }
+            )*
+        }
+    }
+
+    test_set_error! {
+        // name:                (key, value)
+        set_error_key:          ("colour", "grey"),
+        set_error_year:         ("year", "nineteen-ten"),
+        set_error_units:        ("units", "furlongs"),
+    }
+
+    // Test save and load {{{2
+    #[test]
+    fn save_load() {
+        let path = env::temp_dir().join(format!("sharpie-config-{}", std::process::id())).join(Config::FILE_NAME);
+
+        // Missing files give the defaults
+        assert_eq!(Config::default(), Config::load_from(path.clone()).unwrap());
+
+        let mut config = Config::default();
+        config.set("country", "Italy").unwrap();
+        config.set("units", "imperial").unwrap();
+        config.save_to(path.clone()).unwrap();
+
+        assert_eq!(config, Config::load_from(path.clone()).unwrap());
+
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    // Test new_with {{{2
+    #[test]
+    fn new_with() {
+        let config = Config { country: Some("France".into()), year: Some(1905), ..Default::default() };
+        let ship = Ship::new_with(&config, 1920);
+
+        assert_eq!("France", ship.country);
+        assert_eq!("", ship.kind);
+        assert_eq!((1905, 1905, 1905), (ship.year, ship.engine.year, ship.mines.year));
+
+        assert_eq!(1920, Ship::new_with(&Config::default(), 1920).year);
+    }
+}
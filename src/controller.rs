@@ -0,0 +1,219 @@
+use crate::{Ship, BatchConversion, FileFormat, NumberFormat, Provenance, ReportOptions};
+
+use std::error::Error;
+use std::sync::Arc;
+
+// Action {{{1
+/// Something a frontend asks the controller to do.
+///
+#[derive(Clone, Debug)]
+pub enum Action {
+    /// Open a ship file of any format sharpie recognizes.
+    Open(String),
+    /// Read a ship from the contents of a file, such as one piped to stdin.
+    Read(String),
+    /// Convert a SpringSharp 3 file whose numbers are in the given format.
+    Convert(String, NumberFormat),
+    /// Save the current ship to a sharpie file.
+    Save(String),
+    /// Convert each SpringSharp file to a sharpie file alongside it.
+    ConvertAll(Vec<String>),
+    /// Write a report on the current ship.
+    Report(ReportOptions),
+}
+
+// Outcome {{{1
+/// What came of an Action.
+///
+#[derive(Clone, Debug)]
+pub enum Outcome {
+    /// A ship was opened or read and is now the current ship.
+    Opened(FileFormat),
+    /// A SpringSharp file was converted and is now the current ship,
+    /// along with the line each field was read from.
+    Converted(Vec<Provenance>),
+    /// The current ship was saved to the file.
+    Saved(String),
+    /// Files were converted. The first ship that could be opened is now
+    /// the current ship, if any could be. Holds the result for each file,
+    /// including those that failed.
+    ConvertedAll(BatchConversion),
+    /// Report on the current ship.
+    Report(String),
+}
+
+// Controller {{{1
+/// Loads, converts and saves ships for the GUI and command line so
+/// they share the same steps and errors.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Controller {
+    /// Ship the last action opened, if any.
+    pub ship: Option<Arc<Ship>>,
+}
+
+impl Controller { // {{{2
+    // run {{{3
+    /// Carry out an action.
+    ///
+    pub fn run(&mut self, action: Action) -> Result<Outcome, Box<dyn Error>> {
+        match action {
+            Action::Open(file) => {
+                let (ship, format) = Ship::open_any(file)?;
+                self.ship = Some(Arc::new(ship));
+
+                Ok(Outcome::Opened(format))
+            },
+
+            Action::Read(s) => {
+                let (ship, format) = Ship::read_any(&s)?;
+                self.ship = Some(Arc::new(ship));
+
+                Ok(Outcome::Opened(format))
+            },
+
+            Action::Convert(file, format) => {
+                let result = Ship::convert_with_format(file, format)?;
+                let provenance = result.provenance().to_vec();
+                self.ship = Some(Arc::new(result.into_ship()));
+
+                Ok(Outcome::Converted(provenance))
+            },
+
+            Action::Save(file) => {
+                self.current()?.save(file.clone())?;
+
+                Ok(Outcome::Saved(file))
+            },
+
+            Action::ConvertAll(files) => {
+                let (batch, first) = BatchConversion::run(&files);
+                if let Some(ship) = first { self.ship = Some(Arc::new(ship)); }
+
+                Ok(Outcome::ConvertedAll(batch))
+            },
+
+            Action::Report(options) => Ok(Outcome::Report(self.current()?.report_with(&options)?)),
+        }
+    }
+
+    // current {{{3
+    /// The current ship, or an error if there is none.
+    ///
+    fn current(&self) -> Result<&Ship, Box<dyn Error>> {
+        match &self.ship {
+            Some(ship) => Ok(ship),
+            None       => Err("No ship has been opened".into()),
+        }
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod controller {
+    use super::*;
+    use crate::{BatchStatus, ReportFormat};
+
+    use std::fs;
+
+    // Test open {{{2
+    macro_rules! test_open {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, file) = $value;
+
+                    let mut controller = Controller::default();
+                    match controller.run(Action::Open(file.into())).unwrap() {
+                        Outcome::Opened(format) => assert_eq!(expected, format),
+                        outcome => panic!("{:?}", outcome),
+                    }
+                    assert!(controller.ship.is_some());
+                }
+            )*
+        }
+    }
+
+    test_open! {
+        // name:            (format, file)
+        open_sharpie:       (FileFormat::Sharpie, "tests/fixtures/dreadnought_1915.ship"),
+        open_springsharp:   (FileFormat::SpringSharp, "tests/fixtures/dreadnought_1915.sship"),
+    }
+
+    // Test errors {{{2
+    #[test]
+    fn errors() {
+        let mut controller = Controller::default();
+
+        // Nothing to save or report on until a ship is opened
+        assert!(controller.run(Action::Save("unused.ship".into())).is_err());
+        assert!(controller.run(Action::Report(ReportOptions::default())).is_err());
+
+        // A failed open keeps the current ship
+        controller.run(Action::Open("tests/fixtures/destroyer_1935.ship".into())).unwrap();
+        assert!(controller.run(Action::Open("tests/fixtures/no_such_file.ship".into())).is_err());
+        assert!(controller.run(Action::Convert("tests/fixtures/destroyer_1935.ship".into(), NumberFormat::Auto)).is_err());
+        assert!(controller.ship.is_some());
+    }
+
+    // Test convert and report {{{2
+    #[test]
+    fn convert_report() {
+        let mut controller = Controller::default();
+
+        match controller.run(Action::Convert("tests/fixtures/dreadnought_1915.sship".into(), NumberFormat::Auto)).unwrap() {
+            Outcome::Converted(provenance) => assert!(!provenance.is_empty()),
+            outcome => panic!("{:?}", outcome),
+        }
+
+        let options = ReportOptions { format: ReportFormat::Json, ..Default::default() };
+        match controller.run(Action::Report(options.clone())).unwrap() {
+            Outcome::Report(report) => assert_eq!(controller.ship.as_ref().unwrap().report_with(&options).unwrap(), report),
+            outcome => panic!("{:?}", outcome),
+        }
+    }
+
+    // Test read {{{2
+    #[test]
+    fn read() {
+        let s = fs::read_to_string("tests/fixtures/dreadnought_1915.sship").unwrap();
+
+        let mut controller = Controller::default();
+        assert!(matches!(controller.run(Action::Read(s)).unwrap(), Outcome::Opened(FileFormat::SpringSharp)));
+    }
+
+    // Test convert_all {{{2
+    #[test]
+    fn convert_all() {
+        let dir = std::env::temp_dir().join(format!("sharpie-convert-all-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let from = dir.join("dreadnought.sship").to_string_lossy().into_owned();
+        fs::copy("tests/fixtures/dreadnought_1915.sship", &from).unwrap();
+        let files = vec![from.clone(), "tests/fixtures/destroyer_1935.ship".into(), "no_such_file.sship".into()];
+
+        let mut controller = Controller::default();
+        let Outcome::ConvertedAll(batch) = controller.run(Action::ConvertAll(files.clone())).unwrap() else { panic!() };
+        let summary: Vec<String> = batch.to_string().lines().map(String::from).collect();
+        assert_eq!("Converted 1 of 3 files", summary[3]);
+        assert!(summary[0].contains("converted to"), "{}", summary[0]);
+        assert!(summary[1].contains("nothing to convert"), "{}", summary[1]);
+        assert_eq!(1, batch.failed);
+        assert!(dir.join("dreadnought.ship").exists());
+        assert!(controller.ship.is_some());
+
+        // An existing sharpie file is never replaced
+        let Outcome::ConvertedAll(batch) = controller.run(Action::ConvertAll(files)).unwrap() else { panic!() };
+        assert_eq!(0, batch.converted);
+        assert_eq!(BatchStatus::Exists, batch.files[0].status);
+        assert!(batch.files[0].to_string().contains("already exists"), "{}", batch.files[0]);
+
+        // Saving writes the current ship
+        let to = dir.join("saved.ship").to_string_lossy().into_owned();
+        assert!(matches!(controller.run(Action::Save(to.clone())).unwrap(), Outcome::Saved(_)));
+        assert!(Ship::load(to).is_ok());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
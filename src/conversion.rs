@@ -0,0 +1,148 @@
+use crate::{Ship, Tons};
+
+use serde::{Serialize, Deserialize};
+
+// Conversion {{{1
+/// Merchant hull converted to an auxiliary such as an armed merchant
+/// cruiser.
+///
+/// Merchant hulls are built light to commercial standards. The hull takes
+/// a fixed share of the displacement instead of whatever the warship
+/// weights leave over, and the rest is kept for cargo.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Conversion {
+    /// Share of normal displacement taken by the hull, fittings and
+    /// equipment.
+    pub hull_fraction: f64,
+    /// Cargo carried at normal displacement (t).
+    #[serde(default)]
+    pub cargo: f64,
+}
+
+impl Default for Conversion { // {{{2
+    fn default() -> Self {
+        Conversion { hull_fraction: Self::HULL_FRACTION, cargo: 0.0 }
+    }
+}
+
+impl Conversion { // {{{2
+    /// Typical share of displacement taken by a merchant hull.
+    pub const HULL_FRACTION: f64 = 0.3;
+    /// Range of hull fractions found in merchant ships.
+    pub const HULL_FRACTIONS: (f64, f64) = (0.2, 0.5);
+    /// Weakest composite strength a merchant hull is built to.
+    pub const MIN_STR: f64 = 0.3;
+}
+
+impl Ship { // {{{1
+    // min_str {{{2
+    /// Weakest composite strength the hull can have, which is lower for
+    /// merchant hulls.
+    ///
+    pub fn min_str(&self) -> f64 {
+        match self.conversion {
+            Some(_) => Conversion::MIN_STR,
+            None    => Ship::MIN_STR,
+        }
+    }
+
+    // wgt_cargo {{{2
+    /// Weight of cargo carried by a converted merchant ship.
+    ///
+    pub fn wgt_cargo(&self) -> Tons {
+        match &self.conversion {
+            Some(c) => Tons::long(c.cargo),
+            None    => Tons::default(),
+        }
+    }
+
+    // wgt_spare {{{2
+    /// Displacement of a converted merchant ship not taken by its hull,
+    /// weapons, machinery, load or cargo. Negative if they weigh more
+    /// than the displacement.
+    ///
+    /// A warship's hull takes up whatever is left, so it has none.
+    ///
+    pub fn wgt_spare(&self) -> Tons {
+        match &self.conversion {
+            Some(c) => self.hull.d() * (1.0 - c.hull_fraction) - Tons::long(self.wgt_fitted()),
+            None    => Tons::default(),
+        }
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod conversion {
+    use super::*;
+    use crate::Template;
+    use crate::test_support::*;
+
+    fn amc(hull_fraction: f64, cargo: f64) -> Ship {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.conversion = Some(Conversion { hull_fraction, cargo });
+
+        ship
+    }
+
+    // Test wgt_spare {{{2
+    #[test]
+    fn wgt_spare() {
+        let warship = Ship::from_template(Template::Dreadnought1910);
+        assert_eq!(0.0, warship.wgt_spare().to_long());
+        assert_eq!(0.0, warship.wgt_cargo().to_long());
+
+        // Cargo comes out of the spare displacement
+        let (empty, loaded) = (amc(0.3, 0.0), amc(0.3, 500.0));
+        assert_eq!(to_place(empty.wgt_spare().to_long() - 500.0, 2), to_place(loaded.wgt_spare().to_long(), 2));
+
+        // A merchant hull is lighter than a warship's
+        assert!(empty.wgt_spare().to_long() > 0.0);
+        assert_eq!(to_place(warship.hull.d().to_long() * 0.3, 2), to_place(empty.wgt_hull(), 2));
+        assert!(empty.wgt_hull() < warship.wgt_hull());
+    }
+
+    // Test report {{{2
+    #[test]
+    fn report() {
+        let ship = amc(0.3, 500.0);
+        let report = ship.report();
+
+        assert!(report.contains("Hull, fittings & equipment: ") && report.contains(" (merchant hull)"));
+        assert!(report.contains("Fuel, ammunition & stores, cargo: "));
+        assert!(report.contains("    - Cargo: 500 tons, "));
+        assert!(report.contains("    Unused displacement: "));
+
+        let json: serde_json::Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+        assert_eq!(0.3, json["conversion"]["hull_fraction"].as_f64().unwrap());
+        assert_eq!(500.0, json["conversion"]["cargo"]["value"].as_f64().unwrap());
+
+        let warship = Ship::from_template(Template::Dreadnought1910);
+        assert!(!warship.report().contains("merchant hull"));
+        assert!(!warship.report().contains("Unused displacement"));
+    }
+
+    // Test min_str {{{2
+    macro_rules! test_min_str {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, conversion) = $value;
+
+                    let ship = Ship { conversion, ..Ship::from_template(Template::Destroyer1935) };
+
+                    assert_eq!(expected, ship.min_str());
+                }
+            )*
+        }
+    }
+
+    test_min_str! {
+        // name:            (min_str, conversion)
+        min_str_warship:    (Ship::MIN_STR, None),
+        min_str_merchant:   (Conversion::MIN_STR, Some(Conversion::default())),
+    }
+}
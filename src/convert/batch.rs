@@ -0,0 +1,203 @@
+use crate::{Ship, Diagnostic, FileFormat, SHIP_FILE_EXT};
+
+use serde::Serialize;
+
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+
+// BatchStatus {{{1
+/// What came of converting one file of a batch.
+///
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum BatchStatus {
+    /// Converted and saved as a sharpie file.
+    Converted,
+    /// Opened but not a SpringSharp file, so there was nothing to do.
+    Unchanged,
+    /// Not converted because the sharpie file already exists.
+    Exists,
+    /// Could not be opened, converted or saved.
+    Failed,
+}
+
+// FileConversion {{{1
+/// Result of converting one file of a batch.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct FileConversion {
+    /// File that was converted.
+    pub file: String,
+    /// What came of it.
+    pub status: BatchStatus,
+    /// Format the file was found to be in, if it could be opened.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<FileFormat>,
+    /// Sharpie file converted to, or that already existed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    /// Why the file failed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diagnostic: Option<Diagnostic>,
+}
+
+impl fmt::Display for FileConversion { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let to = self.to.as_deref().unwrap_or_default();
+
+        match (self.status, &self.diagnostic, self.format) {
+            (BatchStatus::Failed, Some(d), _) => write!(f, "{}: {}", self.file, d),
+            (BatchStatus::Failed, None, _)    => write!(f, "{}: failed", self.file),
+            (BatchStatus::Exists, _, _)       => write!(f, "{}: not converted, {} already exists", self.file, to),
+            (BatchStatus::Unchanged, _, Some(format)) => write!(f, "{}: {} file, nothing to convert", self.file, format),
+            (BatchStatus::Unchanged, _, None) => write!(f, "{}: nothing to convert", self.file),
+            (BatchStatus::Converted, _, _)    => write!(f, "{}: converted to {}", self.file, to),
+        }
+    }
+}
+
+impl FileConversion { // {{{2
+    // failed {{{3
+    /// Result for a file that could not be opened, converted or saved.
+    ///
+    fn failed(file: &str, format: Option<FileFormat>, diagnostic: Diagnostic) -> FileConversion {
+        FileConversion { file: file.to_string(), status: BatchStatus::Failed, format, to: None, diagnostic: Some(diagnostic) }
+    }
+
+    // convert {{{3
+    /// Convert a SpringSharp file to a sharpie file alongside it, never
+    /// replacing an existing file, and return the ship if it could be
+    /// opened.
+    ///
+    fn convert(file: &str) -> (FileConversion, Option<Ship>) {
+        let (ship, format) = match Ship::open_any(file.to_string()) {
+            Ok(result) => result,
+            Err(error) => return (Self::failed(file, None, Diagnostic::from_error(error.as_ref())), None),
+        };
+
+        let mut result = FileConversion { file: file.to_string(), status: BatchStatus::Unchanged, format: Some(format), to: None, diagnostic: None };
+        if format != FileFormat::SpringSharp { return (result, Some(ship)); }
+
+        let to = Path::new(file).with_extension(SHIP_FILE_EXT).to_string_lossy().into_owned();
+        if Path::new(&to).exists() {
+            result.status = BatchStatus::Exists;
+        } else if let Err(error) = ship.save(to.clone()) {
+            return (Self::failed(file, Some(format), Diagnostic::from_error(error.as_ref())), Some(ship));
+        } else {
+            result.status = BatchStatus::Converted;
+        }
+        result.to = Some(to);
+
+        (result, Some(ship))
+    }
+}
+
+// BatchConversion {{{1
+/// Results of converting many files, one for each file whether or not
+/// it could be converted.
+///
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct BatchConversion {
+    /// Result for each file in the order given.
+    pub files: Vec<FileConversion>,
+    /// Number of files converted.
+    pub converted: usize,
+    /// Number of files that failed.
+    pub failed: usize,
+}
+
+impl fmt::Display for BatchConversion { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for file in self.files.iter() {
+            writeln!(f, "{}", file)?;
+        }
+        write!(f, "Converted {} of {} files", self.converted, self.files.len())
+    }
+}
+
+impl BatchConversion { // {{{2
+    // run {{{3
+    /// Convert each SpringSharp file to a sharpie file alongside it and
+    /// return the results along with the first ship that could be
+    /// opened, if any could be.
+    ///
+    /// A file that cannot be read, has a bad value or even makes the
+    /// converter panic fails on its own and the rest are still converted.
+    ///
+    pub fn run(files: &[String]) -> (BatchConversion, Option<Ship>) {
+        let mut batch = BatchConversion::default();
+        let mut first = None;
+
+        for file in files {
+            let (result, ship) = match panic::catch_unwind(AssertUnwindSafe(|| FileConversion::convert(file))) {
+                Ok(converted) => converted,
+                Err(payload) => {
+                    let reason = payload.downcast_ref::<&str>().map(|s| s.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "unknown error".into());
+
+                    (FileConversion::failed(file, None, Diagnostic { line: None, field: None, reason: format!("Converter failed: {}", reason) }), None)
+                },
+            };
+
+            match result.status {
+                BatchStatus::Converted => batch.converted += 1,
+                BatchStatus::Failed    => batch.failed += 1,
+                _ => (),
+            }
+            if first.is_none() { first = ship; }
+            batch.files.push(result);
+        }
+
+        (batch, first)
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod batch {
+    use super::*;
+
+    use std::fs;
+
+    // Test run {{{2
+    #[test]
+    fn run() {
+        let dir = std::env::temp_dir().join(format!("sharpie-batch-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+
+        let text = fs::read_to_string("tests/fixtures/dreadnought_1915.sship").unwrap();
+        let mut lines: Vec<&str> = text.lines().collect();
+        fs::write(path("good.sship"), &text).unwrap();
+        fs::write(path("short.sship"), lines[..20].join("\n")).unwrap();
+        lines[15] = "wide";
+        fs::write(path("bad.sship"), lines.join("\n")).unwrap();
+
+        let files = vec![path("short.sship"), path("bad.sship"), path("good.sship"), "no_such_file.sship".into()];
+        let (batch, first) = BatchConversion::run(&files);
+
+        assert_eq!((1, 3), (batch.converted, batch.failed));
+        assert_eq!(files.len(), batch.files.len());
+        assert_eq!("Queen Elizabeth", first.unwrap().name);
+
+        let short = batch.files[0].diagnostic.as_ref().unwrap();
+        assert_eq!((Some(21), "Unexpected end of file"), (short.line, short.reason.as_str()));
+
+        let bad = batch.files[1].diagnostic.as_ref().unwrap();
+        assert_eq!((Some(16), Some("hull.b")), (bad.line, bad.field.as_deref()));
+        assert_eq!("Invalid number 'wide'", bad.reason);
+
+        assert_eq!(BatchStatus::Converted, batch.files[2].status);
+        assert!(batch.files[3].diagnostic.as_ref().unwrap().line.is_none());
+
+        // Always machine-readable, whatever failed
+        let json = serde_json::to_value(&batch).unwrap();
+        assert_eq!("Failed", json["files"][1]["status"]);
+        assert_eq!("hull.b", json["files"][1]["diagnostic"]["field"]);
+        assert_eq!(batch.to_string().lines().last(), Some("Converted 1 of 4 files"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,316 @@
+use crate::Ship;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+
+mod springsharp;
+pub use springsharp::NumberFormat;
+
+mod sharpie;
+pub(crate) use sharpie::Version;
+
+mod batch;
+pub use batch::{BatchConversion, BatchStatus, FileConversion};
+
+// Provenance {{{1
+/// Where a Ship field came from when converting a SpringSharp file.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Provenance {
+    /// Path to the field in the Ship, e.g. `hull.lwl` or `batteries[0].num`.
+    pub field: String,
+    /// Line number in the SpringSharp file (starting at 1).
+    pub line: usize,
+    /// Text of the line as it appears in the file.
+    pub value: String,
+}
+
+// Diagnostic {{{1
+/// Why a file could not be converted, with the line and field it failed
+/// at where they are known.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// Line number in the file (starting at 1).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// Path to the field being read, e.g. `hull.lwl`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    /// What went wrong.
+    pub reason: String,
+}
+
+impl fmt::Display for Diagnostic { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.reason)?;
+        if let Some(field) = &self.field { write!(f, " reading {}", field)?; }
+        if let Some(line) = self.line { write!(f, " (line {})", line)?; }
+
+        Ok(())
+    }
+}
+
+impl Error for Diagnostic {}
+
+impl Diagnostic { // {{{2
+    // at {{{3
+    /// Error for a field that could not be read from a line.
+    ///
+    pub fn at(line: usize, field: &str, reason: impl Into<String>) -> Box<dyn Error> {
+        Box::new(Diagnostic { line: Some(line), field: Some(field.to_string()), reason: reason.into() })
+    }
+
+    // from_error {{{3
+    /// Diagnostic for an error, which has no line or field unless the
+    /// error was itself a Diagnostic.
+    ///
+    pub fn from_error(error: &(dyn Error + 'static)) -> Diagnostic {
+        match error.downcast_ref::<Diagnostic>() {
+            Some(d) => d.clone(),
+            None    => Diagnostic { line: None, field: None, reason: error.to_string() },
+        }
+    }
+}
+
+// ConvertResult {{{1
+/// A converted ship along with where each of its fields came from.
+///
+#[derive(Clone, Debug)]
+pub struct ConvertResult {
+    ship: Ship,
+    format: NumberFormat,
+    provenance: Vec<Provenance>,
+}
+
+impl ConvertResult { // {{{2
+    // ship {{{3
+    /// The converted ship.
+    ///
+    pub fn ship(&self) -> &Ship {
+        &self.ship
+    }
+
+    // into_ship {{{3
+    /// Take the converted ship.
+    ///
+    pub fn into_ship(self) -> Ship {
+        self.ship
+    }
+
+    // format {{{3
+    /// Number format used to read the file.
+    ///
+    pub fn format(&self) -> NumberFormat {
+        self.format
+    }
+
+    // provenance {{{3
+    /// Fields read from the file in the order they were read.
+    ///
+    pub fn provenance(&self) -> &[Provenance] {
+        &self.provenance
+    }
+}
+
+// FileFormat {{{1
+/// Kinds of ship file open_any() recognizes.
+///
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum FileFormat {
+    /// Sharpie ship file.
+    Sharpie,
+    /// SpringSharp 3 ship file.
+    SpringSharp,
+    /// Sharpie ship written as a YAML document.
+    Yaml,
+    /// Sharpie ship written as a TOML document.
+    Toml,
+}
+
+impl fmt::Display for FileFormat { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Sharpie     => "Sharpie",
+                Self::SpringSharp => "SpringSharp",
+                Self::Yaml        => "YAML",
+                Self::Toml        => "TOML",
+            }
+        )
+    }
+}
+
+impl FileFormat { // {{{2
+    // detect {{{3
+    /// Guess the format of a file from its contents.
+    ///
+    pub fn detect(text: &str) -> Option<FileFormat> {
+        let line = text.lines()
+            .map(|l| l.trim())
+            .find(|l| !l.is_empty() && !l.starts_with('#'))?;
+
+        if line.starts_with('{') {
+            Some(Self::Sharpie)
+        } else if line.contains("SpringSharp") {
+            Some(Self::SpringSharp)
+        } else if line == "---" || line.starts_with("- ") || line.contains(": ") || line.ends_with(':') {
+            Some(Self::Yaml)
+        } else if (line.starts_with('[') && line.ends_with(']')) || line.contains(" = ") {
+            Some(Self::Toml)
+        } else {
+            None
+        }
+    }
+}
+
+impl Ship { // {{{1
+    // open_any {{{2
+    /// Load a ship from any file format sharpie recognizes, whatever its
+    /// extension, and return the format that was found.
+    ///
+    pub fn open_any(p: String) -> Result<(Ship, FileFormat), Box<dyn Error>> {
+        Ship::read_any(&fs::read_to_string(p)?)
+    }
+
+    // read_any {{{2
+    /// Read a ship from the contents of a file in any format sharpie
+    /// recognizes, such as a file piped to stdin, and return the format
+    /// that was found.
+    ///
+    pub fn read_any(s: &str) -> Result<(Ship, FileFormat), Box<dyn Error>> {
+        match FileFormat::detect(s) {
+            Some(FileFormat::Sharpie)     => Ok((Ship::parse(s)?, FileFormat::Sharpie)),
+            Some(FileFormat::SpringSharp) => {
+                let lines = s.lines().map(String::from).collect();
+                Ok((Ship::convert_lines(lines, NumberFormat::Auto)?.into_ship(), FileFormat::SpringSharp))
+            },
+            Some(FileFormat::Yaml)        => Ok((Ship::from_document(serde_norway::from_str(s)?)?, FileFormat::Yaml)),
+            Some(FileFormat::Toml)        => Ok((Ship::from_document(toml::from_str(s)?)?, FileFormat::Toml)),
+            None                          => Err("Unknown file format".into()),
+        }
+    }
+
+    // from_document {{{2
+    /// Read a ship from a YAML or TOML document with the same fields as
+    /// the ship in a sharpie ship file.
+    ///
+    fn from_document(value: Value) -> Result<Ship, Box<dyn Error>> {
+        Ship::parse(&format!("{}\n{}", json!({"version": 1}), value))
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod convert {
+    use super::*;
+
+    // Test FileFormat::detect {{{2
+    macro_rules! test_detect {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, text) = $value;
+
+                    assert_eq!(expected, FileFormat::detect(text));
+                }
+            )*
+        }
+    }
+
+    test_detect! {
+        // name:              (format, text)
+        detect_sharpie:       (Some(FileFormat::Sharpie), "{\"version\":1}\n{}"),
+        detect_springsharp:   (Some(FileFormat::SpringSharp), "SpringSharp Version 3.0\nShip"),
+        detect_yaml:          (Some(FileFormat::Yaml), "---\nname: Ship"),
+        detect_yaml_comment:  (Some(FileFormat::Yaml), "# ship\nname: Ship"),
+        detect_toml:          (Some(FileFormat::Toml), "[hull]\nb = 10"),
+        detect_toml_key:      (Some(FileFormat::Toml), "name = \"Ship\""),
+        detect_empty:         (None, "\n\n"),
+        detect_unknown:       (None, "Ship"),
+    }
+
+    // Test open_any {{{2
+    macro_rules! test_open_any {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, file) = $value;
+
+                    let (ship, format) = Ship::open_any(file.into()).unwrap();
+
+                    assert_eq!(expected, format);
+                    assert_eq!("Queen Elizabeth", ship.name);
+                }
+            )*
+        }
+    }
+
+    test_open_any! {
+        // name:                (format, file)
+        open_any_sharpie:       (FileFormat::Sharpie, "tests/fixtures/dreadnought_1915.ship"),
+        open_any_springsharp:   (FileFormat::SpringSharp, "tests/fixtures/dreadnought_1915.sship"),
+    }
+
+    // Test read_any {{{2
+    #[test]
+    fn read_any() {
+        for file in ["tests/fixtures/dreadnought_1915.ship", "tests/fixtures/dreadnought_1915.sship"] {
+            let (ship, format) = Ship::open_any(file.into()).unwrap();
+            let (read, read_format) = Ship::read_any(&fs::read_to_string(file).unwrap()).unwrap();
+
+            assert_eq!(format, read_format);
+            assert_eq!(ship.report(), read.report());
+        }
+
+        assert!(Ship::read_any("Ship").is_err());
+    }
+
+    // Test read_any documents {{{2
+    /// Value without any nulls, which TOML cannot write.
+    fn without_nulls(value: Value) -> Value {
+        match value {
+            Value::Object(m) => Value::Object(m.into_iter().filter(|(_, v)| !v.is_null()).map(|(k, v)| (k, without_nulls(v))).collect()),
+            Value::Array(a)  => Value::Array(a.into_iter().map(without_nulls).collect()),
+            v                => v,
+        }
+    }
+
+    macro_rules! test_read_document {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, write) = $value;
+
+                    let (ship, _) = Ship::open_any("tests/fixtures/dreadnought_1915.ship".into()).unwrap();
+                    let text = write(without_nulls(serde_json::to_value(&ship).unwrap()));
+
+                    let (read, format) = Ship::read_any(&text).unwrap();
+                    assert_eq!(expected, format);
+                    assert_eq!(ship.report(), read.report());
+                }
+            )*
+        }
+    }
+
+    test_read_document! {
+        // name:            (format, write)
+        read_yaml:          (FileFormat::Yaml, |v: Value| format!("---\n{}", serde_norway::to_string(&v).unwrap())),
+        read_toml:          (FileFormat::Toml, |v: Value| toml::to_string(&v).unwrap()),
+    }
+
+    #[test]
+    fn read_document_invalid() {
+        assert!(Ship::read_any("---\nname: [Ship").is_err());
+        assert!(Ship::read_any("name = \"Ship\"\nhull = 1").is_err());
+    }
+
+}
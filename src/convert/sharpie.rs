@@ -0,0 +1,293 @@
+use crate::{Ship, Units, SHIP_FILE_VERSION};
+use crate::unknown;
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+use std::error::Error;
+use std::fs;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+// Version {{{1
+/// Holds Ship file version information.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct Version {
+    pub(crate) version: u32,
+    /// Version of sharpie that saved the file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sharpie: Option<String>,
+    /// Checksum of the ship when it was saved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    checksum: Option<String>,
+}
+
+// checksum {{{1
+/// FNV-1a hash of a ship written as compact JSON with its keys sorted,
+/// so reformatting a ship file leaves it alone but changing a value
+/// does not.
+///
+fn checksum(ship: &Value) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+
+    for byte in ship.to_string().bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    format!("{:016x}", hash)
+}
+
+// legacy_units {{{1
+/// Take out the units that earlier versions wrote for every component,
+/// so the imperial ones a saved ship leaves out are not taken for
+/// unknown fields, and return the ship's preference: metric if every
+/// component was metric.
+///
+fn legacy_units(ship: &mut Value) -> Option<Units> {
+    let mut units = Vec::new();
+
+    for key in ["hull", "armor", "mines", "batteries", "torps", "asw"] {
+        let components = match ship.get_mut(key) {
+            Some(Value::Array(a)) => a.iter_mut().collect(),
+            Some(v)               => vec![v],
+            None                  => Vec::new(),
+        };
+        for c in components {
+            if let Some(u) = c.as_object_mut().and_then(|c| c.remove("units")) {
+                units.push(serde_json::from_value::<Units>(u).unwrap_or_default());
+            }
+        }
+    }
+
+    match !units.is_empty() && units.iter().all(|u| *u == Units::Metric) {
+        true  => Some(Units::Metric),
+        false => None,
+    }
+}
+
+impl Ship { // {{{1
+    // load {{{2
+    /// Load ship from a file.
+    ///
+    pub fn load(p: String) -> Result<Ship, Box<dyn Error>> {
+        Ship::parse(&fs::read_to_string(p)?)
+    }
+
+    // parse {{{2
+    /// Read a ship from the contents of a ship file.
+    ///
+    #[allow(clippy::unused_unit)]
+    pub(crate) fn parse(s: &str) -> Result<Ship, Box<dyn Error>> {
+        let mut stream = serde_json::Deserializer::from_str(s).into_iter::<Value>();
+
+        // Handle opening older ship file formats
+        //
+        let version: Version = serde_json::from_value(stream.next().ok_or("")??)?;
+        if version.version == 1 { // No special handling required
+            ()
+        } else { // Cannot open any other versions
+            let err = format!("Cannot open ship files of this version: {}!", version.version);
+            return Err(err.into())
+        }
+
+        let value = stream.next().ok_or("")??;
+        let mut current = value.clone();
+        let units = legacy_units(&mut current);
+
+        let mut ship: Ship = serde_json::from_value(value.clone())?;
+        ship.unknown = unknown::unknown(&current, &serde_json::to_value(&ship)?);
+        if ship.units.is_none() { ship.units = units; }
+
+        if ship.hull.is_underdetermined() {
+            return Err("Ship file sets neither the block coefficient (hull.cb) nor the displacement (hull.d)".into());
+        }
+
+        if let Some(computed) = stream.next() {
+            match computed?.get("computed") {
+                Some(snapshot) => ship.snapshot = Some(serde_json::from_value(snapshot.clone())?),
+                None           => return Err("Ship file has more than the version and the ship".into()),
+            }
+        }
+
+        // Files saved before checksums were added cannot be checked
+        if let Some(sum) = version.checksum && sum != checksum(&value) {
+            ship.edited_since = Some(version.sharpie.unwrap_or_default());
+        }
+
+        ship.sanitize();
+
+        Ok(ship)
+    }
+
+    // save {{{2
+    /// Save ship to a file.
+    ///
+    pub fn save(&self, p: String) -> Result<(), Box<dyn Error>> {
+        let contents = self.file_contents()?;
+
+        // Empty or clear the ship file
+        let mut file = OpenOptions::new().write(true).create(true).truncate(true).open(&p)?;
+        write!(file, "{}", contents)?;
+
+        Ok(())
+    }
+
+    // file_value {{{2
+    /// The ship as it is written to its file, unknown fields and all.
+    ///
+    fn file_value(&self) -> Result<Value, Box<dyn Error>> {
+        let mut value = serde_json::to_value(self)?;
+        unknown::merge(&mut value, &self.unknown);
+
+        Ok(value)
+    }
+
+    // checksum {{{2
+    /// Checksum of the ship as it is written to its file.
+    ///
+    pub(crate) fn checksum(&self) -> Result<String, Box<dyn Error>> {
+        Ok(checksum(&self.file_value()?))
+    }
+
+    // file_contents {{{2
+    /// Contents of the ship file: the file version, the version of
+    /// sharpie and a checksum of the ship, followed by the ship and any
+    /// snapshot of its values.
+    ///
+    pub(crate) fn file_contents(&self) -> Result<String, Box<dyn Error>> {
+        let value = self.file_value()?;
+
+        let version = serde_json::to_string(&Version {
+            version: SHIP_FILE_VERSION,
+            sharpie: Some(env!("CARGO_PKG_VERSION").into()),
+            checksum: Some(checksum(&value)),
+        })?;
+        // Fields are written in the order they are declared unless
+        // unknown fields have to be merged in
+        let ship    = if self.unknown.is_null() { serde_json::to_string(&self)? } else { value.to_string() };
+
+        match &self.snapshot {
+            Some(snapshot) => Ok(format!("{}\n{}\n{}\n", version, ship, serde_json::json!({ "computed": snapshot }))),
+            None           => Ok(format!("{}\n{}\n", version, ship)),
+        }
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod sharpie {
+    use super::*;
+    use crate::Template;
+    use serde_json::json;
+
+    // Test checksum {{{2
+    macro_rules! test_checksum {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, edit) = $value;
+
+                    let ship = Ship::from_template(Template::Dreadnought1910);
+                    let contents = ship.file_contents().unwrap();
+                    let (version, ship) = contents.split_once('\n').unwrap();
+                    let mut ship: Value = serde_json::from_str(ship).unwrap();
+                    let mut version: Value = serde_json::from_str(version).unwrap();
+                    edit(&mut version, &mut ship);
+
+                    let ship = Ship::parse(&format!("{}\n{}", version, serde_json::to_string_pretty(&ship).unwrap())).unwrap();
+                    let codes: Vec<&str> = ship.validate().iter()
+                        .map(|i| i.code)
+                        .filter(|c| *c == "file-edited" || *c == "hull-overdetermined")
+                        .collect();
+
+                    assert_eq!(expected, codes);
+                }
+            )*
+        }
+    }
+
+    test_checksum! {
+        // name:                    (codes, edit(version, ship))
+        checksum_unchanged:         (Vec::<&str>::new(), |_: &mut Value, _: &mut Value| ()),
+        checksum_edited:            (vec!["file-edited"], |_: &mut Value, s: &mut Value| s["hull"]["b"] = json!(90.0)),
+        checksum_old_file:          (Vec::<&str>::new(), |v: &mut Value, s: &mut Value| {
+                                        v.as_object_mut().unwrap().remove("checksum");
+                                        s["hull"]["b"] = json!(90.0);
+                                    }),
+        checksum_overdetermined:    (vec!["file-edited", "hull-overdetermined"], |_: &mut Value, s: &mut Value| s["hull"]["cb"] = json!(0.6)),
+    }
+
+    #[test]
+    fn checksum_saved_version() {
+        let contents = Ship::from_template(Template::Dreadnought1910).file_contents().unwrap();
+        let version: Value = serde_json::from_str(contents.lines().next().unwrap()).unwrap();
+
+        assert_eq!(SHIP_FILE_VERSION as u64, version["version"].as_u64().unwrap());
+        assert_eq!(env!("CARGO_PKG_VERSION"), version["sharpie"]);
+        assert_eq!(16, version["checksum"].as_str().unwrap().len());
+    }
+
+    // Test legacy units {{{2
+    macro_rules! test_legacy_units {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                #[allow(deprecated)]
+                fn $name() {
+                    let (expected, hull, guns) = $value;
+
+                    let mut ship = serde_json::to_value(Ship::from_template(Template::Dreadnought1910)).unwrap();
+                    ship["hull"]["units"] = json!(hull);
+                    for b in ship["batteries"].as_array_mut().unwrap() { b["units"] = json!(guns); }
+
+                    let ship = Ship::parse(&format!("{{\"version\":1}}\n{}", ship)).unwrap();
+
+                    assert_eq!(expected, ship.units);
+                    assert_eq!(hull == "Metric", ship.hull.units == Units::Metric);
+                    assert!(ship.validate().iter().all(|i| i.code != "unknown-fields"));
+                }
+            )*
+        }
+    }
+
+    test_legacy_units! {
+        // name:                (units, hull, batteries)
+        legacy_units_imperial:  (None, "Imperial", "Imperial"),
+        legacy_units_mixed:     (None, "Metric", "Imperial"),
+        legacy_units_metric:    (Some(Units::Metric), "Metric", "Metric"),
+    }
+
+    // Test snapshot {{{2
+    #[test]
+    fn snapshot_saved() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.snapshot = Some(ship.take_snapshot().unwrap());
+
+        let contents = ship.file_contents().unwrap();
+        assert_eq!(3, contents.lines().count());
+        assert!(contents.lines().last().unwrap().starts_with("{\"computed\":{"));
+
+        let loaded = Ship::parse(&contents).unwrap();
+        let snapshot = loaded.snapshot.as_ref().unwrap();
+        assert!(snapshot.is_of(&loaded).unwrap());
+        assert_eq!(Some(vec![]), loaded.snapshot_drift().unwrap());
+        assert!(loaded.validate().iter().all(|i| i.code != "file-edited"));
+
+        let contents = Ship::from_template(Template::Dreadnought1910).file_contents().unwrap();
+        assert_eq!(None, Ship::parse(&contents).unwrap().snapshot);
+        assert!(Ship::parse(&(contents + "{\"other\":1}")).is_err());
+    }
+
+    #[test]
+    fn underdetermined() {
+        let mut ship = serde_json::to_value(Ship::from_template(Template::Dreadnought1910)).unwrap();
+        ship["hull"]["d"] = Value::Null;
+
+        assert!(Ship::parse(&format!("{{\"version\":1}}\n{}", ship)).is_err());
+    }
+
+}
@@ -0,0 +1,646 @@
+use crate::{Ship, Provenance, Diagnostic, ConvertResult, Notes, Trim, Units, Tons};
+use crate::{BowType, BulkheadType, FuelType, BoilerType, DriveType};
+
+use serde::Serialize;
+
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::str::FromStr;
+
+// NumberFormat {{{1
+/// How numbers are written in a SpringSharp file.
+///
+/// SpringSharp writes numbers using the locale of the computer it ran
+/// on, so files saved on European systems use a decimal comma.
+///
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum NumberFormat {
+    /// Work out the format from the contents of the file.
+    #[default]
+    Auto,
+    /// Decimal point and comma thousands separator, e.g. 1,234.5
+    Point,
+    /// Decimal comma and point thousands separator, e.g. 1.234,5
+    Comma,
+}
+
+impl From<String> for NumberFormat { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for NumberFormat {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "point"    => Self::Point,
+            "comma"    => Self::Comma,
+            "auto" | _ => Self::Auto,
+        }
+    }
+}
+
+impl fmt::Display for NumberFormat { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Auto  => "auto",
+                Self::Point => "point",
+                Self::Comma => "comma",
+            }
+        )
+    }
+}
+
+impl NumberFormat { // {{{2
+    // detect {{{3
+    /// Guess the number format from the lines of a file.
+    ///
+    /// Each number with a separator that can only be read one way is a
+    /// vote for that format. Numbers like 1,234 are ambiguous and do
+    /// not count. Ties go to Point, which is what SpringSharp uses by
+    /// default.
+    ///
+    pub fn detect(lines: &[String]) -> NumberFormat {
+        let mut point = 0;
+        let mut comma = 0;
+
+        for line in lines {
+            let line = line.trim();
+            let line = line.strip_prefix('-').unwrap_or(line);
+
+            if !line.chars().any(|c| c.is_ascii_digit()) ||
+               !line.chars().all(|c| c.is_ascii_digit() || c == '.' || c == ',') {
+                continue;
+            }
+
+            let points = line.matches('.').count();
+            let commas = line.matches(',').count();
+            let last = line.rfind(['.', ',']);
+
+            match (points, commas) {
+                (0, 0) => (),
+                (_, 0) if points > 1 => comma += 1,
+                (_, 0) => if Self::decimals(line, last) != 3 { point += 1 },
+                (0, _) if commas > 1 => point += 1,
+                (0, _) => if Self::decimals(line, last) != 3 { comma += 1 },
+                _ => match last.map(|i| &line[i..i+1]) {
+                    Some(",") => comma += 1,
+                    _         => point += 1,
+                },
+            }
+        }
+
+        if comma > point { NumberFormat::Comma } else { NumberFormat::Point }
+    }
+
+    // decimals {{{3
+    /// Number of digits after the separator at index i.
+    ///
+    fn decimals(line: &str, i: Option<usize>) -> usize {
+        match i {
+            Some(i) => line.len() - i - 1,
+            None => 0,
+        }
+    }
+
+    // normalize {{{3
+    /// Rewrite a number so it can be parsed by Rust.
+    ///
+    pub fn normalize(&self, s: &str) -> String {
+        let s = s.trim();
+
+        match self {
+            Self::Comma => s.replace('.', "").replace(',', "."),
+            _           => s.replace(',', ""),
+        }
+    }
+
+    // parse {{{3
+    /// Parse a number written in this format.
+    ///
+    pub fn parse<T: FromStr>(&self, s: &str) -> Result<T, Box<dyn Error>> {
+        match self.normalize(s).parse() {
+            Ok(n) => Ok(n),
+            Err(_) => Err(format!("Invalid number '{}'", s).into()),
+        }
+    }
+}
+
+// Testing NumberFormat {{{2
+#[cfg(test)]
+mod number_format {
+    use super::*;
+
+    // Test detect {{{3
+    macro_rules! test_detect {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, lines) = $value;
+                    let lines: Vec<String> = lines.iter().map(|s: &&str| s.to_string()).collect();
+
+                    assert_eq!(expected, NumberFormat::detect(&lines));
+                }
+            )*
+        }
+    }
+
+    test_detect! {
+        // name:              (format, lines)
+        detect_empty:         (NumberFormat::Point, ["Name", "1915"]),
+        detect_point:         (NumberFormat::Point, ["Name", "0.57", "1915"]),
+        detect_comma:         (NumberFormat::Comma, ["Name", "0,57", "1915"]),
+        detect_ambiguous:     (NumberFormat::Point, ["1,234", "1.234"]),
+        detect_point_both:    (NumberFormat::Point, ["1,234.5"]),
+        detect_comma_both:    (NumberFormat::Comma, ["1.234,5"]),
+        detect_point_thou:    (NumberFormat::Point, ["1,234,567"]),
+        detect_comma_thou:    (NumberFormat::Comma, ["1.234.567"]),
+        detect_negative:      (NumberFormat::Comma, ["-2,5"]),
+        detect_ignore_text:   (NumberFormat::Point, ["Mk I, 1.5 in", "2.5"]),
+    }
+
+    // Test parse {{{3
+    macro_rules! test_parse {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, format, s) = $value;
+
+                    assert_eq!(expected, format.parse::<f64>(s).unwrap());
+                }
+            )*
+        }
+    }
+
+    test_parse! {
+        // name:             (value, format, string)
+        parse_point:         (0.57, NumberFormat::Point, "0.57"),
+        parse_point_thou:    (1234.5, NumberFormat::Point, "1,234.5"),
+        parse_comma:         (0.57, NumberFormat::Comma, "0,57"),
+        parse_comma_thou:    (1234.5, NumberFormat::Comma, "1.234,5"),
+        parse_int:           (1915.0, NumberFormat::Comma, "1915"),
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(NumberFormat::Point.parse::<f64>("abc").is_err());
+    }
+}
+
+// SsLines {{{1
+/// Reads a SpringSharp file line by line, keeping track of which
+/// line each field is read from.
+///
+struct SsLines<I: Iterator<Item = String>> {
+    lines: I,
+    num: usize,
+    format: NumberFormat,
+    provenance: Vec<Provenance>,
+}
+
+impl<I: Iterator<Item = String>> SsLines<I> { // {{{2
+    // new {{{3
+    fn new(lines: I, format: NumberFormat) -> Self {
+        SsLines { lines, num: 0, format, provenance: Vec::new() }
+    }
+
+    // line {{{3
+    /// Read the next line without recording it.
+    ///
+    fn line(&mut self) -> Option<String> {
+        let line = self.lines.next()?;
+        self.num += 1;
+
+        Some(line)
+    }
+
+    // try_next {{{3
+    /// Read the next line as the value of field.
+    ///
+    fn try_next(&mut self, field: &str) -> Option<String> {
+        let value = self.line()?;
+
+        self.provenance.push(Provenance {
+            field: field.to_string(),
+            line: self.num,
+            value: value.clone(),
+        });
+
+        Some(value)
+    }
+
+    // next {{{3
+    /// Read the next line as the value of field, failing at the
+    /// end of the file.
+    ///
+    fn next(&mut self, field: &str) -> Result<String, Box<dyn Error>> {
+        match self.try_next(field) {
+            Some(value) => Ok(value),
+            None => Err(Diagnostic::at(self.num + 1, field, "Unexpected end of file")),
+        }
+    }
+
+    // num {{{3
+    /// Read the next line as a number for field.
+    ///
+    fn num<T: FromStr>(&mut self, field: &str) -> Result<T, Box<dyn Error>> {
+        let value = self.next(field)?;
+
+        match self.format.parse(&value) {
+            Ok(n) => Ok(n),
+            Err(e) => Err(Diagnostic::at(self.num, field, e.to_string())),
+        }
+    }
+}
+
+impl Ship { // {{{1
+    // convert {{{2
+    /// Load a ship from a SpringSharp 3 file and output a sharpie ship
+    ///
+    pub fn convert(p: String) -> Result<Ship, Box<dyn Error>> {
+        Ok(Ship::convert_with_provenance(p)?.into_ship())
+    }
+
+    // convert_with_provenance {{{2
+    /// Load a ship from a SpringSharp 3 file and record which line
+    /// each field was read from
+    ///
+    pub fn convert_with_provenance(p: String) -> Result<ConvertResult, Box<dyn Error>> {
+        Ship::convert_with_format(p, NumberFormat::Auto)
+    }
+
+    // convert_with_format {{{2
+    /// Load a ship from a SpringSharp 3 file whose numbers are written
+    /// in the given format
+    ///
+    pub fn convert_with_format(p: String, format: NumberFormat) -> Result<ConvertResult, Box<dyn Error>> {
+        let f = File::open(p)?;
+        let reader = BufReader::new(f);
+        let text = reader.lines().collect::<Result<Vec<String>, _>>()?;
+
+        Ship::convert_lines(text, format)
+    }
+
+    // convert_lines {{{2
+    /// Load a ship from the lines of a SpringSharp 3 file whose numbers
+    /// are written in the given format
+    ///
+    #[allow(deprecated, clippy::match_like_matches_macro, clippy::single_match, clippy::unused_unit, clippy::wildcard_in_or_patterns)]
+    pub(crate) fn convert_lines(text: Vec<String>, format: NumberFormat) -> Result<ConvertResult, Box<dyn Error>> {
+        let mut ship = Ship::default();
+
+        let format = match format {
+            NumberFormat::Auto => NumberFormat::detect(&text),
+            _ => format,
+        };
+        let mut lines = SsLines::new(text.into_iter(), format);
+
+        let line = lines.line().unwrap_or_default();
+        if line.contains("SpringSharp Version 3.0") {
+            ()
+        } else if line.contains("SpringSharp") {
+            Err(Diagnostic { line: Some(1), field: None, reason: "SpringSharp file too old".into() })?;
+        } else {
+            Err(Diagnostic { line: Some(1), field: None, reason: "Unknown file format".into() })?;
+        }
+
+        ship.name    = lines.next("name")?;
+        ship.country = lines.next("country")?;
+        ship.kind    = lines.next("kind")?;
+
+        // SpringSharp keeps units for each component but sharpie has one
+        // preference for the whole ship
+        ship.hull.units     = lines.next("hull.units")?.into();
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.units = lines.next(&format!("batteries[{}].units", i))?.into(); }
+        ship.torps[0].units = lines.next("torps[0].units")?.into();
+        ship.armor.units    = lines.next("armor.units")?.into();
+
+        ship.year = lines.num("year")?;
+
+        ship.wgts.vital = lines.num("wgts.vital")?;
+
+        ship.hull.set_lwl(lines.num("hull.lwl")?);
+        ship.hull.b          = lines.num("hull.b")?;
+        ship.hull.t          = lines.num("hull.t")?;
+        ship.hull.stern_type = lines.next("hull.stern_type")?.into();
+        ship.hull.set_cb(lines.num("hull.cb")?);
+
+        ship.hull.qd_aft         = lines.num("hull.qd_aft")?;
+        ship.hull.stern_overhang = lines.num("hull.stern_overhang")?;
+        ship.hull.qd_len         = lines.num("hull.qd_len")?;
+        ship.hull.qd_len /= 100.0; // convert from % to decimal
+        ship.hull.qd_fwd         = lines.num("hull.qd_fwd")?;
+        ship.hull.ad_aft         = lines.num("hull.ad_aft")?;
+        ship.hull.fd_len         = lines.num("hull.fd_len")?;
+        ship.hull.fd_len /= 100.0; // convert from % to decimal
+        ship.hull.ad_fwd         = lines.num("hull.ad_fwd")?;
+        ship.hull.fd_aft         = lines.num("hull.fd_aft")?;
+        ship.hull.fc_len         = lines.num("hull.fc_len")?;
+        ship.hull.fc_len /= 100.0; // convert from % to decimal
+        ship.hull.fd_fwd         = lines.num("hull.fd_fwd")?;
+        ship.hull.fc_aft         = lines.num("hull.fc_aft")?;
+        ship.hull.fc_fwd         = lines.num("hull.fc_fwd")?;
+        ship.hull.bow_angle      = lines.num("hull.bow_angle")?;
+
+        for (i, b) in ship.batteries.iter_mut().enumerate() {
+            b.num             = lines.num(&format!("batteries[{}].num", i))?;
+            b.diam             = lines.num(&format!("batteries[{}].diam", i))?;
+            b.kind            = lines.next(&format!("batteries[{}].kind", i))?.into();
+            b.groups[0].above = lines.num(&format!("batteries[{}].groups[0].above", i))?;
+            b.groups[0].below = lines.num(&format!("batteries[{}].groups[0].below", i))?;
+
+            b.set_shell_wgt(Tons::pounds(lines.num(&format!("batteries[{}].shell_wgt", i))?));
+        }
+
+        ship.batteries[0].shells                 = lines.num("batteries[0].shells")?;
+        ship.batteries[0].mount_num              = lines.num("batteries[0].mount_num")?;
+        ship.batteries[0].mount_kind             = lines.next("batteries[0].mount_kind")?.into();
+        ship.batteries[0].groups[0].distribution = lines.next("batteries[0].groups[0].distribution")?.into();
+
+        ship.batteries[1].mount_num              = lines.num("batteries[1].mount_num")?;
+        ship.batteries[1].mount_kind             = lines.next("batteries[1].mount_kind")?.into();
+        ship.batteries[1].groups[0].distribution = lines.next("batteries[1].groups[0].distribution")?.into();
+
+        ship.batteries[2].mount_num              = lines.num("batteries[2].mount_num")?;
+        ship.batteries[2].mount_kind             = lines.next("batteries[2].mount_kind")?.into();
+        ship.batteries[2].groups[0].distribution = lines.next("batteries[2].groups[0].distribution")?.into();
+
+        ship.batteries[3].mount_num              = lines.num("batteries[3].mount_num")?;
+        ship.batteries[3].mount_kind             = lines.next("batteries[3].mount_kind")?.into();
+        ship.batteries[3].groups[0].distribution = lines.next("batteries[3].groups[0].distribution")?.into();
+
+        ship.batteries[4].mount_num              = lines.num("batteries[4].mount_num")?;
+        ship.batteries[4].mount_kind             = lines.next("batteries[4].mount_kind")?.into();
+        ship.batteries[4].groups[0].distribution = lines.next("batteries[4].groups[0].distribution")?.into();
+
+        ship.torps[0].num  = lines.num("torps[0].num")?;
+        ship.torps[1].num  = lines.num("torps[1].num")?;
+        ship.torps[0].diam = lines.num("torps[0].diam")?;
+
+        ship.armor.main.thick = lines.num("armor.main.thick")?;
+        ship.armor.main.len   = lines.num("armor.main.len")?;
+        ship.armor.main.hgt   = lines.num("armor.main.hgt")?;
+
+        ship.armor.end.thick = lines.num("armor.end.thick")?;
+        ship.armor.end.len   = lines.num("armor.end.len")?;
+        ship.armor.end.hgt   = lines.num("armor.end.hgt")?;
+
+        ship.armor.upper.thick = lines.num("armor.upper.thick")?;
+        ship.armor.upper.len   = lines.num("armor.upper.len")?;
+        ship.armor.upper.hgt   = lines.num("armor.upper.hgt")?;
+
+        ship.armor.bulkhead.thick = lines.num("armor.bulkhead.thick")?;
+        ship.armor.bulkhead.len   = lines.num("armor.bulkhead.len")?;
+        ship.armor.bulkhead.hgt   = lines.num("armor.bulkhead.hgt")?;
+
+        for (i, b) in ship.batteries.iter_mut().enumerate() {
+            b.armor_face = lines.num(&format!("batteries[{}].armor_face", i))?;
+            b.armor_back = lines.num(&format!("batteries[{}].armor_back", i))?;
+            b.armor_barb = lines.num(&format!("batteries[{}].armor_barb", i))?;
+        }
+
+        ship.armor.deck.md      = lines.num("armor.deck.md")?;
+        ship.armor.ct_fwd.thick = lines.num("armor.ct_fwd.thick")?;
+        ship.engine.vmax        = lines.num("engine.vmax")?;
+        ship.engine.vcruise     = lines.num("engine.vcruise")?;
+        ship.engine.range       = lines.num("engine.range")?;
+        ship.engine.set_shafts(lines.num("engine.shafts")?);
+        ship.engine.pct_coal    = lines.num("engine.pct_coal")?;
+        ship.engine.pct_coal /= 100.0; // convert from % to decimal
+
+        ship.engine.fuel = FuelType::empty();
+        match lines.next("engine.fuel.Coal")?.as_str() { "True" => ship.engine.fuel.toggle(FuelType::Coal), _ => (), };
+        match lines.next("engine.fuel.Oil")?.as_str() { "True" => ship.engine.fuel.toggle(FuelType::Oil), _ => (), };
+        match lines.next("engine.fuel.Diesel")?.as_str() { "True" => ship.engine.fuel.toggle(FuelType::Diesel), _ => (), };
+        match lines.next("engine.fuel.Gasoline")?.as_str() { "True" => ship.engine.fuel.toggle(FuelType::Gasoline), _ => (), };
+        match lines.next("engine.fuel.Battery")?.as_str() { "True" => ship.engine.fuel.toggle(FuelType::Battery), _ => (), };
+
+        ship.engine.boiler = BoilerType::empty();
+        match lines.next("engine.boiler.Simple")?.as_str() { "True" => ship.engine.boiler.toggle(BoilerType::Simple), _ => (), };
+        match lines.next("engine.boiler.Complex")?.as_str() { "True" => ship.engine.boiler.toggle(BoilerType::Complex), _ => (), };
+        match lines.next("engine.boiler.Turbine")?.as_str() { "True" => ship.engine.boiler.toggle(BoilerType::Turbine), _ => (), };
+
+        ship.engine.drive = DriveType::empty();
+        match lines.next("engine.drive.Direct")?.as_str() { "True" => ship.engine.drive.toggle(DriveType::Direct), _ => (), };
+        match lines.next("engine.drive.Geared")?.as_str() { "True" => ship.engine.drive.toggle(DriveType::Geared), _ => (), };
+        match lines.next("engine.drive.Electric")?.as_str() { "True" => ship.engine.drive.toggle(DriveType::Electric), _ => (), };
+        match lines.next("engine.drive.Hydraulic")?.as_str() { "True" => ship.engine.drive.toggle(DriveType::Hydraulic), _ => (), };
+
+        ship.trim        = Trim::new(lines.num("trim")?).map_err(|e| Diagnostic::at(lines.num, "trim", e))?;
+        ship.hull.bb     = lines.num("hull.bb")?;
+        ship.engine.year = lines.num("engine.year")?;
+
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.year = lines.num(&format!("batteries[{}].year", i))?; }
+
+        ship.hull.bow_type = lines.next("hull.bow_type")?.into();
+        let ram_len        = lines.num("hull.bow_type.ram_len")?;
+        ship.hull.bow_type = match ship.hull.bow_type {
+            BowType::Ram(_) => BowType::Ram(ram_len),
+            _ => ship.hull.bow_type,
+        };
+            
+        ship.torps[1].units = lines.next("torps[1].units")?.into();
+        ship.mines.units    = lines.next("mines.units")?.into();
+        ship.asw[0].units   = lines.next("asw[0].units")?.into();
+        ship.asw[1].units   = lines.next("asw[1].units")?.into();
+
+        let units = [ship.hull.units, ship.armor.units, ship.mines.units].into_iter()
+            .chain(ship.batteries.iter().map(|b| b.units))
+            .chain(ship.torps.iter().map(|t| t.units))
+            .chain(ship.asw.iter().map(|a| a.units));
+        if units.into_iter().all(|u| u == Units::Metric) { ship.units = Some(Units::Metric); }
+
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.len = lines.num(&format!("batteries[{}].len", i))?; }
+
+        ship.batteries[1].shells = lines.num("batteries[1].shells")?;
+        ship.batteries[2].shells = lines.num("batteries[2].shells")?;
+        ship.batteries[3].shells = lines.num("batteries[3].shells")?;
+        ship.batteries[4].shells = lines.num("batteries[4].shells")?;
+
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[1].distribution  = lines.next(&format!("batteries[{}].groups[1].distribution", i))?.into(); }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[1].above         = lines.num(&format!("batteries[{}].groups[1].above", i))?; }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[1].two_mounts_up = match lines.next(&format!("batteries[{}].groups[1].two_mounts_up", i))?.as_str() { "True" => true, _ => false, }; }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[1].on            = lines.num(&format!("batteries[{}].groups[1].on", i))?; }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[1].below         = lines.num(&format!("batteries[{}].groups[1].below", i))?; }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[1].lower_deck    = match lines.next(&format!("batteries[{}].groups[1].lower_deck", i))?.as_str() { "True" => true, _ => false, }; }
+
+        ship.torps[0].mounts     = lines.num("torps[0].mounts")?;
+        ship.torps[1].mounts     = lines.num("torps[1].mounts")?;
+        ship.torps[1].diam       = lines.num("torps[1].diam")?;
+        ship.torps[0].len        = lines.num("torps[0].len")?;
+        ship.torps[1].len        = lines.num("torps[1].len")?;
+        ship.torps[0].mount_kind = lines.next("torps[0].mount_kind")?.into();
+        ship.torps[1].mount_kind = lines.next("torps[1].mount_kind")?.into();
+
+        ship.mines.num        = lines.num("mines.num")?;
+        ship.mines.reload     = lines.num("mines.reload")?;
+        ship.mines.wgt        = lines.num("mines.wgt")?;
+        ship.mines.mount_kind = lines.next("mines.mount_kind")?.into();
+
+        ship.asw[0].num    = lines.num("asw[0].num")?;
+        ship.asw[1].num    = lines.num("asw[1].num")?;
+        ship.asw[0].reload = lines.num("asw[0].reload")?;
+        ship.asw[1].reload = lines.num("asw[1].reload")?;
+        ship.asw[0].wgt    = lines.num("asw[0].wgt")?;
+        ship.asw[1].wgt    = lines.num("asw[1].wgt")?;
+        ship.asw[0].kind   = lines.next("asw[0].kind")?.into();
+        ship.asw[1].kind   = lines.next("asw[1].kind")?.into();
+
+        ship.wgts.hull  = lines.num("wgts.hull")?;
+        ship.wgts.on    = lines.num("wgts.on")?;
+        ship.wgts.above = lines.num("wgts.above")?;
+
+        ship.armor.incline               = lines.num("armor.incline")?;
+        ship.armor.bulge.thick           = lines.num("armor.bulge.thick")?;
+        ship.armor.bulge.len             = lines.num("armor.bulge.len")?;
+        ship.armor.bulge.hgt             = lines.num("armor.bulge.hgt")?;
+
+        ship.armor.bh_kind =
+            match lines.num("armor.bh_kind")? {
+                0 => BulkheadType::Additional,
+                1 | _ => BulkheadType::Strengthened,
+            };
+
+        ship.armor.bh_beam               = lines.num("armor.bh_beam")?;
+        ship.armor.deck.fc               = lines.num("armor.deck.fc")?;
+        ship.armor.deck.qd               = lines.num("armor.deck.qd")?;
+        ship.armor.deck.kind             = lines.next("armor.deck.kind")?.into();
+        ship.armor.ct_aft.thick          = lines.num("armor.ct_aft.thick")?;
+
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[0].above  = lines.num(&format!("batteries[{}].groups[0].above", i))?; }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[0].below  = lines.num(&format!("batteries[{}].groups[0].below", i))?; }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[1].above  = lines.num(&format!("batteries[{}].groups[1].above", i))?; }
+        // Ignore extra reads of ship.batteries.groups[1].on, because, duplicate data in the file makes sense
+        for _ in ship.batteries.iter_mut() { lines.line(); }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[1].below  = lines.num(&format!("batteries[{}].groups[1].below", i))?; }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[0].layout = lines.next(&format!("batteries[{}].groups[0].layout", i))?.into(); }
+        for (i, b) in ship.batteries.iter_mut().enumerate() { b.groups[1].layout = lines.next(&format!("batteries[{}].groups[1].layout", i))?.into(); }
+
+        ship.wgts.void = Some(lines.num("wgts.void")?);
+
+        // Superfluous ship.batteries[4].layout
+        for _ in 1..34 { lines.line(); }
+
+        let start = lines.provenance.len();
+        let mut notes = Vec::new();
+        while let Some(line) = lines.try_next("notes") { notes.push(line); }
+
+        // Keep any report SpringSharp embedded in the notes apart from them
+        if let Some(r) = Notes::ss_report_lines(&notes) {
+            for (i, p) in lines.provenance[start..].iter_mut().enumerate() {
+                p.field = if i < r.start { format!("notes.text[{}]", i) }
+                    else if i < r.end { format!("notes.ss_report[{}]", i - r.start) }
+                    else { format!("notes.text[{}]", i - r.len()) };
+            }
+        } else {
+            for (i, p) in lines.provenance[start..].iter_mut().enumerate() { p.field = format!("notes.text[{}]", i); }
+        }
+        ship.notes = Notes::from_lines(notes);
+
+        // SpringSharp does not store the number of mounts in Group 0 that
+        // are on the deck so we have to calculate it from the other numbers
+        for (i, b) in ship.batteries.iter_mut().enumerate() {
+            let placed = b.groups[0].above + b.groups[0].below +
+                b.groups[1].above + b.groups[1].on + b.groups[1].below;
+
+            b.groups[0].on = match b.mount_num.checked_sub(placed) {
+                Some(on) => on,
+                None => {
+                    let field = format!("batteries[{}].mount_num", i);
+                    let line = lines.provenance.iter().find(|p| p.field == field).map_or(0, |p| p.line);
+
+                    Err(Diagnostic::at(line, &field, format!("{} mounts placed but only {} in the battery", placed, b.mount_num)))?
+                },
+            };
+        }
+
+        // SpringSharp uses hull year for torpedo, mine and ASW year
+        for t in ship.torps.iter_mut() { t.year = ship.year; }
+        ship.mines.year = ship.year;
+        for a in ship.asw.iter_mut() { a.year = ship.year; }
+
+        ship.sanitize();
+
+        Ok(ConvertResult { ship, format, provenance: lines.provenance })
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod springsharp {
+    use super::*;
+    use crate::Diagnostic;
+
+    // Test convert_with_provenance {{{2
+    #[test]
+    fn convert_with_provenance() {
+        let result = Ship::convert_with_provenance("tests/fixtures/dreadnought_1915.sship".into()).unwrap();
+        let find = |field: &str| result.provenance().iter().find(|p| p.field == field).unwrap().clone();
+
+        assert_eq!(Provenance { field: "name".into(), line: 2, value: "Queen Elizabeth".into() }, find("name"));
+        assert_eq!(Provenance { field: "hull.lwl".into(), line: 15, value: "634".into() }, find("hull.lwl"));
+        assert_eq!(16, find("hull.b").line);
+        assert_eq!(634.0, result.ship().hull.lwl());
+    }
+
+    // Test convert_with_format {{{2
+    #[test]
+    fn convert_decimal_comma() {
+        let point = Ship::convert_with_format("tests/fixtures/dreadnought_1915.sship".into(), NumberFormat::Auto).unwrap();
+        let comma = Ship::convert_with_format("tests/fixtures/dreadnought_1915_comma.sship".into(), NumberFormat::Auto).unwrap();
+
+        assert_eq!(NumberFormat::Point, point.format());
+        assert_eq!(NumberFormat::Comma, comma.format());
+        assert_eq!(point.ship().report(), comma.ship().report());
+    }
+
+    #[test]
+    fn convert_explicit_format() {
+        let result = Ship::convert_with_format("tests/fixtures/dreadnought_1915_comma.sship".into(), NumberFormat::Point).unwrap();
+
+        assert_eq!(NumberFormat::Point, result.format());
+        assert_eq!(905.0, result.ship().hull.b);
+    }
+
+    // Test mount diagnostics {{{2
+    #[test]
+    fn mount_diagnostic() {
+        let text = std::fs::read_to_string("tests/fixtures/dreadnought_1915.sship").unwrap();
+        let lines: Vec<String> = text.lines().map(String::from).collect();
+        let mount_num = Ship::convert_lines(lines.clone(), NumberFormat::Auto).unwrap()
+            .provenance().iter().find(|p| p.field == "batteries[0].mount_num").unwrap().clone();
+
+        let mut lines = lines;
+        lines[mount_num.line - 1] = "0".into();
+        let error = Ship::convert_lines(lines, NumberFormat::Auto).unwrap_err();
+        let d = Diagnostic::from_error(error.as_ref());
+
+        assert_eq!((Some(mount_num.line), Some("batteries[0].mount_num")), (d.line, d.field.as_deref()));
+    }
+
+    // Test units {{{2
+    #[test]
+    fn convert_units() {
+        let text = std::fs::read_to_string("tests/fixtures/dreadnought_1915.sship").unwrap();
+        let mut lines: Vec<String> = text.lines().map(String::from).collect();
+        let result = Ship::convert_lines(lines.clone(), NumberFormat::Auto).unwrap();
+        let units: Vec<usize> = result.provenance().iter().filter(|p| p.field.ends_with("units")).map(|p| p.line).collect();
+
+        assert_eq!(12, units.len());
+        assert_eq!(None, result.ship().units);
+
+        // Only a ship that is metric throughout prefers metric
+        lines[units[0] - 1] = "1".into();
+        assert_eq!(None, Ship::convert_lines(lines.clone(), NumberFormat::Auto).unwrap().ship().units);
+
+        for line in units { lines[line - 1] = "1".into(); }
+        assert_eq!(Some(Units::Metric), Ship::convert_lines(lines, NumberFormat::Auto).unwrap().ship().units);
+    }
+}
@@ -0,0 +1,226 @@
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+// CrewQuality {{{1
+/// Training and experience of the crew.
+///
+/// This is a gameplay aid for wargames and does not affect any of the
+/// design calculations. Regular crews have no effect at all.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum CrewQuality {
+    Green,
+    #[default]
+    Regular,
+    Veteran,
+    Elite,
+}
+
+impl From<String> for CrewQuality { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for CrewQuality {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "1"     => Self::Green,
+            "2"     => Self::Veteran,
+            "3"     => Self::Elite,
+            "0" | _ => Self::Regular,
+        }
+    }
+}
+
+impl fmt::Display for CrewQuality { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Green   => "Green",
+                Self::Regular => "Regular",
+                Self::Veteran => "Veteran",
+                Self::Elite   => "Elite",
+            }
+        )
+    }
+}
+
+impl CrewQuality { // {{{2
+    // is_set {{{3
+    /// True if the crew quality changes anything.
+    ///
+    pub fn is_set(&self) -> bool {
+        *self != Self::Regular
+    }
+
+    // rate_of_fire {{{3
+    /// Multiplier applied to the rate of fire.
+    ///
+    pub fn rate_of_fire(&self) -> f64 {
+        match self {
+            Self::Green   => 0.75,
+            Self::Regular => 1.0,
+            Self::Veteran => 1.15,
+            Self::Elite   => 1.3,
+        }
+    }
+
+    // damage_control {{{3
+    /// Multiplier applied to the number of hits needed to sink the ship.
+    ///
+    pub fn damage_control(&self) -> f64 {
+        match self {
+            Self::Green   => 0.85,
+            Self::Regular => 1.0,
+            Self::Veteran => 1.1,
+            Self::Elite   => 1.2,
+        }
+    }
+}
+
+// Testing CrewQuality {{{2
+#[cfg(test)]
+mod crew_quality {
+    use super::*;
+
+    // Test rate_of_fire {{{3
+    macro_rules! test_rate_of_fire {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, quality) = $value;
+
+                    assert_eq!(expected, quality.rate_of_fire());
+                }
+            )*
+        }
+    }
+
+    test_rate_of_fire! {
+        // name:             (factor, quality)
+        rate_of_fire_green:   (0.75, CrewQuality::Green),
+        rate_of_fire_regular: (1.0, CrewQuality::Regular),
+        rate_of_fire_veteran: (1.15, CrewQuality::Veteran),
+        rate_of_fire_elite:   (1.3, CrewQuality::Elite),
+    }
+
+    // Test damage_control {{{3
+    macro_rules! test_damage_control {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, quality) = $value;
+
+                    assert_eq!(expected, quality.damage_control());
+                }
+            )*
+        }
+    }
+
+    test_damage_control! {
+        // name:                (factor, quality)
+        damage_control_green:   (0.85, CrewQuality::Green),
+        damage_control_regular: (1.0, CrewQuality::Regular),
+        damage_control_veteran: (1.1, CrewQuality::Veteran),
+        damage_control_elite:   (1.2, CrewQuality::Elite),
+    }
+}
+
+// CrewModel {{{1
+/// How the size of the crew is estimated.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum CrewModel {
+    /// SpringSharp's power law on displacement.
+    #[default]
+    Classic,
+    /// Classic adjusted for the era and for coal fired boilers.
+    Adjusted,
+    /// Maximum crew set by the user.
+    Override(u32),
+}
+
+impl fmt::Display for CrewModel { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Classic     => write!(f, "Classic"),
+            Self::Adjusted    => write!(f, "Era adjusted"),
+            Self::Override(_) => write!(f, "User set"),
+        }
+    }
+}
+
+impl CrewModel { // {{{2
+    /// Ratio of minimum to maximum crew.
+    pub const MIN_RATIO: f64 = 0.7692;
+
+    // classic {{{3
+    /// SpringSharp's estimate of the maximum crew.
+    ///
+    pub fn classic(d: f64) -> u32 {
+        (d.powf(0.75) * 0.65) as u32
+    }
+
+    // era_factor {{{3
+    /// Multiplier on the classic crew for the year the ship was laid down.
+    ///
+    /// Early ships needed more hands for sail and hand worked machinery
+    /// while later ships used more labour saving machinery.
+    ///
+    pub fn era_factor(year: u32) -> f64 {
+               if year < 1890 {
+            1.15
+        } else if year < 1920 {
+            1.0
+        } else {
+            0.9
+        }
+    }
+
+    // crew_max {{{3
+    /// Estimated maximum crew.
+    ///
+    pub fn crew_max(&self, d: f64, year: u32, coal: bool) -> u32 {
+        match self {
+            Self::Classic     => Self::classic(d),
+            Self::Adjusted    => (Self::classic(d) as f64 *
+                Self::era_factor(year) *
+                if coal { 1.1 } else { 1.0 }) as u32,
+            Self::Override(n) => *n,
+        }
+    }
+}
+
+// Testing CrewModel {{{2
+#[cfg(test)]
+mod crew_model {
+    use super::*;
+
+    // Test crew_max {{{3
+    macro_rules! test_crew_max {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, model, year, coal) = $value;
+
+                    assert_eq!(expected, CrewModel::crew_max(&model, 1000.0, year, coal));
+                }
+            )*
+        }
+    }
+
+    test_crew_max! {
+        // name:                 (crew, model, year, coal)
+        crew_max_classic:        (115, CrewModel::Classic, 1880, true),
+        crew_max_adjusted_early: (145, CrewModel::Adjusted, 1880, true),
+        crew_max_adjusted_coal:  (126, CrewModel::Adjusted, 1910, true),
+        crew_max_adjusted_oil:   (115, CrewModel::Adjusted, 1910, false),
+        crew_max_adjusted_late:  (103, CrewModel::Adjusted, 1930, false),
+        crew_max_override:       (250, CrewModel::Override(250), 1910, true),
+    }
+}
@@ -0,0 +1,462 @@
+use crate::Ship;
+
+use serde::Serialize;
+
+// Constants {{{1
+/// Step between points of the GZ curve (degrees).
+const GZ_STEP: f64 = 5.0;
+/// Step between points of the power curve (kts).
+const SPEED_STEP: f64 = 1.0;
+/// Colours of the series in an SVG plot, in order.
+const COLORS: [&str; 4] = ["#1f77b4", "#d62728", "#2ca02c", "#ff7f0e"];
+
+// Series {{{1
+/// Points of one curve, ready to plot.
+///
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct Series {
+    /// Name shown in the legend.
+    pub name: String,
+    /// Points of the curve in order of x.
+    pub points: Vec<(f64, f64)>,
+}
+
+impl Series { // {{{2
+    // path {{{3
+    /// SVG path commands drawing the series in a box of width by height
+    /// that spans the bounds, with y increasing up the box.
+    ///
+    pub fn path(&self, bounds: &Bounds, width: f64, height: f64) -> String {
+        let x = |v: f64| (v - bounds.x_min) / (bounds.x_max - bounds.x_min) * width;
+        let y = |v: f64| height - (v - bounds.y_min) / (bounds.y_max - bounds.y_min) * height;
+
+        self.points.iter()
+            .enumerate()
+            .map(|(i, (px, py))| format!("{} {:.2} {:.2}", if i == 0 { "M" } else { "L" }, x(*px), y(*py)))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
+
+// Bounds {{{1
+/// Range of the axes of a plot.
+///
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub struct Bounds {
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+}
+
+// Plot {{{1
+/// Curves drawn on the same axes.
+///
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct Plot {
+    /// What the plot shows.
+    pub title: &'static str,
+    /// Label of the x axis.
+    pub x_label: &'static str,
+    /// Label of the y axis.
+    pub y_label: &'static str,
+    /// Curves on the plot.
+    pub series: Vec<Series>,
+}
+
+impl Plot { // {{{2
+    // bounds {{{3
+    /// Range of the axes that holds every curve.
+    ///
+    /// Both axes start at zero or below and are never empty.
+    ///
+    pub fn bounds(&self) -> Bounds {
+        let mut b = Bounds { x_min: 0.0, x_max: 0.0, y_min: 0.0, y_max: 0.0 };
+
+        for (x, y) in self.series.iter().flat_map(|s| s.points.iter()) {
+            b.x_min = b.x_min.min(*x);
+            b.x_max = b.x_max.max(*x);
+            b.y_min = b.y_min.min(*y);
+            b.y_max = b.y_max.max(*y);
+        }
+
+        if b.x_max <= b.x_min { b.x_max = b.x_min + 1.0; }
+        if b.y_max <= b.y_min { b.y_max = b.y_min + 1.0; }
+
+        b
+    }
+
+    // paths {{{3
+    /// SVG path commands for each curve in a box of width by height.
+    ///
+    pub fn paths(&self, width: f64, height: f64) -> Vec<String> {
+        let bounds = self.bounds();
+
+        self.series.iter().map(|s| s.path(&bounds, width, height)).collect()
+    }
+
+    // svg {{{3
+    /// Standalone SVG image of the plot, width by height pixels, with the
+    /// title, axis ranges and a legend.
+    ///
+    pub fn svg(&self, width: f64, height: f64) -> String {
+        const MARGIN: f64 = 30.0;
+
+        let bounds = self.bounds();
+        let (w, h) = (width - MARGIN * 2.0, height - MARGIN * 2.0);
+
+        let mut svg = vec![
+            format!("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">",
+                w = width, h = height),
+            format!("<text x=\"{:.2}\" y=\"20\" text-anchor=\"middle\" font-weight=\"bold\">{}</text>", width / 2.0, self.title),
+            format!("<rect x=\"{m}\" y=\"{m}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"none\" stroke=\"#808080\"/>", w, h, m = MARGIN),
+        ];
+
+        for (i, path) in self.paths(w, h).iter().enumerate() {
+            svg.push(format!("<path transform=\"translate({m} {m})\" d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                path, COLORS[i % COLORS.len()], m = MARGIN));
+        }
+
+        svg.push(format!("<text x=\"{}\" y=\"{:.2}\" font-size=\"10\">{} to {:.0}, {} to {:.2}</text>",
+            MARGIN, height - MARGIN / 2.0, self.x_label, bounds.x_max, self.y_label, bounds.y_max));
+
+        let mut x = MARGIN;
+        for (i, series) in self.series.iter().enumerate() {
+            svg.push(format!("<text x=\"{:.2}\" y=\"{:.2}\" font-size=\"10\" fill=\"{}\">{}</text>",
+                x, height - 4.0, COLORS[i % COLORS.len()], series.name));
+            x += series.name.len() as f64 * 6.0 + 12.0;
+        }
+
+        svg.push("</svg>".into());
+        svg.join("\n")
+    }
+}
+
+// strip {{{1
+/// Step series of thickness against position along the waterline (ft)
+/// for the armoured stretches (from, to, thickness), zero elsewhere.
+///
+fn strip(name: &str, lwl: f64, sections: &[(f64, f64, f64)]) -> Series {
+    let mut sections: Vec<(f64, f64, f64)> = sections.iter()
+        .map(|(from, to, thick)| (from.clamp(0.0, lwl), to.clamp(0.0, lwl), *thick))
+        .filter(|(from, to, thick)| to > from && *thick > 0.0)
+        .collect();
+    sections.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut points = vec![(0.0, 0.0)];
+    for (from, to, thick) in sections {
+        points.extend([(from, 0.0), (from, thick), (to, thick), (to, 0.0)]);
+    }
+    points.push((lwl, 0.0));
+
+    Series { name: name.into(), points }
+}
+
+impl Ship { // {{{1
+    // power_curve {{{2
+    /// Horsepower needed at each speed up to the top speed.
+    ///
+    pub fn power_curve(&self) -> Series {
+        let mut points = Vec::new();
+        if !self.engine.is_powered() { return Series { name: self.name.clone(), points }; }
+
+        let (d, lwl, leff, cs, ws) = (self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws());
+
+        let mut v = 0.0;
+        while v < self.engine.vmax {
+            points.push((v, self.engine.hp(v, d, lwl, leff, cs, ws)));
+            v += SPEED_STEP;
+        }
+        points.push((self.engine.vmax, self.engine.hp(self.engine.vmax, d, lwl, leff, cs, ws)));
+
+        Series { name: self.name.clone(), points }
+    }
+
+    // gz_curve {{{2
+    /// Righting lever (ft) at each angle of heel (degrees) up to deck
+    /// edge immersion.
+    ///
+    /// The lever comes from the wall-sided formula, which only holds
+    /// until the deck edge goes under, so the curve stops there.
+    ///
+    pub fn gz_curve(&self) -> Series {
+        let (gm, bm) = (self.metacenter(), self.bm());
+        let gz = |deg: f64| {
+            let a = deg.to_radians();
+            a.sin() * (gm + bm / 2.0 * a.tan().powi(2))
+        };
+
+        let deck_edge = self.deck_edge_angle();
+        let mut points = Vec::new();
+
+        let mut deg = 0.0;
+        while deg < deck_edge {
+            points.push((deg, gz(deg)));
+            deg += GZ_STEP;
+        }
+        points.push((deck_edge, gz(deck_edge)));
+
+        Series { name: self.name.clone(), points }
+    }
+}
+
+// Protection {{{1
+impl Ship {
+    // armor_profile {{{2
+    /// Thickness (in) of the belts and deck at each position along the
+    /// waterline (ft from the bow), as a strip chart.
+    ///
+    /// The main and upper belts are centred amidships with the end belts
+    /// split evenly either side of the main belt. The forecastle and
+    /// quarterdeck armour covers their lengths of the deck and box decks
+    /// the vitals they cover and 8 ft beyond, as Deck::areas() has them.
+    ///
+    pub fn armor_profile(&self) -> Plot {
+        let lwl = self.hull.lwl();
+        let mid = lwl / 2.0;
+        let (main, end, upper, deck) = (&self.armor.main, &self.armor.end, &self.armor.upper, &self.armor.deck);
+
+        let centred = |len: f64, thick: f64| (mid - len / 2.0, mid + len / 2.0, thick);
+        let (fc, qd) = (self.hull.fc_len * lwl, self.hull.qd_len * lwl);
+
+        let deck_sections = match deck.vitals_len(&deck.kind, self.hull.d().to_long(), lwl, self.wgt_engine_est(), self.wgt_mag_room()) {
+            Some(len) => vec![centred(len + 16.0, deck.md)],
+            None      => vec![(0.0, fc, deck.fc), (fc, lwl - qd, deck.md), (lwl - qd, lwl, deck.qd)],
+        };
+
+        let series = [
+            strip("Main belt", lwl, &[centred(main.len, main.thick)]),
+            strip("End belts", lwl, &[
+                (mid - main.len / 2.0 - end.len / 2.0, mid - main.len / 2.0, end.thick),
+                (mid + main.len / 2.0, mid + main.len / 2.0 + end.len / 2.0, end.thick),
+            ]),
+            strip("Upper belt", lwl, &[centred(upper.len, upper.thick)]),
+            strip("Deck", lwl, &deck_sections),
+        ];
+
+        Plot {
+            title: "Protection profile",
+            x_label: "Position from bow (ft)",
+            y_label: "Thickness (in)",
+            series: series.into_iter().filter(|s| s.points.len() > 2).collect(),
+        }
+    }
+}
+
+// compare {{{1
+/// Speed-power and GZ curves of two ships overlaid on the same axes.
+///
+pub fn compare(a: &Ship, b: &Ship) -> Vec<Plot> {
+    let named = |s: Series, default: &str| {
+        if s.name.is_empty() { Series { name: default.into(), ..s } } else { s }
+    };
+
+    vec![
+        Plot {
+            title: "Speed and power",
+            x_label: "Speed (kts)",
+            y_label: "Power (hp)",
+            series: vec![named(a.power_curve(), "Ship A"), named(b.power_curve(), "Ship B")],
+        },
+        Plot {
+            title: "Righting lever",
+            x_label: "Heel (degrees)",
+            y_label: "GZ (ft)",
+            series: vec![named(a.gz_curve(), "Ship A"), named(b.gz_curve(), "Ship B")],
+        },
+    ]
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod curves {
+    use super::*;
+    use crate::Template;
+    use crate::armor::DeckType;
+    use crate::test_support::*;
+
+    // Test power_curve {{{2
+    #[test]
+    fn power_curve() {
+        let ship = Ship::from_template(Template::Destroyer1935);
+        let curve = ship.power_curve();
+
+        assert_eq!((0.0, 0.0), curve.points[0]);
+        assert_eq!(ship.engine.vmax, curve.points.last().unwrap().0);
+        assert!(curve.points.windows(2).all(|p| p[1].1 > p[0].1));
+
+        let hp = ship.engine.hp_max(ship.hull.d().to_long(), ship.hull.lwl(), ship.hull.leff(), ship.hull.cs(), ship.hull.ws());
+        assert_eq!(to_place(hp, 0), to_place(curve.points.last().unwrap().1, 0));
+
+        let mut hulk = ship.clone();
+        hulk.engine.vmax = 0.0;
+        assert!(hulk.power_curve().points.is_empty());
+    }
+
+    // Test gz_curve {{{2
+    #[test]
+    fn gz_curve() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let curve = ship.gz_curve();
+
+        assert_eq!((0.0, 0.0), curve.points[0]);
+        assert_eq!(to_place(ship.deck_edge_angle(), 4), to_place(curve.points.last().unwrap().0, 4));
+
+        // Slope at the origin is the metacentric height
+        let (deg, gz) = curve.points[1];
+        assert_eq!(to_place(ship.metacenter(), 1), to_place(gz / deg.to_radians(), 1));
+    }
+
+    // Test bounds {{{2
+    macro_rules! test_bounds {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, points) = $value;
+
+                    let plot = Plot { series: vec![Series { name: "".into(), points }], ..Default::default() };
+
+                    assert_eq!(expected, plot.bounds());
+                }
+            )*
+        }
+    }
+
+    test_bounds! {
+        // name:          (bounds, points)
+        bounds_empty:     (Bounds { x_min: 0.0, x_max: 1.0, y_min: 0.0, y_max: 1.0 }, vec![]),
+        bounds_positive:  (Bounds { x_min: 0.0, x_max: 30.0, y_min: 0.0, y_max: 5.0 }, vec![(10.0, 1.0), (30.0, 5.0)]),
+        bounds_negative:  (Bounds { x_min: 0.0, x_max: 40.0, y_min: -2.0, y_max: 3.0 }, vec![(0.0, -2.0), (40.0, 3.0)]),
+    }
+
+    // Test path {{{2
+    #[test]
+    fn path() {
+        let series = Series { name: "".into(), points: vec![(0.0, 0.0), (5.0, 10.0), (10.0, 5.0)] };
+        let bounds = Bounds { x_min: 0.0, x_max: 10.0, y_min: 0.0, y_max: 10.0 };
+
+        assert_eq!("M 0.00 100.00 L 50.00 0.00 L 100.00 50.00", series.path(&bounds, 100.0, 100.0));
+        assert_eq!("", Series::default().path(&bounds, 100.0, 100.0));
+    }
+
+    // Test strip {{{2
+    macro_rules! test_strip {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, sections) = $value;
+
+                    assert_eq!(expected, strip("", 100.0, &sections).points);
+                }
+            )*
+        }
+    }
+
+    test_strip! {
+        // name:            (points, sections)
+        strip_none:         (vec![(0.0, 0.0), (100.0, 0.0)], vec![]),
+        strip_unarmored:    (vec![(0.0, 0.0), (100.0, 0.0)], vec![(20.0, 80.0, 0.0)]),
+        strip_centre:       (vec![(0.0, 0.0), (20.0, 0.0), (20.0, 9.0), (80.0, 9.0), (80.0, 0.0), (100.0, 0.0)],
+                             vec![(20.0, 80.0, 9.0)]),
+        strip_clamped:      (vec![(0.0, 0.0), (0.0, 0.0), (0.0, 2.0), (100.0, 2.0), (100.0, 0.0), (100.0, 0.0)],
+                             vec![(-10.0, 110.0, 2.0)]),
+        strip_sorted:       (vec![(0.0, 0.0), (0.0, 0.0), (0.0, 1.0), (10.0, 1.0), (10.0, 0.0),
+                                  (90.0, 0.0), (90.0, 3.0), (100.0, 3.0), (100.0, 0.0), (100.0, 0.0)],
+                             vec![(90.0, 100.0, 3.0), (0.0, 10.0, 1.0)]),
+    }
+
+    // Test armor_profile {{{2
+    #[test]
+    fn armor_profile() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let plot = ship.armor_profile();
+        let lwl = ship.hull.lwl();
+
+        assert_eq!(vec!["Main belt", "End belts", "Upper belt", "Deck"], plot.series.iter().map(|s| s.name.as_str()).collect::<Vec<_>>());
+
+        // Every strip spans the waterline
+        for s in plot.series.iter() {
+            assert_eq!((0.0, 0.0), s.points[0]);
+            assert_eq!((lwl, 0.0), *s.points.last().unwrap());
+        }
+
+        // The main belt is as long and thick as given
+        let main = &plot.series[0].points;
+        assert_eq!(to_place(ship.armor.main.len, 2), to_place(main[3].0 - main[2].0, 2));
+        assert_eq!(ship.armor.main.thick, main[2].1);
+
+        // The end belts meet the main belt
+        let ends = &plot.series[1].points;
+        assert_eq!(to_place(main[2].0, 2), to_place(ends[3].0, 2));
+
+        // Unarmoured belts are left out
+        let mut ship = ship.clone();
+        ship.armor.upper.thick = 0.0;
+        assert_eq!(3, ship.armor_profile().series.len());
+    }
+
+    // Test box deck profile {{{2
+    macro_rules! test_box_profile {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (kind, box_len) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.armor.deck.kind = kind;
+                    ship.armor.deck.box_len = box_len;
+
+                    let len = ship.armor.deck.vitals_len(&ship.armor.deck.kind, ship.hull.d().to_long(), ship.hull.lwl(),
+                        ship.wgt_engine_est(), ship.wgt_mag_room()).unwrap();
+                    if let Some(given) = box_len { assert_eq!(given, len); }
+
+                    // The box reaches 8 ft beyond the vitals at each end
+                    let plot = ship.armor_profile();
+                    let deck = &plot.series.iter().find(|s| s.name == "Deck").unwrap().points;
+                    assert_eq!(to_place(len + 16.0, 2), to_place(deck[3].0 - deck[2].0, 2));
+                    assert_eq!(ship.armor.deck.md, deck[2].1);
+                }
+            )*
+        }
+    }
+
+    test_box_profile! {
+        // name:                (kind, box_len)
+        box_profile_machinery:  (DeckType::BoxOverMachinery, None),
+        box_profile_magazine:   (DeckType::BoxOverMagazine, None),
+        box_profile_both:       (DeckType::BoxOverBoth, None),
+        box_profile_given:      (DeckType::BoxOverBoth, Some(150.0)),
+    }
+
+    // Test svg {{{2
+    #[test]
+    fn svg() {
+        let plot = Ship::from_template(Template::Dreadnought1910).armor_profile();
+        let svg = plot.svg(600.0, 200.0);
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>"));
+        assert_eq!(plot.series.len(), svg.matches("<path ").count());
+        assert!(svg.contains("Protection profile"));
+    }
+
+    // Test compare {{{2
+    #[test]
+    fn compare_ships() {
+        let mut a = Ship::from_template(Template::Dreadnought1910);
+        a.name = "Dreadnought".into();
+        let b = Ship::from_template(Template::FastBattleship1940);
+
+        let plots = compare(&a, &b);
+
+        assert_eq!(2, plots.len());
+        for plot in plots.iter() {
+            assert_eq!(vec!["Dreadnought", "Ship B"], plot.series.iter().map(|s| s.name.as_str()).collect::<Vec<_>>());
+            assert_eq!(2, plot.paths(100.0, 100.0).len());
+        }
+    }
+}
@@ -0,0 +1,533 @@
+//! Reference for the values sharpie derives from a design.
+
+use std::fmt;
+
+// Source {{{1
+/// Where a formula comes from.
+///
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Source {
+    /// Reproduced from SpringSharp 3b3.
+    SpringSharp,
+    /// Added by sharpie.
+    Sharpie,
+}
+
+impl fmt::Display for Source { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::SpringSharp => "SpringSharp 3b3",
+                Self::Sharpie     => "sharpie",
+            }
+        )
+    }
+}
+
+// Formula {{{1
+/// Description of a value derived from a design.
+///
+#[derive(PartialEq, Clone, Debug)]
+pub struct Formula {
+    /// Name of the value, as used by `Ship::get()` and the API.
+    pub name: &'static str,
+    /// What the value is and how it is found.
+    pub description: &'static str,
+    /// Values it is calculated from.
+    pub inputs: &'static [&'static str],
+    /// Where the formula comes from.
+    pub source: Source,
+}
+
+impl fmt::Display for Formula { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.name)?;
+        writeln!(f, "    {}", self.description)?;
+        writeln!(f, "    Inputs: {}", self.inputs.join(", "))?;
+        write!(f, "    Source: {}", self.source)
+    }
+}
+
+// FORMULAS {{{1
+/// Every documented formula in alphabetical order.
+///
+const FORMULAS: &[Formula] = &[
+    Formula {
+        name: "aa_ceiling",
+        description: "Greatest height (ft) at which a battery can usefully engage aircraft: 14,000 ft times the bore to the power 0.6 and the square root of a 45th of the calibres, times the square of the sine of the greatest elevation, taking two thirds. Zero if the guns cannot elevate to 45 degrees.",
+        inputs: &["diam", "len", "elevation_max"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "armor_backing",
+        description: "Weight of the timber backing and fastenings armor plates are carried on: a fifth of the weight of wrought iron plates (the usual material before 1877) and a tenth of compound plates (before 1890). Steel plates need none.",
+        inputs: &["armor.material", "year", "armor", "gun_armor"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "armor_inch",
+        description: "Weight (t) of a square foot of armour plate one inch thick, 0.0185 t or about 41.4 lb, a little over the 40.8 lb of rolled iron or steel. Every armour area is multiplied by its thickness and this to give its weight.",
+        inputs: &["armor"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "bm",
+        description: "Height of the metacentre above the centre of buoyancy (ft), from the waterplane coefficient, beam, draught and block coefficient.",
+        inputs: &["hull.bb", "hull.t", "cb", "cwp"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "bunker_factor",
+        description: "How far the engines' fuel economy is from that of 1910, which the fuel needed for the range is divided by. Reciprocating engines, and turbines before 1898, lose a seventieth for each year before 1910. Turbines gain a twentieth a year from 1910 to 1920 and a sixtieth a year after that, to twice as economical from 1950.",
+        inputs: &["engine.boiler", "engine.year"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "bunker_volume",
+        description: "Volume (cu ft) of the fuel at maximum displacement, with coal stowing at 45 cu ft and oil at 38 cu ft per ton. It must fit in a fifth of the hull volume up to the deck.",
+        inputs: &["bunker_max", "engine.pct_coal", "engine.fuel", "d_normal", "wp", "freeboard_dist"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "compartments",
+        description: "Number of main transverse watertight compartments, as given or from the waterline length with bulkheads 40 ft apart before 1890, 35 ft before 1905, 30 ft after and 60 ft in merchant hulls.",
+        inputs: &["compartments", "lwl", "year", "conversion"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "condition_d",
+        description: "Displacement (t) in each load condition: standard without fuel, trial with two thirds of the normal fuel, normal, full load with 1.8 times the normal fuel and emergency overload with fuel in the spare bunker space up to 5% of the normal displacement more.",
+        inputs: &["d_normal", "bunker", "bunker_space", "bunker_volume", "engine.stowage"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "condition_gm",
+        description: "Metacentric height (ft) in a load condition, from the centre of buoyancy and metacentre at the condition's draught and the normal centre of gravity shifted by the fuel taken on or burnt at half the normal draught.",
+        inputs: &["condition_d", "kg", "hull.t", "hull.bb", "cwp"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "cost_dollar",
+        description: "Cost in millions of US dollars from the light displacement, machinery, guns and crew, rising with the year after 1912.",
+        inputs: &["d_normal", "wgt_load", "wgt_engine", "wgt_borne", "crew_max", "year"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "cost_lb",
+        description: "Cost in millions of British pounds, a quarter of the cost in dollars.",
+        inputs: &["cost_dollar"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "crew_max",
+        description: "Largest complement from the displacement and year using the chosen crew model.",
+        inputs: &["d_normal", "year", "engine.fuel", "crew_model"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "crew_min",
+        description: "Smallest complement, a fixed share of the largest.",
+        inputs: &["crew_max"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "d_engine_factor",
+        description: "Power (hp) per ton of machinery for the engine types fitted, summed over them and divided by their number in d_engine. Simple expansion engines rise from 1.2 in 1860 to 4.075 from 1950, complex expansion engines to 8.5 with a jump of 1 a year from 1906 to 1910, and turbines and engines that do not burn fuel for steam to 19 with a jump from 1903 to 1910.",
+        inputs: &["engine.boiler", "engine.fuel", "engine.year"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "d_lite",
+        description: "Light displacement (t): normal displacement without fuel, ammunition and stores.",
+        inputs: &["d_normal", "wgt_load"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "d_max",
+        description: "Maximum displacement (t): normal displacement with full bunkers, taken as 80% more than the standard fuel load.",
+        inputs: &["d_normal", "wgt_bunker"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "d_normal",
+        description: "Normal displacement (t), as given or from the block coefficient and the hull dimensions.",
+        inputs: &["hull.d", "hull.cb", "lwl", "hull.bb", "hull.t"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "d_std",
+        description: "Standard displacement (t) as defined by the Washington and London treaties: normal displacement without fuel.",
+        inputs: &["d_normal", "wgt_bunker"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "damage_shell_num",
+        description: "Non-critical shell hits of the main battery's calibre needed to sink the ship: flotation divided by the calibre cubed over the damage model's shell divisor (2 unless house rules change it), adjusted for the year.",
+        inputs: &["flotation", "damage_shell_size", "params.shell_divisor", "year"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "damage_torp_num",
+        description: "Non-critical torpedo hits needed to sink the ship, from its flotation, torpedo protection, stability and hull space, scaled by the damage model's torpedo weight (1.313 t unless house rules change it) over the weight of the ship's own torpedoes.",
+        inputs: &["flotation", "torp_beam", "armor.bulkhead", "room", "stability_adj", "torps", "params.torp_wgt"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "deck_area",
+        description: "Deck area (sq ft) left for weapons, boats and additions after superstructure, fittings and boiler uptakes, from the waterplane area.",
+        inputs: &["wp", "uptake_deck_area"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "deck_room",
+        description: "Room on deck per man of the smallest crew: the waterplane area not taken by above water torpedoes, times the average freeboard. Below 0.9 the deck is cramped.",
+        inputs: &["wp", "deck_space", "crew_min", "freeboard_dist"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "diameter_calc",
+        description: "Diameter (ft) of a mount, used for its face and back armour, the barbette and the beam and deck area it needs: the calibre times a factor growing from 1.44 for one gun to 2.0 for five, times one and the reciprocal of the calibre to a power shrinking from 0.61 to 0.14. Guns under 12 in add 12 ft divided by the calibre, and turrets holding several guns over 1 in abreast are a tenth smaller.",
+        inputs: &["diam", "batteries.groups.layout"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "elevation_max",
+        description: "Greatest elevation of a battery (degrees). Broadside, casemate and Coles turret guns reach 45 degrees if built for high angles, otherwise 15. Other surface guns reach 15 before 1905, 20 before 1920, 30 before 1930 and 40 after. High angle guns reach 85, except in closed barbettes before 1930, or 1935 for dual-purpose guns, where they reach 70.",
+        inputs: &["kind", "mount_kind", "year"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "endurance_days",
+        description: "Days the stores last the full complement: the endurance if given, otherwise a fiftieth of the displacement eaten at 0.004 t per man per day before 1900, 0.005 t before 1930 and 0.006 t after. Stores weigh the full complement times the days at the same rate when an endurance is given.",
+        inputs: &["endurance", "crew_max", "d_normal", "year"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "floodable_compartments",
+        description: "Most adjacent compartments amidships that can flood before the parallel sinkage, found by the lost buoyancy method with 85% permeability, comes within 3 inches of the average freeboard.",
+        inputs: &["compartments", "lwl", "hull.b", "hull.t", "cb", "wp", "freeboard_dist"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "flotation",
+        description: "Pounds of non-critical shell hits needed to sink the ship, from the reserve buoyancy above the waterline, reduced for poor stability, weak hulls and crowded holds.",
+        inputs: &["freeboard_dist", "wp", "d_normal", "stability_adj", "str_comp", "room", "year"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "free",
+        description: "Average freeboard (ft) at a battery's mounts. Each group takes the freeboard of the part of the deck its distribution puts its mounts on: the forward and after decks by the number of mounts on each for even distributions, or between the middle and the end of a deck for mounts at the ends or placed forward or aft on one deck.",
+        inputs: &["batteries.groups.distribution", "hull.fd_fwd", "hull.fd_aft", "hull.ad_fwd", "hull.ad_aft", "hull.fc_len", "hull.fd_len"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "free_cap",
+        description: "Freeboard (ft) used for seakeeping, strength and flotation. Above a third of the beam it grows with its square, as three times the freeboard squared over the beam. Below that, ships whose guns are all broadside guns below the deck lose 6 ft.",
+        inputs: &["freeboard_dist", "hull.b", "batteries.mount_kind"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "freeboard_dist",
+        description: "Average freeboard (ft) of the forward and after decks amidships, leaving out the forecastle and quarterdeck.",
+        inputs: &["hull.fd_len", "hull.ad_len", "hull freeboards"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "gun_concentration",
+        description: "How much of the broadside is concentrated in a few heavy mounts, which strains the hull more than the same weight spread out.",
+        inputs: &["batteries", "wgt_broad"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "gun_position",
+        description: "Where on their deck the mounts of a group placed on one deck sit, as a fraction of the waterline: a quarter, half or three quarters of the forward or after deck's length. Groups spread along the ship count as 1 for the first group and 0 for the second, and two groups less than 0.2 apart are treated as grouped amidships in super_factor_long.",
+        inputs: &["batteries.groups.distribution", "hull.fd_len", "hull.ad_len"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "gun_super_factor",
+        description: "How high the gun weight sits, averaged over the guns by weight: 1 on the deck and more for superfiring mounts.",
+        inputs: &["batteries", "freeboard_dist"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "gunnery_modifier",
+        description: "Accuracy of the ship's gunfire against an average gun platform: steadiness over 50, divided by the recoil effect where it is above 1. Used by the duel simulator.",
+        inputs: &["steadiness", "recoil"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "house_hgt",
+        description: "Height (ft) of the face a gunhouse's face and back armour covers: five eighths of the calibre, doubled for Coles turrets, which enclose the whole gun, and never less than 7.5 ft.",
+        inputs: &["diam", "mount_kind"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "hull_material",
+        description: "How much stronger the hull is than one of the usual material for the year, which the strength formulas assume: iron (usual before 1880) needs a fifth more structural weight than riveted steel, partial welding (from 1930) 5% less and full welding (from 1940) 10% less.",
+        inputs: &["hull.material", "year"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "hull_room",
+        description: "Crowding of the hull from room, made worse by torpedo bulkheads set in from the side. Above about 1.1 the hull is cramped.",
+        inputs: &["room", "hull.b", "armor.bh_beam"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "kb",
+        description: "Height of the centre of buoyancy above the keel (ft), from the draught and the block and waterplane coefficients.",
+        inputs: &["hull.t", "cb", "cwp"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "kg",
+        description: "Height of the centre of gravity above the keel (ft), worked back from the metacentric height.",
+        inputs: &["kb", "bm", "metacenter"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "leff",
+        description: "Effective length for resistance (ft). Transom sterns add a share of the beam to the waterline length.",
+        inputs: &["lwl", "hull.bb", "cs", "hull.stern_type"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "metacenter",
+        description: "Metacentric height (ft), from the beam and how far the adjusted stability is above 0.5.",
+        inputs: &["hull.b", "stability_adj"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "recoil",
+        description: "Effect of the broadside's recoil on the ship, from its weight and height against her size, beam, stability and steadiness. Larger values make a poorer gun platform.",
+        inputs: &["wgt_broad", "d_normal", "freeboard_dist", "gun_super_factor", "hull.bb", "stability_adj", "steadiness"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "roll_period",
+        description: "Time (s) for the ship to roll from one side to the other and back, from the beam and metacentric height.",
+        inputs: &["hull.bb", "metacenter"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "room",
+        description: "Share of the hull taken by the weights stowed below: magazines, stores, guns, machinery and vital and hull weights, each weighted by the space it needs.",
+        inputs: &["wgt_mag", "d_normal", "wgt_borne", "wgt_engine", "wgts.vital", "wgts.hull", "hull_space"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "seakeeping",
+        description: "Seakeeping ability, from freeboard, stability, length, weights at the ends and draught, limited by steadiness. 1 is average.",
+        inputs: &["free_cap", "stability", "hull.bb", "lwl", "armor.end", "wgt_hull_plus", "super_factor_long", "steadiness"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "stability",
+        description: "Inherent stability: displacement and beam against the sum of each weight times a fixed lever for its height, or a lever from a centre of gravity height where one is given. Below 1 the ship risks capsizing.",
+        inputs: &["d_normal", "hull.bb", "hull.t", "armor", "batteries", "wgts", "superstructure", "equipment", "torp_director", "deck_room", "vcg"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "stability_adj",
+        description: "Stability adjusted for the trim, which trades stability for steadiness.",
+        inputs: &["stability", "trim"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "steadiness",
+        description: "Steadiness as a gun platform, from the trim and the seakeeping calculation, up to 100.",
+        inputs: &["trim", "seaboat"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "str_comp",
+        description: "Composite strength, combining cross-sectional and longitudinal strength and leaning towards the weaker. 1 is the usual standard for a warship.",
+        inputs: &["str_cross", "str_long"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "str_cross",
+        description: "Cross-sectional strength: structural hull weight against the depth of the hull and the weight of guns, armour and machinery it must carry, adjusted for the hull material.",
+        inputs: &["wgt_struct", "hull.bb", "hull.t", "freeboard_dist", "wgt_broad", "wgt_borne", "gun_concentration", "gun_super_factor", "hp_max", "year", "hull_material"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "str_long",
+        description: "Longitudinal strength: hull weight against the length to depth ratio and the weights carried at the ends, adjusted for the hull material.",
+        inputs: &["wgt_hull_plus", "armor.bulkhead", "lwl", "hull.t", "free_cap", "armor.end", "wgt_borne", "super_factor_long", "year", "hull_material"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "super_factor_long",
+        description: "Lever of the main battery's weight in the longitudinal strength and seakeeping calculations, smaller for guns grouped amidships: a single group on one deck or two groups close together by gun_position.",
+        inputs: &["batteries", "gun_super_factor", "hull_room"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "throw_wgt",
+        description: "Weight of shells (lbs) fired per minute by all guns, adjusted for crew quality.",
+        inputs: &["batteries", "crew_quality"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "trial_speed",
+        description: "Speed (kts) in each load condition on the power needed for the top speed at normal displacement, with the draught and wetted surface of each condition.",
+        inputs: &["engine.vmax", "condition_d", "hull.t", "wp"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "vcg_lever",
+        description: "Lever in the stability calculation of a weight whose height above the keel is given: 1 at the waterline and 3 at the deck, using the average freeboard.",
+        inputs: &["hull.t", "freeboard"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "vmax",
+        description: "Top speed (kts), as given. The power to reach it follows from the hull's frictional and wave making resistance.",
+        inputs: &["engine.vmax"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "wgt_adj",
+        description: "Share of a full mount's weight each mount of a battery weighs, averaged over its mounts. Turrets holding two to five guns abreast weigh three quarters as much per mount as the guns would alone; other layouts weigh the same.",
+        inputs: &["batteries.groups.layout"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "wgt_boilers",
+        description: "Weight of the boilers (t), a share of the machinery weight that falls from 50% before 1900 to 40% after 1920 and is nothing for engines without boilers. The engines take 30% (turbines) or 35% (reciprocating), 70% without boilers and 5% more for electric or hydraulic drive; shafting and auxiliaries take the rest.",
+        inputs: &["wgt_engine", "engine.fuel", "engine.boiler", "engine.drive", "engine.year"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "wgt_borne",
+        description: "Weight of the guns scaled by how their mounts carry them and doubled, standing for the gun weight carried high in the mounts.",
+        inputs: &["batteries"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "wgt_hull",
+        description: "Weight of the hull (t): whatever the other weights leave of the normal displacement, or a fixed share of it for a merchant conversion.",
+        inputs: &["d_normal", "wgt_guns", "wgt_armor", "wgt_engine", "wgt_load", "wgt_misc", "conversion"],
+        source: Source::SpringSharp,
+    },
+    Formula {
+        name: "wgt_reload_gear",
+        description: "Weight of the rails, davits and hoists handling a set of torpedo reloads (t) where they are powered: 30% of the torpedoes' weight for reloads on deck and 20% below water, beyond the quarter taken by their stowage. Deck reloads also take a 3 ft lane along each set.",
+        inputs: &["torps.reload_gear", "torps.mount_kind", "torps.num", "torps.diam", "torps.len", "torps.year"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "wgt_spare",
+        description: "Displacement (t) of a merchant conversion not taken by its hull, weapons, machinery, load or cargo.",
+        inputs: &["d_normal", "conversion", "wgt_fitted"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "wgt_torp_director",
+        description: "Weight of a torpedo director (t) in a ship with torpedoes that aims them from one: 1 t of sights before 1912, 2 t with a deflection calculator, 3.5 t with transmitters to the tubes from 1925 and 5 t with a torpedo control computer from 1935.",
+        inputs: &["torp_director", "torps", "year"],
+        source: Source::Sharpie,
+    },
+    Formula {
+        name: "wp_calc",
+        description: "Coefficients of the waterplane area coefficient, which is the first plus the second times the prismatic coefficient. The first is 0.262 and the second 0.76 for cruiser and round sterns, 0.79 for small transoms and 0.81 for large ones. Hulls with less than two shafts or a block coefficient of 0.75 or more use 0.175 and 0.875.",
+        inputs: &["hull.stern_type", "engine.shafts", "cb"],
+        source: Source::SpringSharp,
+    },
+];
+
+// formula {{{1
+/// Description of the value with this name, or None if it is not
+/// documented.
+///
+pub fn formula(name: &str) -> Option<&'static Formula> {
+    FORMULAS.iter().find(|f| f.name == name)
+}
+
+// formulas {{{1
+/// Every documented formula in alphabetical order.
+///
+pub fn formulas() -> &'static [Formula] {
+    FORMULAS
+}
+
+// search {{{1
+/// Formulas whose name or description contains the text, ignoring case.
+///
+pub fn search(text: &str) -> Vec<&'static Formula> {
+    let text = text.to_lowercase();
+
+    FORMULAS.iter()
+        .filter(|f| f.name.contains(&text) || f.description.to_lowercase().contains(&text))
+        .collect()
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod docs {
+    use super::*;
+    use crate::{Ship, Template};
+
+    // Test formula {{{2
+    macro_rules! test_formula {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, name) = $value;
+
+                    assert_eq!(expected, formula(name).map(|f| f.source));
+                }
+            )*
+        }
+    }
+
+    test_formula! {
+        // name:            (source, name)
+        formula_inherited:  (Some(Source::SpringSharp), "stability_adj"),
+        formula_new:        (Some(Source::Sharpie), "kg"),
+        formula_missing:    (None, "nothing"),
+    }
+
+    // Test search {{{2
+    macro_rules! test_search {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, text) = $value;
+
+                    assert_eq!(expected, search(text).iter().map(|f| f.name).collect::<Vec<_>>());
+                }
+            )*
+        }
+    }
+
+    test_search! {
+        // name:            (names, text)
+        search_name:        (vec!["str_comp", "str_cross", "str_long"], "str_"),
+        search_description: (vec!["d_std"], "TREATIES"),
+        search_none:        (Vec::<&str>::new(), "warp drive"),
+    }
+
+    // Test table {{{2
+    #[test]
+    fn table() {
+        // Sorted so the reference reads in order and names are unique
+        assert!(FORMULAS.windows(2).all(|f| f[0].name < f[1].name));
+        assert!(FORMULAS.iter().all(|f| !f.description.is_empty() && !f.inputs.is_empty()));
+
+        // Every value Ship::get() calculates is documented
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        for name in ["d_lite", "d_std", "d_normal", "d_max", "vmax", "crew_min", "crew_max", "cost_lb", "cost_dollar"] {
+            assert!(ship.get(name).is_some() && formula(name).is_some(), "{}", name);
+        }
+
+        let text = formula("stability_adj").unwrap().to_string();
+        assert!(text.starts_with("stability_adj\n    "));
+        assert!(text.ends_with("    Source: SpringSharp 3b3"));
+    }
+}
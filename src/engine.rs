@@ -1,5 +1,3 @@
-use crate::Hull;
-
 use bitflags::{bitflags, bitflags_match};
 use serde::{Serialize, Deserialize};
 
@@ -41,14 +39,17 @@ pub struct Engine {
 impl Engine { // {{{2
     /// XXX: self.range is divided by this in bunker()
     const RANGE: f64 = 7000.0;
+    /// Largest funnel cross section per foot of beam (sq ft).
+    const FUNNEL_AREA: f64 = 1.7;
+    /// Stowage of coal in the bunkers (cu ft per ton).
+    pub const COAL_STOWAGE: f64 = 45.0;
+    /// Stowage of oil in the tanks (cu ft per ton).
+    pub const OIL_STOWAGE: f64 = 38.0;
 
     // set_shafts {{{3
-    /// Set the number of shafts in the engine and set any
-    /// Hull parameters that depend on the number of shafts.
+    /// Set the number of shafts in the engine.
     ///
-    pub fn set_shafts(&mut self, shafts: u32, hull: &mut Hull) -> u32 {
-        hull.set_shafts(shafts);
-
+    pub fn set_shafts(&mut self, shafts: u32) -> u32 {
         self.shafts = shafts;
         shafts
     }
@@ -60,10 +61,27 @@ impl Engine { // {{{2
         self.shafts
     }
 
+    // propulsion {{{3
+    /// Whether the ship can move under its own power.
+    ///
+    /// A ship with no maximum speed is an immobile floating battery or a
+    /// hulk. Any machinery it has is ignored.
+    ///
+    pub fn propulsion(&self) -> Propulsion {
+        if self.vmax > 0.0 { Propulsion::Powered } else { Propulsion::None }
+    }
+
+    // is_powered {{{3
+    /// True if the ship can move under its own power.
+    ///
+    pub fn is_powered(&self) -> bool {
+        self.propulsion() != Propulsion::None
+    }
+
     // hp {{{3
     /// Horsepower required to achieve a given speed.
     ///
-    fn hp(&self, v: f64, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
+    pub fn hp(&self, v: f64, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
         let len_hp =
             if v <= 15.0 {
                 lwl - (leff - lwl) 
@@ -97,6 +115,8 @@ impl Engine { // {{{2
     ///
     // XXX: Should vcruise be set to a minimum somewhere else?
     pub fn hp_cruise(&self, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
+        if !self.is_powered() { return 0.0; }
+
         self.hp(self.vcruise.min(self.vmax), d, lwl, leff, cs, ws)
     }
 
@@ -171,13 +191,14 @@ impl Engine { // {{{2
     /// Bunkerage weight.
     ///
     pub fn bunker(&self, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
+        if !self.is_powered() { return 0.0; }
         if self.vcruise == 0.0 { return 0.0; } // catch divide by zero
 
-        let bunker = self.range as f64 / (1.0 + 0.4 * (1.0 - self.pct_coal as f64));
+        let bunker = self.range as f64 / (1.0 + 0.4 * (1.0 - self.pct_coal));
         let bunker = bunker / self.boiler.bunker_factor(self.year);
 
         bunker /
-            (1.8 / self.hp_cruise(d, lwl, leff, cs, ws) * Self::RANGE as f64 * self.vcruise * 0.1) +
+            (1.8 / self.hp_cruise(d, lwl, leff, cs, ws) * Self::RANGE * self.vcruise * 0.1) +
             d * 0.005
     }
 
@@ -188,6 +209,15 @@ impl Engine { // {{{2
         self.bunker(d, lwl, leff, cs, ws) * 1.8
     }
 
+    // stowage {{{3
+    /// Average space taken by a ton of fuel (cu ft), from the share of
+    /// coal and oil burnt.
+    ///
+    pub fn stowage(&self) -> f64 {
+        let coal = if self.fuel.contains(FuelType::Coal) { self.pct_coal } else { 0.0 };
+
+        coal * Self::COAL_STOWAGE + (1.0 - coal) * Self::OIL_STOWAGE
+    }
 
     // num_engines {{{3
     /// Number of steam engines.
@@ -196,10 +226,49 @@ impl Engine { // {{{2
         self.boiler.num_engines()
     }
 
+    // uptake_area {{{3
+    /// Cross section of the boiler uptakes needed for a given horsepower.
+    ///
+    /// Boilers need less uptake for their power as they improve, and oil
+    /// fired boilers less than coal fired ones. Engines without boilers
+    /// need none.
+    ///
+    pub fn uptake_area(&self, hp: f64) -> f64 {
+        if !self.is_powered() || !self.fuel.is_steam() { return 0.0; }
+
+        let factor =
+                   if self.year < 1905 { 0.015 }
+            else if self.year < 1915 { 0.01 }
+            else if self.year < 1925 { 0.006 }
+            else                     { 0.0025 };
+
+        hp * factor * if self.fuel.contains(FuelType::Coal) { 1.0 } else { 0.8 }
+    }
+
+    // funnels {{{3
+    /// Number of funnels needed to carry the boiler uptakes of a hull of
+    /// beam b.
+    ///
+    pub fn funnels(&self, hp: f64, b: f64) -> u32 {
+        let area = self.uptake_area(hp);
+        if area == 0.0 || b == 0.0 { return 0; }
+
+        f64::max((area / Self::funnel_area(b)).ceil(), 1.0) as u32
+    }
+
+    // funnel_area {{{3
+    /// Largest uptake cross section a funnel of a hull of beam b carries.
+    ///
+    pub fn funnel_area(b: f64) -> f64 {
+        b * Self::FUNNEL_AREA
+    }
+
     // d_engine {{{3
     /// Displacement of the engine.
     ///
     pub fn d_engine(&self, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
+        if !self.is_powered() { return 0.0; }
+
         let factor = self.boiler.d_engine_factor(self.year, self.fuel.clone());
         let early =
             if self.year <= 1889 {
@@ -214,10 +283,37 @@ impl Engine { // {{{2
         ) / early
     }
 
+    // wgt_shares {{{3
+    /// Shares of the machinery weight taken by the boilers, the engines
+    /// with their gearing or motors, and the shafting and auxiliaries.
+    ///
+    /// Boilers grew lighter for their output as they improved. Turbines
+    /// are lighter than reciprocating engines, while electric and
+    /// hydraulic drives add motors or pumps. Engines that burn their fuel
+    /// directly need no boilers.
+    ///
+    pub fn wgt_shares(&self) -> (f64, f64, f64) {
+        if !self.is_powered() { return (0.0, 0.0, 0.0); }
+
+        let boilers =
+                 if !self.fuel.is_steam() { 0.0 }
+            else if self.year < 1900      { 0.5 }
+            else if self.year < 1920      { 0.45 }
+            else                          { 0.4 };
+
+        let engines =
+                 if !self.fuel.is_steam()         { 0.7 }
+            else if self.boiler.is_reciprocating() { 0.35 }
+            else                                   { 0.3 } +
+            if self.drive.intersects(DriveType::Electric | DriveType::Hydraulic) { 0.05 } else { 0.0 };
+
+        (boilers, engines, 1.0 - boilers - engines)
+    }
 }
 
 // Testing Engine {{{2
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod engine {
     use super::*;
     use crate::test_support::*;
@@ -248,6 +344,64 @@ mod engine {
         hp_v_other_hi_year_boundary: (19655.91, 24.0, 1890),
     }
 
+    // Test stowage {{{3
+    macro_rules! test_stowage {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, fuel, pct_coal) = $value;
+                    let mut eng = Engine::default();
+                    eng.fuel = fuel;
+                    eng.pct_coal = pct_coal;
+
+                    assert_eq!(expected, to_place(eng.stowage(), 2));
+                }
+            )*
+        }
+    }
+    test_stowage! {
+        // name:            (stowage, fuel, pct_coal)
+        stowage_coal:       (45.0, FuelType::Coal, 1.0),
+        stowage_oil:        (38.0, FuelType::Oil, 0.0),
+        stowage_mixed:      (41.5, FuelType::Coal | FuelType::Oil, 0.5),
+        stowage_diesel:     (38.0, FuelType::Diesel, 1.0),
+    }
+
+    // Test wgt_shares {{{3
+    macro_rules! test_wgt_shares {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, fuel, boiler, drive, year) = $value;
+                    let mut eng = Engine::default();
+                    eng.vmax = 20.0;
+                    eng.fuel = fuel;
+                    eng.boiler = boiler;
+                    eng.drive = drive;
+                    eng.year = year;
+
+                    let (b, e, s) = eng.wgt_shares();
+                    assert_eq!(expected, (to_place(b, 2), to_place(e, 2), to_place(s, 2)));
+                }
+            )*
+        }
+    }
+    test_wgt_shares! {
+        // name:                    ((boilers, engines, shafting), fuel, boiler, drive, year)
+        wgt_shares_recip:           ((0.5, 0.35, 0.15), FuelType::Coal, BoilerType::Complex, DriveType::Direct, 1895),
+        wgt_shares_turbine:         ((0.45, 0.3, 0.25), FuelType::Coal | FuelType::Oil, BoilerType::Turbine, DriveType::Direct, 1910),
+        wgt_shares_geared:          ((0.4, 0.3, 0.3), FuelType::Oil, BoilerType::Turbine, DriveType::Geared, 1935),
+        wgt_shares_electric:        ((0.4, 0.35, 0.25), FuelType::Oil, BoilerType::Turbine, DriveType::Electric, 1920),
+        wgt_shares_diesel:          ((0.0, 0.7, 0.3), FuelType::Diesel, BoilerType::empty(), DriveType::Direct, 1930),
+    }
+
+    #[test]
+    fn wgt_shares_unpowered() {
+        assert_eq!((0.0, 0.0, 0.0), Engine::default().wgt_shares());
+    }
+
     // Test hp_max {{{3
     macro_rules! test_hp_max {
         ($($name:ident: $value:expr,)*) => {
@@ -565,6 +719,93 @@ mod engine {
         d_engine_early: (168.32, 1889),
         d_engine_late: (165.21, 1890),
     }
+
+    // Test no propulsion {{{3
+    macro_rules! test_no_propulsion {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (year, fuel, boiler, vcruise, range) = $value;
+                    let mut eng = Engine::default();
+                    eng.year = year;
+                    eng.fuel = fuel;
+                    eng.boiler = boiler;
+                    eng.vcruise = vcruise;
+                    eng.range = range;
+
+                    let lwl = 500.0; let leff = 500.0;
+                    let cs = 0.2563; let ws = 12000.0; let d = 1000.0;
+
+                    assert_eq!(Propulsion::None, eng.propulsion());
+                    assert_eq!(0.0, eng.hp_max(d, lwl, leff, cs, ws));
+                    assert_eq!(0.0, eng.hp_cruise(d, lwl, leff, cs, ws));
+                    assert_eq!(0.0, eng.pw_max(d, lwl, cs, ws));
+                    assert_eq!(0.0, eng.bunker(d, lwl, leff, cs, ws));
+                    assert_eq!(0.0, eng.d_engine(d, lwl, leff, cs, ws));
+                }
+            )*
+        }
+    }
+    test_no_propulsion! {
+        // name:                      (year, fuel, boiler, vcruise, range)
+        no_propulsion_battery:        (0, FuelType::empty(), BoilerType::empty(), 0.0, 0),
+        no_propulsion_hulk:           (1890, FuelType::Coal, BoilerType::Simple, 10.0, 2000),
+    }
+
+    #[test]
+    fn propulsion_powered() {
+        let eng = Engine { vmax: 10.0, ..Default::default() };
+
+        assert_eq!(Propulsion::Powered, eng.propulsion());
+    }
+
+    // Test uptakes {{{3
+    macro_rules! test_uptakes {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year, fuel) = $value;
+                    let eng = Engine { year, fuel, vmax: 20.0, ..Default::default() };
+
+                    assert_eq!(expected, (to_place(eng.uptake_area(20000.0), 1), eng.funnels(20000.0, 60.0)));
+                }
+            )*
+        }
+    }
+    test_uptakes! {
+        // name:              ((area, funnels), year, fuel)
+        uptakes_early:        ((300.0, 3), 1900, FuelType::Coal),
+        uptakes_1910:         ((200.0, 2), 1910, FuelType::Coal),
+        uptakes_1920:         ((120.0, 2), 1920, FuelType::Coal),
+        uptakes_late:         ((50.0, 1), 1930, FuelType::Coal),
+        uptakes_oil:          ((40.0, 1), 1930, FuelType::Oil),
+        uptakes_mixed:        ((50.0, 1), 1930, FuelType::Coal | FuelType::Oil),
+        uptakes_diesel:       ((0.0, 0), 1930, FuelType::Diesel),
+    }
+}
+
+// Propulsion {{{1
+/// Whether a ship has working machinery.
+///
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum Propulsion {
+    /// No propulsion: floating batteries and hulks.
+    None,
+    /// Engine driven.
+    Powered,
+}
+
+impl fmt::Display for Propulsion { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::None    => "None",
+                Self::Powered => "Powered",
+            }
+        )
+    }
 }
 
 // FuelType {{{1
@@ -744,8 +985,10 @@ impl BoilerType {
     }
 
     // d_engine_factor {{{3
-    /// XXX: I do not know what this does.
+    /// Power (hp) per ton of machinery for each engine type fitted in
+    /// the year, summed over the types.
     ///
+    /// d_engine() divides it by the number of types to average it.
     pub fn d_engine_factor(&self, year: u32, fuel: FuelType) -> f64 {
         let a = if self.is_simple() {
                     if year <= 1884 { 1.2 + (year - 1860) as f64 * 0.05 }
@@ -773,8 +1016,12 @@ impl BoilerType {
     }
 
     // bunker_factor {{{3
-    /// XXX: I do not know what this does.
+    /// Fuel economy of the engines relative to 1910, which the fuel
+    /// needed for the range is divided by.
     ///
+    /// Reciprocating engines, and turbines before 1898, improve by a
+    /// seventieth a year up to 1910 and turbines faster after it.
+    #[allow(clippy::if_same_then_else)]
     pub fn bunker_factor(&self, year: u32) -> f64 {
         if self.is_reciprocating() {
             1.0 - (1910 - year) as f64 / 70.0 
@@ -0,0 +1,156 @@
+use crate::Tons;
+
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+// EquipmentKind {{{1
+/// Kind of minor equipment.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum EquipmentKind {
+    #[default]
+    Boats,
+    Paravanes,
+    SmokeGenerator,
+    Minesweeping,
+}
+
+impl fmt::Display for EquipmentKind { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Boats          => "boats",
+                Self::Paravanes      => "paravanes",
+                Self::SmokeGenerator => "smoke generator",
+                Self::Minesweeping   => "minesweeping gear",
+            }
+        )
+    }
+}
+
+impl EquipmentKind { // {{{2
+    // lever {{{3
+    /// Multiplier for the item's weight in the stability calculation.
+    ///
+    /// Boats sit on booms and davits above the freeboard deck. Everything
+    /// else is stowed on it.
+    ///
+    pub fn lever(&self) -> f64 {
+        match self {
+            Self::Boats => 4.0,
+            _           => 3.0,
+        }
+    }
+}
+
+// Equipment {{{1
+/// An item of minor equipment carried on deck.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Equipment {
+    /// Kind of item.
+    pub kind: EquipmentKind,
+    /// Weight of the item. The standard weight for the displacement and
+    /// year if not set.
+    #[serde(default)]
+    pub wgt: Option<Tons>,
+}
+
+impl Equipment { // {{{2
+    /// Year boats' outfits shrank as motor boats replaced pulling boats.
+    const MOTOR_BOAT_YEAR: u32 = 1920;
+    /// Year smoke generators burning chemicals in quantity came into use.
+    const CHEMICAL_SMOKE_YEAR: u32 = 1925;
+    /// Year heavier high-speed paravanes and Oropesa sweeps came into use.
+    const HIGH_SPEED_YEAR: u32 = 1930;
+    /// Year magnetic and acoustic sweeps were added to the wire sweep.
+    const INFLUENCE_SWEEP_YEAR: u32 = 1940;
+
+    // wgt {{{3
+    /// Weight of the item.
+    ///
+    pub fn wgt(&self, d: f64, year: u32) -> Tons {
+        self.wgt.unwrap_or(self.wgt_std(d, year))
+    }
+
+    // wgt_std {{{3
+    /// Standard weight of an item of this kind in a ship of this
+    /// displacement and year.
+    ///
+    pub fn wgt_std(&self, d: f64, year: u32) -> Tons {
+        match self.kind {
+            EquipmentKind::Boats          => Tons::long(d) * if year >= Self::MOTOR_BOAT_YEAR { 0.003 } else { 0.004 },
+            EquipmentKind::Paravanes      => Tons::long(if year >= Self::HIGH_SPEED_YEAR { 3.0 } else { 2.0 }),
+            EquipmentKind::SmokeGenerator => Tons::long(if year >= Self::CHEMICAL_SMOKE_YEAR { 5.0 } else { 3.0 }),
+            EquipmentKind::Minesweeping   => Tons::long(
+                     if year >= Self::INFLUENCE_SWEEP_YEAR { 30.0 }
+                else if year >= Self::HIGH_SPEED_YEAR      { 15.0 }
+                else                                       {  8.0 }
+            ),
+        }
+    }
+
+    // desc {{{3
+    /// Description of a list of items, e.g. "boats, 2 x paravanes".
+    ///
+    pub fn desc(items: &[Equipment]) -> String {
+        [EquipmentKind::Boats, EquipmentKind::Paravanes, EquipmentKind::SmokeGenerator, EquipmentKind::Minesweeping].iter()
+            .map(|k| (k, items.iter().filter(|e| e.kind == *k).count()))
+            .filter(|(_, n)| *n > 0)
+            .map(|(k, n)| if n == 1 { k.to_string() } else { format!("{} x {}", n, k) })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod equipment {
+    use super::*;
+    use crate::test_support::*;
+
+    // Test wgt {{{2
+    macro_rules! test_wgt {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, kind, wgt, year) = $value;
+
+                    let e = Equipment { kind, wgt };
+
+                    assert_eq!(expected, to_place(e.wgt(10_000.0, year).to_long(), 1));
+                }
+            )*
+        }
+    }
+
+    test_wgt! {
+        // name:                 (wgt, kind, wgt, year)
+        wgt_boats:               (40.0, EquipmentKind::Boats, None, 1910),
+        wgt_boats_motor:         (30.0, EquipmentKind::Boats, None, 1930),
+        wgt_paravanes:           (2.0, EquipmentKind::Paravanes, None, 1918),
+        wgt_paravanes_fast:      (3.0, EquipmentKind::Paravanes, None, 1935),
+        wgt_smoke:               (3.0, EquipmentKind::SmokeGenerator, None, 1918),
+        wgt_smoke_chemical:      (5.0, EquipmentKind::SmokeGenerator, None, 1925),
+        wgt_sweep_wire:          (8.0, EquipmentKind::Minesweeping, None, 1918),
+        wgt_sweep_oropesa:       (15.0, EquipmentKind::Minesweeping, None, 1935),
+        wgt_sweep_influence:     (30.0, EquipmentKind::Minesweeping, None, 1942),
+        wgt_set:                 (12.5, EquipmentKind::Paravanes, Some(Tons::long(12.5)), 1918),
+    }
+
+    // Test desc {{{2
+    #[test]
+    fn desc() {
+        let item = |kind| Equipment { kind, wgt: None };
+
+        assert_eq!("", Equipment::desc(&[]));
+        assert_eq!("boats, 2 x paravanes, smoke generator", Equipment::desc(&[
+            item(EquipmentKind::Paravanes),
+            item(EquipmentKind::SmokeGenerator),
+            item(EquipmentKind::Boats),
+            item(EquipmentKind::Paravanes),
+        ]));
+    }
+}
@@ -0,0 +1,261 @@
+use crate::{Ship, Tons, BalanceStep};
+
+use serde::Serialize;
+use std::fmt;
+
+// FitTargets {{{1
+/// Known figures of a real ship to fit a design to.
+///
+#[derive(Clone, Debug, Default)]
+pub struct FitTargets {
+    /// Standard displacement.
+    pub d_std: Option<Tons>,
+    /// Maximum speed (kts).
+    pub vmax: Option<f64>,
+    /// Shaft horsepower at maximum speed.
+    pub hp: Option<f64>,
+    /// Main belt thickness (in).
+    pub belt: Option<f64>,
+    /// Composite strength the hull is fitted to; the weight the design
+    /// does not account for is taken up in the hull until it reaches
+    /// this (1.0 for a real ship). The miscellaneous hull weight is left
+    /// as it is without it.
+    pub strength: Option<f64>,
+}
+
+// Residual {{{1
+/// How far a fitted design is from one of the known figures.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Residual {
+    /// Figure being compared.
+    pub name: &'static str,
+    /// Known value.
+    pub target: f64,
+    /// Value for the fitted design.
+    pub value: f64,
+}
+
+impl fmt::Display for Residual { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {:.2} against {:.2} ({:+.2}, {:+.1}%)",
+            self.name, self.value, self.target, self.error(), self.pct())
+    }
+}
+
+impl Residual { // {{{2
+    // error {{{3
+    /// Difference between the design and the known value.
+    ///
+    pub fn error(&self) -> f64 {
+        self.value - self.target
+    }
+
+    // pct {{{3
+    /// Difference as a percentage of the known value.
+    ///
+    pub fn pct(&self) -> f64 {
+        if self.target == 0.0 { return 0.0; } // catch divide by zero
+
+        self.error() / self.target * 100.0
+    }
+}
+
+// Fit {{{1
+/// A design fitted to known figures.
+///
+#[derive(Clone, Debug)]
+pub struct Fit {
+    /// Fitted design.
+    pub ship: Ship,
+    /// How far the fitted design is from each known figure.
+    pub residuals: Vec<Residual>,
+    /// False if the standard displacement could not be matched.
+    pub converged: bool,
+    /// Each iteration made to match the standard displacement.
+    pub trace: Vec<BalanceStep>,
+}
+
+impl fmt::Display for Fit { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Block coefficient: {:.3}", self.ship.hull.cb())?;
+        writeln!(f, "Miscellaneous hull weight: {} t", self.ship.wgts.hull)?;
+        for r in self.residuals.iter() {
+            writeln!(f, "{}", r)?;
+        }
+        if !self.converged {
+            writeln!(f, "Standard displacement did not converge after {} iterations", self.trace.len())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Fit { // {{{2
+    // trace_table {{{3
+    /// The iterations made to match the standard displacement as a
+    /// table.
+    ///
+    pub fn trace_table(&self) -> String {
+        crate::balance::trace_table(&self.trace)
+    }
+}
+
+impl Ship { // {{{1
+    // fit {{{2
+    /// Adjust the free coefficients of the design to reproduce a real
+    /// ship.
+    ///
+    /// The belt and speed are set from the known figures. The design is
+    /// balanced to the standard displacement, adjusting the normal
+    /// displacement and with it the Block Coefficient. If a strength is
+    /// given the miscellaneous hull weight then takes up the weight the
+    /// design does not account for, bringing the composite strength down
+    /// to it. Horsepower is not adjusted and is only compared.
+    ///
+    pub fn fit(&self, targets: &FitTargets) -> Fit {
+        let mut ship = self.clone();
+
+        if let Some(belt) = targets.belt { ship.armor.main.thick = belt; }
+        if let Some(vmax) = targets.vmax { ship.engine.vmax = vmax; }
+
+        // Displacement {{{3
+        let (mut converged, mut trace) = (true, Vec::new());
+        if let Some(d_std) = targets.d_std {
+            let balance = ship.balance(d_std);
+            (ship, converged, trace) = (balance.ship, balance.converged, balance.trace);
+        }
+
+        // Miscellaneous weight {{{3
+        if let Some(strength) = targets.strength {
+            ship.wgts.hull = 0;
+            if ship.str_comp() > strength {
+                let (mut lo, mut hi) = (0, ship.hull.d().to_long() as u32);
+
+                while lo < hi {
+                    let mid = (lo + hi).div_ceil(2);
+                    ship.wgts.hull = mid;
+
+                    if ship.str_comp() >= strength { lo = mid; } else { hi = mid - 1; }
+                }
+                ship.wgts.hull = lo;
+            }
+        }
+
+        // Residuals {{{3
+        let mut residuals = Vec::new();
+        let hp = ship.engine.hp_max(ship.hull.d().to_long(), ship.hull.lwl(), ship.hull.leff(), ship.hull.cs(), ship.hull.ws());
+
+        if let Some(d_std) = targets.d_std { residuals.push(Residual { name: "Standard displacement (t)", target: d_std.to_long(), value: ship.d_std().to_long() }); }
+        if let Some(vmax) = targets.vmax   { residuals.push(Residual { name: "Speed (kts)", target: vmax, value: ship.engine.vmax }); }
+        if let Some(t) = targets.hp        { residuals.push(Residual { name: "Shaft horsepower", target: t, value: hp }); }
+        if let Some(belt) = targets.belt   { residuals.push(Residual { name: "Main belt (in)", target: belt, value: ship.armor.main.thick }); }
+        if let Some(s) = targets.strength  { residuals.push(Residual { name: "Composite strength", target: s, value: ship.str_comp() }); }
+
+        Fit { ship, residuals, converged, trace }
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod fit {
+    use super::*;
+    use crate::Template;
+    use crate::test_support::*;
+
+    // Test pct {{{2
+    macro_rules! test_pct {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((error, pct), target, value) = $value;
+
+                    let r = Residual { name: "", target, value };
+
+                    assert_eq!(error, to_place(r.error(), 2));
+                    assert_eq!(pct, to_place(r.pct(), 2));
+                }
+            )*
+        }
+    }
+
+    test_pct! {
+        // name:       ((error, pct), target, value)
+        pct_over:      ((10.0, 10.0), 100.0, 110.0),
+        pct_under:     ((-25.0, -25.0), 100.0, 75.0),
+        pct_zero:      ((5.0, 0.0), 0.0, 5.0),
+    }
+
+    // Test fit {{{2
+    #[test]
+    fn fit() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let targets = FitTargets {
+            d_std: Some(ship.d_std() * 1.05),
+            vmax: Some(22.0),
+            hp: Some(30_000.0),
+            belt: Some(12.0),
+            strength: Some(1.0),
+        };
+
+        let fit = ship.fit(&targets);
+
+        assert!(fit.converged);
+        assert!(fit.trace.len() > 1);
+        assert!((fit.ship.d_std() - targets.d_std.unwrap()).to_long().abs() < 0.5);
+        assert_eq!(22.0, fit.ship.engine.vmax);
+        assert_eq!(12.0, fit.ship.armor.main.thick);
+        assert!(fit.ship.hull.cb() > ship.hull.cb());
+
+        // Strength is brought down to the target by the last ton
+        assert!(fit.ship.str_comp() >= 1.0);
+        let mut heavier = fit.ship.clone();
+        heavier.wgts.hull += 1;
+        assert!(heavier.str_comp() < 1.0);
+
+        assert_eq!(
+            vec!["Standard displacement (t)", "Speed (kts)", "Shaft horsepower", "Main belt (in)", "Composite strength"],
+            fit.residuals.iter().map(|r| r.name).collect::<Vec<_>>()
+        );
+    }
+
+    // Test hull weight {{{2
+    macro_rules! test_fit_hull_weight {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((wgt, residuals), strength) = $value;
+
+                    // Overloaded even without miscellaneous weight
+                    let mut ship = Ship::load("tests/fixtures/protected_cruiser_1895.ship".into()).unwrap();
+                    ship.wgts.hull = 500;
+
+                    let fit = ship.fit(&FitTargets { strength, ..Default::default() });
+
+                    assert_eq!(wgt, fit.ship.wgts.hull);
+                    assert_eq!(residuals, fit.residuals.len());
+                    assert!(fit.converged && fit.trace.is_empty());
+                }
+            )*
+        }
+    }
+
+    test_fit_hull_weight! {
+        // name:            ((hull weight, residuals), strength)
+        fit_weak:           ((0, 1), Some(1.0)),
+        fit_kept:           ((500, 0), None),
+    }
+
+    // Test not converging {{{2
+    #[test]
+    fn fit_not_converged() {
+        let ship = Ship::from_template(Template::Destroyer1935);
+        let fit = ship.fit(&FitTargets { d_std: Some(Tons::long(-100.0)), ..Default::default() });
+
+        assert!(!fit.converged);
+        assert!(fit.to_string().contains("Standard displacement did not converge after 50 iterations"));
+    }
+}
@@ -0,0 +1,478 @@
+use crate::{Ship, ShipHeader, Tons, SHIP_FILE_EXT, SS_SHIP_FILE_EXT, check_json_version};
+use crate::treaty::{Treaty, TreatyCategory};
+
+use format_num::format_num;
+use serde_json::{json, Value};
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+/// Ships in each treaty category of each country, with the file each
+/// was loaded from.
+pub type TreatyTonnage<'a> = BTreeMap<String, BTreeMap<TreatyCategory, Vec<(&'a str, &'a Ship)>>>;
+
+// FleetEdit {{{1
+/// Change made to every ship in a fleet.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum FleetEdit {
+    /// Set the field at a key path, such as `country`, to a value.
+    Set(String, Value),
+    /// Name the ships from a pattern in which `%d` is replaced by the
+    /// ship's place in the fleet, counting from 1.
+    Renumber(String),
+    /// Add years to the year of every ship's engine.
+    EngineYear(i32),
+}
+
+// Fleet {{{1
+/// A collection of ship designs.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Fleet {
+    /// Ships along with the file each was loaded from.
+    pub ships: Vec<(String, Ship)>,
+}
+
+impl Fleet { // {{{2
+    // load_dir {{{3
+    /// Load every sharpie and SpringSharp ship file in a directory.
+    ///
+    pub fn load_dir(dir: &str) -> Result<Fleet, Box<dyn Error>> {
+        let mut ships = Vec::new();
+        for file in Self::ship_files(dir)? {
+            match Ship::open_any(file.clone()) {
+                Ok((ship, _)) => ships.push((file, ship)),
+                Err(error)    => return Err(format!("{}: {}", file, error).into()),
+            }
+        }
+
+        Ok(Fleet { ships })
+    }
+
+    // peek_dir {{{3
+    /// Summarise every sharpie and SpringSharp ship file in a directory
+    /// without loading the ships.
+    ///
+    pub fn peek_dir(dir: &str) -> Result<Vec<(String, ShipHeader)>, Box<dyn Error>> {
+        let mut headers = Vec::new();
+        for file in Self::ship_files(dir)? {
+            match ShipHeader::peek(&file) {
+                Ok(header) => headers.push((file, header)),
+                Err(error) => return Err(format!("{}: {}", file, error).into()),
+            }
+        }
+
+        Ok(headers)
+    }
+
+    // ship_files {{{3
+    /// Sharpie and SpringSharp ship files in a directory, sorted by name.
+    ///
+    fn ship_files(dir: &str) -> Result<Vec<String>, Box<dyn Error>> {
+        let mut files: Vec<String> = fs::read_dir(dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == SHIP_FILE_EXT || e == SS_SHIP_FILE_EXT))
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+
+        Ok(files)
+    }
+
+    // apply {{{3
+    /// Make an edit to every ship in the fleet.
+    ///
+    /// No ship is changed if the edit cannot be made to all of them.
+    ///
+    pub fn apply(&mut self, edit: &FleetEdit) -> Result<(), Box<dyn Error>> {
+        let mut ships = self.ships.clone();
+
+        for (i, (file, ship)) in ships.iter_mut().enumerate() {
+            let result = match edit {
+                FleetEdit::Set(path, value) => ship.set(path, value.clone()),
+                FleetEdit::Renumber(pattern) =>
+                    ship.set("name", json!(pattern.replace("%d", &(i + 1).to_string()))),
+                FleetEdit::EngineYear(years) => {
+                    let year = ship.engine.year as i64 + *years as i64;
+                    ship.set("engine.year", json!(year))
+                },
+            };
+
+            if let Err(error) = result { return Err(format!("{}: {}", file, error).into()); }
+        }
+
+        self.ships = ships;
+
+        Ok(())
+    }
+
+    // check_save {{{3
+    /// Check that every ship can be saved as a sharpie file, and its
+    /// text report alongside it if reports is true, and return the
+    /// sharpie files.
+    ///
+    /// A ship may only be saved over the file it was loaded from, so it
+    /// is an error if two ships would be saved to the same file or a
+    /// file to be written already exists and is not the ship's own.
+    ///
+    pub fn check_save(&self, reports: bool) -> Result<Vec<String>, Box<dyn Error>> {
+        let files: Vec<String> = self.ships.iter()
+            .map(|(file, _)| Path::new(file).with_extension(SHIP_FILE_EXT).to_string_lossy().into_owned())
+            .collect();
+
+        for (i, ((file, _), to)) in self.ships.iter().zip(files.iter()).enumerate() {
+            if files[..i].contains(to) {
+                return Err(format!("{}: more than one ship would be saved to this file", to).into());
+            }
+            if to != file && Path::new(to).exists() {
+                return Err(format!("{}: already exists and is not the file {} was loaded from", to, file).into());
+            }
+        }
+
+        if reports { self.check_reports()?; }
+
+        Ok(files)
+    }
+
+    // save {{{3
+    /// Save every ship as a sharpie file and return the files written.
+    ///
+    /// Ships loaded from SpringSharp files are saved next to them rather
+    /// than over them. Nothing is saved if check_save() fails.
+    ///
+    pub fn save(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let files = self.check_save(false)?;
+
+        for ((_, ship), to) in self.ships.iter().zip(files.iter()) {
+            if let Err(error) = ship.save(to.clone()) { return Err(format!("{}: {}", to, error).into()); }
+        }
+
+        Ok(files)
+    }
+
+    // save_reports {{{3
+    /// Save a text report for every ship alongside its file and return
+    /// the files written.
+    ///
+    /// Nothing is saved if any of the reports already exists.
+    ///
+    pub fn save_reports(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let files = self.check_reports()?;
+
+        for ((_, ship), to) in self.ships.iter().zip(files.iter()) {
+            if let Err(error) = fs::write(to, ship.report()) { return Err(format!("{}: {}", to, error).into()); }
+        }
+
+        Ok(files)
+    }
+
+    // check_reports {{{3
+    /// Check that none of the text reports, alongside each ship's file,
+    /// already exists and return them.
+    ///
+    fn check_reports(&self) -> Result<Vec<String>, Box<dyn Error>> {
+        let files: Vec<String> = self.ships.iter()
+            .map(|(file, _)| Path::new(file).with_extension("txt").to_string_lossy().into_owned())
+            .collect();
+
+        match files.iter().find(|to| Path::new(to).exists()) {
+            Some(to) => Err(format!("{}: report already exists", to).into()),
+            None     => Ok(files),
+        }
+    }
+
+    // treaty_tonnage {{{3
+    /// Ships in each treaty category of each country.
+    ///
+    /// Countries that signed the treaty are grouped under the name of the
+    /// signatory.
+    ///
+    pub fn treaty_tonnage(&self, treaty: Treaty) -> TreatyTonnage<'_> {
+        let mut tonnage = TreatyTonnage::new();
+
+        for (file, ship) in self.ships.iter() {
+            let country = treaty.signatory(&ship.country)
+                .map(|s| s.to_string())
+                .unwrap_or(ship.country.clone());
+
+            tonnage.entry(country).or_default()
+                .entry(treaty.category(ship)).or_default()
+                .push((file.as_str(), ship));
+        }
+
+        tonnage
+    }
+
+    // treaty_report {{{3
+    /// Tonnage of the fleet in each treaty category of each country
+    /// against the treaty's limits.
+    ///
+    pub fn treaty_report(&self, treaty: Treaty) -> String {
+        let mut r = String::new();
+
+        let _ = writeln!(r, "{} tonnage", treaty);
+
+        for (country, categories) in self.treaty_tonnage(treaty) {
+            let _ = writeln!(r);
+            let _ = writeln!(r, "{}{}",
+                if country.is_empty() { "Unknown country" } else { &country },
+                if treaty.signatory(&country).is_none() { " (not a signatory)" } else { "" }
+            );
+
+            for (category, ships) in categories {
+                let total: Tons = ships.iter().map(|(_, s)| s.d_std()).sum();
+
+                let _ = match treaty.limit(&country, category) {
+                    Some(limit) => writeln!(r, "    {}: {} / {} tons{}",
+                        category,
+                        format_num!(",.0", total.to_long()),
+                        format_num!(",.0", limit.to_long()),
+                        if total > limit {
+                            format!(" - OVER LIMIT by {} tons", format_num!(",.0", (total - limit).to_long()))
+                        } else { "".into() }
+                    ),
+                    None => writeln!(r, "    {}: {} tons (no limit)",
+                        category,
+                        format_num!(",.0", total.to_long())
+                    ),
+                };
+
+                for (file, ship) in ships {
+                    let _ = writeln!(r, "        - {} ({}): {} tons",
+                        ship.name,
+                        file,
+                        format_num!(",.0", ship.d_std().to_long())
+                    );
+                    for issue in treaty.check(ship) {
+                        let _ = writeln!(r, "          Caution: {}", issue);
+                    }
+                }
+            }
+        }
+
+        r
+    }
+
+    // treaty_json {{{3
+    /// Tonnage of the fleet in each treaty category of each country
+    /// against the treaty's limits as JSON in a schema version.
+    ///
+    /// Tonnages are standard displacements in long tons. Categories
+    /// without a limit have a null limit.
+    ///
+    pub fn treaty_json(&self, treaty: Treaty, version: u32) -> Result<String, Box<dyn Error>> {
+        check_json_version(version)?;
+
+        let countries: Vec<Value> = self.treaty_tonnage(treaty).into_iter().map(|(country, categories)| {
+            let categories: Vec<Value> = categories.into_iter().map(|(category, ships)| {
+                let total: Tons = ships.iter().map(|(_, s)| s.d_std()).sum();
+                let limit = treaty.limit(&country, category);
+
+                json!({
+                    "category": category.to_string(),
+                    "tonnage": total.to_long(),
+                    "limit": limit.map(|l| l.to_long()),
+                    "over": limit.is_some_and(|l| total > l),
+                    "ships": ships.iter().map(|(file, ship)| json!({
+                        "name": ship.name,
+                        "file": file,
+                        "tonnage": ship.d_std().to_long(),
+                        "cautions": treaty.check(ship),
+                    })).collect::<Vec<Value>>(),
+                })
+            }).collect();
+
+            json!({
+                "country": country,
+                "signatory": treaty.signatory(&country).is_some(),
+                "categories": categories,
+            })
+        }).collect();
+
+        let report = json!({
+            "schema_version": version,
+            "treaty": treaty.to_string(),
+            "countries": countries,
+        });
+
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod fleet {
+    use super::*;
+    use crate::Template;
+
+    fn ship(name: &str, country: &str, template: Template) -> (String, Ship) {
+        let mut ship = Ship::from_template(template);
+        ship.name = name.into();
+        ship.country = country.into();
+
+        (format!("{}.ship", name), ship)
+    }
+
+    // Test load_dir {{{2
+    #[test]
+    fn load_dir() {
+        let fleet = Fleet::load_dir("tests/fixtures").unwrap();
+
+        assert!(fleet.ships.len() >= 5);
+        assert!(fleet.ships.iter().all(|(f, _)| f.ends_with(SHIP_FILE_EXT) || f.ends_with(SS_SHIP_FILE_EXT)));
+    }
+
+    // Test peek_dir {{{2
+    #[test]
+    fn peek_dir() {
+        let fleet = Fleet::load_dir("tests/fixtures").unwrap();
+        let headers = Fleet::peek_dir("tests/fixtures").unwrap();
+
+        assert_eq!(fleet.ships.len(), headers.len());
+        for ((file, ship), (peeked, header)) in fleet.ships.iter().zip(headers.iter()) {
+            assert_eq!((file, &ship.name), (peeked, &header.name));
+        }
+    }
+
+    // Test apply {{{2
+    macro_rules! test_apply {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, edit) = $value;
+
+                    let mut fleet = Fleet { ships: vec![
+                        ship("A", "USA", Template::Dreadnought1910),
+                        ship("B", "USA", Template::Destroyer1935),
+                    ]};
+                    for (_, s) in fleet.ships.iter_mut() { s.engine.year = 1920; }
+
+                    fleet.apply(&edit).unwrap();
+
+                    assert_eq!(expected, fleet.ships.iter()
+                        .map(|(_, s)| (s.name.as_str(), s.country.as_str(), s.engine.year))
+                        .collect::<Vec<_>>());
+                }
+            )*
+        }
+    }
+
+    test_apply! {
+        // name:           (ships, edit)
+        apply_country:     (vec![("A", "Britain", 1920), ("B", "Britain", 1920)], FleetEdit::Set("country".into(), json!("Britain"))),
+        apply_renumber:    (vec![("Design 1935-1", "USA", 1920), ("Design 1935-2", "USA", 1920)], FleetEdit::Renumber("Design 1935-%d".into())),
+        apply_year:        (vec![("A", "USA", 1925), ("B", "USA", 1925)], FleetEdit::EngineYear(5)),
+        apply_year_back:   (vec![("A", "USA", 1910), ("B", "USA", 1910)], FleetEdit::EngineYear(-10)),
+    }
+
+    #[test]
+    fn apply_invalid() {
+        let mut fleet = Fleet { ships: vec![
+            ship("A", "USA", Template::Dreadnought1910),
+            ship("B", "USA", Template::Destroyer1935),
+        ]};
+
+        assert!(fleet.apply(&FleetEdit::Set("nothing".into(), json!(1))).unwrap_err().to_string().starts_with("A.ship: "));
+        assert!(fleet.apply(&FleetEdit::EngineYear(-5000)).is_err());
+        assert!(fleet.ships.iter().all(|(_, s)| s.country == "USA"));
+    }
+
+    // Test save {{{2
+    #[test]
+    fn save_same_file() {
+        let fleet = Fleet { ships: vec![
+            ("/nonexistent/A.ship".into(), Ship::from_template(Template::Dreadnought1910)),
+            ("/nonexistent/A.sship".into(), Ship::from_template(Template::Dreadnought1910)),
+        ]};
+
+        assert!(fleet.save().unwrap_err().to_string().contains("more than one ship"));
+    }
+
+    #[test]
+    fn save_over_other() {
+        let dir = std::env::temp_dir().join(format!("sharpie-fleet-save-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let file = |name: &str| dir.join(name).to_string_lossy().into_owned();
+
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        ship.save(file("A.ship")).unwrap();
+        fs::write(file("A.txt"), "").unwrap();
+
+        // The sharpie file is the ship's own, its report is not
+        let own = Fleet { ships: vec![(file("A.ship"), ship.clone())] };
+        assert!(own.check_save(false).is_ok());
+        assert!(own.check_save(true).unwrap_err().to_string().contains("report already exists"));
+        assert!(own.save_reports().is_err());
+
+        // A SpringSharp ship is not saved over another sharpie file
+        let other = Fleet { ships: vec![(file("A.sship"), ship)] };
+        assert!(other.save().unwrap_err().to_string().contains("already exists"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // Test treaty_tonnage {{{2
+    #[test]
+    fn treaty_tonnage() {
+        let fleet = Fleet { ships: vec![
+            ship("A", "USA", Template::Dreadnought1910),
+            ship("B", "United States", Template::FastBattleship1940),
+            ship("C", "USA", Template::Destroyer1935),
+            ship("D", "Germany", Template::Dreadnought1910),
+        ]};
+        let tonnage = fleet.treaty_tonnage(Treaty::Washington);
+
+        assert_eq!(vec!["Germany", "United States"], tonnage.keys().collect::<Vec<_>>());
+        assert_eq!(2, tonnage["United States"][&TreatyCategory::CapitalShip].len());
+        assert_eq!(1, tonnage["United States"][&TreatyCategory::Auxiliary].len());
+    }
+
+    // Test treaty_report {{{2
+    #[test]
+    fn treaty_report() {
+        let mut fleet = Fleet { ships: vec![
+            ship("A", "France", Template::Dreadnought1910),
+            ship("B", "Germany", Template::Dreadnought1910),
+        ]};
+        let report = fleet.treaty_report(Treaty::Washington);
+
+        assert!(report.starts_with("Washington Naval Treaty tonnage"));
+        assert!(report.contains("Germany (not a signatory)"));
+        assert!(report.contains(" / 175,000 tons\n"));
+        assert!(report.contains("        - A (A.ship): "));
+
+        for i in 0..10 { fleet.ships.push(ship(&format!("F{}", i), "France", Template::Dreadnought1910)); }
+        assert!(fleet.treaty_report(Treaty::Washington).contains(" / 175,000 tons - OVER LIMIT by "));
+    }
+
+    // Test treaty_json {{{2
+    #[test]
+    fn treaty_json() {
+        let mut fleet = Fleet { ships: vec![
+            ship("A", "France", Template::Dreadnought1910),
+            ship("B", "Germany", Template::Dreadnought1910),
+        ]};
+        let json: Value = serde_json::from_str(&fleet.treaty_json(Treaty::Washington, 2).unwrap()).unwrap();
+
+        assert_eq!(2, json["schema_version"]);
+        assert_eq!("France", json["countries"][0]["country"]);
+        assert_eq!(true, json["countries"][0]["signatory"]);
+        assert_eq!(175_000.0, json["countries"][0]["categories"][0]["limit"].as_f64().unwrap());
+        assert_eq!(false, json["countries"][0]["categories"][0]["over"]);
+        assert_eq!("A.ship", json["countries"][0]["categories"][0]["ships"][0]["file"]);
+        assert_eq!(Value::Null, json["countries"][1]["categories"][0]["limit"]);
+
+        for i in 0..10 { fleet.ships.push(ship(&format!("F{}", i), "France", Template::Dreadnought1910)); }
+        let json: Value = serde_json::from_str(&fleet.treaty_json(Treaty::Washington, 1).unwrap()).unwrap();
+        assert_eq!(true, json["countries"][0]["categories"][0]["over"]);
+
+        assert!(fleet.treaty_json(Treaty::Washington, 0).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{Hull, FileFormat, NumberFormat, SHIP_FILE_VERSION};
+use crate::convert::Version;
+use crate::hull::estimate;
+
+use serde::{Serialize, Deserialize};
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+// ShipHeader {{{1
+/// Summary of a ship file, read without loading the whole ship.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct ShipHeader {
+    pub name: String,
+    pub country: String,
+    pub kind: String,
+    pub year: u32,
+    /// Normal displacement (t).
+    pub d: f64,
+    /// Format of the file.
+    pub format: FileFormat,
+}
+
+/// Parts of a sharpie ship file the header is read from. Every other
+/// field is skipped over without being built.
+///
+#[derive(Deserialize)]
+struct Peek {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    country: String,
+    #[serde(default)]
+    kind: String,
+    #[serde(default)]
+    year: u32,
+    hull: PeekHull,
+}
+
+/// Parts of the hull the displacement is found from.
+///
+#[derive(Deserialize)]
+struct PeekHull {
+    d: Option<f64>,
+    cb: Option<f64>,
+    #[serde(default)]
+    lwl: f64,
+    #[serde(default)]
+    bb: f64,
+    #[serde(default)]
+    t: f64,
+}
+
+impl ShipHeader { // {{{2
+    /// Lines of a SpringSharp 3 file holding the name, country, kind,
+    /// year, waterline length, draught, block coefficient and beam
+    /// including bulges, counting from 1.
+    const SS_LINES: [usize; 8] = [2, 3, 4, 13, 15, 17, 19, 129];
+
+    // peek {{{3
+    /// Read the summary of a sharpie or SpringSharp ship file.
+    ///
+    /// Only the start of a SpringSharp file is read. A sharpie file is
+    /// read through but only the summary's fields are kept.
+    ///
+    pub fn peek(path: &str) -> Result<ShipHeader, Box<dyn Error>> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let mut first = String::new();
+        reader.read_line(&mut first)?;
+
+        match FileFormat::detect(&first) {
+            Some(FileFormat::Sharpie)     => Self::peek_sharpie(&first, reader),
+            Some(FileFormat::SpringSharp) => Self::peek_springsharp(&first, reader),
+            Some(format)                  => Err(format!("{} ship files are not supported", format).into()),
+            None                          => Err("Unknown file format".into()),
+        }
+    }
+
+    // peek_sharpie {{{3
+    /// Read the summary from the rest of a sharpie file after its version.
+    ///
+    fn peek_sharpie(first: &str, reader: impl BufRead) -> Result<ShipHeader, Box<dyn Error>> {
+        let version: Version = serde_json::from_str(first)?;
+        if version.version != SHIP_FILE_VERSION {
+            return Err(format!("Cannot open ship files of this version: {}!", version.version).into());
+        }
+
+        let mut stream = serde_json::Deserializer::from_reader(reader).into_iter::<Peek>();
+        let peek = stream.next().ok_or("Ship file has no ship")??;
+
+        let hull = peek.hull;
+        let d = match (hull.d, hull.cb) {
+            (Some(d), _)     => d,
+            (None, Some(cb)) => estimate::displacement(hull.lwl, hull.bb, hull.t, cb),
+            (None, None)     => return Err("Ship file sets neither the block coefficient (hull.cb) nor the displacement (hull.d)".into()),
+        };
+
+        Ok(ShipHeader {
+            name: peek.name,
+            country: peek.country,
+            kind: peek.kind,
+            year: peek.year,
+            d,
+            format: FileFormat::Sharpie,
+        })
+    }
+
+    // peek_springsharp {{{3
+    /// Read the summary from the start of a SpringSharp 3 file.
+    ///
+    fn peek_springsharp(first: &str, reader: impl BufRead) -> Result<ShipHeader, Box<dyn Error>> {
+        if !first.contains("SpringSharp Version 3.0") { return Err("SpringSharp file too old".into()); }
+
+        let last = Self::SS_LINES[Self::SS_LINES.len() - 1];
+        let mut lines = vec![first.trim_end().to_string()];
+        for line in reader.lines().take(last - 1) { lines.push(line?.trim().to_string()); }
+        if lines.len() < last { return Err("SpringSharp file is too short".into()); }
+
+        let format = NumberFormat::detect(&lines);
+        let [name, country, kind, year, lwl, t, cb, bb] = Self::SS_LINES.map(|n| lines[n - 1].as_str());
+
+        let mut hull = Hull::default();
+        hull.set_lwl(format.parse(lwl)?);
+        hull.bb = format.parse(bb)?;
+        hull.t = format.parse(t)?;
+        hull.set_cb(format.parse(cb)?);
+
+        Ok(ShipHeader {
+            name: name.into(),
+            country: country.into(),
+            kind: kind.into(),
+            year: format.parse(year)?,
+            d: hull.d().to_long(),
+            format: FileFormat::SpringSharp,
+        })
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod header {
+    use super::*;
+    use crate::Ship;
+    use crate::test_support::*;
+
+    // Test peek {{{2
+    macro_rules! test_peek {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (format, file) = $value;
+
+                    let (ship, _) = Ship::open_any(file.to_string()).unwrap();
+                    let header = ShipHeader::peek(file).unwrap();
+
+                    assert_eq!(format, header.format);
+                    assert_eq!(
+                        (ship.name, ship.country, ship.kind, ship.year),
+                        (header.name, header.country, header.kind, header.year)
+                    );
+                    assert_eq!(to_place(ship.hull.d().to_long(), 3), to_place(header.d, 3));
+                }
+            )*
+        }
+    }
+
+    test_peek! {
+        // name:                (format, file)
+        peek_sharpie:           (FileFormat::Sharpie, "tests/fixtures/dreadnought_1915.ship"),
+        peek_springsharp:       (FileFormat::SpringSharp, "tests/fixtures/dreadnought_1915.sship"),
+        peek_destroyer:         (FileFormat::Sharpie, "tests/fixtures/destroyer_1935.ship"),
+    }
+
+    // Test peek errors {{{2
+    #[test]
+    fn peek_errors() {
+        assert!(ShipHeader::peek("tests/fixtures/no_such_file.ship").is_err());
+        assert!(ShipHeader::peek("tests/fixtures/README.md").is_err());
+    }
+
+    // Test peek by block coefficient {{{2
+    #[test]
+    fn peek_cb() {
+        let mut ship = Ship::from_template(crate::Template::Dreadnought1910);
+        let d = ship.hull.d().to_long();
+        ship.hull.set_cb(ship.hull.cb());
+
+        let file = std::env::temp_dir().join(format!("sharpie-peek-{}.ship", std::process::id()));
+        let path = file.to_string_lossy().into_owned();
+        ship.save(path.clone()).unwrap();
+
+        assert_eq!(to_place(d, 3), to_place(ShipHeader::peek(&path).unwrap().d, 3));
+
+        std::fs::remove_file(file).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use crate::units::Units;
+use crate::units::{Units, Tons};
 
 use serde::{Serialize, Deserialize};
 
@@ -10,7 +10,9 @@ use std::fmt;
 ///
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Hull {
-    /// Units
+    /// Units SpringSharp flagged the hull as entered in.
+    #[deprecated(note = "only used by version 1 and 2 JSON reports, use Ship::units for how values are shown")]
+    #[serde(default, skip_serializing_if = "Units::is_imperial")]
     pub units: Units,
 
     /// Block Coefficient at normal displacement.
@@ -41,14 +43,6 @@ pub struct Hull {
     /// Draft: Maximum hull draft at normal displacement.
     pub t: f64,
 
-    /// The Waterplane Coefficient is calculated differently if the engine has
-    /// less than two shafts. Set this to true if the engine has less than two
-    /// shafts but set to false otherwise.
-    ///
-    // NOTE: Do not serialize as this is a derived value
-    #[serde(skip)]
-        boxy: bool,
-
     /// Type of bow.
     pub bow_type: BowType,
     /// Type of stern.
@@ -87,9 +81,14 @@ pub struct Hull {
     /// Average rake of stem from waterline to staff.
     /// Positive angles indicate an overhang.
     pub bow_angle: f64,
+
+    /// How the hull is built. The usual method for the year if not set.
+    #[serde(default)]
+    pub material: Option<HullMaterial>,
 }
 
 impl Default for Hull { // {{{2
+    #[allow(deprecated)]
     fn default() -> Hull {
         Hull {
             units: Units::Imperial,
@@ -103,8 +102,6 @@ impl Default for Hull { // {{{2
             bb: 0.0,
             t: 0.0,
 
-            boxy: false,
-
             bow_type: BowType::Normal,
             stern_type: SternType::Cruiser,
             stern_overhang: 0.0,
@@ -115,6 +112,8 @@ impl Default for Hull { // {{{2
             qd_len: 0.0, qd_fwd: 0.0, qd_aft: 0.0,
 
             bow_angle: 0.0,
+
+            material: None,
         }
     }
 }
@@ -122,18 +121,19 @@ impl Default for Hull { // {{{2
 impl Hull { // {{{2
     /// Volume of one long ton of seawater in cubic feet.
     pub const FT3_PER_TON_SEA: f64 = 35.0;
-
-    // set_shafts {{{3
-    /// Set any derived values that depend on the
-    /// number of shafts in the engine.
-    ///
-    pub fn set_shafts(&mut self, shafts: u32) {
-        if shafts < 2 {
-            self.boxy = true;
-        } else {
-            self.boxy = false;
-        }
-    }
+    /// Acceleration due to gravity (ft/s²).
+    pub const G: f64 = 32.174;
+    /// Feet per second in one knot.
+    pub const FPS_PER_KNOT: f64 = 1.68781;
+    /// Froude number below which a hull is driven economically.
+    pub const ECONOMIC_FROUDE: f64 = 0.35;
+    /// Share of the bulge volume that holds liquid or is flooded.
+    pub const BULGE_FILL: f64 = 0.25;
+    /// Longest waterline (ft) the strength formulas are calibrated against.
+    pub const STRENGTH_LWL: f64 = 860.0;
+    /// Extra bow freeboard needed for each unit of speed-length ratio
+    /// above 1, as a share of the minimum.
+    pub const WET_SPEED: f64 = 0.3;
 
     // freeboard_desc {{{3
     /// Get a description of the freeboard.
@@ -214,14 +214,9 @@ impl Hull { // {{{2
     /// Calculate the Block Coefficient for a given displacment.
     ///
     // XXX: Should this only return values between 0.3 and 1.0 (inclusive)?
-    pub fn cb_calc(&self, d: f64, t: f64) -> f64 {
-        let volume = self.lwl() * self.bb * t;
-
-        if volume == 0.0 {
-            0.0
-        } else {
-            (d * Self::FT3_PER_TON_SEA / volume).min(1.0).max(0.0)
-        }
+    #[allow(clippy::manual_clamp)] // clamp() would let NaN through
+    pub fn cb_calc(&self, d: Tons, t: f64) -> f64 {
+        estimate::cb(d.to_long(), self.lwl(), self.bb, t).min(1.0).max(0.0)
     }
 
     // set_cb {{{3
@@ -238,9 +233,9 @@ impl Hull { // {{{2
     /// Normal Displacement (t).
     ///
     /// Return a perviously set value or caluculate from cb if unset.
-    pub fn d(&self) -> f64 {
+    pub fn d(&self) -> Tons {
         match self.d {
-            Some(d) => d,
+            Some(d) => Tons::long(d),
             None    => self.d_calc(self.cb(),),
         }
     }
@@ -248,26 +243,60 @@ impl Hull { // {{{2
     // d_calc {{{3
     /// Calculate the displacement for a given Block Coefficient.
     ///
-    pub fn d_calc(&self, cb: f64) -> f64 {
-        cb * self.lwl() * self.bb * self.t / Self::FT3_PER_TON_SEA
+    pub fn d_calc(&self, cb: f64) -> Tons {
+        Tons::long(estimate::displacement(self.lwl(), self.bb, self.t, cb))
     }
 
     // set_d {{{3
     /// Set the Displacement and unset the Block Coefficient.
     ///
-    pub fn set_d(&mut self, d: f64) -> f64 {
-        self.d = Some(d);
+    pub fn set_d(&mut self, d: Tons) -> Tons {
+        self.d = Some(d.to_long());
         self.cb = None;
 
         d
     }
 
+    // is_overdetermined {{{3
+    /// True if both the Block Coefficient and the Displacement are set.
+    ///
+    /// This only happens when a ship file is edited by hand. Each is
+    /// then used as it is so they do not agree with each other.
+    ///
+    pub fn is_overdetermined(&self) -> bool {
+        self.cb.is_some() && self.d.is_some()
+    }
+
+    // is_underdetermined {{{3
+    /// True if neither the Block Coefficient nor the Displacement is set,
+    /// leaving nothing to calculate the other from.
+    ///
+    pub fn is_underdetermined(&self) -> bool {
+        self.cb.is_none() && self.d.is_none()
+    }
+
+    // given_mut {{{3
+    /// Block Coefficient, displacement and lengths as given in the ship
+    /// file, by the path to each, so they can be sanitized.
+    ///
+    pub(crate) fn given_mut(&mut self) -> [(&'static str, &mut Option<f64>); 4] {
+        [
+            ("hull.cb", &mut self.cb),
+            ("hull.d", &mut self.d),
+            ("hull.lwl", &mut self.lwl),
+            ("hull.loa", &mut self.loa),
+        ]
+    }
+
     // cwp {{{3
     /// Waterplane Area Coefficient (Parsons).
     ///
-    pub fn cwp(&self) -> f64 {
+    /// Hulls driven by less than two shafts have fuller sterns and use the
+    /// same coefficients as very full hulls.
+    ///
+    pub fn cwp(&self, shafts: u32) -> f64 {
         let (a, f) = 
-            if self.boxy || self.cb() >= 0.75 {
+            if shafts < 2 || self.cb() >= 0.75 {
                 (0.175, 0.875)
             } else {
                 self.stern_type.wp_calc()
@@ -288,8 +317,29 @@ impl Hull { // {{{2
     // wp {{{3
     /// Waterplane Area.
     ///
-    pub fn wp(&self) -> f64 {
-        self.cwp() * self.lwl() * self.b
+    pub fn wp(&self, shafts: u32) -> f64 {
+        self.cwp(shafts) * self.lwl() * self.b
+    }
+
+    // kb {{{3
+    /// Height of the center of buoyancy above the keel (Morrish).
+    ///
+    pub fn kb(&self, shafts: u32) -> f64 {
+        if self.cwp(shafts) == 0.0 { return 0.0; } // catch divide by zero
+
+        self.t * (5.0 / 6.0 - self.cb() / (3.0 * self.cwp(shafts)))
+    }
+
+    // bm {{{3
+    /// Height of the transverse metacenter above the center of buoyancy.
+    ///
+    /// Estimated from the waterplane coefficient rather than an actual
+    /// waterplane. Bulges count towards the beam.
+    ///
+    pub fn bm(&self, shafts: u32) -> f64 {
+        if self.t == 0.0 || self.cb() == 0.0 { return 0.0; } // catch divide by zero
+
+        self.bb.powf(2.0) * self.cwp(shafts).powf(2.0) / (11.7 * self.t * self.cb())
     }
 
     // ws {{{3
@@ -298,7 +348,7 @@ impl Hull { // {{{2
     pub fn ws(&self) -> f64 {
         if self.t == 0.0 { return 0.0; } // catch divide by zero
                                          //
-        self.lwl() * self.t * 1.7 + (self.d() * Self::FT3_PER_TON_SEA / self.t)
+        self.lwl() * self.t * 1.7 + (self.d().to_long() * Self::FT3_PER_TON_SEA / self.t)
     }
 
     // set_lwl {{{3
@@ -321,6 +371,30 @@ impl Hull { // {{{2
         len
     }
 
+    // scale {{{3
+    /// Scale every dimension of the hull by factor, keeping its form.
+    ///
+    /// A set displacement grows with the cube of the factor; a set Block
+    /// Coefficient is unchanged.
+    ///
+    pub fn scale(&mut self, factor: f64) {
+        self.d   = self.d.map(|d| d * factor.powi(3));
+        self.lwl = self.lwl.map(|len| len * factor);
+        self.loa = self.loa.map(|len| len * factor);
+
+        self.b *= factor;
+        self.bb *= factor;
+        self.t *= factor;
+
+        if let BowType::Ram(len) = self.bow_type { self.bow_type = BowType::Ram(len * factor); }
+        self.stern_overhang *= factor;
+
+        for h in [&mut self.fc_fwd, &mut self.fc_aft, &mut self.fd_fwd, &mut self.fd_aft,
+                  &mut self.ad_fwd, &mut self.ad_aft, &mut self.qd_fwd, &mut self.qd_aft] {
+            *h *= factor;
+        }
+    }
+
     // lwl {{{3
     /// Length at the waterline.
     ///
@@ -370,8 +444,8 @@ impl Hull { // {{{2
     // t_calc {{{3
     /// Draft at given displacment.
     ///
-    pub fn t_calc(&self, d: f64) -> f64 {
-        self.t + (d - self.d()) / (self.wp() / Hull::FT3_PER_TON_SEA)
+    pub fn t_calc(&self, d: Tons, shafts: u32) -> f64 {
+        self.t + (d - self.d()).to_long() / (self.wp(shafts) / Hull::FT3_PER_TON_SEA)
     }
 
     // ts {{{3
@@ -411,17 +485,63 @@ impl Hull { // {{{2
     }
 
     // freeboard_dist {{{3
-    /// XXX: I do not know what this does.
+    /// Average freeboard (ft) of the forward and after decks, leaving out
+    /// the forecastle and quarterdeck.
     ///
     pub fn freeboard_dist(&self) -> f64 {
        (self.fd() * self.fd_len + self.ad() * self.ad_len()) / (self.fd_len + self.ad_len()) 
     }
 
-    // is_wet_fwd {{{3
-    /// Does the ship tend to be wet forward?
+    // fc_fwd_min {{{3
+    /// Lowest freeboard at the bow that keeps the ship dry forward at
+    /// speed v.
+    ///
+    /// Up to a speed-length ratio of 1 this is the usual rule of thumb of
+    /// 1.1 x sqrt(lwl). Faster ships throw more water over the bow and
+    /// need more.
+    ///
+    pub fn fc_fwd_min(&self, v: f64) -> f64 {
+        let root = self.lwl().sqrt();
+        if root == 0.0 { return 0.0; } // Catch divide by zero
+
+        1.1 * root * (1.0 + f64::max(v / root - 1.0, 0.0) * Self::WET_SPEED)
+    }
+
+    // fc_fwd_margin {{{3
+    /// Freeboard at the bow above the minimum needed at speed v. The ship
+    /// is wet forward if this is negative.
+    ///
+    pub fn fc_fwd_margin(&self, v: f64) -> f64 {
+        self.fc_fwd - self.fc_fwd_min(v)
+    }
+
+    // head_sea_factor {{{3
+    /// Seakeeping multiplier when heading into the seas.
+    ///
+    /// A high bow keeps the ship dry while a bluff hull slams and loses
+    /// speed.
+    ///
+    pub fn head_sea_factor(&self) -> f64 {
+        if self.lwl() == 0.0 || self.cb() == 0.0 { return 0.0; } // Catch divide by zero
+
+        let bow = (self.fc_fwd / (1.1 * self.lwl().sqrt())).clamp(0.5, 1.5);
+        let fineness = (0.55 / self.cb()).powf(0.25).clamp(0.8, 1.2);
+
+        bow.sqrt() * fineness
+    }
+
+    // following_sea_factor {{{3
+    /// Seakeeping multiplier when running before the seas.
+    ///
+    /// A low quarterdeck is easily pooped and the stern shape decides
+    /// how well the ship resists broaching.
     ///
-    pub fn is_wet_fwd(&self) -> bool {
-        self.fc_fwd < (1.1 * self.lwl().sqrt())
+    pub fn following_sea_factor(&self, vmax: f64) -> f64 {
+        if self.lwl() == 0.0 { return 0.0; } // Catch divide by zero
+
+        let stern = (self.qd_aft / (0.8 * self.lwl().sqrt())).clamp(0.5, 1.5);
+
+        stern.sqrt() * self.stern_type.following_sea_factor(vmax / self.lwl().sqrt())
     }
 
     // fc {{{3
@@ -454,8 +574,11 @@ impl Hull { // {{{2
 
 
     // free_cap {{{3
-    /// XXX: I do not know what this does.
+    /// Freeboard (ft) for the seakeeping, strength and flotation
+    /// calculations.
     ///
+    /// Freeboard above a third of the beam counts for more. Below it,
+    /// ships with only broadside guns below the deck lose 6 ft of it.
     pub fn free_cap(&self, cap_calc_broadside: bool) -> f64 {
         if self.freeboard() > (self.b / 3.0) {
             self.freeboard().powf(2.0) * 3.0 / self.b
@@ -474,6 +597,70 @@ impl Hull { // {{{2
         self.leff().sqrt()
     }
 
+    // froude {{{3
+    /// Froude number at a given speed (kts).
+    ///
+    pub fn froude(&self, v: f64) -> f64 {
+        if self.lwl() <= 0.0 { return 0.0; } // Catch divide by zero
+
+        v * Self::FPS_PER_KNOT / (Self::G * self.lwl()).sqrt()
+    }
+
+    // lwl_for_froude {{{3
+    /// Waterline length needed to reach a speed (kts) at a given Froude number.
+    ///
+    pub fn lwl_for_froude(v: f64, froude: f64) -> f64 {
+        if froude <= 0.0 { return 0.0; } // Catch divide by zero
+
+        (v * Self::FPS_PER_KNOT / froude).powf(2.0) / Self::G
+    }
+
+    // hump {{{3
+    /// Where a speed (kts) sits relative to the main resistance hump.
+    ///
+    pub fn hump(&self, v: f64) -> Hump {
+        let froude = self.froude(v);
+
+               if froude < 0.4 {
+            Hump::Clear
+        } else if froude < 0.5 {
+            Hump::Approaching
+        } else if froude < 0.6 {
+            Hump::Past
+        } else {
+            Hump::Clear
+        }
+    }
+
+    // size_factor {{{3
+    /// Correction to hull strength for hulls longer than STRENGTH_LWL.
+    ///
+    /// The strength formulas only compare weights and proportions, so
+    /// they rate a scaled up hull as strong as the original. Stress from
+    /// the ship's own weight actually grows with size (the square-cube
+    /// law), so strength falls off in proportion to length beyond the
+    /// longest ships the formulas were calibrated against.
+    ///
+    pub fn size_factor(&self) -> f64 {
+        if self.lwl() > Self::STRENGTH_LWL {
+            Self::STRENGTH_LWL / self.lwl()
+        } else {
+            1.0
+        }
+    }
+
+    // bulge_void {{{3
+    /// Weight of liquid in torpedo bulges of the given length.
+    ///
+    /// The bulges run from the bilge to the waterline and taper to
+    /// nothing at the bottom.
+    ///
+    pub fn bulge_void(&self, len: f64) -> f64 {
+        if self.bb <= self.b { return 0.0; }
+
+        (self.bb - self.b) * len * self.t * Self::BULGE_FILL / Self::FT3_PER_TON_SEA
+    }
+
     // len2beam {{{3
     /// Length to beam ratio.
     ///
@@ -487,6 +674,7 @@ impl Hull { // {{{2
 
 // Testing Hull {{{2
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod hull {
     use super::*;
     use crate::test_support::*;
@@ -566,7 +754,7 @@ mod hull {
                     let (expected, d, lwl, bb, t,) = $value;
 
                     let mut hull = Hull::default();
-                    hull.set_d(d);
+                    hull.set_d(Tons::long(d));
                     hull.set_lwl(lwl);
                     hull.bb = bb;
                     hull.t = t;
@@ -608,7 +796,7 @@ mod hull {
                     hull.bb = bb;
                     hull.t = t;
 
-                    assert!(expected == to_place(hull.d(), 2));
+                    assert!(expected == to_place(hull.d().to_long(), 2));
                 }
             )*
         }
@@ -631,24 +819,50 @@ mod hull {
             $(
                 #[test]
                 fn $name() {
-                    let (expected, boxy, cb) = $value;
+                    let (expected, shafts, cb) = $value;
 
                     let mut hull = Hull::default();
                     hull.set_cb(cb);
-                    hull.boxy = boxy;
 
-                    println!("{}", hull.cwp());
-                    assert!(expected == to_place(hull.cwp(), 5));
+                    println!("{}", hull.cwp(shafts));
+                    assert!(expected == to_place(hull.cwp(shafts), 5));
                 }
             )*
         }
     }
     test_cwp! {
-        // name: (cwp, boxy, cb)
-        cwp_test_1: (0.64045, true, 0.5),
-        cwp_test_2: (0.83761, false, 0.75),
-        cwp_test_3: (0.66628, false, 0.5),
-        cwp_test_4: (0.59708, false, 0.35),
+        // name: (cwp, shafts, cb)
+        cwp_test_1: (0.64045, 1, 0.5),
+        cwp_test_2: (0.83761, 2, 0.75),
+        cwp_test_3: (0.66628, 2, 0.5),
+        cwp_test_4: (0.59708, 2, 0.35),
+        cwp_test_5: (0.64045, 0, 0.5),
+    }
+
+    // kb and bm {{{3
+    macro_rules! test_kb_bm {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (kb, bm, t, cb) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.bb = 20.0;
+                    hull.t = t;
+                    hull.set_cb(cb);
+
+                    assert_eq!(kb, to_place(hull.kb(2), 2));
+                    assert_eq!(bm, to_place(hull.bm(2), 2));
+                }
+            )*
+        }
+    }
+    test_kb_bm! {
+        // name:      (kb, bm, t, cb)
+        kb_bm_t_eq_0: (0.0, 0.0, 0.0, 0.5),
+        kb_bm_test_1: (5.83, 3.04, 10.0, 0.5),
+        kb_bm_test_2: (5.35, 3.2, 10.0, 0.75),
     }
 
     // ws {{{3
@@ -660,7 +874,7 @@ mod hull {
                     let mut hull = Hull::default();
 
                     let (expected, t) = $value;
-                    hull.set_d(1000.0);
+                    hull.set_d(Tons::long(1000.0));
                     hull.set_lwl(100.0);
                     hull.t = t;
 
@@ -757,13 +971,13 @@ mod hull {
                     let (expected, d_plus) = $value;
 
                     let mut hull = Hull::default();
-                    hull.set_d(5000.0);
+                    hull.set_d(Tons::long(5000.0));
                     hull.set_lwl(500.0);
                     hull.b = 50.0;
                     hull.bb = hull.b;
                     hull.t = 10.0;
 
-                    assert!(expected == to_place(hull.t_calc(hull.d() + d_plus), 2));
+                    assert!(expected == to_place(hull.t_calc(hull.d() + Tons::long(d_plus), 2), 2));
                 }
             )*
         }
@@ -782,7 +996,7 @@ mod hull {
                     let (expected, t) = $value;
 
                     let mut hull = Hull::default();
-                    hull.set_d(5000.0);
+                    hull.set_d(Tons::long(5000.0));
                     hull.set_lwl(500.0);
                     hull.t = t;
                     hull.b = 50.0;
@@ -918,28 +1132,92 @@ mod hull {
         // name:             (dist, fc_len)
         freeboard_dist_test: (13.33, 10.0),
     }
-    // is_wet_fwd {{{3
-    macro_rules! test_is_wet_fwd {
+    // fc_fwd_margin {{{3
+    macro_rules! test_fc_fwd_margin {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((min, margin), fc_fwd, v) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.fc_fwd = fc_fwd;
+                    hull.set_lwl(100.0);
+
+                    assert_eq!(min, to_place(hull.fc_fwd_min(v), 2));
+                    assert_eq!(margin, to_place(hull.fc_fwd_margin(v), 2));
+                }
+            )*
+        }
+    }
+
+    test_fc_fwd_margin! {
+        // name:                ((min, margin), fc_fwd, v)
+        fc_fwd_margin_wet:      ((11.0, -11.0), 0.0, 10.0),
+        fc_fwd_margin_dry:      ((11.0, 9.0), 20.0, 10.0),
+        fc_fwd_margin_slow:     ((11.0, 9.0), 20.0, 5.0),
+        fc_fwd_margin_fast:     ((14.3, 5.7), 20.0, 20.0),
+        fc_fwd_margin_no_speed: ((11.0, 9.0), 20.0, 0.0),
+    }
+
+    // head_sea_factor {{{3
+    macro_rules! test_head_sea_factor {
         ($($name:ident: $value:expr,)*) => {
             $(
                 #[test]
                 fn $name() {
-                    let (expected, fc_fwd) = $value;
+                    let (expected, fc_fwd, cb) = $value;
 
                     let mut hull = Hull::default();
                     hull.fc_fwd = fc_fwd;
                     hull.set_lwl(100.0);
+                    hull.b = 10.0;
+                    hull.bb = hull.b;
+                    hull.t = 5.0;
+                    hull.set_cb(cb);
+
+                    assert_eq!(expected, to_place(hull.head_sea_factor(), 2));
+                }
+            )*
+        }
+    }
+
+    test_head_sea_factor! {
+        // name:            (factor, fc_fwd, cb)
+        head_sea_dry:       (1.0, 11.0, 0.55),
+        head_sea_wet:       (0.71, 0.0, 0.55),
+        head_sea_high:      (1.22, 20.0, 0.55),
+        head_sea_bluff:     (0.86, 11.0, 1.0),
+        head_sea_fine:      (1.1, 11.0, 0.38),
+    }
+
+    // following_sea_factor {{{3
+    macro_rules! test_following_sea_factor {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, qd_aft, stern_type, vmax) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.qd_aft = qd_aft;
+                    hull.stern_type = stern_type;
+                    hull.set_lwl(100.0);
 
-                    assert_eq!(expected, hull.is_wet_fwd());
+                    assert_eq!(expected, to_place(hull.following_sea_factor(vmax), 2));
                 }
             )*
         }
     }
 
-    test_is_wet_fwd! {
-        // name:          (is_wet_fwd, fc_fwd)
-        is_wet_fwd_true:  (true, 0.0),
-        is_wet_fwd_false: (false, 20.0),
+    test_following_sea_factor! {
+        // name:                   (factor, qd_aft, stern_type, vmax)
+        following_sea_cruiser:     (1.0, 8.0, SternType::Cruiser, 10.0),
+        following_sea_low:         (0.71, 0.0, SternType::Cruiser, 10.0),
+        following_sea_round:       (0.95, 8.0, SternType::Round, 10.0),
+        following_sea_transom:     (0.8, 8.0, SternType::TransomLg, 10.0),
+        following_sea_transom_sm:  (0.9, 8.0, SternType::TransomSm, 10.0),
+        following_sea_fast:        (1.05, 8.0, SternType::TransomLg, 12.0),
     }
 
     // fc {{{3
@@ -1111,6 +1389,90 @@ mod hull {
         vn_test_2: (14.14, 200.0),
     }
 
+    // froude {{{3
+    macro_rules! test_froude {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (froude, hump, v) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.set_lwl(400.0);
+
+                    assert_eq!(froude, to_place(hull.froude(v), 2));
+                    assert_eq!(hump, hull.hump(v));
+                }
+            )*
+        }
+    }
+
+    test_froude! {
+        // name:             (froude, hump, v)
+        froude_slow:         (0.25, Hump::Clear, 17.0),
+        froude_approaching:  (0.45, Hump::Approaching, 30.0),
+        froude_past:         (0.54, Hump::Past, 36.0),
+        froude_beyond:       (0.65, Hump::Clear, 44.0),
+    }
+
+    #[test]
+    fn lwl_for_froude() {
+        assert_eq!(400.0, to_place(Hull::lwl_for_froude(30.0, 0.4463), 0));
+        assert_eq!(0.0, Hull::lwl_for_froude(30.0, 0.0));
+    }
+
+    // size_factor {{{3
+    macro_rules! test_size_factor {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, lwl) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.set_lwl(lwl);
+
+                    assert_eq!(expected, to_place(hull.size_factor(), 3));
+                }
+            )*
+        }
+    }
+
+    test_size_factor! {
+        // name:            (factor, lwl)
+        size_factor_small:  (1.0, 400.0),
+        size_factor_limit:  (1.0, 860.0),
+        size_factor_large:  (0.966, 890.0),
+        size_factor_huge:   (0.76, 1132.0),
+    }
+
+    // bulge_void {{{3
+    macro_rules! test_bulge_void {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, b, bb, len) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.b = b;
+                    hull.bb = bb;
+                    hull.t = 28.0;
+
+                    assert_eq!(expected, to_place(hull.bulge_void(len), 1));
+                }
+            )*
+        }
+    }
+
+    test_bulge_void! {
+        // name:            (tons, b, bb, len)
+        bulge_void_none:    (0.0, 90.0, 90.0, 400.0),
+        bulge_void_narrow:  (0.0, 90.0, 80.0, 400.0),
+        bulge_void:         (1120.0, 90.0, 104.0, 400.0),
+        bulge_void_short:   (560.0, 90.0, 104.0, 200.0),
+    }
+
     // len2beam {{{3
     macro_rules! test_len2beam {
         ($($name:ident: $value:expr,)*) => {
@@ -1137,6 +1499,220 @@ mod hull {
 
 }
 
+// Hump {{{1
+/// Position of a speed relative to the main wave-making resistance hump.
+///
+#[derive(PartialEq, Serialize, Clone, Copy, Debug)]
+pub enum Hump {
+    /// Well below or well beyond the hump.
+    Clear,
+    /// Resistance is rising steeply towards the hump.
+    Approaching,
+    /// Just over the hump where resistance is highest for the speed.
+    Past,
+}
+
+impl fmt::Display for Hump { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Clear       => "clear of",
+            Self::Approaching => "approaching",
+            Self::Past        => "just past",
+        })
+    }
+}
+
+// estimate {{{1
+/// Quick displacement calculations that do not need a Hull or Ship.
+///
+/// Lengths are in feet and displacements in long tons of seawater.
+///
+pub mod estimate {
+    use super::Hull;
+
+    // displacement {{{2
+    /// Displacement of a hull with the given dimensions and Block Coefficient.
+    ///
+    pub fn displacement(lwl: f64, b: f64, t: f64, cb: f64) -> f64 {
+        cb * lwl * b * t / Hull::FT3_PER_TON_SEA
+    }
+
+    // cb {{{2
+    /// Block Coefficient needed for a displacement with the given dimensions.
+    ///
+    pub fn cb(d: f64, lwl: f64, b: f64, t: f64) -> f64 {
+        let volume = lwl * b * t;
+        if volume == 0.0 { return 0.0; } // Catch divide by zero
+
+        d * Hull::FT3_PER_TON_SEA / volume
+    }
+
+    // beam {{{2
+    /// Beam needed for a displacement with the given length, draught and
+    /// Block Coefficient.
+    ///
+    pub fn beam(d: f64, lwl: f64, t: f64, cb: f64) -> f64 {
+        let area = lwl * t * cb;
+        if area == 0.0 { return 0.0; } // Catch divide by zero
+
+        d * Hull::FT3_PER_TON_SEA / area
+    }
+
+    // Testing estimate {{{2
+    #[cfg(test)]
+    #[allow(clippy::module_inception)]
+    mod estimate {
+        use super::*;
+        use crate::test_support::*;
+
+        macro_rules! test_estimate {
+            ($($name:ident: $value:expr,)*) => {
+                $(
+                    #[test]
+                    fn $name() {
+                        let (expected, actual) = $value;
+
+                        assert_eq!(expected, to_place(actual, 2));
+                    }
+                )*
+            }
+        }
+
+        test_estimate! {
+            // name:                (expected, estimate)
+            estimate_displacement:  (10000.0, displacement(500.0, 70.0, 20.0, 0.5)),
+            estimate_cb:            (0.5, cb(10000.0, 500.0, 70.0, 20.0)),
+            estimate_cb_zero:       (0.0, cb(10000.0, 0.0, 70.0, 20.0)),
+            estimate_beam:          (70.0, beam(10000.0, 500.0, 20.0, 0.5)),
+            estimate_beam_zero:     (0.0, beam(10000.0, 500.0, 20.0, 0.0)),
+        }
+    }
+}
+
+// HullMaterial {{{1
+/// What the hull is built of and how it is joined.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum HullMaterial {
+    /// Iron plates and frames, riveted.
+    RivetedIron,
+    /// Mild or high tensile steel, riveted.
+    #[default]
+    RivetedSteel,
+    /// Riveted seams with welded bulkheads, decks and fittings.
+    PartiallyWelded,
+    /// Welded throughout.
+    Welded,
+}
+
+impl fmt::Display for HullMaterial { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::RivetedIron     => "riveted iron",
+                Self::RivetedSteel    => "riveted steel",
+                Self::PartiallyWelded => "partially welded steel",
+                Self::Welded          => "welded steel",
+            }
+        )
+    }
+}
+
+impl HullMaterial { // {{{2
+    /// Year steel replaced iron for warship hulls.
+    const STEEL_YEAR: u32 = 1880;
+    /// Year welding came into use for bulkheads, decks and fittings.
+    const PARTIAL_WELDING_YEAR: u32 = 1930;
+    /// Year all-welded hulls became usual.
+    const WELDING_YEAR: u32 = 1940;
+
+    // for_year {{{3
+    /// Usual material for a ship laid down in year.
+    ///
+    pub fn for_year(year: u32) -> HullMaterial {
+             if year < Self::STEEL_YEAR           { Self::RivetedIron }
+        else if year < Self::PARTIAL_WELDING_YEAR { Self::RivetedSteel }
+        else if year < Self::WELDING_YEAR         { Self::PartiallyWelded }
+        else                                      { Self::Welded }
+    }
+
+    // wgt_factor {{{3
+    /// Structural weight needed for the same strength as a riveted steel
+    /// hull.
+    ///
+    /// Iron is weaker than steel for its weight. Welding saves the
+    /// overlapping plates, butt straps and rivet heads of riveted seams.
+    ///
+    pub fn wgt_factor(&self) -> f64 {
+        match self {
+            Self::RivetedIron     => 1.2,
+            Self::RivetedSteel    => 1.0,
+            Self::PartiallyWelded => 0.95,
+            Self::Welded          => 0.9,
+        }
+    }
+
+    // strength_factor {{{3
+    /// Strength of a hull of this material against one of the usual
+    /// material for the year, which the strength formulas assume.
+    ///
+    pub fn strength_factor(&self, year: u32) -> f64 {
+        Self::for_year(year).wgt_factor() / self.wgt_factor()
+    }
+}
+
+// Testing HullMaterial {{{2
+#[cfg(test)]
+mod hull_material {
+    use super::*;
+
+    // Test for_year {{{3
+    macro_rules! test_for_year {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year) = $value;
+
+                    assert_eq!(expected, HullMaterial::for_year(year));
+                }
+            )*
+        }
+    }
+
+    test_for_year! {
+        // name:                (material, year laid down)
+        for_year_warrior:       (HullMaterial::RivetedIron, 1859),
+        for_year_iris:          (HullMaterial::RivetedIron, 1875),
+        for_year_dreadnought:   (HullMaterial::RivetedSteel, 1905),
+        for_year_early_welding: (HullMaterial::PartiallyWelded, 1932),
+        for_year_fletcher:      (HullMaterial::Welded, 1941),
+    }
+
+    // Test strength_factor {{{3
+    macro_rules! test_strength_factor {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, material, year) = $value;
+
+                    assert_eq!(expected, (material.strength_factor(year) * 100.0).round() / 100.0);
+                }
+            )*
+        }
+    }
+
+    test_strength_factor! {
+        // name:                      (factor, material, year)
+        strength_usual:               (1.0, HullMaterial::RivetedSteel, 1910),
+        strength_early_steel:         (1.2, HullMaterial::RivetedSteel, 1875),
+        strength_iron_late:           (0.83, HullMaterial::RivetedIron, 1895),
+        strength_welded_early:        (1.06, HullMaterial::Welded, 1935),
+        strength_riveted_late:        (0.9, HullMaterial::RivetedSteel, 1942),
+    }
+}
+
 // SternType {{{1
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub enum SternType {
@@ -1158,6 +1734,7 @@ impl From<String> for SternType { // {{{2
 }
 
 impl From<&str> for SternType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::TransomSm,
@@ -1180,8 +1757,31 @@ impl fmt::Display for SternType { // {{{2
 }
 
 impl SternType { // {{{2
+    /// Speed-length ratio above which a transom stern rides well in
+    /// following seas.
+    const TRANSOM_FAST: f64 = 1.2;
+
+    // following_sea_factor {{{3
+    /// Seakeeping multiplier in following seas for a given speed-length
+    /// ratio.
+    ///
+    /// Transom sterns are pooped at low speeds but a fast ship with a
+    /// large transom rides ahead of the seas.
+    ///
+    pub fn following_sea_factor(&self, speed_len: f64) -> f64 {
+        let fast = speed_len >= Self::TRANSOM_FAST;
+
+        match self {
+            Self::TransomSm => if fast { 1.0 } else { 0.9 },
+            Self::TransomLg => if fast { 1.05 } else { 0.8 },
+            Self::Cruiser   => 1.0,
+            Self::Round     => 0.95,
+        }
+    }
+
     // wp_calc {{{3
-    /// XXX: I do not know what this does.
+    /// Coefficients (a, f) of the waterplane area coefficient, a + f *
+    /// Cp, for the stern.
     ///
     pub fn wp_calc(&self) -> (f64, f64) {
         match self {
@@ -1193,7 +1793,8 @@ impl SternType { // {{{2
     }
 
     // leff {{{3
-    /// XXX: I do not know what this does.
+    /// Effective length (ft) for resistance. Transom sterns add a share
+    /// of the beam to the waterline length.
     ///
     pub fn leff(&self, lwl: f64, bb: f64, cs: f64) -> f64 {
         if cs == 0.0 { return 0.0 } // catch divide by zero
@@ -1279,6 +1880,7 @@ impl From<String> for BowType { // {{{2
 }
 
 impl From<&str> for BowType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::BulbStraight,
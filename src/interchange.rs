@@ -0,0 +1,600 @@
+use crate::{Ship, Template, ScaleOptions, Tons};
+use crate::engine::{FuelType, BoilerType, DriveType};
+use crate::weapons::{Battery, Torpedoes, GunType, MountType, GunLayoutType, GunDistributionType};
+
+use serde::{Serialize, Deserialize};
+use std::error::Error;
+
+// Interchange {{{1
+/// A design in the community interchange format.
+///
+/// The format is flat JSON with standard field names, each carrying its
+/// unit, for tools that keep designs without knowing how sharpie or
+/// SpringSharp describe a ship. Values sharpie calculates, such as the
+/// standard displacement and power, are written for reference and
+/// ignored when a design is read back.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct Interchange {
+    /// Always "naval-design-interchange".
+    pub format: String,
+    /// Version of the format.
+    pub version: u32,
+
+    pub name: String,
+    pub country: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub year: u32,
+
+    pub displacement_standard_t: Option<f64>,
+    pub displacement_normal_t: f64,
+    pub displacement_full_t: Option<f64>,
+    pub length_wl_ft: f64,
+    pub length_oa_ft: Option<f64>,
+    pub beam_ft: f64,
+    pub draught_ft: f64,
+
+    pub armament: Vec<InterchangeGuns>,
+    pub torpedoes: Vec<InterchangeTorpedoes>,
+
+    pub belt_in: f64,
+    pub belt_ends_in: f64,
+    pub belt_upper_in: f64,
+    pub deck_in: f64,
+    pub conning_tower_in: f64,
+
+    pub fuel: Vec<String>,
+    pub engines: Vec<String>,
+    pub drive: Vec<String>,
+    pub coal_pct: f64,
+    pub shafts: u32,
+    pub power_hp: Option<f64>,
+    pub speed_kts: f64,
+    pub cruise_kts: f64,
+    pub range_nm: u32,
+
+    pub crew: Option<u32>,
+    /// Accuracy against an average gun platform, for games that
+    /// simulate gunnery.
+    pub gunnery_modifier: Option<f64>,
+}
+
+// InterchangeGuns {{{1
+/// Guns of one calibre in mounts of one layout in the interchange
+/// format.
+///
+/// Entries for the same guns in the same kind of mount, one after
+/// another, are groups of one battery.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct InterchangeGuns {
+    pub count: u32,
+    pub calibre_in: f64,
+    pub barrel_length_cal: f64,
+    pub year: u32,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub mount: String,
+    pub guns_per_mount: u32,
+    /// How the guns sit in each mount. Older files without it are read
+    /// as the usual layout for guns_per_mount.
+    pub layout: String,
+    pub face_in: f64,
+    pub barbette_in: f64,
+}
+
+impl InterchangeGuns { // {{{2
+    // same_guns {{{3
+    /// True if both entries are the same guns in the same kind of mount,
+    /// whatever their number and layout.
+    ///
+    fn same_guns(&self, other: &InterchangeGuns) -> bool {
+        self.calibre_in == other.calibre_in &&
+            self.barrel_length_cal == other.barrel_length_cal &&
+            self.year == other.year &&
+            self.kind == other.kind &&
+            self.mount == other.mount &&
+            self.face_in == other.face_in &&
+            self.barbette_in == other.barbette_in
+    }
+}
+
+// InterchangeTorpedoes {{{1
+/// Torpedoes of one size in the interchange format.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct InterchangeTorpedoes {
+    pub count: u32,
+    pub mounts: u32,
+    pub diameter_in: f64,
+    pub length_ft: f64,
+}
+
+impl Interchange { // {{{2
+    /// Name of the format.
+    pub const FORMAT: &'static str = "naval-design-interchange";
+    /// Version of the format written.
+    pub const VERSION: u32 = 1;
+}
+
+// Names {{{1
+const FUELS: [(&str, FuelType); 5] = [
+    ("coal", FuelType::Coal),
+    ("oil", FuelType::Oil),
+    ("diesel", FuelType::Diesel),
+    ("gasoline", FuelType::Gasoline),
+    ("battery", FuelType::Battery),
+];
+
+const ENGINES: [(&str, BoilerType); 3] = [
+    ("reciprocating_simple", BoilerType::Simple),
+    ("reciprocating_complex", BoilerType::Complex),
+    ("turbine", BoilerType::Turbine),
+];
+
+const DRIVES: [(&str, DriveType); 4] = [
+    ("direct", DriveType::Direct),
+    ("geared", DriveType::Geared),
+    ("electric", DriveType::Electric),
+    ("hydraulic", DriveType::Hydraulic),
+];
+
+const MOUNTS: [(&str, MountType); 7] = [
+    ("broadside", MountType::Broadside),
+    ("coles_turret", MountType::ColesTurret),
+    ("open_barbette", MountType::OpenBarbette),
+    ("turret", MountType::ClosedBarbette),
+    ("deck_and_hoist", MountType::DeckAndHoist),
+    ("deck", MountType::Deck),
+    ("casemate", MountType::Casemate),
+];
+
+const GUNS: [(&str, GunType); 7] = [
+    ("muzzle_loading", GunType::MuzzleLoading),
+    ("breech_loading", GunType::BreechLoading),
+    ("quick_firing", GunType::QuickFiring),
+    ("anti_air", GunType::AntiAir),
+    ("dual_purpose", GunType::DualPurpose),
+    ("rapid_fire", GunType::RapidFire),
+    ("machine_gun", GunType::MachineGun),
+];
+
+const LAYOUTS: [(&str, GunLayoutType); 15] = [
+    ("single", GunLayoutType::Single),
+    ("twin_2_row", GunLayoutType::Twin2Row),
+    ("quad_4_row", GunLayoutType::Quad4Row),
+    ("twin", GunLayoutType::Twin),
+    ("two_gun", GunLayoutType::TwoGun),
+    ("quad_2_row", GunLayoutType::Quad2Row),
+    ("triple", GunLayoutType::Triple),
+    ("three_gun", GunLayoutType::ThreeGun),
+    ("sextuple_2_row", GunLayoutType::Sex2Row),
+    ("quad", GunLayoutType::Quad),
+    ("four_gun", GunLayoutType::FourGun),
+    ("octuple_2_row", GunLayoutType::Oct2Row),
+    ("quintuple", GunLayoutType::Quint),
+    ("five_gun", GunLayoutType::FiveGun),
+    ("decuple_2_row", GunLayoutType::Dec2Row),
+];
+
+// names {{{2
+/// Interchange names of the flags that are set.
+///
+fn names<T: bitflags::Flags + Clone>(table: &[(&'static str, T)], flags: &T) -> Vec<String> {
+    table.iter()
+        .filter(|(_, f)| flags.contains(f.clone()))
+        .map(|(n, _)| n.to_string())
+        .collect()
+}
+
+// flags {{{2
+/// Flags named by interchange names.
+///
+fn flags<T: bitflags::Flags + Clone>(table: &[(&'static str, T)], names: &[String], what: &str) -> Result<T, Box<dyn Error>> {
+    let mut flags = T::empty();
+    for name in names {
+        flags.insert(lookup(table, name, what)?);
+    }
+
+    Ok(flags)
+}
+
+// name {{{2
+/// Interchange name of a value.
+///
+fn name<T: PartialEq>(table: &[(&'static str, T)], value: &T) -> String {
+    table.iter()
+        .find(|(_, v)| v == value)
+        .map(|(n, _)| n.to_string())
+        .unwrap_or_default()
+}
+
+// lookup {{{2
+/// Value with an interchange name.
+///
+fn lookup<T: Clone>(table: &[(&'static str, T)], name: &str, what: &str) -> Result<T, Box<dyn Error>> {
+    table.iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, v)| v.clone())
+        .ok_or_else(|| format!("Unknown {}: {}", what, name).into())
+}
+
+// layout {{{2
+/// Layout of the mounts of an entry, by name or else the usual one for
+/// the number of guns in each.
+///
+fn layout(guns: &InterchangeGuns) -> Result<GunLayoutType, Box<dyn Error>> {
+    if !guns.layout.is_empty() {
+        let layout = lookup(&LAYOUTS, &guns.layout, "layout")?;
+        if guns.guns_per_mount != 0 && guns.guns_per_mount != layout.guns_per() {
+            return Err(format!("{} mounts hold {} guns, not {}", guns.layout, layout.guns_per(), guns.guns_per_mount).into());
+        }
+
+        return Ok(layout);
+    }
+
+    match guns.guns_per_mount.max(1) {
+        1 => Ok(GunLayoutType::Single),
+        2 => Ok(GunLayoutType::Twin),
+        3 => Ok(GunLayoutType::Triple),
+        4 => Ok(GunLayoutType::Quad),
+        5 => Ok(GunLayoutType::Quint),
+        n => Err(format!("Mounts must hold 1 to 5 guns, not {}", n).into()),
+    }
+}
+
+impl Ship { // {{{1
+    // to_interchange {{{2
+    /// Ship in the interchange format.
+    ///
+    pub fn to_interchange(&self) -> Interchange {
+        let (d, lwl, leff, cs, ws) = (self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws());
+
+        Interchange {
+            format: Interchange::FORMAT.into(),
+            version: Interchange::VERSION,
+
+            name: self.name.clone(),
+            country: self.country.clone(),
+            kind: self.kind.clone(),
+            year: self.year,
+
+            displacement_standard_t: Some(self.d_std().to_long()),
+            displacement_normal_t: d,
+            displacement_full_t: Some(self.d_max().to_long()),
+            length_wl_ft: lwl,
+            length_oa_ft: Some(self.hull.loa()),
+            beam_ft: self.hull.b,
+            draught_ft: self.hull.t,
+
+            armament: self.batteries.iter()
+                .filter(|b| b.num > 0)
+                .flat_map(|b| {
+                    let guns = |count, guns_per_mount, layout| InterchangeGuns {
+                        count,
+                        calibre_in: b.diam,
+                        barrel_length_cal: b.len,
+                        year: b.year,
+                        kind: name(&GUNS, &b.kind),
+                        mount: name(&MOUNTS, &b.mount_kind),
+                        guns_per_mount,
+                        layout,
+                        face_in: b.armor_face,
+                        barbette_in: b.armor_barb,
+                    };
+
+                    // One entry per group, or for the whole battery if
+                    // its groups place no mounts
+                    let groups: Vec<InterchangeGuns> = b.groups.iter()
+                        .filter(|g| g.num_mounts() > 0)
+                        .map(|g| guns(g.num_guns(), g.layout.guns_per(), name(&LAYOUTS, &g.layout)))
+                        .collect();
+
+                    if groups.is_empty() {
+                        vec![guns(b.num, b.num.checked_div(b.mount_num).unwrap_or(0), String::new())]
+                    } else {
+                        groups
+                    }
+                })
+                .collect(),
+            torpedoes: self.torps.iter()
+                .filter(|t| t.num > 0)
+                .map(|t| InterchangeTorpedoes { count: t.num, mounts: t.mounts, diameter_in: t.diam, length_ft: t.len })
+                .collect(),
+
+            belt_in: self.armor.main.thick,
+            belt_ends_in: self.armor.end.thick,
+            belt_upper_in: self.armor.upper.thick,
+            deck_in: self.armor.deck.md,
+            conning_tower_in: self.armor.ct_fwd.thick,
+
+            fuel: names(&FUELS, &self.engine.fuel),
+            engines: names(&ENGINES, &self.engine.boiler),
+            drive: names(&DRIVES, &self.engine.drive),
+            coal_pct: self.engine.pct_coal * 100.0,
+            shafts: self.engine.shafts(),
+            power_hp: Some(self.engine.hp_max(d, lwl, leff, cs, ws)),
+            speed_kts: self.engine.vmax,
+            cruise_kts: self.engine.vcruise,
+            range_nm: self.engine.range,
+
+            crew: Some(self.crew_max()),
+            gunnery_modifier: Some(self.gunnery_modifier()),
+        }
+    }
+
+    // from_interchange {{{2
+    /// Ship from a design in the interchange format.
+    ///
+    /// The template nearest in size and year is scaled to the waterline
+    /// length to supply what the format does not describe, such as the
+    /// freeboard, hull form and where the guns are.
+    ///
+    pub fn from_interchange(design: &Interchange) -> Result<Ship, Box<dyn Error>> {
+        if design.format != Interchange::FORMAT {
+            return Err(format!("Not a {} file", Interchange::FORMAT).into());
+        }
+        if design.version > Interchange::VERSION {
+            return Err(format!("{} version {} is newer than the supported version {}",
+                Interchange::FORMAT, design.version, Interchange::VERSION).into());
+        }
+        if design.displacement_normal_t <= 0.0 || design.length_wl_ft <= 0.0 || design.beam_ft <= 0.0 || design.draught_ft <= 0.0 {
+            return Err("Displacement, length, beam and draught must be greater than zero".into());
+        }
+
+        let template = Template::ALL.iter()
+            .min_by(|a, b| Self::template_distance(**a, design).total_cmp(&Self::template_distance(**b, design)))
+            .copied()
+            .unwrap_or_default();
+        let base = Ship::from_template(template);
+        let mut ship = base.scaled(design.length_wl_ft / base.hull.lwl(), &ScaleOptions::default())?;
+
+        ship.name = design.name.clone();
+        ship.country = design.country.clone();
+        ship.kind = design.kind.clone();
+        ship.year = design.year;
+        ship.notes.text = vec![format!("Imported from {} based on the {} template", Interchange::FORMAT, template)];
+
+        ship.hull.set_d(Tons::long(design.displacement_normal_t));
+        ship.hull.set_lwl(design.length_wl_ft);
+        ship.hull.b = design.beam_ft;
+        ship.hull.bb = design.beam_ft;
+        ship.hull.t = design.draught_ft;
+
+        let mut batteries: Vec<Battery> = Vec::new();
+        let mut prev: Option<&InterchangeGuns> = None;
+        let mut group = 0;
+        for g in design.armament.iter() {
+            let layout = layout(g)?;
+            let per = layout.guns_per();
+            if g.count % per != 0 {
+                return Err(format!("{} guns do not fit in mounts of {}", g.count, per).into());
+            }
+
+            // The same guns as the last entry are its battery's next group
+            let same = prev.is_some_and(|p| p.same_guns(g));
+            prev = Some(g);
+
+            let mut b = match batteries.pop() {
+                Some(b) if same && group + 1 < b.groups.len() => { group += 1; b },
+                last => {
+                    batteries.extend(last);
+                    group = 0;
+                    Battery::default()
+                },
+            };
+
+            b.diam = g.calibre_in;
+            b.len = g.barrel_length_cal;
+            b.year = if g.year > 0 { g.year } else { design.year };
+            b.kind = lookup(&GUNS, &g.kind, "gun type")?;
+            b.mount_kind = lookup(&MOUNTS, &g.mount, "mount")?;
+            b.armor_face = g.face_in;
+            b.armor_barb = g.barbette_in;
+
+            let distribution = match b.mount_kind {
+                MountType::Broadside | MountType::Casemate => GunDistributionType::SidesEven,
+                _                                          => GunDistributionType::CenterlineEven,
+            };
+            b.set_group(group, (0, g.count / per, 0), layout, distribution);
+
+            batteries.push(b);
+        }
+        batteries.resize(usize::max(batteries.len(), base.batteries.len()), Battery::default());
+        ship.batteries = batteries;
+
+        let mut torps: Vec<Torpedoes> = design.torpedoes.iter()
+            .map(|t| Torpedoes {
+                year: design.year,
+                mounts: t.mounts,
+                num: t.count,
+                diam: t.diameter_in,
+                len: t.length_ft,
+                mount_kind: base.torps.first().map(|b| b.mount_kind.clone()).unwrap_or_default(),
+                ..Default::default()
+            })
+            .collect();
+        torps.resize(usize::max(torps.len(), base.torps.len()), Torpedoes::default());
+        ship.torps = torps;
+
+        ship.armor.main.thick = design.belt_in;
+        ship.armor.end.thick = design.belt_ends_in;
+        ship.armor.upper.thick = design.belt_upper_in;
+        ship.armor.deck.md = design.deck_in;
+        ship.armor.ct_fwd.thick = design.conning_tower_in;
+
+        ship.engine.year = design.year;
+        ship.engine.fuel = flags(&FUELS, &design.fuel, "fuel")?;
+        ship.engine.boiler = flags(&ENGINES, &design.engines, "engine")?;
+        ship.engine.drive = flags(&DRIVES, &design.drive, "drive")?;
+        ship.engine.pct_coal = design.coal_pct / 100.0;
+        ship.engine.set_shafts(design.shafts);
+        ship.engine.vmax = design.speed_kts;
+        ship.engine.vcruise = design.cruise_kts;
+        ship.engine.range = design.range_nm;
+
+        ship.default_years();
+
+        Ok(ship)
+    }
+
+    // template_distance {{{2
+    /// How far a template is from a design in size and year.
+    ///
+    fn template_distance(template: Template, design: &Interchange) -> f64 {
+        let ship = Ship::from_template(template);
+
+        (design.displacement_normal_t / ship.hull.d().to_long()).ln().abs() +
+            (design.year as f64 - template.year() as f64).abs() / 50.0
+    }
+
+    // export_interchange {{{2
+    /// Ship as an interchange format file.
+    ///
+    pub fn export_interchange(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(&self.to_interchange())?)
+    }
+
+    // import_interchange {{{2
+    /// Ship from the contents of an interchange format file.
+    ///
+    pub fn import_interchange(s: &str) -> Result<Ship, Box<dyn Error>> {
+        Ship::from_interchange(&serde_json::from_str(s)?)
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod interchange {
+    use super::*;
+    use crate::test_support::*;
+
+    // Test round trip {{{2
+    macro_rules! test_round_trip {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let template = $value;
+
+                    let ship = Ship::from_template(template);
+                    let design = ship.to_interchange();
+                    let copy = Ship::import_interchange(&ship.export_interchange().unwrap()).unwrap();
+
+                    // Everything the format describes comes back
+                    let back = Interchange {
+                        displacement_standard_t: design.displacement_standard_t,
+                        displacement_full_t: design.displacement_full_t,
+                        length_oa_ft: design.length_oa_ft,
+                        power_hp: design.power_hp,
+                        crew: design.crew,
+                        gunnery_modifier: design.gunnery_modifier,
+                        ..copy.to_interchange()
+                    };
+                    assert_eq!(design, back);
+
+                    // And gives much the same ship
+                    assert_eq!(to_place(ship.d_std().to_long() / copy.d_std().to_long(), 1), 1.0);
+                }
+            )*
+        }
+    }
+
+    test_round_trip! {
+        // name:                    template
+        round_trip_ironclad:        Template::CentralBattery1870,
+        round_trip_dreadnought:     Template::Dreadnought1910,
+        round_trip_destroyer:       Template::Destroyer1935,
+    }
+
+    // Test errors {{{2
+    macro_rules! test_errors {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, edit) = $value;
+
+                    let mut design = Ship::from_template(Template::Dreadnought1910).to_interchange();
+                    edit(&mut design);
+
+                    let err = Ship::from_interchange(&design).unwrap_err().to_string();
+                    assert!(err.starts_with(expected), "{}", err);
+                }
+            )*
+        }
+    }
+
+    test_errors! {
+        // name:            (error, edit)
+        error_format:       ("Not a naval-design-interchange", |d: &mut Interchange| d.format = "ship".into()),
+        error_version:      ("naval-design-interchange version 2", |d: &mut Interchange| d.version = 2),
+        error_dimensions:   ("Displacement, length", |d: &mut Interchange| d.beam_ft = 0.0),
+        error_mount:        ("Unknown mount: tower", |d: &mut Interchange| d.armament[0].mount = "tower".into()),
+        error_fuel:         ("Unknown fuel: peat", |d: &mut Interchange| d.fuel = vec!["peat".into()]),
+        error_layout:       ("Mounts must hold", |d: &mut Interchange| { d.armament[0].count = 12; d.armament[0].guns_per_mount = 6; d.armament[0].layout.clear(); }),
+        error_layout_name:  ("Unknown layout: heptuple", |d: &mut Interchange| d.armament[0].layout = "heptuple".into()),
+        error_layout_guns:  ("twin mounts hold 2 guns, not 3", |d: &mut Interchange| d.armament[0].guns_per_mount = 3),
+        error_mounts:       ("10 guns do not fit", |d: &mut Interchange| { d.armament[0].guns_per_mount = 3; d.armament[0].layout.clear(); }),
+    }
+
+    // Test layouts {{{2
+    macro_rules! test_layouts {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (entries, groups) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    for (i, (mounts, layout)) in groups.iter().enumerate() {
+                        ship.batteries[0].set_group(i, (0, *mounts, 0), layout.clone(), GunDistributionType::CenterlineEven);
+                    }
+
+                    let design = ship.to_interchange();
+                    assert_eq!(entries, design.armament.iter().filter(|g| g.calibre_in == ship.batteries[0].diam).count());
+
+                    let copy = Ship::import_interchange(&ship.export_interchange().unwrap()).unwrap();
+                    assert_eq!(ship.batteries[0].num, copy.batteries[0].num);
+                    assert_eq!(ship.batteries[0].mount_num, copy.batteries[0].mount_num);
+                    for (i, (mounts, layout)) in groups.iter().enumerate() {
+                        assert_eq!(*mounts, copy.batteries[0].groups[i].num_mounts());
+                        assert_eq!(*layout, copy.batteries[0].groups[i].layout);
+                    }
+                    assert_eq!(design.armament, copy.to_interchange().armament);
+                }
+            )*
+        }
+    }
+
+    test_layouts! {
+        // name:            (entries, [(mounts, layout)])
+        layout_two_row:     (1, [(3, GunLayoutType::Quad2Row)].to_vec()),
+        layout_mixed:       (2, [(2, GunLayoutType::Twin), (3, GunLayoutType::Triple)].to_vec()),
+        layout_mixed_rows:  (2, [(4, GunLayoutType::Twin2Row), (1, GunLayoutType::Sex2Row)].to_vec()),
+    }
+
+    #[test]
+    fn layout_legacy() {
+        // Files written before layouts were named
+        let mut design = Ship::from_template(Template::Dreadnought1910).to_interchange();
+        design.armament[0].layout.clear();
+
+        let ship = Ship::from_interchange(&design).unwrap();
+        assert_eq!(GunLayoutType::Twin, ship.batteries[0].groups[0].layout);
+    }
+
+    // Test template {{{2
+    #[test]
+    fn nearest_template() {
+        let mut design = Ship::from_template(Template::Destroyer1935).to_interchange();
+        design.year = 1942;
+
+        let ship = Ship::from_interchange(&design).unwrap();
+        assert!(ship.notes.text[0].contains(&Template::Destroyer1935.to_string()));
+    }
+}
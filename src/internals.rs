@@ -0,0 +1,174 @@
+use crate::{Ship, Hull, BowType};
+
+impl Ship { // {{{1
+    // internals {{{2
+    /// Intermediate values of the calculations by name, such as `cs` or
+    /// `battery[0].gun_wgt`, for checking against SpringSharp's own
+    /// spreadsheets.
+    ///
+    pub fn internals(&self) -> Vec<(String, String)> {
+        let hull = &self.hull;
+        let (d, lwl, leff, cs, ws) = (hull.d().to_long(), hull.lwl(), hull.leff(), hull.cs(), hull.ws());
+
+        let mut v: Vec<(String, String)> = Vec::new();
+        let mut push = |k: &str, val: String| v.push((k.to_string(), val));
+
+        // Gun batteries {{{3
+        push("wgt_guns", self.wgt_guns().to_string());
+        push("wgt_gun_mounts", self.wgt_gun_mounts().to_string());
+        push("wgt_mag", self.wgt_mag().to_string());
+        push("wgt_gun_armor", self.wgt_gun_armor().to_string());
+        push("wgt_borne", self.wgt_borne().to_string());
+        push("super_factor", self.gun_super_factor().to_string());
+        push("gun_wtf", self.gun_wtf().to_string());
+
+        for (i, b) in self.batteries.iter().enumerate() {
+            for (k, val) in b.internals(hull.clone(), self.wgt_broad()) {
+                push(&format!("battery[{}].{}", i, k), val);
+            }
+        }
+
+        // Hull {{{3
+        push("cs", cs.to_string());
+        push("cm", Hull::cm(hull.cb()).to_string());
+        push("cp", Hull::cp(hull.cb()).to_string());
+        push("cwp", self.cwp().to_string());
+        push("wp", self.wp().to_string());
+        push("ws", ws.to_string());
+        push("ts", hull.ts().to_string());
+        push("stem_len", hull.stem_len().to_string());
+        if let BowType::Ram(len) = hull.bow_type { push("ram_len", len.to_string()); }
+        push("freeboard_dist", hull.freeboard_dist().to_string());
+        push("leff", leff.to_string());
+
+        // Resistance and power {{{3
+        let engine = &self.engine;
+        push("rf_max", engine.rf_max(ws).to_string());
+        push("rf_cruise", engine.rf_cruise(ws).to_string());
+        push("rw_max", engine.rw_max(d, lwl, cs).to_string());
+        push("rw_cruise", engine.rw_cruise(d, lwl, cs).to_string());
+        push("pw_max", engine.pw_max(d, lwl, cs, ws).to_string());
+        push("pw_cruise", engine.pw_cruise(d, lwl, cs, ws).to_string());
+        push("hp_max", engine.hp_max(d, lwl, leff, cs, ws).to_string());
+        push("hp_cruise", engine.hp_cruise(d, lwl, leff, cs, ws).to_string());
+
+        // Weights {{{3
+        push("wgt_load", self.wgt_load().to_string());
+        push("wgt_hull", self.wgt_hull().to_string());
+        push("wgt_hull_plus", self.wgt_hull_plus().to_string());
+        push("wgt_misc", self.wgt_misc().to_long().to_string());
+        push("wgt_armor", self.wgt_armor().to_string());
+        push("wgt_belt_main", self.armor.main.wgt(d, self.cwp(), hull.b).to_string());
+        push("wgt_belt_upper", self.armor.upper.wgt(d, self.cwp(), hull.b).to_string());
+        push("wgt_belt_end", self.armor.end.wgt(d, self.cwp(), hull.b).to_string());
+        push("wgt_deck", self.armor.deck.wgt(hull.clone(), engine.shafts(), self.wgt_mag_room(), self.wgt_engine_est()).to_string());
+
+        // Machinery {{{3
+        push("wgt_engine", self.wgt_engine().to_string());
+        push("d_engine", engine.d_engine(d, lwl, leff, cs, ws).to_string());
+        push("d_factor", self.d_factor().to_string());
+        push("bunker_normal", engine.bunker(d, lwl, leff, cs, ws).to_string());
+        push("bunker_factor", engine.boiler.bunker_factor(engine.year).to_string());
+        push("fuel", format!("{:?}", engine.fuel));
+        push("boiler", format!("{:?}", engine.boiler));
+        push("drive", format!("{:?}", engine.drive));
+        push("num_engines", engine.num_engines().to_string());
+
+        // Stability and strength {{{3
+        push("stability", self.stability().to_string());
+        push("seaboat", self.seaboat().to_string());
+        push("kb", self.kb().to_string());
+        push("bm", self.bm().to_string());
+        push("kg", self.kg().to_string());
+        push("gm", self.metacenter().to_string());
+        push("gun_concentration", self.gun_concentration().to_string());
+        push("str_cross", self.str_cross().to_string());
+        push("str_long", self.str_long().to_string());
+        push("str_comp", self.str_comp().to_string());
+        push("flotation", self.flotation().to_string());
+
+        v
+    }
+
+    // internals_csv {{{2
+    /// Intermediate values as a CSV file with a key and value on each line.
+    ///
+    pub fn internals_csv(&self) -> String {
+        let mut s = String::from("key,value\n");
+
+        for (k, v) in self.internals() {
+            s.push_str(&format!("{},{}\n", csv_field(&k), csv_field(&v)));
+        }
+
+        s
+    }
+}
+
+// csv_field {{{1
+/// Quote a CSV field if it holds a comma, quote or line break.
+///
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod internals {
+    use super::*;
+    use crate::Template;
+
+    // Test internals {{{2
+    #[test]
+    fn internals() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let v = ship.internals();
+
+        // Keys are unique so spreadsheets can look them up
+        for (i, (k, _)) in v.iter().enumerate() {
+            assert!(!v[..i].iter().any(|(other, _)| other == k), "{}", k);
+        }
+
+        let get = |key: &str| v.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+        assert_eq!(Some(ship.hull.cs().to_string()), get("cs"));
+        assert_eq!(Some(ship.batteries[0].gun_wgt().to_string()), get("battery[0].gun_wgt"));
+        assert!(get("battery[0].group[1].layout").is_some());
+    }
+
+    // Test internals_csv {{{2
+    #[test]
+    fn internals_csv() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let csv = ship.internals_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!("key,value", lines[0]);
+        assert_eq!(ship.internals().len() + 1, lines.len());
+        assert!(lines.contains(&format!("cs,{}", ship.hull.cs()).as_str()));
+    }
+
+    // Test csv_field {{{2
+    macro_rules! test_csv_field {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, s) = $value;
+
+                    assert_eq!(expected, csv_field(s));
+                }
+            )*
+        }
+    }
+
+    test_csv_field! {
+        // name:                (expected, field)
+        csv_field_plain:        ("1.25", "1.25"),
+        csv_field_comma:        ("\"Coal, oil\"", "Coal, oil"),
+        csv_field_quote:        ("\"2\"\" gun\"", "2\" gun"),
+    }
+}
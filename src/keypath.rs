@@ -0,0 +1,289 @@
+use crate::Ship;
+
+use format_num::format_num;
+use serde_json::{json, Value};
+
+use std::error::Error;
+
+impl Ship { // {{{1
+    // get {{{2
+    /// Value at a key path into the ship such as `hull.lwl` or
+    /// `batteries[0].num`, or None if there is nothing there.
+    ///
+    /// Paths name the fields of the ship file, along with these values
+    /// calculated from the design: `d_lite`, `d_std`, `d_normal`,
+    /// `d_max` (t), `vmax` (kts), `crew_min`, `crew_max`, `cost_lb` and
+    /// `cost_dollar` (millions).
+    ///
+    pub fn get(&self, path: &str) -> Option<Value> {
+        let calculated = match path {
+            "d_lite"      => Some(json!(self.d_lite().to_long().round())),
+            "d_std"       => Some(json!(self.d_std().to_long().round())),
+            "d_normal"    => Some(json!(self.hull.d().to_long().round())),
+            "d_max"       => Some(json!(self.d_max().to_long().round())),
+            "vmax"        => Some(json!(self.engine.vmax)),
+            "crew_min"    => Some(json!(self.crew_min())),
+            "crew_max"    => Some(json!(self.crew_max())),
+            "cost_lb"     => Some(json!(self.cost_lb())),
+            "cost_dollar" => Some(json!(self.cost_dollar())),
+            _             => None,
+        };
+        if calculated.is_some() { return calculated; }
+
+        let ship = serde_json::to_value(self).ok()?;
+        lookup(&ship, path).cloned()
+    }
+
+    // set {{{2
+    /// Set the field at a key path into the ship.
+    ///
+    /// Only fields of the ship file can be set, not calculated values.
+    /// The ship is left as it was if there is no field at the path or
+    /// the value does not fit it, and is sanitized after it is set.
+    ///
+    pub fn set(&mut self, path: &str, value: Value) -> Result<(), Box<dyn Error>> {
+        let mut ship = serde_json::to_value(&*self)?;
+
+        match lookup_mut(&mut ship, path) {
+            Some(v) => *v = value,
+            None    => return Err(format!("No field at {}", path).into()),
+        }
+
+        match serde_json::from_value(ship) {
+            Ok(ship) => {
+                let old = std::mem::replace(self, ship);
+                self.edited_since = old.edited_since;
+                self.snapshot = old.snapshot;
+                self.unknown = old.unknown;
+
+                // Keep what was clamped when the ship was loaded
+                let clamped = self.sanitize();
+                self.clamped = old.clamped;
+                for issue in clamped {
+                    if !self.clamped.contains(&issue) { self.clamped.push(issue); }
+                }
+
+                Ok(())
+            },
+            Err(e)   => Err(format!("Invalid value for {}: {}", path, e).into()),
+        }
+    }
+
+    // expand {{{2
+    /// Line with each `{{path}}` placeholder replaced by the value at
+    /// that path.
+    ///
+    /// Placeholders that do not name a value are left as they are so
+    /// mistakes show in the report.
+    ///
+    pub fn expand(&self, line: &str) -> String {
+        let mut s = String::new();
+        let mut rest = line;
+
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else { break; };
+            let end = start + 2 + len + 2;
+
+            s.push_str(&rest[..start]);
+            match self.get(rest[start + 2..end - 2].trim()) {
+                Some(v) => s.push_str(&value_str(&v)),
+                None    => s.push_str(&rest[start..end]),
+            }
+            rest = &rest[end..];
+        }
+        s.push_str(rest);
+
+        s
+    }
+}
+
+// lookup {{{1
+/// Value at a key path into a JSON value.
+///
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    let mut v = value;
+
+    for part in path.split('.') {
+        let mut indices = part.split('[');
+
+        let key = indices.next()?;
+        if !key.is_empty() { v = v.get(key)?; }
+
+        for i in indices {
+            v = v.get(i.strip_suffix(']')?.parse::<usize>().ok()?)?;
+        }
+    }
+
+    Some(v)
+}
+
+// lookup_mut {{{1
+/// Mutable value at a key path into a JSON value.
+///
+fn lookup_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    let mut v = value;
+
+    for part in path.split('.') {
+        let mut indices = part.split('[');
+
+        let key = indices.next()?;
+        if !key.is_empty() { v = v.get_mut(key)?; }
+
+        for i in indices {
+            v = v.get_mut(i.strip_suffix(']')?.parse::<usize>().ok()?)?;
+        }
+    }
+
+    Some(v)
+}
+
+// value_str {{{1
+/// Value as it is written in a report: text without quotes, counts
+/// and years as they are, whole measurements with thousands separators
+/// and others to two places.
+///
+fn value_str(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) if n.is_f64() => match n.as_f64() {
+            Some(f) if f.fract() == 0.0 => format_num!(",.0", f),
+            Some(f) => format!("{:.2}", f),
+            None    => n.to_string(),
+        },
+        Value::Number(n) => n.to_string(),
+        Value::Null => "".into(),
+        v => v.to_string(),
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod keypath {
+    use super::*;
+    use crate::Tons;
+    use crate::test_support::*;
+
+    // Test get {{{2
+    macro_rules! test_get {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, path) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.name = "Dreadnought".into();
+                    ship.engine.vmax = 21.0;
+                    ship.batteries[0].num = 10;
+
+                    assert_eq!(expected, ship.get(path));
+                }
+            )*
+        }
+    }
+
+    test_get! {
+        // name:            (value, path)
+        get_field:          (Some(json!("Dreadnought")), "name"),
+        get_nested:         (Some(json!(21.0)), "engine.vmax"),
+        get_index:          (Some(json!(10)), "batteries[0].num"),
+        get_missing:        (None, "hull.nothing"),
+        get_out_of_range:   (None, "batteries[99].num"),
+        get_bad_index:      (None, "batteries[x].num"),
+    }
+
+    #[test]
+    fn get_calculated() {
+        let mut ship = Ship { hull: get_hull(), ..Default::default() };
+        ship.engine.vmax = 21.0;
+
+        assert_eq!(Some(json!(ship.d_std().to_long().round())), ship.get("d_std"));
+        assert_eq!(Some(json!(ship.engine.vmax)), ship.get("engine.vmax"));
+    }
+
+    // Test set {{{2
+    macro_rules! test_set {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (ok, path, value) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull();
+                    let before = ship.clone();
+
+                    assert_eq!(ok, ship.set(path, value.clone()).is_ok());
+                    if ok {
+                        assert_eq!(Some(value), ship.get(path));
+                    } else {
+                        assert_eq!(serde_json::to_value(before).unwrap(), serde_json::to_value(ship).unwrap());
+                    }
+                }
+            )*
+        }
+    }
+
+    test_set! {
+        // name:            (ok, path, value)
+        set_text:           (true, "country", json!("Britain")),
+        set_nested:         (true, "engine.year", json!(1915)),
+        set_index:          (true, "batteries[1].num", json!(4)),
+        set_missing:        (false, "hull.nothing", json!(1)),
+        set_calculated:     (false, "d_std", json!(1)),
+        set_wrong_type:     (false, "engine.year", json!("soon")),
+    }
+
+    #[test]
+    fn set_keeps_unsaved() {
+        let mut ship = Ship { hull: get_hull(), year: 1910, ..Default::default() };
+        ship.snapshot = Some(ship.take_snapshot().unwrap());
+        ship.edited_since = Some("0.1.0".into());
+        ship.engine.pct_coal = 2.0;
+        let clamped = ship.sanitize();
+        ship.unknown = json!({"future": 1});
+
+        ship.set("engine.vcruise", json!(-1.0)).unwrap();
+
+        assert!(ship.snapshot.is_some());
+        assert_eq!(Some("0.1.0".into()), ship.edited_since);
+        assert_eq!(json!({"future": 1}), ship.unknown);
+        assert_eq!(clamped[0], ship.clamped[0]);
+        assert_eq!(vec!["engine.pct_coal", "engine.vcruise"],
+            ship.clamped.iter().map(|i| i.field).collect::<Vec<_>>());
+    }
+
+    // Test expand {{{2
+    macro_rules! test_expand {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, line) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.name = "Dreadnought".into();
+                    ship.year = 1910;
+                    ship.engine.vmax = 21.0;
+                    ship.hull = get_hull();
+                    ship.hull.set_d(Tons::long(18_110.0));
+                    ship.hull.b = 82.5;
+
+                    assert_eq!(expected, ship.expand(line));
+                }
+            )*
+        }
+    }
+
+    test_expand! {
+        // name:            (expanded, line)
+        expand_none:        ("No placeholders", "No placeholders"),
+        expand_text:        ("HMS Dreadnought", "HMS {{name}}"),
+        expand_year:        ("Laid down 1910", "Laid down {{year}}"),
+        expand_number:      ("18,110 t at 21 kts", "{{d_normal}} t at {{ vmax }} kts"),
+        expand_fraction:    ("Beam 82.50 ft", "Beam {{hull.b}} ft"),
+        expand_unknown:     ("{{nothing}} stays", "{{nothing}} stays"),
+        expand_unclosed:    ("Open {{name", "Open {{name"),
+    }
+}
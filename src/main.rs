@@ -1,8 +1,23 @@
-use clap::{Parser, Subcommand};
-use rfd::FileDialog;
-use sharpie::{Ship, SHIP_FILE_EXT, SS_SHIP_FILE_EXT};
+use clap::{Args, Parser, Subcommand};
+use rfd::{FileDialog, MessageDialog, MessageLevel};
+use slint::{Model, ModelRc, Timer, TimerMode, VecModel};
+use slint::winit_030::{WinitWindowAccessor, EventResult, winit::event::WindowEvent};
+use sharpie::curves;
+use sharpie::docs;
+use sharpie::names;
+use sharpie::{Controller, Action, Outcome, NumberFormat};
+use sharpie::{Ship, Config, Fleet, FleetEdit, Refit, FileIssues, FileFormat, ReportOptions, ReportSection, Severity, ScaleOptions, FitTargets, Tons, Units, SHIP_FILE_EXT, SS_SHIP_FILE_EXT};
+use sharpie::{check_json_version, to_json_versioned, REPORT_JSON_VERSION};
 
+use std::cell::RefCell;
 use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
 
 slint::include_modules!();
 
@@ -24,8 +39,41 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
+    Balance {
+        #[arg(help = "Sharpie or SpringSharp 3 file to balance")]
+        file: String,
+
+        #[arg(long)]
+        #[arg(help = "Standard displacement to balance the design to (t)")]
+        d_std: f64,
+
+        #[arg(short, long)]
+        #[arg(help = "Filename to save the balanced design to")]
+        to: Option<String>,
+
+        #[arg(long)]
+        #[arg(help = "Show each iteration of the balance")]
+        trace_optimize: bool,
+
+        #[arg(short, long, default_value = "text", value_parser = ["text", "json"])]
+        #[arg(help = "Format of the result")]
+        format: String,
+    },
+
     Load {
-        file: String
+        #[arg(help = "Sharpie or SpringSharp 3 file to load")]
+        file: String,
+
+        #[command(flatten)]
+        output: ReportArgs,
+    },
+
+    Report {
+        #[arg(help = "Sharpie or SpringSharp 3 file to report on, or - to read it from stdin")]
+        file: String,
+
+        #[command(flatten)]
+        output: ReportArgs,
     },
 
     Convert {
@@ -37,87 +85,731 @@ enum Commands {
         to: Option<String>,
 
         #[arg(short, long)]
-        #[arg(help = "Show ship report after conversion")]
+        #[arg(help = "Show ship report after conversion (implied by --output)")]
+        report: bool,
+
+        #[arg(short, long)]
+        #[arg(help = "Show which line each field was converted from")]
+        provenance: bool,
+
+        #[arg(short, long, default_value = "auto", value_parser = ["auto", "point", "comma"])]
+        #[arg(help = "Decimal separator used in the file")]
+        number_format: String,
+
+        #[command(flatten)]
+        output: ReportArgs,
+    },
+
+    ConvertAll {
+        #[arg(required = true)]
+        #[arg(help = "SpringSharp 3 files to convert to sharpie files alongside them")]
+        files: Vec<String>,
+
+        #[arg(short, long, default_value = "text", value_parser = ["text", "json"])]
+        #[arg(help = "Format of the conversion results")]
+        format: String,
+
+        #[arg(long, default_value_t = REPORT_JSON_VERSION)]
+        #[arg(help = "Schema version of JSON output")]
+        output_version: u32,
+    },
+
+    Validate {
+        #[arg(required = true)]
+        #[arg(help = "Sharpie or SpringSharp 3 files to validate")]
+        files: Vec<String>,
+
+        #[arg(short, long, default_value = "text", value_parser = ["text", "json"])]
+        #[arg(help = "Format of the validation results")]
+        format: String,
+
+        #[arg(long, default_value_t = REPORT_JSON_VERSION)]
+        #[arg(help = "Schema version of JSON output")]
+        output_version: u32,
+
+        #[arg(long, default_value = "failure", value_parser = ["warning", "failure"])]
+        #[arg(help = "Exit with an error if any file has an issue this serious")]
+        fail_on: String,
+    },
+
+    Checklist {
+        #[arg(help = "Sharpie or SpringSharp 3 file to review")]
+        file: String,
+
+        #[arg(short, long, default_value = "text", value_parser = ["text", "json"])]
+        #[arg(help = "Format of the checklist")]
+        format: String,
+
+        #[arg(long, default_value_t = REPORT_JSON_VERSION)]
+        #[arg(help = "Schema version of JSON output")]
+        output_version: u32,
+    },
+
+    Snapshot {
+        #[arg(help = "Sharpie file to keep the calculated values in")]
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Compare the values kept in the file with today's instead of saving them")]
+        verify: bool,
+    },
+
+    Scale {
+        #[arg(help = "Sharpie or SpringSharp 3 file to scale")]
+        file: String,
+
+        #[arg(help = "Factor to scale the ship's dimensions by")]
+        factor: f64,
+
+        #[arg(short, long)]
+        #[arg(help = "Filename to save the scaled ship to")]
+        to: Option<String>,
+
+        #[arg(short, long)]
+        #[arg(help = "Show ship report after scaling (implied by --output)")]
+        report: bool,
+
+        #[arg(short, long)]
+        #[arg(help = "Scale the calibre of the guns")]
+        armament: bool,
+
+        #[arg(short = 'A', long)]
+        #[arg(help = "Scale the thickness of the armour")]
+        armor: bool,
+
+        #[command(flatten)]
+        output: ReportArgs,
+    },
+
+    Fit {
+        #[arg(help = "Sharpie or SpringSharp 3 file of the ship to fit")]
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Known standard displacement (long tons unless --tonnes)")]
+        displacement: Option<f64>,
+
+        #[arg(long)]
+        #[arg(help = "Known displacement is in metric tonnes")]
+        tonnes: bool,
+
+        #[arg(short = 'k', long)]
+        #[arg(help = "Known maximum speed (kts)")]
+        speed: Option<f64>,
+
+        #[arg(short = 'p', long)]
+        #[arg(help = "Known shaft horsepower at maximum speed")]
+        shp: Option<f64>,
+
+        #[arg(short, long)]
+        #[arg(help = "Known main belt thickness (in)")]
+        belt: Option<f64>,
+
+        #[arg(long)]
+        #[arg(help = "Composite strength the hull is fitted to, taking up unaccounted weight in the hull (1.0 for a real ship)")]
+        strength: Option<f64>,
+
+        #[arg(short, long)]
+        #[arg(help = "Filename to save the fitted ship to")]
+        to: Option<String>,
+
+        #[arg(long)]
+        #[arg(help = "Show each iteration made to match the displacement")]
+        trace_optimize: bool,
+    },
+
+    AsBuilt {
+        #[arg(help = "Sharpie or SpringSharp 3 file of the design")]
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Seed for the variations, the same seed gives the same ship")]
+        seed: u64,
+
+        #[arg(short, long)]
+        #[arg(help = "Filename to save the ship as built to")]
+        to: Option<String>,
+    },
+
+    Export {
+        #[arg(help = "Sharpie or SpringSharp 3 file to export")]
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Filename to save the interchange file to (stdout if not given)")]
+        to: Option<String>,
+    },
+
+    Import {
+        #[arg(help = "Interchange file to import")]
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Filename to save the imported ship to")]
+        to: Option<String>,
+
+        #[arg(short, long)]
+        #[arg(help = "Show ship report after importing (implied by --output)")]
         report: bool,
+
+        #[command(flatten)]
+        output: ReportArgs,
     },
+
+    Profile {
+        #[arg(help = "Sharpie or SpringSharp 3 file to plot")]
+        file: String,
+
+        #[arg(short, long, default_value = "svg", value_parser = ["svg", "json"])]
+        #[arg(help = "Format of the protection profile")]
+        format: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Filename to save the profile to (stdout if not given)")]
+        to: Option<String>,
+    },
+
+    Internals {
+        #[arg(help = "Sharpie or SpringSharp 3 file to show the intermediate values of")]
+        file: String,
+
+        #[arg(short, long, default_value = "text", value_parser = ["text", "csv"])]
+        #[arg(help = "Format of the intermediate values")]
+        format: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Filename to save the values to (stdout if not given)")]
+        to: Option<String>,
+    },
+
+    Explain {
+        #[arg(help = "Derived value to explain, or text to search for (all if not given)")]
+        name: Option<String>,
+    },
+
+    Names {
+        #[arg(help = "Country to name ships for (preferred country if not given)")]
+        country: Option<String>,
+
+        #[arg(help = "Type of ship, such as \"Battleship\" (preferred type if not given)")]
+        kind: Option<String>,
+
+        #[arg(short, long, default_value_t = 10)]
+        #[arg(help = "Number of names to suggest")]
+        number: usize,
+    },
+
+    Config {
+        #[arg(value_parser = ["country", "kind", "year", "designer", "units"])]
+        #[arg(help = "Preference to show or set (all if not given)")]
+        key: Option<String>,
+
+        #[arg(help = "Value to set the preference to, or \"\" to unset it")]
+        value: Option<String>,
+    },
+
+    Fleet {
+        #[command(subcommand)]
+        command: FleetCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum FleetCommands {
+    List {
+        #[arg(help = "Directory of ship files")]
+        dir: String,
+    },
+
+    Treaty {
+        #[arg(help = "Directory of ship files")]
+        dir: String,
+
+        #[arg(short, long, default_value = "washington", value_parser = ["washington", "london"])]
+        #[arg(help = "Treaty to check tonnage against")]
+        treaty: String,
+
+        #[arg(short, long, default_value = "text", value_parser = ["text", "json"])]
+        #[arg(help = "Format of the tonnage report")]
+        format: String,
+
+        #[arg(long, default_value_t = REPORT_JSON_VERSION)]
+        #[arg(help = "Schema version of JSON output")]
+        output_version: u32,
+    },
+
+    Edit {
+        #[arg(help = "Directory of ship files")]
+        dir: String,
+
+        #[arg(short, long, value_name = "PATH=VALUE")]
+        #[arg(help = "Set the field at a key path of every ship, such as country=Britain")]
+        set: Vec<String>,
+
+        #[arg(short, long, value_name = "PATTERN")]
+        #[arg(help = "Name the ships from a pattern, with %d replaced by 1, 2, ...")]
+        renumber: Option<String>,
+
+        #[arg(short, long, allow_hyphen_values = true)]
+        #[arg(help = "Add years to every ship's engine year")]
+        engine_year: Option<i32>,
+
+        #[arg(short = 'n', long)]
+        #[arg(help = "Show the changes without saving them")]
+        dry_run: bool,
+    },
+
+    Refit {
+        #[arg(help = "Directory of ship files")]
+        dir: String,
+
+        #[arg(help = "Refit profile to apply to the matching ships")]
+        profile: String,
+
+        #[arg(short, long, default_value = "text", value_parser = ["text", "json"])]
+        #[arg(help = "Format of the refit summary")]
+        format: String,
+
+        #[arg(long, default_value_t = REPORT_JSON_VERSION)]
+        #[arg(help = "Schema version of JSON output")]
+        output_version: u32,
+
+        #[arg(short, long)]
+        #[arg(help = "Write a text report alongside each ship that is saved")]
+        reports: bool,
+
+        #[arg(short = 'n', long)]
+        #[arg(help = "Show the summary without saving the ships")]
+        dry_run: bool,
+    },
+}
+
+#[derive(Args)]
+struct ReportArgs {
+    #[arg(short, long)]
+    #[arg(help = "Write the ship report to a file instead of stdout")]
+    output: Option<String>,
+
+    #[arg(short, long, default_value = "text", value_parser = ["text", "md", "html", "json", "bbcode"])]
+    #[arg(help = "Format of the ship report")]
+    format: String,
+
+    #[arg(short, long, value_delimiter = ',')]
+    #[arg(value_parser = ["header", "displacement", "dimensions", "armament", "armor", "machinery", "complement",
+        "cost", "weights", "survivability", "hull-form", "strength", "notes"])]
+    #[arg(help = "Comma separated sections of the ship report to include (default all)")]
+    sections: Vec<String>,
+
+    #[arg(short, long, value_parser = ["both", "imperial", "metric"])]
+    #[arg(help = "Units to show values in (default from the config, or both)")]
+    units: Option<String>,
+
+    #[arg(long, default_value_t = REPORT_JSON_VERSION)]
+    #[arg(help = "Schema version of JSON output")]
+    output_version: u32,
+}
+
+/// Units the report is limited to, or None for both.
+///
+fn report_units(units: &str) -> Option<Units> {
+    match units {
+        "imperial" => Some(Units::Imperial),
+        "metric"   => Some(Units::Metric),
+        _          => None,
+    }
+}
+
+/// The user's preferences, or the defaults with a warning if they cannot
+/// be read, so a broken preferences file does not stop sharpie working.
+///
+fn load_config() -> Config {
+    Config::load().unwrap_or_else(|err| {
+        eprintln!("Ignoring preferences: {}", err);
+        Config::default()
+    })
+}
+
+// GUI state {{{1
+//
+/// State the GUI keeps between callbacks.
+///
+#[derive(Default)]
+struct AppState {
+    /// Opens and saves ships; its current ship is the one being shown.
+    controller: Controller,
+    /// Units the report is limited to, or None for both.
+    units: Option<Units>,
+    /// Section the report is limited to, or None for all of it.
+    section: Option<ReportSection>,
+    /// Designer named at the foot of the report.
+    designer: Option<String>,
+    /// User's preferences, for new ships.
+    config: Config,
+    /// Files dropped on the window that have not been opened yet.
+    dropped: Vec<String>,
+    /// Opens the dropped files once the last of them has arrived.
+    drop_timer: Timer,
+    /// Number of the latest report asked for, so a report that finishes
+    /// after a newer one was asked for is not shown over it.
+    report_gen: Arc<AtomicU64>,
+}
+
+type State = Rc<RefCell<AppState>>;
+
+// Report {{{1
+//
+/// Calculate the current ship's report on a background thread and show
+/// it when it is done so the GUI stays responsive.
+///
+fn show_report(ui: &MainWindow, state: &State) {
+    let state = state.borrow();
+    let Some(ship) = state.controller.ship.clone() else { return; };
+
+    ui.set_report_str("Calculating...".into());
+    let options = ReportOptions {
+        units: state.units,
+        sections: state.section.into_iter().collect(),
+        designer: state.designer.clone(),
+        ..Default::default()
+    };
+
+    let latest = Arc::clone(&state.report_gen);
+    let generation = latest.fetch_add(1, Ordering::SeqCst) + 1;
+
+    let ui = ui.as_weak();
+    std::thread::spawn(move || {
+        let computed = Ship::compute_with(&ship, &options);
+        let values = key_values(&ship);
+
+        let _ = ui.upgrade_in_event_loop(move |ui| {
+            if latest.load(Ordering::SeqCst) != generation { return; }
+
+            let issues: Vec<IssueData> = computed.issues.iter().map(|i| IssueData {
+                failure: i.severity == Severity::Failure,
+                code: i.code.into(),
+                field: i.field.into(),
+                message: i.message.clone().into(),
+                section: i.section().map(|s| s.to_string()).unwrap_or_default().into(),
+            }).collect();
+
+            ui.set_report_str(computed.report.into());
+            ui.set_issues(ModelRc::new(VecModel::from(issues)));
+            ui.set_values(ModelRc::new(VecModel::from(values)));
+        });
+    });
+}
+
+/// Key values of a ship, each with how it is found to show when the
+/// pointer is over it.
+///
+fn key_values(ship: &Ship) -> Vec<ValueData> {
+    let Ok(snapshot) = ship.take_snapshot() else { return Vec::new(); };
+
+    snapshot.values.iter().map(|(name, value)| ValueData {
+        name: name.into(),
+        value: if value.abs() >= 100.0 { format!("{:.0}", value) } else { format!("{:.2}", value) }.into(),
+        tip: docs::formula(name)
+            .map(|f| format!("{}\n\nInputs: {}\nSource: {}", f.description, f.inputs.join(", "), f.source))
+            .unwrap_or_default()
+            .into(),
+    }).collect()
+}
+
+/// Limit the report to the section an issue concerns, or show all of it
+/// again if the issue was already selected.
+///
+fn select_issue(ui: MainWindow, state: &State, i: i32) {
+    let section = match ui.get_issues().row_data(i as usize) {
+        Some(issue) if i != ui.get_selected_issue() && !issue.section.is_empty() => Some(issue.section.as_str().into()),
+        _ => None,
+    };
+
+    ui.set_selected_issue(if section.is_some() { i } else { -1 });
+    state.borrow_mut().section = section;
+
+    show_report(&ui, state);
+}
+
+/// Show a newly opened ship's report in full.
+///
+fn show_new_ship(ui: &MainWindow, state: &State) {
+    ui.set_plots(ModelRc::default());
+    ui.set_selected_issue(-1);
+    state.borrow_mut().section = None;
+
+    show_report(ui, state);
+}
+
+/// Write the current ship's report to stdout or a file.
+///
+fn write_report(controller: &mut Controller, args: ReportArgs) -> Result<(), Box<dyn Error>> {
+    let config = load_config();
+    let options = ReportOptions {
+        format: args.format.into(),
+        sections: args.sections.into_iter().map(|s| s.into()).collect(),
+        units: args.units.map_or(config.units, |u| report_units(&u)),
+        version: args.output_version,
+        designer: config.designer,
+        ..Default::default()
+    };
+    let Outcome::Report(report) = controller.run(Action::Report(options))? else { unreachable!() };
+
+    match args.output {
+        Some(file) => fs::write(file, report + "\n")?,
+        None       => match writeln!(io::stdout(), "{}", report) {
+            // Stop quietly if the reader of a pipe has gone
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => (),
+            result => result?,
+        },
+    }
+
+    Ok(())
+}
+
+// Explain {{{1
+//
+/// Describe the formula for name or list the formulas that mention it.
+fn explain(name: &str) -> Result<String, Box<dyn Error>> {
+    if let Some(formula) = docs::formula(name) { return Ok(formula.to_string()); }
+
+    let found = if name.is_empty() { docs::formulas().iter().collect() } else { docs::search(name) };
+    match found.len() {
+        0 => Err(format!("No formula named {}", name).into()),
+        _ => Ok(found.iter().map(|f| f.to_string()).collect::<Vec<_>>().join("\n\n")),
+    }
+}
+
+// Internals {{{1
+//
+/// Intermediate values of a ship's calculations, one `key = value` per line.
+fn internals_text(ship: &Ship) -> String {
+    ship.internals().iter().map(|(k, v)| format!("{} = {}", k, v)).collect::<Vec<_>>().join("\n")
 }
 
 // Load and Convert {{{1
 //
+/// Carry out an action on the GUI's controller, showing any error.
+///
+fn run_action(state: &State, action: Action) -> Option<Outcome> {
+    let result = state.borrow_mut().controller.run(action);
+
+    match result {
+        Ok(outcome) => Some(outcome),
+        Err(error)  => { show_error(&error.to_string()); None },
+    }
+}
+
+/// Tell the user something went wrong.
+///
+fn show_error(message: &str) {
+    MessageDialog::new()
+        .set_title("Error")
+        .set_description(message)
+        .set_level(MessageLevel::Error)
+        .show();
+}
+
+/// Path of the file picked in a dialog, or None if it was cancelled.
+///
+fn picked(file: Option<PathBuf>) -> Option<String> {
+    file.map(|f| f.to_string_lossy().into_owned())
+}
+
 /// Convert a Springsharp 3b3 file to sharpie format and show the ship report.
 ///
-fn convert_ship(ui: MainWindow) {
-    let file = FileDialog::new()
+fn convert_ship(ui: MainWindow, state: &State) {
+    let Some(file) = picked(FileDialog::new()
         .set_title("Springsharp file to convert")
         .add_filter(SS_SHIP_FILE_EXT, &[SS_SHIP_FILE_EXT,])
         .add_filter("all", &["*",])
-        .pick_file()
-        .unwrap_or_default()
-        .into_os_string()
-        .into_string()
-        .unwrap();
+        .pick_file()) else { return; };
 
-    match Ship::convert(file) {
-        Ok(ship) => {
-            ui.set_report_str(ship.report().into());
-            save_ship(ship);
-        },
+    if run_action(state, Action::Convert(file, NumberFormat::Auto)).is_some() {
+        show_new_ship(&ui, state);
+        save_ship(state);
+    }
+}
 
-        // TODO: Show errors in the GUI
-        Err(error) => eprintln!("{}", error),
-    };
+/// Year a new ship is laid down in if the user has not chosen one.
+const NEW_SHIP_YEAR: u32 = 1920;
+
+/// Start an empty ship of the user's preferred country, type and year
+/// and show its report.
+///
+fn new_ship(ui: MainWindow, state: &State) {
+    let ship = Ship::new_with(&state.borrow().config, NEW_SHIP_YEAR);
+    state.borrow_mut().controller.ship = Some(Arc::new(ship));
+
+    show_new_ship(&ui, state);
 }
 
-/// Load a sharpie ship file and show the ship report.
+/// Load a sharpie or SpringSharp ship file and show the ship report.
 ///
-fn load_ship(ui: MainWindow) {
-    let file = FileDialog::new()
-        .set_title("Sharpie file to load")
+fn load_ship(ui: MainWindow, state: &State) {
+    let Some(file) = picked(FileDialog::new()
+        .set_title("Ship file to load")
+        .add_filter("ships", &[SHIP_FILE_EXT, SS_SHIP_FILE_EXT,])
         .add_filter(SHIP_FILE_EXT, &[SHIP_FILE_EXT,])
+        .add_filter(SS_SHIP_FILE_EXT, &[SS_SHIP_FILE_EXT,])
+        .add_filter("all", &["*",])
+        .pick_file()) else { return; };
+
+    if run_action(state, Action::Open(file)).is_some() { show_new_ship(&ui, state); }
+}
+
+// Drag and drop {{{1
+//
+/// Time to wait after a file is dropped on the window for the rest of
+/// the files dropped with it.
+const DROP_DELAY: Duration = Duration::from_millis(100);
+
+/// Queue a file dropped on the window and open the queue once every file
+/// in the drop has arrived.
+///
+fn drop_file(ui: &MainWindow, state: &State, file: String) {
+    let mut s = state.borrow_mut();
+    s.dropped.push(file);
+
+    let (h, st) = (ui.as_weak(), state.clone());
+    s.drop_timer.start(TimerMode::SingleShot, DROP_DELAY, move || { open_dropped(h.unwrap(), &st); });
+}
+
+/// Show a single dropped ship as if it had been loaded, or convert
+/// several and summarise how each went.
+///
+fn open_dropped(ui: MainWindow, state: &State) {
+    let files = std::mem::take(&mut state.borrow_mut().dropped);
+
+    if let [file] = files.as_slice() {
+        if let Some(Outcome::Opened(format)) = run_action(state, Action::Open(file.clone())) {
+            show_new_ship(&ui, state);
+            if format == FileFormat::SpringSharp { save_ship(state); }
+        }
+        return;
+    }
+
+    let Some(Outcome::ConvertedAll(summary)) = run_action(state, Action::ConvertAll(files)) else { return; };
+    show_new_ship(&ui, state);
+
+    MessageDialog::new()
+        .set_title("Dropped ship files")
+        .set_description(summary.to_string())
+        .set_level(MessageLevel::Info)
+        .show();
+}
+
+/// Load a second ship and overlay its curves on those of the ship
+/// being shown.
+///
+fn compare_ship(ui: MainWindow, state: &State) {
+    let Some(ship) = state.borrow().controller.ship.clone() else { return; };
+
+    let Some(file) = picked(FileDialog::new()
+        .set_title("Ship file to compare with")
+        .add_filter("ships", &[SHIP_FILE_EXT, SS_SHIP_FILE_EXT,])
         .add_filter("all", &["*",])
-        .pick_file()
-        .unwrap_or_default()
-        .into_os_string()
-        .into_string()
-        .unwrap();
-
-    match Ship::load(file) {
-        Ok(ship) =>
-            ui.set_report_str(ship.report().into()),
-
-        // TODO: Show errors in the GUI
-        Err(error) => eprintln!("{}", error),
+        .pick_file()) else { return; };
+
+    // The ship compared with does not replace the one being shown
+    let mut other = Controller::default();
+    match other.run(Action::Open(file)) {
+        Ok(_) => {
+            let Some(other) = other.ship else { return; };
+            let num = |v: f64| if v.abs() >= 100.0 { format!("{:.0}", v) } else { format!("{:.2}", v) };
+
+            let plots: Vec<PlotData> = curves::compare(&ship, &other).iter().map(|p| {
+                let (bounds, paths) = (p.bounds(), p.paths(100.0, 100.0));
+
+                PlotData {
+                    title: p.title.into(),
+                    x_label: p.x_label.into(),
+                    y_label: p.y_label.into(),
+                    x_max: num(bounds.x_max).into(),
+                    y_max: num(bounds.y_max).into(),
+                    a_name: p.series[0].name.clone().into(),
+                    b_name: p.series[1].name.clone().into(),
+                    a_path: paths[0].clone().into(),
+                    b_path: paths[1].clone().into(),
+                }
+            }).collect();
+
+            ui.set_plots(ModelRc::new(VecModel::from(plots)));
+        },
+
+        Err(error) => show_error(&error.to_string()),
     };
 }
 
-/// Save a ship to a file.
+/// Change the units the report is shown in and show the current ship
+/// again.
+///
+fn change_units(ui: MainWindow, state: &State, units: &str) {
+    state.borrow_mut().units = report_units(units);
+
+    show_report(&ui, state);
+}
+
+/// Suggest names for the ship being shown, or for the preferred country
+/// and type if no ship is shown.
 ///
-fn save_ship(ship: Ship) {
-    let file = FileDialog::new()
+fn suggest_names(ui: MainWindow, state: &State) {
+    let (country, kind) = match &state.borrow().controller.ship {
+        Some(ship) => (ship.country.clone(), ship.kind.clone()),
+        None => {
+            let config = &state.borrow().config;
+            (config.country.clone().unwrap_or_default(), config.kind.clone().unwrap_or_default())
+        },
+    };
+
+    ui.set_names_str(names::suggest(&country, &kind, 10).join(", ").into());
+}
+
+/// Save the ship being shown to a file.
+///
+fn save_ship(state: &State) {
+    let Some(file) = picked(FileDialog::new()
         .set_title("Sharpie file to save")
         .set_file_name("SHIP.".to_owned() + SHIP_FILE_EXT)
         .add_filter(SHIP_FILE_EXT, &[SHIP_FILE_EXT,])
         .add_filter("all", &["*",])
-        .save_file()
-        .unwrap_or_default()
-        .into_os_string()
-        .into_string()
-        .unwrap();
-
-    match ship.save(file) {
-        Ok(_) => (),
-        // TODO: Show errors in the GUI
-        Err(error) => eprintln!("{}", error),
-    };
+        .save_file()) else { return; };
+
+    run_action(state, Action::Save(file));
 }
 
 // Run the GUI {{{1
 //
 fn run_gui() -> Result<(), Box<dyn Error>> {
     let ui = MainWindow::new().unwrap();
+    let state = State::default();
+
+    let config = load_config();
+    ui.set_units(config.units.map_or("both".into(), |u| u.to_string().into()));
+    state.borrow_mut().units = config.units;
+    state.borrow_mut().designer = config.designer.clone();
+    state.borrow_mut().config = config;
+
+    ui.on_new_ship     ({ let h = ui.as_weak(); let s = state.clone(); move || { new_ship(h.unwrap(), &s); }});
+    ui.on_load_ship    ({ let h = ui.as_weak(); let s = state.clone(); move || { load_ship(h.unwrap(), &s); }});
+    ui.on_convert_ship ({ let h = ui.as_weak(); let s = state.clone(); move || { convert_ship(h.unwrap(), &s); }});
+    ui.on_compare_ship ({ let h = ui.as_weak(); let s = state.clone(); move || { compare_ship(h.unwrap(), &s); }});
+    ui.on_units_changed({ let h = ui.as_weak(); let s = state.clone(); move |u| { change_units(h.unwrap(), &s, &u); }});
+    ui.on_issue_clicked({ let h = ui.as_weak(); let s = state.clone(); move |i| { select_issue(h.unwrap(), &s, i); }});
+    ui.on_suggest_names({ let h = ui.as_weak(); let s = state.clone(); move || { suggest_names(h.unwrap(), &s); }});
+    ui.on_explain      ({ let h = ui.as_weak(); move |n| {
+        let text = explain(n.trim()).unwrap_or_else(|e| e.to_string());
+        h.unwrap().set_explain_str(text.into());
+    }});
 
-    ui.on_load_ship   ({ let h = ui.as_weak(); move || { load_ship(h.unwrap()); }});
-    ui.on_convert_ship({ let h = ui.as_weak(); move || { convert_ship(h.unwrap()); }});
+    ui.window().on_winit_window_event({ let h = ui.as_weak(); let s = state.clone(); move |_, event| {
+        match event {
+            WindowEvent::DroppedFile(path) => {
+                drop_file(&h.unwrap(), &s, path.to_string_lossy().into_owned());
+                EventResult::PreventDefault
+            },
+            _ => EventResult::Propagate,
+        }
+    }});
 
     match ui.run() {
         Ok(_) => Ok(()),
@@ -131,39 +823,348 @@ fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
 
      match cli.command {
-        Some(Commands::Load { file }) => {
-            match Ship::load(file) {
-                Ok(ship) => {
-                    println!("{}", ship.report());
-                    #[cfg(debug_assertions)]
-                    if cli.debug { eprintln!("{}", ship.internals()); }
+        Some(Commands::Balance { file, d_std, to, trace_optimize, format }) => {
+            let (ship, _) = Ship::open_any(file)?;
+            let balance = ship.balance(Tons::long(d_std));
 
+            if format == "json" {
+                let mut json = serde_json::json!({
+                    "d": balance.ship.hull.d().to_long(),
+                    "d_std": balance.ship.d_std().to_long(),
+                    "target": balance.target.to_long(),
+                    "converged": balance.converged,
+                });
+                if trace_optimize { json["trace"] = serde_json::to_value(&balance.trace)?; }
+
+                println!("{}", serde_json::to_string_pretty(&json)?);
+            } else {
+                if trace_optimize { print!("{}", balance.trace_table()); }
+                print!("{}", balance);
+            }
+
+            if let Some(to) = to { balance.ship.save(to)?; }
+
+            Ok(())
+        },
+
+        Some(Commands::Load { file, output }) => {
+            let mut controller = Controller::default();
+
+            if let Outcome::Opened(format) = controller.run(Action::Open(file))?
+                && format != FileFormat::Sharpie {
+                eprintln!("Loaded {} file", format);
+            }
+            #[cfg(debug_assertions)]
+            if cli.debug && let Some(ship) = &controller.ship { eprintln!("{}", internals_text(ship)); }
+
+            write_report(&mut controller, output)
+        },
+
+        Some(Commands::Report { file, output }) => {
+            let mut controller = Controller::default();
+            match file.as_str() {
+                "-" => controller.run(Action::Read(io::read_to_string(io::stdin())?))?,
+                _   => controller.run(Action::Open(file))?,
+            };
+
+            write_report(&mut controller, output)
+        },
+
+        Some(Commands::Convert { from, to, report, provenance, number_format, output }) => {
+            let mut controller = Controller::default();
+
+            if let Outcome::Converted(lines) = controller.run(Action::Convert(from, number_format.into()))?
+                && provenance {
+                for p in lines {
+                    println!("{:>4}: {} = {}", p.line, p.field, p.value);
+                }
+            }
+
+            if report || output.output.is_some() { write_report(&mut controller, output)?; }
+            #[cfg(debug_assertions)]
+            if cli.debug && let Some(ship) = &controller.ship { eprintln!("{}", internals_text(ship)); }
+
+            if let Some(to) = to { controller.run(Action::Save(to))?; }
+
+            Ok(())
+        },
+
+        Some(Commands::ConvertAll { files, format, output_version }) => {
+            if format == "json" { check_json_version(output_version)?; }
+
+            let mut controller = Controller::default();
+            let Outcome::ConvertedAll(batch) = controller.run(Action::ConvertAll(files))? else { unreachable!() };
+
+            match format.as_str() {
+                "json" => println!("{}", to_json_versioned(&batch, output_version)?),
+                _      => println!("{}", batch),
+            }
+
+            match batch.failed {
+                0 => Ok(()),
+                _ => Err(format!("{} of {} files could not be converted", batch.failed, batch.files.len()).into()),
+            }
+        },
+
+        Some(Commands::Validate { files, format, output_version, fail_on }) => {
+            let results: Vec<FileIssues> = files.into_iter().map(FileIssues::check).collect();
+
+            match format.as_str() {
+                "json" => println!("{}", to_json_versioned(&serde_json::json!({ "files": results }), output_version)?),
+                _      => for r in results.iter() { println!("{}", r); },
+            }
+
+            let failed = results.iter().filter(|r| r.fails(fail_on.as_str().into())).count();
+            match failed {
+                0 => Ok(()),
+                _ => Err(format!("{} of {} files failed validation", failed, results.len()).into()),
+            }
+        },
+
+        Some(Commands::Checklist { file, format, output_version }) => {
+            let (ship, _) = Ship::open_any(file)?;
+            let checklist = ship.checklist();
+
+            match format.as_str() {
+                "json" => println!("{}", to_json_versioned(&checklist, output_version)?),
+                _      => println!("{}", checklist),
+            }
+
+            match checklist.pass() {
+                true  => Ok(()),
+                false => Err(format!("{} of {} checks failed", checklist.steps.len() - checklist.passed(), checklist.steps.len()).into()),
+            }
+        },
+
+        Some(Commands::Snapshot { file, verify }) => {
+            let mut ship = Ship::load(file.clone())?;
+
+            if !verify {
+                ship.snapshot = Some(ship.take_snapshot()?);
+                ship.save(file.clone())?;
+                println!("Saved snapshot to {}", file);
+                return Ok(());
+            }
+
+            let Some(snapshot) = &ship.snapshot else {
+                return Err(format!("{} has no snapshot", file).into());
+            };
+            if !snapshot.is_of(&ship)? {
+                println!("The design has been changed since the snapshot was taken");
+            }
+
+            let drift = ship.snapshot_drift()?.unwrap_or_default();
+            for d in drift.iter() { println!("{}", d); }
+
+            match drift.len() {
+                0 => { println!("No values have drifted since sharpie {}", snapshot.sharpie); Ok(()) },
+                n => Err(format!("{} values have drifted since sharpie {}", n, snapshot.sharpie).into()),
+            }
+        },
+
+        Some(Commands::Scale { file, factor, to, report, armament, armor, output }) => {
+            let mut controller = Controller::default();
+            controller.run(Action::Open(file))?;
+
+            if let Some(ship) = &controller.ship {
+                controller.ship = Some(Arc::new(ship.scaled(factor, &ScaleOptions { armament, armor })?));
+            }
+
+            if report || output.output.is_some() { write_report(&mut controller, output)?; }
+            if let Some(to) = to { controller.run(Action::Save(to))?; }
+
+            Ok(())
+        },
+
+        Some(Commands::Fit { file, displacement, tonnes, speed, shp, belt, strength, to, trace_optimize }) => {
+            let (ship, _) = Ship::open_any(file)?;
+            let d_std = displacement.map(|d| if tonnes { Tons::tonnes(d) } else { Tons::long(d) });
+            let fit = ship.fit(&FitTargets { d_std, vmax: speed, hp: shp, belt, strength });
+
+            if trace_optimize { print!("{}", fit.trace_table()); }
+            print!("{}", fit);
+
+            match to {
+                Some(to) => fit.ship.save(to),
+                None     => Ok(()),
+            }
+        },
+
+        Some(Commands::AsBuilt { file, seed, to }) => {
+            let (ship, _) = Ship::open_any(file)?;
+            let built = ship.as_built(seed);
+
+            print!("{}", built);
+
+            match to {
+                Some(to) => built.ship.save(to),
+                None     => Ok(()),
+            }
+        },
+
+        Some(Commands::Export { file, to }) => {
+            let (ship, _) = Ship::open_any(file)?;
+            let s = ship.export_interchange()?;
+
+            match to {
+                Some(to) => Ok(fs::write(to, s)?),
+                None     => { println!("{}", s); Ok(()) },
+            }
+        },
+
+        Some(Commands::Import { file, to, report, output }) => {
+            let ship = Ship::import_interchange(&fs::read_to_string(file)?)?;
+            let mut controller = Controller { ship: Some(Arc::new(ship)) };
+
+            if report || output.output.is_some() { write_report(&mut controller, output)?; }
+            if let Some(to) = to { controller.run(Action::Save(to))?; }
+
+            Ok(())
+        },
+
+        Some(Commands::Profile { file, format, to }) => {
+            let (ship, _) = Ship::open_any(file)?;
+            let plot = ship.armor_profile();
+
+            let s = match format.as_str() {
+                "json" => serde_json::to_string_pretty(&plot)?,
+                _      => plot.svg(800.0, 240.0),
+            };
+
+            match to {
+                Some(to) => Ok(fs::write(to, s + "\n")?),
+                None     => { println!("{}", s); Ok(()) },
+            }
+        },
+
+        Some(Commands::Internals { file, format, to }) => {
+            let (ship, _) = Ship::open_any(file)?;
+
+            let s = match format.as_str() {
+                "csv" => ship.internals_csv(),
+                _     => internals_text(&ship) + "\n",
+            };
+
+            match to {
+                Some(to) => Ok(fs::write(to, s)?),
+                None     => { print!("{}", s); Ok(()) },
+            }
+        },
+
+        Some(Commands::Explain { name }) => {
+            println!("{}", explain(name.as_deref().unwrap_or_default())?);
+
+            Ok(())
+        },
+
+        Some(Commands::Names { country, kind, number }) => {
+            let config = load_config();
+            let country = country.or(config.country).unwrap_or_default();
+            let kind = kind.or(config.kind).unwrap_or_default();
+
+            for name in names::suggest(&country, &kind, number) {
+                println!("{}", name);
+            }
+
+            Ok(())
+        },
+
+        Some(Commands::Config { key, value }) => {
+            match (key, value) {
+                // Setting a preference replaces a broken file
+                (Some(key), Some(value)) => {
+                    let mut config = load_config();
+                    config.set(&key, &value)?;
+                    config.save()
+                },
+                (Some(key), None) => {
+                    println!("{}", Config::load()?.get(&key)?.unwrap_or_default());
+                    Ok(())
+                },
+                (None, _) => {
+                    let config = Config::load()?;
+                    for key in Config::KEYS {
+                        println!("{} = {}", key, config.get(key)?.unwrap_or_default());
+                    }
                     Ok(())
                 },
+            }
+        },
 
-                Err(error) => Err(error),
+        Some(Commands::Fleet { command: FleetCommands::List { dir } }) => {
+            for (file, h) in Fleet::peek_dir(&dir)? {
+                println!("{}: {}, {} {} laid down {}, {:.0} t", file, h.name, h.country, h.kind, h.year, h.d);
             }
+
+            Ok(())
+        },
+
+        Some(Commands::Fleet { command: FleetCommands::Treaty { dir, treaty, format, output_version } }) => {
+            let fleet = Fleet::load_dir(&dir)?;
+            match format.as_str() {
+                "json" => println!("{}", fleet.treaty_json(treaty.into(), output_version)?),
+                _      => print!("{}", fleet.treaty_report(treaty.into())),
+            }
+
+            Ok(())
         },
 
-        Some(Commands::Convert { from, to, report }) => {
-            match Ship::convert(from) {
-                Ok(ship) => {
-                    if report    { println!("{}", ship.report()); }
-                    #[cfg(debug_assertions)]
-                    if cli.debug { eprintln!("{}", ship.internals()); }
+        Some(Commands::Fleet { command: FleetCommands::Edit { dir, set, renumber, engine_year, dry_run } }) => {
+            let mut edits = Vec::new();
+            for s in set {
+                let Some((path, value)) = s.split_once('=') else {
+                    return Err(format!("Expected PATH=VALUE: {}", s).into());
+                };
+                // Anything that is not JSON is taken as text
+                let value = serde_json::from_str(value).unwrap_or(value.into());
+                edits.push(FleetEdit::Set(path.into(), value));
+            }
+            if let Some(pattern) = renumber { edits.push(FleetEdit::Renumber(pattern)); }
+            if let Some(years) = engine_year { edits.push(FleetEdit::EngineYear(years)); }
 
-                    match to {
-                        Some(to) => match ship.save(to) {
-                            Ok(_) => Ok(()),
-                            Err(error) => Err(error),
-                        },
+            let mut fleet = Fleet::load_dir(&dir)?;
+            for edit in edits.iter() { fleet.apply(edit)?; }
 
-                        None => Ok(()),
+            if dry_run {
+                for (file, ship) in fleet.ships.iter() {
+                    println!("{}: {} (engine {})", file, ship.name, ship.engine.year);
+                    for edit in edits.iter() {
+                        if let FleetEdit::Set(path, _) = edit {
+                            println!("    {} = {}", path, ship.get(path).unwrap_or_default());
+                        }
                     }
-                },
+                }
+                Ok(())
+            } else {
+                for file in fleet.save()? { println!("Saved {}", file); }
+                Ok(())
+            }
+        },
+
+        Some(Commands::Fleet { command: FleetCommands::Refit { dir, profile, format, output_version, reports, dry_run } }) => {
+            if format == "json" { check_json_version(output_version)?; }
+
+            let refit = Refit::load(&profile)?;
+            let mut fleet = Fleet::load_dir(&dir)?;
+            let summary = fleet.refit(&refit)?;
 
-                Err(error) => Err(error),
+            // Ships the refit passed over are left as they are
+            fleet.ships.retain(|(file, _)| summary.ships.iter().any(|s| &s.file == file));
+            if !dry_run { fleet.check_save(reports)?; }
+
+            match format.as_str() {
+                "json" => println!("{}", to_json_versioned(&summary, output_version)?),
+                _      => println!("{}", summary),
+            }
+
+            if !dry_run {
+                for file in fleet.save()? { eprintln!("Saved {}", file); }
+                if reports {
+                    for file in fleet.save_reports()? { eprintln!("Saved {}", file); }
+                }
             }
+
+            Ok(())
         },
 
         // No subcommand means launch the GUI
@@ -179,4 +1180,3 @@ fn verify_cli() {
 
     Cli::command().debug_assert();
 }
-
@@ -0,0 +1,282 @@
+//! Suggested names for new ships.
+
+use crate::ShipKind;
+
+// Navy {{{1
+/// Names a navy gave its ships of each kind, in the order it used them.
+///
+struct Navy {
+    /// Lower case names and adjectives the country is known by.
+    aliases: &'static [&'static str],
+    battleships: &'static [&'static str],
+    cruisers: &'static [&'static str],
+    destroyers: &'static [&'static str],
+}
+
+impl Navy { // {{{2
+    // names {{{3
+    /// Names for ships of a kind. Anything that is not a battleship or
+    /// destroyer takes a cruiser's name.
+    ///
+    fn names(&self, kind: ShipKind) -> &'static [&'static str] {
+        match kind {
+            ShipKind::Battleship => self.battleships,
+            ShipKind::Destroyer  => self.destroyers,
+            _                    => self.cruisers,
+        }
+    }
+
+    // is {{{3
+    /// True if the navy belongs to the country, given by name or by
+    /// a word of its name.
+    ///
+    fn is(&self, country: &str) -> bool {
+        let country = country.trim().to_lowercase();
+
+        self.aliases.iter().any(|a| country == *a || country.split(|c: char| !c.is_alphanumeric()).any(|w| w == *a))
+    }
+}
+
+// NAVIES {{{1
+const NAVIES: &[Navy] = &[
+    Navy {
+        aliases: &["britain", "british", "england", "english", "united kingdom", "uk", "royal navy"],
+        battleships: &["Dreadnought", "Bellerophon", "Superb", "Temeraire", "St Vincent", "Collingwood", "Vanguard",
+            "Neptune", "Colossus", "Hercules", "Orion", "Monarch", "Conqueror", "Thunderer", "King George V",
+            "Centurion", "Ajax", "Audacious", "Iron Duke", "Marlborough", "Benbow", "Emperor of India",
+            "Queen Elizabeth", "Warspite", "Valiant", "Barham", "Malaya", "Royal Sovereign", "Royal Oak", "Revenge",
+            "Resolution", "Ramillies", "Nelson", "Rodney", "Anson", "Howe"],
+        cruisers: &["Arethusa", "Aurora", "Galatea", "Penelope", "Caroline", "Cleopatra", "Calliope", "Cordelia",
+            "Hawkins", "Kent", "Berwick", "Cornwall", "Cumberland", "Suffolk", "London", "Devonshire", "Sussex",
+            "Shropshire", "Norfolk", "Dorsetshire", "York", "Exeter", "Leander", "Achilles", "Southampton",
+            "Newcastle", "Sheffield", "Glasgow", "Birmingham", "Belfast", "Edinburgh"],
+        destroyers: &["Acasta", "Achates", "Ambuscade", "Ardent", "Beagle", "Bulldog", "Comet", "Crusader", "Daring",
+            "Decoy", "Eclipse", "Echo", "Faulknor", "Fearless", "Glowworm", "Grenville", "Hardy", "Havock",
+            "Hotspur", "Icarus", "Ivanhoe", "Jervis", "Javelin", "Kelly", "Kipling", "Lightning", "Legion"],
+    },
+    Navy {
+        aliases: &["usa", "us", "united states", "america", "american"],
+        battleships: &["South Carolina", "Michigan", "Delaware", "North Dakota", "Florida", "Utah", "Wyoming",
+            "Arkansas", "New York", "Texas", "Nevada", "Oklahoma", "Pennsylvania", "Arizona", "New Mexico",
+            "Mississippi", "Idaho", "Tennessee", "California", "Colorado", "Maryland", "West Virginia",
+            "Washington", "North Carolina", "Indiana", "Massachusetts", "Alabama", "Iowa", "Missouri", "Wisconsin"],
+        cruisers: &["Omaha", "Milwaukee", "Cincinnati", "Raleigh", "Detroit", "Richmond", "Concord", "Trenton",
+            "Marblehead", "Memphis", "Pensacola", "Salt Lake City", "Northampton", "Chester", "Louisville",
+            "Chicago", "Houston", "Augusta", "Portland", "Indianapolis", "New Orleans", "Astoria", "Minneapolis",
+            "Tuscaloosa", "San Francisco", "Quincy", "Vincennes", "Wichita", "Brooklyn", "Phoenix", "Boise",
+            "Honolulu", "Helena"],
+        destroyers: &["Farragut", "Dewey", "Hull", "Macdonough", "Worden", "Dale", "Monaghan", "Aylwin", "Porter",
+            "Selfridge", "McDougal", "Winslow", "Mahan", "Cummings", "Drayton", "Lamson", "Flusser", "Reid",
+            "Case", "Conyngham", "Cassin", "Shaw", "Tucker", "Downes", "Cushing", "Perkins", "Smith", "Preston",
+            "Gridley", "Craven", "Somers", "Warrington", "Sims", "Hughes", "Anderson", "Hammann", "Benson"],
+    },
+    Navy {
+        aliases: &["germany", "german", "deutschland", "prussia", "prussian"],
+        battleships: &["Nassau", "Westfalen", "Rheinland", "Posen", "Helgoland", "Ostfriesland", "Thüringen",
+            "Oldenburg", "Kaiser", "Friedrich der Grosse", "Kaiserin", "Prinzregent Luitpold", "König Albert",
+            "König", "Grosser Kurfürst", "Markgraf", "Kronprinz", "Bayern", "Baden", "Sachsen", "Württemberg",
+            "Bismarck", "Tirpitz", "Scharnhorst", "Gneisenau"],
+        cruisers: &["Dresden", "Emden", "Karlsruhe", "Rostock", "Magdeburg", "Breslau", "Stralsund", "Strassburg",
+            "Kolberg", "Mainz", "Cöln", "Augsburg", "Pillau", "Elbing", "Wiesbaden", "Frankfurt", "Königsberg",
+            "Nürnberg", "Leipzig", "Admiral Hipper", "Blücher", "Prinz Eugen", "Seydlitz", "Lützow"],
+        destroyers: &["Leberecht Maass", "Georg Thiele", "Max Schultz", "Richard Beitzen", "Paul Jacobi",
+            "Theodor Riedel", "Hermann Schoemann", "Bruno Heinemann", "Wolfgang Zenker", "Hans Lody",
+            "Bernd von Arnim", "Erich Giese", "Erich Koellner", "Friedrich Ihn", "Erich Steinbrinck",
+            "Friedrich Eckoldt", "Diether von Roeder", "Hans Lüdemann", "Hermann Künne", "Karl Galster",
+            "Wilhelm Heidkamp", "Anton Schmitt", "Möwe", "Seeadler", "Greif", "Falke", "Kondor", "Albatros",
+            "Wolf", "Iltis", "Jaguar", "Leopard", "Luchs", "Tiger"],
+    },
+    Navy {
+        aliases: &["japan", "japanese", "nippon"],
+        battleships: &["Settsu", "Kawachi", "Fuso", "Yamashiro", "Ise", "Hyuga", "Nagato", "Mutsu", "Kaga", "Tosa",
+            "Kongo", "Hiei", "Haruna", "Kirishima", "Amagi", "Akagi", "Kii", "Owari", "Yamato", "Musashi",
+            "Shinano"],
+        cruisers: &["Tenryu", "Tatsuta", "Kuma", "Tama", "Kitakami", "Oi", "Kiso", "Nagara", "Isuzu", "Natori",
+            "Yura", "Kinu", "Abukuma", "Sendai", "Jintsu", "Naka", "Yubari", "Furutaka", "Kako", "Aoba",
+            "Kinugasa", "Myoko", "Nachi", "Haguro", "Ashigara", "Takao", "Atago", "Maya", "Chokai", "Mogami",
+            "Mikuma", "Suzuya", "Kumano", "Tone", "Chikuma"],
+        destroyers: &["Fubuki", "Shirayuki", "Hatsuyuki", "Miyuki", "Murakumo", "Shinonome", "Usugumo",
+            "Shirakumo", "Isonami", "Uranami", "Ayanami", "Shikinami", "Asagiri", "Yugiri", "Amagiri", "Sagiri",
+            "Oboro", "Akebono", "Sazanami", "Ushio", "Akatsuki", "Hibiki", "Ikazuchi", "Inazuma", "Kagero",
+            "Shiranui", "Kuroshio", "Oyashio", "Hatsukaze", "Yukikaze", "Amatsukaze", "Tokitsukaze"],
+    },
+    Navy {
+        aliases: &["france", "french"],
+        battleships: &["Courbet", "Jean Bart", "France", "Paris", "Bretagne", "Provence", "Lorraine", "Normandie",
+            "Languedoc", "Flandre", "Gascogne", "Béarn", "Dunkerque", "Strasbourg", "Richelieu", "Clemenceau"],
+        cruisers: &["Duguay-Trouin", "Lamotte-Picquet", "Primauguet", "Duquesne", "Tourville", "Suffren",
+            "Colbert", "Foch", "Dupleix", "Algérie", "Pluton", "Jeanne d'Arc", "Émile Bertin",
+            "La Galissonnière", "Jean de Vienne", "Gloire", "Marseillaise", "Montcalm", "Georges Leygues"],
+        destroyers: &["Chacal", "Jaguar", "Léopard", "Lynx", "Panthère", "Tigre", "Guépard", "Bison", "Lion",
+            "Vauban", "Valmy", "Verdun", "Aigle", "Vautour", "Albatros", "Gerfaut", "Milan", "Épervier",
+            "Le Fantasque", "L'Audacieux", "Le Malin", "Le Terrible", "Le Triomphant", "L'Indomptable",
+            "Mogador", "Volta", "Le Hardi"],
+    },
+    Navy {
+        aliases: &["italy", "italian", "italia"],
+        battleships: &["Dante Alighieri", "Conte di Cavour", "Giulio Cesare", "Leonardo da Vinci", "Andrea Doria",
+            "Caio Duilio", "Francesco Caracciolo", "Cristoforo Colombo", "Marcantonio Colonna",
+            "Francesco Morosini", "Littorio", "Vittorio Veneto", "Roma", "Impero"],
+        cruisers: &["Alberto di Giussano", "Alberico da Barbiano", "Bartolomeo Colleoni",
+            "Giovanni delle Bande Nere", "Luigi Cadorna", "Armando Diaz", "Raimondo Montecuccoli",
+            "Muzio Attendolo", "Duca d'Aosta", "Eugenio di Savoia", "Trento", "Trieste", "Bolzano", "Zara",
+            "Fiume", "Gorizia", "Pola"],
+        destroyers: &["Alvise da Mosto", "Antonio da Noli", "Antonio Pigafetta", "Alpino", "Artigliere", "Ascari",
+            "Aviere", "Bersagliere", "Carabiniere", "Corazziere", "Fuciliere", "Geniere", "Granatiere",
+            "Lanciere", "Maestrale", "Grecale", "Libeccio", "Scirocco", "Folgore", "Baleno", "Fulmine", "Lampo"],
+    },
+    Navy {
+        aliases: &["russia", "russian", "soviet union", "soviet", "ussr"],
+        battleships: &["Gangut", "Poltava", "Petropavlovsk", "Sevastopol", "Imperatritsa Mariya",
+            "Imperatritsa Ekaterina Velikaya", "Imperator Aleksandr III", "Imperator Nikolai I", "Izmail",
+            "Borodino", "Kinburn", "Navarin", "Sovetsky Soyuz", "Sovetskaya Ukraina", "Sovetskaya Rossiya",
+            "Sovetskaya Belorussiya"],
+        cruisers: &["Svetlana", "Admiral Greig", "Admiral Spiridov", "Admiral Butakov", "Admiral Nakhimov",
+            "Admiral Lazarev", "Admiral Istomin", "Admiral Kornilov", "Kirov", "Voroshilov", "Maxim Gorky",
+            "Molotov", "Kalinin", "Kaganovich", "Chapayev", "Chkalov", "Zheleznyakov", "Frunze", "Kuibyshev"],
+        destroyers: &["Novik", "Leningrad", "Moskva", "Kharkov", "Minsk", "Baku", "Tbilisi", "Gnevny", "Grozny",
+            "Gromky", "Grozyashchy", "Gordy", "Bodry", "Bystry", "Bditelny", "Boiky", "Besposhchadny",
+            "Sokrushitelny", "Steregushchy", "Storozhevoy", "Silny", "Smetlivy", "Stoiky"],
+    },
+    Navy {
+        aliases: &["austria-hungary", "austria", "austrian", "hungary", "austro-hungarian"],
+        battleships: &["Viribus Unitis", "Tegetthoff", "Prinz Eugen", "Szent István", "Erzherzog Franz Ferdinand",
+            "Radetzky", "Zrínyi", "Habsburg", "Árpád", "Babenberg"],
+        cruisers: &["Admiral Spaun", "Novara", "Saida", "Helgoland", "Sankt Georg", "Kaiser Karl VI",
+            "Kaiserin und Königin Maria Theresia", "Aspern", "Szigetvár", "Zenta"],
+        destroyers: &["Tátra", "Balaton", "Csepel", "Lika", "Orjen", "Triglav", "Huszár", "Ulan", "Streiter",
+            "Wildfang", "Scharfschütze", "Uskoke", "Turul", "Pandúr", "Csikós", "Réka", "Velebit", "Dinara",
+            "Dukla"],
+    },
+];
+
+// OTHER {{{1
+/// Names for a country sharpie has no list for.
+///
+const OTHER: Navy = Navy {
+    aliases: &[],
+    battleships: &["Invincible", "Indomitable", "Intrepid", "Resolute", "Defiance", "Valour", "Vigilant",
+        "Steadfast", "Relentless", "Implacable", "Formidable", "Redoubtable", "Victorious", "Triumph",
+        "Dauntless", "Fortitude"],
+    cruisers: &["Orion", "Sirius", "Vega", "Altair", "Rigel", "Antares", "Arcturus", "Aldebaran", "Canopus",
+        "Capella", "Procyon", "Deneb", "Pollux", "Castor", "Regulus", "Spica"],
+    destroyers: &["Tempest", "Typhoon", "Tornado", "Cyclone", "Hurricane", "Squall", "Falcon", "Hawk", "Kestrel",
+        "Merlin", "Osprey", "Harrier", "Lance", "Rapier", "Sabre", "Javelin"],
+};
+
+// kind {{{1
+/// Kind of ship a type such as "Heavy cruiser" or "Battlecruiser" names.
+///
+fn kind(kind: &str) -> ShipKind {
+    let kind = kind.to_lowercase();
+
+    if kind.contains("battle") || kind.contains("dreadnought") || kind.contains("ironclad") {
+        ShipKind::Battleship
+    } else if kind.contains("destroyer") || kind.contains("torpedo") || kind.contains("escort") {
+        ShipKind::Destroyer
+    } else if kind.contains("cruiser") {
+        ShipKind::Cruiser
+    } else {
+        ShipKind::Other
+    }
+}
+
+// numeral {{{1
+/// Roman numeral for the times a name has been reused.
+///
+fn numeral(n: usize) -> String {
+    const NUMERALS: [(usize, &str); 8] = [(90, "XC"), (50, "L"), (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I")];
+
+    let (mut n, mut s) = (n, String::new());
+    for (value, numeral) in NUMERALS.iter() {
+        while n >= *value {
+            s.push_str(numeral);
+            n -= value;
+        }
+    }
+    s
+}
+
+// suggest {{{1
+/// Suggest n names for ships of a country and type, both as written in a
+/// ship's `country` and `kind`.
+///
+/// Names are taken in the order the navy used them. Countries sharpie
+/// has no list for get names any navy might use. Once the list runs out
+/// the names are used again, numbered II, III and so on as navies did.
+///
+pub fn suggest(country: &str, kind_of: &str, n: usize) -> Vec<String> {
+    let navy = NAVIES.iter().find(|navy| navy.is(country)).unwrap_or(&OTHER);
+    let names = navy.names(kind(kind_of));
+
+    (0..n)
+        .map(|i| match i / names.len() {
+            0     => names[i % names.len()].to_string(),
+            reuse => format!("{} {}", names[i % names.len()], numeral(reuse + 1)),
+        })
+        .collect()
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod names {
+    use super::*;
+
+    // Test suggest {{{2
+    macro_rules! test_suggest {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, country, kind) = $value;
+
+                    assert_eq!(expected, suggest(country, kind, 3));
+                }
+            )*
+        }
+    }
+
+    test_suggest! {
+        // name:                     (names, country, kind)
+        suggest_britain_battleship:  (vec!["Dreadnought", "Bellerophon", "Superb"], "Britain", "Battleship"),
+        suggest_great_britain:       (vec!["Dreadnought", "Bellerophon", "Superb"], "Great Britain", "Dreadnought"),
+        suggest_usa_cruiser:         (vec!["Omaha", "Milwaukee", "Cincinnati"], "USA", "Heavy cruiser"),
+        suggest_japan_destroyer:     (vec!["Fubuki", "Shirayuki", "Hatsuyuki"], "Imperial Japanese Navy", "Destroyer"),
+        suggest_austria:             (vec!["Viribus Unitis", "Tegetthoff", "Prinz Eugen"], "Austria-Hungary", "Battlecruiser"),
+        suggest_other_kind:          (vec!["Orion", "Sirius", "Vega"], "Ruritania", ""),
+        suggest_other_country:       (vec!["Invincible", "Indomitable", "Intrepid"], "Ruritania", "Battleship"),
+    }
+
+    #[test]
+    fn suggest_reused() {
+        let names = suggest("Austria-Hungary", "Battleship", 25);
+
+        assert_eq!(25, names.len());
+        assert_eq!("Viribus Unitis II", names[10]);
+        assert_eq!("Viribus Unitis III", names[20]);
+    }
+
+    // Test numeral {{{2
+    macro_rules! test_numeral {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, n) = $value;
+
+                    assert_eq!(expected, numeral(n));
+                }
+            )*
+        }
+    }
+
+    test_numeral! {
+        // name:      (numeral, n)
+        numeral_2:    ("II", 2),
+        numeral_4:    ("IV", 4),
+        numeral_9:    ("IX", 9),
+        numeral_14:   ("XIV", 14),
+        numeral_49:   ("XLIX", 49),
+    }
+}
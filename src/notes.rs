@@ -0,0 +1,247 @@
+use serde::{Serialize, Deserialize};
+
+use std::ops::Range;
+
+// Notes {{{1
+/// Free-form notes about a design along with optional metadata.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(from = "NotesFormat")]
+pub struct Notes {
+    /// Lines of free-form text.
+    pub text: Vec<String>,
+    /// Tags used to organize designs.
+    pub tags: Vec<String>,
+    /// Who created the design.
+    pub author: Option<String>,
+    /// Where the design came from.
+    pub source_url: Option<String>,
+    /// Terms the design is shared under.
+    pub license: Option<String>,
+    /// Report SpringSharp embedded in the file the design was converted
+    /// from. It describes the design as SpringSharp last saw it and is
+    /// kept apart from the notes so it is not reported as one of them.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub ss_report: Vec<String>,
+}
+
+impl Notes { // {{{2
+    /// Heading of the last section of a SpringSharp report.
+    const SS_REPORT_END: &str = "Ship space, strength and comments:";
+
+    // from_lines {{{3
+    /// Notes read from a SpringSharp file, with any report SpringSharp
+    /// embedded in them split from the user's notes.
+    ///
+    pub fn from_lines(lines: Vec<String>) -> Notes {
+        match Notes::ss_report_lines(&lines) {
+            Some(r) => Notes {
+                text: lines[..r.start].iter().chain(lines[r.end..].iter()).cloned().collect(),
+                ss_report: lines[r].to_vec(),
+                ..Default::default()
+            },
+            None => Notes { text: lines, ..Default::default() },
+        }
+    }
+
+    // ss_report_lines {{{3
+    /// Lines of an embedded SpringSharp report, or None if there is no
+    /// report.
+    ///
+    /// The report starts with the "Name, Country Type laid down Year"
+    /// heading followed by the displacement and runs to the end of the
+    /// comments about the ship's space, strength and seakeeping.
+    ///
+    pub fn ss_report_lines(lines: &[String]) -> Option<Range<usize>> {
+        let start = (0..lines.len()).find(|i| {
+            Notes::is_ss_report_heading(&lines[*i]) &&
+                lines[i + 1..].iter()
+                    .find(|s| !s.trim().is_empty())
+                    .is_some_and(|s| s.starts_with("Displacement:"))
+        })?;
+
+        let end = match lines[start..].iter().position(|s| s.starts_with(Notes::SS_REPORT_END)) {
+            Some(i) => start + i + 1 + lines[start + i + 1..].iter()
+                .take_while(|s| s.starts_with(char::is_whitespace) && !s.trim().is_empty() ||
+                    s.starts_with("Hull strength"))
+                .count(),
+            None => lines.len(),
+        };
+
+        Some(start..end)
+    }
+
+    // is_ss_report_heading {{{3
+    /// True if a line is the heading of a SpringSharp report.
+    ///
+    fn is_ss_report_heading(line: &str) -> bool {
+        line.rsplit_once(" laid down ")
+            .is_some_and(|(_, year)| year.trim().len() == 4 && year.trim().chars().all(|c| c.is_ascii_digit()))
+    }
+
+    // has_metadata {{{3
+    /// True if any of the metadata fields are set.
+    ///
+    pub fn has_metadata(&self) -> bool {
+        !self.tags.is_empty() ||
+            self.author.is_some() ||
+            self.source_url.is_some() ||
+            self.license.is_some()
+    }
+
+    // metadata {{{3
+    /// Report lines for the metadata fields that are set.
+    ///
+    pub fn metadata(&self) -> Vec<String> {
+        let mut v = Vec::new();
+
+        if !self.tags.is_empty() { v.push(format!("Tags: {}", self.tags.join(", "))); }
+        if let Some(s) = &self.author     { v.push(format!("Author: {}", s)); }
+        if let Some(s) = &self.source_url { v.push(format!("Source: {}", s)); }
+        if let Some(s) = &self.license    { v.push(format!("License: {}", s)); }
+
+        v
+    }
+}
+
+// NotesFormat {{{2
+/// Ways notes can be stored in a ship file.
+///
+/// Older files store notes as a plain list of lines.
+///
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum NotesFormat {
+    Lines(Vec<String>),
+    Fields {
+        #[serde(default)]
+        text: Vec<String>,
+        #[serde(default)]
+        tags: Vec<String>,
+        #[serde(default)]
+        author: Option<String>,
+        #[serde(default)]
+        source_url: Option<String>,
+        #[serde(default)]
+        license: Option<String>,
+        #[serde(default)]
+        ss_report: Vec<String>,
+    },
+}
+
+impl From<NotesFormat> for Notes {
+    fn from(format: NotesFormat) -> Self {
+        match format {
+            NotesFormat::Lines(text) => Notes { text, ..Default::default() },
+            NotesFormat::Fields { text, tags, author, source_url, license, ss_report } =>
+                Notes { text, tags, author, source_url, license, ss_report },
+        }
+    }
+}
+
+// Testing {{{2
+//
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod notes {
+    use super::*;
+
+    // deserialize {{{3
+    macro_rules! test_deserialize {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, json) = $value;
+
+                    assert_eq!(expected, serde_json::from_str::<Notes>(json).unwrap());
+                }
+            )*
+        }
+    }
+
+    test_deserialize! {
+        // name:              (notes, json)
+        deserialize_lines:    (Notes { text: vec!["a".into(), "b".into()], ..Default::default() }, r#"["a","b"]"#),
+        deserialize_empty:    (Notes::default(), r#"{}"#),
+        deserialize_partial:  (Notes { author: Some("me".into()), ..Default::default() }, r#"{"author":"me"}"#),
+        deserialize_fields:   (
+            Notes {
+                text: vec!["a".into()],
+                tags: vec!["cruiser".into()],
+                author: Some("me".into()),
+                source_url: Some("http://example.com".into()),
+                license: Some("CC0".into()),
+                ..Default::default()
+            },
+            r#"{"text":["a"],"tags":["cruiser"],"author":"me","source_url":"http://example.com","license":"CC0"}"#
+        ),
+    }
+
+    // round trip {{{3
+    #[test]
+    fn round_trip() {
+        let notes = Notes {
+            text: vec!["a".into()],
+            tags: vec!["cruiser".into(), "treaty".into()],
+            license: Some("CC0".into()),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&notes).unwrap();
+
+        assert_eq!(notes, serde_json::from_str(&json).unwrap());
+    }
+
+    // metadata {{{3
+    #[test]
+    fn metadata() {
+        let notes = Notes {
+            tags: vec!["cruiser".into(), "treaty".into()],
+            author: Some("me".into()),
+            ..Default::default()
+        };
+
+        assert!(notes.has_metadata());
+        assert_eq!(vec!["Tags: cruiser, treaty", "Author: me"], notes.metadata());
+        assert!(!Notes::default().has_metadata());
+    }
+
+    // from_lines {{{3
+    macro_rules! test_from_lines {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((text, ss_report), lines) = $value;
+
+                    let notes = Notes::from_lines(lines.iter().map(|s: &&str| s.to_string()).collect());
+                    assert_eq!(text, notes.text);
+                    assert_eq!(ss_report, notes.ss_report);
+                }
+            )*
+        }
+    }
+
+    const REPORT: [&str; 9] = [
+        "Warspite, Britain Battleship laid down 1913",
+        "",
+        "Displacement:",
+        "    29,150 t normal",
+        "Ship space, strength and comments:",
+        "    Space    - Hull below water: 62.1 %",
+        "Hull strength (Relative):",
+        "        - Overall: 1.26",
+        "    Excellent seaboat",
+    ];
+
+    test_from_lines! {
+        // name:                  ((text, ss_report), lines)
+        from_lines_none:          ((vec!["a", "b"], Vec::<&str>::new()), vec!["a", "b"]),
+        from_lines_not_report:    ((vec!["Laid down in 1913", "laid down 1913"], Vec::<&str>::new()), vec!["Laid down in 1913", "laid down 1913"]),
+        from_lines_only:          ((Vec::<&str>::new(), REPORT.to_vec()), REPORT.to_vec()),
+        from_lines_around:        ((vec!["before", "", "after"], REPORT.to_vec()),
+                                   [&["before"], &REPORT[..], &["", "after"]].concat()),
+        from_lines_no_end:        ((vec!["before"], REPORT[..4].to_vec()),
+                                   [&["before"], &REPORT[..4]].concat()),
+    }
+}
@@ -0,0 +1,154 @@
+use serde::{Serialize, Deserialize};
+
+use std::fmt;
+
+// Params {{{1
+/// Constants of the damage model, for wargame groups that fit the
+/// survivability figures to their own rules.
+///
+/// A constant that is not given, or is not positive, keeps the value
+/// SpringSharp uses.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    /// Damage a shell hit does is the cube of its calibre (in) divided
+    /// by this, so a larger divisor means more hits to sink a ship.
+    #[serde(default)]
+    pub shell_divisor: Option<f64>,
+    /// Calibre (in) of the shells a ship without guns counts its hits in.
+    #[serde(default)]
+    pub shell_size: Option<f64>,
+    /// Weapon weight (t) of the torpedo one torpedo hit is reckoned
+    /// in. Heavier torpedoes than this take fewer hits to sink a ship.
+    #[serde(default)]
+    pub torp_wgt: Option<f64>,
+}
+
+impl fmt::Display for Params { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "calibre cubed / {:.2}, {:.1}\" shells without guns, {:.3} t torpedo",
+            self.shell_divisor(), self.shell_size(), self.torp_wgt())
+    }
+}
+
+impl Params { // {{{2
+    /// SpringSharp's shell damage divisor.
+    pub const SHELL_DIVISOR: f64 = 2.0;
+    /// SpringSharp's shell size (in) for ships without guns.
+    pub const SHELL_SIZE: f64 = 6.0;
+    /// SpringSharp's torpedo weapon weight (t).
+    pub const TORP_WGT: f64 = 1.313;
+
+    // or {{{3
+    /// Value if it is given and positive, otherwise the default.
+    ///
+    fn or(value: Option<f64>, default: f64) -> f64 {
+        value.filter(|v| *v > 0.0).unwrap_or(default)
+    }
+
+    // shell_divisor {{{3
+    /// Divisor of the cube of a shell's calibre that gives its damage.
+    ///
+    pub fn shell_divisor(&self) -> f64 {
+        Self::or(self.shell_divisor, Self::SHELL_DIVISOR)
+    }
+
+    // shell_size {{{3
+    /// Calibre (in) of the shells a ship without guns counts its hits in.
+    ///
+    pub fn shell_size(&self) -> f64 {
+        Self::or(self.shell_size, Self::SHELL_SIZE)
+    }
+
+    // torp_wgt {{{3
+    /// Weapon weight (t) of the torpedo a torpedo hit is reckoned in.
+    ///
+    pub fn torp_wgt(&self) -> f64 {
+        Self::or(self.torp_wgt, Self::TORP_WGT)
+    }
+
+    // shell_damage {{{3
+    /// Damage a hit from a shell of a calibre (in) does.
+    ///
+    pub fn shell_damage(&self, diam: f64) -> f64 {
+        diam.powf(3.0) / self.shell_divisor()
+    }
+
+    // is_set {{{3
+    /// True if any constant is given.
+    ///
+    pub fn is_set(&self) -> bool {
+        *self != Params::default()
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod params {
+    use super::*;
+    use crate::Ship;
+    use crate::weapons::{GunDistributionType, GunLayoutType, TorpedoMountType};
+    use crate::test_support::*;
+
+    // Test damage {{{2
+    macro_rules! test_damage {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((shells, torps), params) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull();
+                    ship.year = 1910;
+                    ship.batteries[0].diam = 12.0;
+                    ship.batteries[0].len = 45.0;
+                    ship.batteries[0].year = 1910;
+                    ship.batteries[0].set_layout((1, 4, 0), GunLayoutType::Twin, GunDistributionType::CenterlineEven);
+                    ship.torps[0].year = 1910;
+                    ship.torps[0].num = 4;
+                    ship.torps[0].mounts = 4;
+                    ship.torps[0].diam = 18.0;
+                    ship.torps[0].len = 17.0;
+                    ship.torps[0].mount_kind = TorpedoMountType::SubmergedSideTubes;
+                    ship.params = params;
+
+                    assert_eq!(shells, to_place(ship.damage_shell_num(), 1));
+                    assert_eq!(torps, to_place(ship.damage_torp_num(), 1));
+                }
+            )*
+        }
+    }
+
+    test_damage! {
+        // name:               ((shells, torpedoes), params)
+        damage_default:        ((3.3, 3.1), Params::default()),
+        damage_springsharp:    ((3.3, 3.1), Params { shell_divisor: Some(2.0), shell_size: Some(6.0), torp_wgt: Some(1.313) }),
+        damage_not_positive:   ((3.3, 3.1), Params { shell_divisor: Some(0.0), shell_size: Some(-1.0), torp_wgt: Some(0.0) }),
+        damage_shell_divisor:  ((6.6, 3.1), Params { shell_divisor: Some(4.0), ..Default::default() }),
+        damage_torp_wgt:       ((3.3, 6.1), Params { torp_wgt: Some(2.626), ..Default::default() }),
+    }
+
+    #[test]
+    fn shell_size() {
+        let mut ship = Ship { hull: get_hull(), ..Default::default() };
+        assert_eq!(6.0, ship.damage_shell_size());
+
+        ship.params.shell_size = Some(4.0);
+        assert_eq!(4.0, ship.damage_shell_size());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("calibre cubed / 2.00, 6.0\" shells without guns, 1.313 t torpedo", Params::default().to_string());
+        assert!(!Params::default().is_set());
+        assert!(Params { torp_wgt: Some(1.5), ..Default::default() }.is_set());
+
+        let mut ship = Ship { hull: get_hull(), year: 1910, ..Default::default() };
+        assert!(!ship.report().contains("House rules"));
+
+        ship.params.shell_divisor = Some(4.0);
+        assert!(ship.report().contains("    House rules: calibre cubed / 4.00, 6.0\" shells without guns, 1.313 t torpedo\n"));
+    }
+}
@@ -0,0 +1,280 @@
+use serde::Serialize;
+
+// Ratios {{{1
+/// Design ratios used to compare a ship against historical designs.
+///
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Ratios {
+    /// Shaft horsepower per ton of normal displacement.
+    pub shp: f64,
+    /// Belt weight as a percentage of normal displacement.
+    pub belt: f64,
+    /// Broadside weight (lbs) per ton of normal displacement.
+    pub broadside: f64,
+    /// Crew per ton of normal displacement.
+    pub crew: f64,
+}
+
+impl Ratios { // {{{2
+    // new {{{3
+    /// Calculate ratios from raw design values.
+    ///
+    pub fn new(d: f64, shp: f64, belt: f64, broadside: f64, crew: f64) -> Ratios {
+        if d <= 0.0 { return Ratios::default(); }
+
+        Ratios {
+            shp: shp / d,
+            belt: belt / d * 100.0,
+            broadside: broadside / d,
+            crew: crew / d,
+        }
+    }
+
+    // distance {{{3
+    /// Average relative difference between two sets of ratios.
+    ///
+    /// 0.0 means identical and 1.0 means completely different.
+    ///
+    fn distance(&self, other: &Ratios) -> f64 {
+        (Self::rel_diff(self.shp, other.shp) +
+         Self::rel_diff(self.belt, other.belt) +
+         Self::rel_diff(self.broadside, other.broadside) +
+         Self::rel_diff(self.crew, other.crew)) / 4.0
+    }
+
+    // rel_diff {{{3
+    /// Relative difference between two values.
+    ///
+    fn rel_diff(a: f64, b: f64) -> f64 {
+        let max = a.abs().max(b.abs());
+
+        if max == 0.0 { 0.0 } else { (a - b).abs() / max }
+    }
+}
+
+// Historical {{{1
+/// A real ship to compare designs against.
+///
+/// Figures are approximate and taken at normal displacement.
+///
+struct Historical {
+    name: &'static str,
+    year: u32,
+    kind: &'static str,
+    /// Normal displacement (tons).
+    d: f64,
+    /// Designed horsepower.
+    shp: f64,
+    /// Belt weight as a percentage of normal displacement.
+    belt: f64,
+    /// Broadside weight (lbs).
+    broadside: f64,
+    /// Complement.
+    crew: f64,
+}
+
+impl Historical { // {{{2
+    // ratios {{{3
+    /// Design ratios of the historical ship.
+    ///
+    fn ratios(&self) -> Ratios {
+        Ratios {
+            belt: self.belt,
+            ..Ratios::new(self.d, self.shp, 0.0, self.broadside, self.crew)
+        }
+    }
+}
+
+// Dataset {{{2
+const SHIPS: [Historical; 20] = [
+    Historical { name: "HMS Devastation",    year: 1869, kind: "Battleship",    d:  9330.0, shp:   6650.0, belt: 20.0, broadside:  2800.0, crew:  358.0 },
+    Historical { name: "HMS Royal Sovereign",year: 1889, kind: "Battleship",    d: 14150.0, shp:  11000.0, belt: 10.0, broadside:  5500.0, crew:  692.0 },
+    Historical { name: "USS Olympia",        year: 1891, kind: "Cruiser",       d:  5870.0, shp:  17300.0, belt:  0.0, broadside:  1250.0, crew:  411.0 },
+    Historical { name: "HMS Havock",         year: 1892, kind: "Destroyer",     d:   240.0, shp:   3400.0, belt:  0.0, broadside:    30.0, crew:   46.0 },
+    Historical { name: "HMS Dreadnought",    year: 1905, kind: "Battleship",    d: 18120.0, shp:  23000.0, belt: 10.0, broadside:  6800.0, crew:  773.0 },
+    Historical { name: "USS South Carolina", year: 1906, kind: "Battleship",    d: 16000.0, shp:  16500.0, belt: 12.0, broadside:  6960.0, crew:  869.0 },
+    Historical { name: "HMS Invincible",     year: 1906, kind: "Battlecruiser", d: 17250.0, shp:  41000.0, belt:  6.0, broadside:  6800.0, crew:  784.0 },
+    Historical { name: "SMS Nassau",         year: 1907, kind: "Battleship",    d: 18870.0, shp:  22000.0, belt: 12.0, broadside:  5330.0, crew: 1008.0 },
+    Historical { name: "USS Nevada",         year: 1912, kind: "Battleship",    d: 27500.0, shp:  26500.0, belt: 15.0, broadside: 14000.0, crew:  864.0 },
+    Historical { name: "HMS Arethusa",       year: 1912, kind: "Cruiser",       d:  3520.0, shp:  40000.0, belt:  8.0, broadside:   324.0, crew:  270.0 },
+    Historical { name: "HMS Queen Elizabeth",year: 1912, kind: "Battleship",    d: 29150.0, shp:  75000.0, belt: 13.0, broadside: 15360.0, crew: 1016.0 },
+    Historical { name: "HMS Hood",           year: 1916, kind: "Battlecruiser", d: 42670.0, shp: 144000.0, belt:  9.0, broadside: 15360.0, crew: 1433.0 },
+    Historical { name: "IJN Nagato",         year: 1917, kind: "Battleship",    d: 32720.0, shp:  80000.0, belt: 12.0, broadside: 17640.0, crew: 1333.0 },
+    Historical { name: "USS Omaha",          year: 1918, kind: "Cruiser",       d:  7050.0, shp:  90000.0, belt:  5.0, broadside:   840.0, crew:  458.0 },
+    Historical { name: "HMS Kent",           year: 1924, kind: "Cruiser",       d:  9850.0, shp:  80000.0, belt:  1.0, broadside:  2048.0, crew:  685.0 },
+    Historical { name: "HMS Afridi",         year: 1936, kind: "Destroyer",     d:  1850.0, shp:  44000.0, belt:  0.0, broadside:   400.0, crew:  190.0 },
+    Historical { name: "Bismarck",           year: 1936, kind: "Battleship",    d: 45950.0, shp: 150170.0, belt: 15.0, broadside: 14110.0, crew: 2065.0 },
+    Historical { name: "USS Iowa",           year: 1940, kind: "Battleship",    d: 48425.0, shp: 212000.0, belt:  9.0, broadside: 24300.0, crew: 1921.0 },
+    Historical { name: "USS Fletcher",       year: 1941, kind: "Destroyer",     d:  2500.0, shp:  60000.0, belt:  0.0, broadside:   275.0, crew:  273.0 },
+    Historical { name: "USS Cleveland",      year: 1940, kind: "Cruiser",       d: 11740.0, shp: 100000.0, belt:  5.0, broadside:  1560.0, crew: 1255.0 },
+];
+
+// RatioCheck {{{1
+/// How one design ratio compares with comparable historical ships.
+///
+#[derive(Serialize, Clone, Debug)]
+pub struct RatioCheck {
+    /// Name of the ratio.
+    pub name: &'static str,
+    /// Path to the Ship field that most affects the ratio.
+    #[serde(skip)]
+    pub field: &'static str,
+    /// Value for the design.
+    pub value: f64,
+    /// Lowest value among comparable ships.
+    pub min: f64,
+    /// Highest value among comparable ships.
+    pub max: f64,
+    /// Relative distance outside of min..max (0.0 if inside).
+    pub divergence: f64,
+}
+
+impl RatioCheck { // {{{2
+    // new {{{3
+    /// Compare a value against a set of historical values.
+    ///
+    fn new(name: &'static str, field: &'static str, value: f64, hist: &[f64]) -> RatioCheck {
+        let min = hist.iter().cloned().fold(f64::INFINITY, f64::min);
+        let max = hist.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        let divergence =
+                 if value > max { Ratios::rel_diff(value, max) }
+            else if value < min { Ratios::rel_diff(value, min) }
+            else                { 0.0 };
+
+        RatioCheck { name, field, value, min, max, divergence }
+    }
+}
+
+// Analogue {{{1
+/// A historical ship similar to the design.
+///
+#[derive(Serialize, Clone, Debug)]
+pub struct Analogue {
+    /// Name of ship.
+    pub name: &'static str,
+    /// Year ship laid down.
+    pub year: u32,
+    /// Type of ship.
+    pub kind: &'static str,
+    /// Distance from the design (0.0 = identical).
+    pub distance: f64,
+}
+
+// Plausibility {{{1
+/// Comparison of a design with real ships of similar year and type.
+///
+#[derive(Serialize, Clone, Debug)]
+pub struct Plausibility {
+    /// Per-ratio comparisons.
+    pub checks: Vec<RatioCheck>,
+    /// Overall plausibility (1.0 = within historical practice, 0.0 = far outside).
+    pub score: f64,
+    /// Closest historical ships, nearest first.
+    pub analogues: Vec<Analogue>,
+}
+
+impl Plausibility { // {{{2
+    /// Years either side of the design year to search for comparable ships.
+    const YEAR_WINDOW: u32 = 15;
+    /// Number of analogues to list.
+    const ANALOGUES: usize = 3;
+    /// Divergence beyond which a ratio is outside historical practice.
+    pub const DIVERGENCE_MAX: f64 = 0.25;
+
+    // new {{{3
+    /// Compare design ratios against the historical dataset.
+    ///
+    /// Ships of the same type laid down within YEAR_WINDOW years are
+    /// used when available, falling back to any type in the window and
+    /// finally the whole dataset.
+    ///
+    pub fn new(year: u32, kind: &str, ratios: &Ratios) -> Plausibility {
+        let kind = kind.to_lowercase();
+
+        let in_window = |s: &&Historical| s.year.abs_diff(year) <= Self::YEAR_WINDOW;
+        let same_kind = |s: &&Historical| {
+            let k = s.kind.to_lowercase();
+            !kind.is_empty() && (kind.contains(&k) || k.contains(&kind))
+        };
+
+        let mut pool: Vec<&Historical> = SHIPS.iter().filter(in_window).filter(same_kind).collect();
+        if pool.is_empty() { pool = SHIPS.iter().filter(in_window).collect(); }
+        if pool.is_empty() { pool = SHIPS.iter().collect(); }
+
+        let hist: Vec<Ratios> = pool.iter().map(|s| s.ratios()).collect();
+
+        let checks = vec![
+            RatioCheck::new("SHP per ton", "engine.vmax", ratios.shp, &hist.iter().map(|r| r.shp).collect::<Vec<f64>>()),
+            RatioCheck::new("Belt % of displacement", "armor.main", ratios.belt, &hist.iter().map(|r| r.belt).collect::<Vec<f64>>()),
+            RatioCheck::new("Broadside lbs per ton", "batteries", ratios.broadside, &hist.iter().map(|r| r.broadside).collect::<Vec<f64>>()),
+            RatioCheck::new("Crew per ton", "crew_model", ratios.crew, &hist.iter().map(|r| r.crew).collect::<Vec<f64>>()),
+        ];
+
+        let score = 1.0 - checks.iter().map(|c| c.divergence).sum::<f64>() / checks.len() as f64;
+
+        let mut analogues: Vec<Analogue> = pool.iter().zip(hist.iter())
+            .map(|(s, r)| Analogue {
+                name: s.name,
+                year: s.year,
+                kind: s.kind,
+                distance: ratios.distance(r),
+            })
+            .collect();
+        analogues.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        analogues.truncate(Self::ANALOGUES);
+
+        Plausibility { checks, score, analogues }
+    }
+}
+
+// Testing {{{2
+//
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod plausibility {
+    use super::*;
+    use crate::test_support::*;
+
+    // rel_diff {{{3
+    macro_rules! test_rel_diff {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, a, b) = $value;
+
+                    assert_eq!(expected, to_place(Ratios::rel_diff(a, b), 3));
+                }
+            )*
+        }
+    }
+
+    test_rel_diff! {
+        // name:          (diff, a, b)
+        rel_diff_zero:    (0.0, 0.0, 0.0),
+        rel_diff_same:    (0.0, 2.0, 2.0),
+        rel_diff_half:    (0.5, 1.0, 2.0),
+        rel_diff_reverse: (0.5, 2.0, 1.0),
+    }
+
+    // new {{{3
+    #[test]
+    fn historical_design_is_plausible() {
+        let ratios = Ratios::new(18120.0, 23000.0, 1812.0, 6800.0, 773.0);
+        let p = Plausibility::new(1905, "Battleship", &ratios);
+
+        assert_eq!(1.0, to_place(p.score, 3));
+        assert_eq!("HMS Dreadnought", p.analogues[0].name);
+    }
+
+    #[test]
+    fn fast_armored_small_ship_is_implausible() {
+        let ratios = Ratios::new(5000.0, 200000.0, 1500.0, 6000.0, 400.0);
+        let p = Plausibility::new(1910, "Cruiser", &ratios);
+
+        assert!(p.score < 0.7);
+        assert!(p.checks[0].divergence > 0.0);
+        assert!(p.checks[1].divergence > 0.0);
+    }
+}
@@ -0,0 +1,23 @@
+//! The types most programs using sharpie need, for a glob import:
+//!
+//! ```
+//! use sharpie::prelude::*;
+//!
+//! let ship = Ship::from_template(Template::Dreadnought1910);
+//! assert_eq!(MountType::ClosedBarbette, ship.batteries[0].mount_kind);
+//! ```
+//!
+//! Items are only ever added to the prelude between minor versions.
+//! Everything here is also exported from the crate root, along with the
+//! less common types of the ship's parts.
+
+pub use crate::{Ship, Template, ShipKind, FileFormat};
+pub use crate::{ReportOptions, ReportFormat, ReportSection};
+pub use crate::{DesignIssue, Severity};
+pub use crate::{Units, Tons, Choice};
+
+pub use crate::{Hull, Armor, Engine, Battery, Torpedoes};
+pub use crate::{GunType, MountType, GunLayoutType, GunDistributionType};
+pub use crate::{FuelType, BoilerType, DriveType};
+
+pub use crate::{ScaleOptions, FitTargets, Condition};
@@ -0,0 +1,387 @@
+use crate::{Ship, Fleet};
+
+use serde::{Serialize, Deserialize};
+use serde_json::{json, Value};
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+
+// RefitFilter {{{1
+/// Ships a refit is made to. A field that is not given matches every
+/// ship.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct RefitFilter {
+    /// Country of the ships, ignoring case.
+    pub country: Option<String>,
+    /// Text the ships' type contains, ignoring case, such as "battleship".
+    pub kind: Option<String>,
+    /// Earliest year the ships were laid down.
+    pub year_min: Option<u32>,
+    /// Latest year the ships were laid down.
+    pub year_max: Option<u32>,
+}
+
+impl RefitFilter { // {{{2
+    // matches {{{3
+    /// True if the refit is made to the ship.
+    ///
+    pub fn matches(&self, ship: &Ship) -> bool {
+        self.country.as_ref().is_none_or(|c| c.eq_ignore_ascii_case(&ship.country)) &&
+        self.kind.as_ref().is_none_or(|k| ship.kind.to_lowercase().contains(&k.to_lowercase())) &&
+        self.year_min.is_none_or(|y| ship.year >= y) &&
+        self.year_max.is_none_or(|y| ship.year <= y)
+    }
+}
+
+// Refit {{{1
+/// A modernization made to every ship of a fleet that matches a filter,
+/// such as a new anti-aircraft outfit, radar or torpedo bulges.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct Refit {
+    /// Name of the refit.
+    pub name: String,
+    /// Ships the refit is made to.
+    pub filter: RefitFilter,
+    /// Fields to set at key paths, such as `batteries[4].num` for a new
+    /// anti-aircraft battery.
+    pub set: BTreeMap<String, Value>,
+    /// Amounts to add to the numbers at key paths, such as `wgts.above`
+    /// for the weight of radar.
+    pub add: BTreeMap<String, f64>,
+    /// Width (ft) the torpedo bulges add to the beam.
+    pub bulges: f64,
+}
+
+impl Refit { // {{{2
+    // load {{{3
+    /// Load a refit profile from a JSON file.
+    ///
+    pub fn load(file: &str) -> Result<Refit, Box<dyn Error>> {
+        match serde_json::from_str(&fs::read_to_string(file)?) {
+            Ok(refit) => Ok(refit),
+            Err(e)    => Err(format!("{}: {}", file, e).into()),
+        }
+    }
+
+    // apply {{{3
+    /// Make the refit to a ship.
+    ///
+    /// The ship is left as it was if the refit cannot be made to it.
+    ///
+    pub fn apply(&self, ship: &mut Ship) -> Result<(), Box<dyn Error>> {
+        let mut refitted = ship.clone();
+
+        for (path, value) in self.set.iter() {
+            refitted.set(path, value.clone())?;
+        }
+
+        for (path, amount) in self.add.iter() {
+            let value = match refitted.get(path) {
+                Some(Value::Number(n)) if n.is_u64() && amount.fract() == 0.0 => json!(n.as_u64().unwrap_or_default() as i64 + *amount as i64),
+                Some(Value::Number(n)) => json!(n.as_f64().unwrap_or_default() + amount),
+                _ => return Err(format!("No number at {}", path).into()),
+            };
+            refitted.set(path, value)?;
+        }
+
+        if self.bulges > 0.0 {
+            let bb = f64::max(refitted.hull.bb, refitted.hull.b) + self.bulges;
+            refitted.set("hull.bb", json!(bb))?;
+        }
+
+        *ship = refitted;
+
+        Ok(())
+    }
+}
+
+// RefitResult {{{1
+/// What a refit did to one ship.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct RefitResult {
+    /// File the ship was loaded from.
+    pub file: String,
+    /// Name of the ship.
+    pub name: String,
+    /// Cost of the refit in millions of US dollars.
+    pub cost: f64,
+    /// Weight (t) added on and above the deck.
+    pub topweight: f64,
+    /// Stability before the refit.
+    pub stability_before: f64,
+    /// Stability after the refit.
+    pub stability_after: f64,
+    /// Stability warnings the refit brought on.
+    pub warnings: Vec<String>,
+}
+
+impl fmt::Display for RefitResult { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({}): ${:.2} million, {:+.0} t topweight, stability {:.2} -> {:.2}",
+            self.name, self.file, self.cost, self.topweight, self.stability_before, self.stability_after)?;
+
+        for w in self.warnings.iter() {
+            write!(f, "\n    Warning: {}", w)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl RefitResult { // {{{2
+    // topweight {{{3
+    /// Weight (t) carried on and above the deck.
+    ///
+    fn topweight(ship: &Ship) -> f64 {
+        ship.wgts.on as f64 + ship.wgts.above as f64 +
+            ship.wgt_guns() + ship.wgt_gun_mounts() +
+            ship.wgt_superstructure().to_long() + ship.wgt_equipment().to_long()
+    }
+
+    // cost {{{3
+    /// Cost (millions of US dollars) of the weight a refit adds, at the
+    /// rates `cost_dollar()` prices new construction at.
+    ///
+    /// Only weight added is paid for, as the hull structure a refit
+    /// displaces is already built.
+    ///
+    fn cost(before: &Ship, after: &Ship) -> f64 {
+        let added = |wgt: fn(&Ship) -> f64| f64::max(wgt(after) - wgt(before), 0.0);
+
+        (added(|s| s.wgt_misc().to_long() + s.wgt_armor()) * 0.00014 +
+            added(Ship::wgt_engine) * 0.00056 +
+            added(Ship::wgt_borne) * 8.0 * 0.00042) *
+            before.cost_inflation()
+    }
+
+    // warnings {{{3
+    /// Stability warnings for a ship.
+    ///
+    fn warnings(ship: &Ship) -> Vec<String> {
+        let mut warnings: Vec<String> = ship.stability_criteria().iter()
+            .filter(|c| !c.pass())
+            .map(|c| c.to_string())
+            .collect();
+
+        let (min, _) = Ship::CHECK_STABILITY;
+        if ship.stability_adj() < min {
+            warnings.push(format!("Stability is {:.2}, below {:.2}", ship.stability_adj(), min));
+        }
+        if ship.capsize_warn() {
+            warnings.push("Ship will capsize".into());
+        }
+
+        warnings
+    }
+
+    // new {{{3
+    /// Compare a ship before and after a refit.
+    ///
+    pub fn new(file: &str, before: &Ship, after: &Ship) -> RefitResult {
+        let was = Self::warnings(before);
+
+        RefitResult {
+            file: file.into(),
+            name: after.name.clone(),
+            cost: Self::cost(before, after),
+            topweight: Self::topweight(after) - Self::topweight(before),
+            stability_before: before.stability_adj(),
+            stability_after: after.stability_adj(),
+            warnings: Self::warnings(after).into_iter().filter(|w| !was.contains(w)).collect(),
+        }
+    }
+}
+
+// RefitSummary {{{1
+/// Results of a refit across a fleet.
+///
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct RefitSummary {
+    /// Name of the refit.
+    pub refit: String,
+    /// Result for each ship refitted, in fleet order.
+    pub ships: Vec<RefitResult>,
+    /// Number of ships the filter left out.
+    pub skipped: usize,
+    /// Total cost in millions of US dollars.
+    pub cost: f64,
+    /// Total weight (t) added on and above the deck.
+    pub topweight: f64,
+    /// Number of ships the refit brought stability warnings to.
+    pub unstable: usize,
+}
+
+impl fmt::Display for RefitSummary { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.refit)?;
+        for s in self.ships.iter() {
+            writeln!(f, "{}", s)?;
+        }
+        write!(f, "Refitted {} of {} ships for ${:.2} million, {:+.0} t topweight, {} with new stability warnings",
+            self.ships.len(), self.ships.len() + self.skipped, self.cost, self.topweight, self.unstable)
+    }
+}
+
+impl Fleet { // {{{1
+    // refit {{{2
+    /// Make a refit to every ship that matches its filter and summarise
+    /// what it cost and did to the ships' stability.
+    ///
+    /// No ship is changed if the refit cannot be made to all of them.
+    ///
+    pub fn refit(&mut self, refit: &Refit) -> Result<RefitSummary, Box<dyn Error>> {
+        let mut ships = self.ships.clone();
+        let mut summary = RefitSummary { refit: refit.name.clone(), ..Default::default() };
+
+        for (file, ship) in ships.iter_mut() {
+            if !refit.filter.matches(ship) {
+                summary.skipped += 1;
+                continue;
+            }
+
+            let before = ship.clone();
+            if let Err(error) = refit.apply(ship) { return Err(format!("{}: {}", file, error).into()); }
+
+            let result = RefitResult::new(file, &before, ship);
+            summary.cost += result.cost;
+            summary.topweight += result.topweight;
+            if !result.warnings.is_empty() { summary.unstable += 1; }
+            summary.ships.push(result);
+        }
+
+        self.ships = ships;
+
+        Ok(summary)
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod refit {
+    use super::*;
+    use crate::Template;
+    use crate::test_support::to_place;
+
+    fn ship(name: &str, country: &str, template: Template) -> (String, Ship) {
+        let mut ship = Ship::from_template(template);
+        ship.name = name.into();
+        ship.country = country.into();
+
+        (format!("{}.ship", name), ship)
+    }
+
+    // Test matches {{{2
+    macro_rules! test_matches {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, filter) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    (ship.country, ship.kind, ship.year) = ("USA".into(), "Battleship".into(), 1910);
+
+                    assert_eq!(expected, filter.matches(&ship));
+                }
+            )*
+        }
+    }
+
+    test_matches! {
+        // name:              (matches, filter)
+        matches_all:          (true, RefitFilter::default()),
+        matches_country:      (true, RefitFilter { country: Some("usa".into()), ..Default::default() }),
+        matches_country_not:  (false, RefitFilter { country: Some("Japan".into()), ..Default::default() }),
+        matches_kind:         (true, RefitFilter { kind: Some("battle".into()), ..Default::default() }),
+        matches_kind_not:     (false, RefitFilter { kind: Some("cruiser".into()), ..Default::default() }),
+        matches_year:         (true, RefitFilter { year_min: Some(1910), year_max: Some(1910), ..Default::default() }),
+        matches_year_min:     (false, RefitFilter { year_min: Some(1911), ..Default::default() }),
+        matches_year_max:     (false, RefitFilter { year_max: Some(1909), ..Default::default() }),
+    }
+
+    // Test apply {{{2
+    #[test]
+    fn apply() {
+        let refit: Refit = serde_json::from_value(json!({
+            "set": { "batteries[4].num": 8 },
+            "add": { "wgts.above": 20, "armor.main.thick": 1.5 },
+            "bulges": 10.0,
+        })).unwrap();
+
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        let (above, thick, b) = (ship.wgts.above, ship.armor.main.thick, ship.hull.b);
+        refit.apply(&mut ship).unwrap();
+
+        assert_eq!(8, ship.batteries[4].num);
+        assert_eq!(above + 20, ship.wgts.above);
+        assert_eq!(thick + 1.5, ship.armor.main.thick);
+        assert_eq!(b + 10.0, ship.hull.bb);
+    }
+
+    #[test]
+    fn apply_invalid() {
+        let refit: Refit = serde_json::from_value(json!({
+            "set": { "nothing": 1 },
+            "add": { "wgts.above": 20 },
+        })).unwrap();
+
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        let above = ship.wgts.above;
+
+        assert!(refit.apply(&mut ship).is_err());
+        assert_eq!(above, ship.wgts.above);
+    }
+
+    // Test refit {{{2
+    #[test]
+    fn refit() {
+        let mut fleet = Fleet { ships: vec![
+            ship("A", "USA", Template::Dreadnought1910),
+            ship("B", "USA", Template::Destroyer1935),
+            ship("C", "Japan", Template::Dreadnought1910),
+        ]};
+        let refit = Refit {
+            name: "Radar".into(),
+            filter: RefitFilter { country: Some("USA".into()), ..Default::default() },
+            add: BTreeMap::from([("wgts.above".to_string(), 600.0)]),
+            ..Default::default()
+        };
+
+        let summary = fleet.refit(&refit).unwrap();
+
+        assert_eq!(vec!["A", "B"], summary.ships.iter().map(|s| s.name.as_str()).collect::<Vec<_>>());
+        assert_eq!(1, summary.skipped);
+        assert_eq!(1200.0, to_place(summary.topweight, 1));
+        assert!(summary.cost > 0.0);
+        assert!(summary.ships.iter().all(|s| s.stability_after < s.stability_before));
+
+        // The destroyer cannot carry 600 t more above the deck
+        assert_eq!(1, summary.unstable);
+        assert!(summary.ships[0].warnings.is_empty());
+        assert!(!summary.ships[1].warnings.is_empty());
+
+        assert_eq!(Ship::from_template(Template::Dreadnought1910).wgts.above, fleet.ships[2].1.wgts.above);
+
+        let text = summary.to_string();
+        assert!(text.starts_with("Radar\nA (A.ship): $"));
+        assert!(text.ends_with(&format!("Refitted 2 of 3 ships for ${:.2} million, +1200 t topweight, 1 with new stability warnings", summary.cost)));
+    }
+
+    #[test]
+    fn refit_invalid() {
+        let mut fleet = Fleet { ships: vec![
+            ship("A", "USA", Template::Dreadnought1910),
+        ]};
+        let refit = Refit { set: BTreeMap::from([("nothing".to_string(), json!(1))]), ..Default::default() };
+
+        assert!(fleet.refit(&refit).unwrap_err().to_string().starts_with("A.ship: "));
+    }
+}
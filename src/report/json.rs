@@ -0,0 +1,390 @@
+use crate::{Ship, Condition, Notes, ReportOptions, ReportFormat, check_json_version, REPORT_JSON_VERSION};
+use crate::armor;
+use crate::units::{self, Units::*, UnitType::*, metric};
+
+use serde_json::{json, Value};
+
+use std::error::Error;
+
+// qty {{{1
+/// Build a JSON value for a quantity in both imperial and metric units.
+///
+fn qty(val: f64, unit_type: units::UnitType, units: units::Units) -> Value {
+    json!({
+        "value": val,
+        "unit": unit_type.abbr(Imperial),
+        "metric_unit": unit_type.abbr(Metric),
+        "metric": metric(val, unit_type, units),
+    })
+}
+
+// val {{{1
+/// Build a JSON value for a quantity with a fixed unit.
+///
+fn val(val: f64, unit: &str) -> Value {
+    json!({ "value": val, "unit": unit })
+}
+
+impl Ship { // {{{1
+    // report_with {{{2
+    /// Print report in any format.
+    ///
+    pub fn report_with(&self, options: &ReportOptions) -> Result<String, Box<dyn Error>> {
+        if options.format == ReportFormat::Json { return self.report_json_version(options.version); }
+
+        let mut s = String::new();
+        self.write_report(&mut s, options)?;
+
+        Ok(s)
+    }
+
+    // report_json {{{2
+    /// Print report as JSON.
+    ///
+    /// Contains the same values as report() without any formatting so
+    /// that other programs can consume them directly.
+    ///
+    pub fn report_json(&self) -> Result<String, Box<dyn Error>> {
+        self.report_json_version(REPORT_JSON_VERSION)
+    }
+
+    // report_json_version {{{2
+    /// Print report as JSON in an earlier schema version.
+    ///
+    /// Version 1 has the notes as a list of lines rather than the text
+    /// along with the notes metadata. Versions 1 and 2 give the value
+    /// itself as the metric value of components SpringSharp flagged as
+    /// metric.
+    ///
+    #[allow(deprecated)]
+    pub fn report_json_version(&self, version: u32) -> Result<String, Box<dyn Error>> {
+        check_json_version(version)?;
+
+        let (d, lwl, leff, cs, ws) = (self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws());
+        let legacy = |units: units::Units| if version < 3 { units } else { Imperial };
+        let hull_units = legacy(self.hull.units);
+        let armor_units = legacy(self.armor.units);
+
+        let belt = |b: &armor::Belt| json!({
+            "thick": qty(b.thick, LengthSmall, armor_units),
+            "len": qty(b.len, LengthLong, armor_units),
+            "hgt": qty(b.hgt, LengthLong, armor_units),
+            "wgt": val(b.wgt(lwl, self.cwp(), self.hull.b), "t"),
+        });
+
+        // The citadel bulkheads replace the upper belt's own
+        let mut upper = belt(&self.armor.upper);
+        upper["wgt"] = val(self.armor.upper_wgt(lwl, self.cwp(), self.hull.b), "t");
+
+        let mut report = json!({
+            "schema_version": version,
+
+            "name": self.name,
+            "country": self.country,
+            "kind": self.kind,
+            "year": self.year,
+            "engine_year": self.engine.year,
+            "ship_type": self.ship_type(),
+            "design_failures": self.design_failures(),
+            "issues": self.validate(),
+
+            "displacement": {
+                "light": val(self.d_lite().to_long(), "t"),
+                "standard": val(self.d_std().to_long(), "t"),
+                "normal": val(d, "t"),
+                "full_load": val(self.d_max().to_long(), "t"),
+            },
+
+            "conditions": Condition::ALL.iter().map(|&c| json!({
+                "condition": c.to_string(),
+                "displacement": val(self.condition_d(c).to_long(), "t"),
+                "draught": qty(self.condition_t(c), LengthLong, hull_units),
+                "cb": self.condition_cb(c),
+                "gm": qty(self.condition_gm(c), LengthLong, hull_units),
+                "speed": val(self.trial_speed(c), "kts"),
+            })).collect::<Vec<_>>(),
+
+            "dimensions": {
+                "loa": qty(self.hull.loa(), LengthLong, hull_units),
+                "lwl": qty(lwl, LengthLong, hull_units),
+                "beam": qty(self.hull.b, LengthLong, hull_units),
+                "bulges": qty(self.hull.bb, LengthLong, hull_units),
+                "draught": qty(self.hull.t, LengthLong, hull_units),
+                "draught_max": qty(self.t_max(), LengthLong, hull_units),
+            },
+
+            "batteries": self.batteries.iter().filter(|b| b.num > 0).map(|b| json!({
+                "num": b.num,
+                "diam": qty(b.diam, LengthSmall, legacy(b.units)),
+                "len": val(b.len, "cal"),
+                "kind": b.kind.to_string(),
+                "mount_kind": b.mount_kind.to_string(),
+                "training": b.training_drive().to_string(),
+                "over_length": b.over_length(),
+                "elevation_max": val(b.elevation_max(), "deg"),
+                "anti_aircraft": b.is_anti_aircraft(),
+                "aa_ceiling": qty(b.aa_ceiling(), LengthLong, Imperial),
+                "year": b.year,
+                "shell_wgt": qty(b.shell_wgt().to_pounds(), Weight, legacy(b.units)),
+                "shells": b.shells,
+                "shell_wgt_avg": qty(b.shell_wgt_avg(), Weight, legacy(b.units)),
+                "rounds": b.rounds().iter().map(|(t, n)| json!({
+                    "kind": t.to_string(),
+                    "num": n,
+                })).collect::<Vec<Value>>(),
+                "beam_req": qty(b.beam_req(), LengthLong, hull_units),
+                "arcs": {
+                    "ahead": b.arcs(self.hull.clone()).0,
+                    "broadside": b.arcs(self.hull.clone()).1,
+                    "astern": b.arcs(self.hull.clone()).2,
+                },
+                "groups": b.groups.iter().filter(|sb| sb.num_mounts() > 0).map(|sb| json!({
+                    "mounts": sb.num_mounts(),
+                    "layout": sb.layout.to_string(),
+                    "distribution": sb.distribution.desc(sb.num_mounts(), self.hull.fc_len + self.hull.fd_len),
+                    "above": sb.above,
+                    "below": sb.below,
+                    "tiers": {
+                        "deck": sb.mount_tiers().deck,
+                        "super1": sb.mount_tiers().super1,
+                        "super2": sb.mount_tiers().super2,
+                    },
+                })).collect::<Vec<Value>>(),
+            })).collect::<Vec<Value>>(),
+            "broadside_wgt": qty(self.wgt_broad(), Weight, Imperial),
+            "sea_state": self.sea_state.to_string(),
+            "sea_state_broadside_wgt": qty(self.wgt_broad_at(self.sea_state), Weight, Imperial),
+            "throw_wgt": qty(self.throw_wgt(), Weight, Imperial),
+
+            "torpedoes": self.torps.iter().filter(|t| t.num > 0).map(|t| json!({
+                "num": t.num,
+                "mounts": t.mounts,
+                "diam": qty(t.diam, LengthSmall, legacy(t.units)),
+                "len": qty(t.len, LengthLong, legacy(t.units)),
+                "mount_kind": t.mount_kind.desc(t.num, t.mounts),
+                "wgt": val(t.wgt_weaps(), "t"),
+                "reload_gear": val(t.wgt_reload_gear(), "t"),
+            })).collect::<Vec<Value>>(),
+            "torpedo_director": val(self.wgt_torp_director().to_long(), "t"),
+
+            "mines": if self.mines.num > 0 { json!({
+                "num": self.mines.num,
+                "reload": self.mines.reload,
+                "wgt": qty(self.mines.wgt, Weight, legacy(self.mines.units)),
+                "mount_kind": self.mines.mount_kind.desc(),
+                "wgt_total": val(self.mines.wgt_weaps(), "t"),
+            }) } else { Value::Null },
+
+            "asw": self.asw.iter().filter(|a| a.num > 0).map(|a| json!({
+                "num": a.num,
+                "reload": a.reload,
+                "wgt": qty(a.wgt, Weight, legacy(a.units)),
+                "kind": a.kind.desc(),
+                "wgt_total": val(a.wgt_weaps(), "t"),
+            })).collect::<Vec<Value>>(),
+
+            "superstructure": self.superstructure.iter().map(|s| json!({
+                "kind": s.kind.to_string(),
+                "wgt": val(s.wgt(d, self.year).to_long(), "t"),
+                "windage": qty(s.windage(lwl, self.hull.b, self.year), Area, Imperial),
+            })).collect::<Vec<Value>>(),
+
+            "equipment": self.equipment.iter().map(|e| json!({
+                "kind": e.kind.to_string(),
+                "wgt": val(e.wgt(d, self.year).to_long(), "t"),
+            })).collect::<Vec<Value>>(),
+
+            "armor": {
+                "main": belt(&self.armor.main),
+                "end": belt(&self.armor.end),
+                "upper": upper,
+                "citadel": self.armor.citadel.as_ref().map(|c| json!({
+                    "thick": qty(c.thick, LengthSmall, armor_units),
+                    "incline": val(c.incline, "deg"),
+                    "wgt": val(self.armor.citadel_wgt(lwl, self.cwp(), self.hull.b), "t"),
+                })),
+                "bulkhead": belt(&self.armor.bulkhead),
+                "bulge": belt(&self.armor.bulge),
+                "incline": val(self.armor.incline, "deg"),
+                "material": self.armor_material().to_string(),
+                "belt_above_water": qty(self.armor.belt_above_water(self.hull.t, self.hull.freeboard_dist()), LengthLong, Imperial),
+                "belt_coverage": val(self.armor.belt_coverage(lwl) * 100.0, "%"),
+                "deck": {
+                    "kind": self.armor.deck.kind.to_string(),
+                    "fc": qty(self.armor.deck.fc, LengthSmall, armor_units),
+                    "md": qty(self.armor.deck.md, LengthSmall, armor_units),
+                    "qd": qty(self.armor.deck.qd, LengthSmall, armor_units),
+                    "splinter": self.armor.deck.splinter.as_ref().map(|s| json!({
+                        "kind": s.kind.to_string(),
+                        "thick": qty(s.thick, LengthSmall, armor_units),
+                    })),
+                },
+                "ct_fwd": qty(self.armor.ct_fwd.thick, LengthSmall, armor_units),
+                "ct_aft": qty(self.armor.ct_aft.thick, LengthSmall, armor_units),
+                "guns": self.batteries.iter().filter(|b| b.num > 0).map(|b| json!({
+                    "face": qty(b.armor_face, LengthSmall, legacy(b.units)),
+                    "back": qty(b.armor_back, LengthSmall, legacy(b.units)),
+                    "barb": qty(b.armor_barb, LengthSmall, legacy(b.units)),
+                })).collect::<Vec<Value>>(),
+                "plates": self.armor_plates().iter().map(|p| json!({
+                    "name": p.name,
+                    "area": qty(p.area, Area, Imperial),
+                    "wgt": val(p.wgt.to_long(), "t"),
+                    "wgt_per_area": qty(p.wgt_per_area(), WeightPerArea, Imperial),
+                })).collect::<Vec<Value>>(),
+            },
+
+            "machinery": {
+                "propulsion": self.engine.propulsion().to_string(),
+                "fuel": self.engine.fuel.to_string(),
+                "boiler": self.engine.boiler.to_string(),
+                "drive": self.engine.drive.to_string(),
+                "shafts": self.engine.shafts(),
+                "hp_max": qty(self.engine.hp_max(d, lwl, leff, cs, ws), Power, Imperial),
+                "vmax": val(self.engine.vmax, "kts"),
+                "trial_speed": {
+                    "standard": val(self.trial_speed(Condition::Standard), "kts"),
+                    "normal": val(self.trial_speed(Condition::Normal), "kts"),
+                    "full": val(self.trial_speed(Condition::Full), "kts"),
+                },
+                "vcruise": val(self.engine.vcruise, "kts"),
+                "range": val(self.engine.range as f64, "nm"),
+                "bunker_max": val(self.engine.bunker_max(d, lwl, leff, cs, ws), "t"),
+                "bunker_volume": qty(self.bunker_volume(), Volume, Imperial),
+                "bunker_space": qty(self.bunker_space(), Volume, Imperial),
+                "range_max": val(self.range_max() as f64, "nm"),
+                "pct_coal": val(self.engine.pct_coal * 100.0, "%"),
+                "training_power": qty(self.training_power(), Power, Imperial),
+                "funnels": self.funnels(),
+                "uptake_area": qty(self.uptake_area(), Area, Imperial),
+            },
+
+            "complement": {
+                "min": self.crew_min(),
+                "max": self.crew_max(),
+                "model": self.crew_model.to_string(),
+                "endurance_days": self.endurance_days(),
+            },
+
+            "cost": {
+                "pounds": val(self.cost_lb(), "million"),
+                "dollars": val(self.cost_dollar(), "million"),
+            },
+
+            "weights": {
+                "armament": val(self.wgt_guns() + self.wgt_gun_mounts() + self.wgt_weaps(), "t"),
+                "guns": val(self.wgt_guns() + self.wgt_gun_mounts(), "t"),
+                "weapons": val(self.wgt_weaps(), "t"),
+                "armor": val(self.wgt_armor(), "t"),
+                "gun_armor": val(self.wgt_gun_armor(), "t"),
+                "armor_backing": val(self.wgt_armor_backing().to_long(), "t"),
+                "machinery": val(self.wgt_engine(), "t"),
+                "boilers": val(self.wgt_boilers().to_long(), "t"),
+                "engines": val(self.wgt_engines().to_long(), "t"),
+                "shafting": val(self.wgt_shafting().to_long(), "t"),
+                "hull": val(self.wgt_hull(), "t"),
+                "load": val(self.wgt_load(), "t"),
+                "stores": val(self.wgt_stores().to_long(), "t"),
+                "shells": val(self.wgt_mag_shells(), "t"),
+                "propellant": val(self.wgt_mag_propellant(), "t"),
+                "hoists": val(self.wgt_mag_hoists(), "t"),
+                "misc": val(self.wgt_misc().to_long(), "t"),
+                "superstructure": val(self.wgt_superstructure().to_long(), "t"),
+                "equipment": val(self.wgt_equipment().to_long(), "t"),
+                "void": val(self.wgt_void().to_long(), "t"),
+            },
+
+            "survivability": {
+                "flotation": qty(self.flotation(), Weight, Imperial),
+                "shell_num": self.damage_shell_num(),
+                "shell_size": qty(self.damage_shell_size(), LengthSmall, Imperial),
+                "torp_num": self.damage_torp_num(),
+                "compartments": self.compartments(),
+                "compartment_len": qty(self.compartment_len(), LengthLong, Imperial),
+                "floodable_compartments": self.floodable_compartments(),
+                "crew_quality": self.crew_quality.to_string(),
+                "crew_shell_num": self.damage_shell_num() * self.crew_quality.damage_control(),
+                "crew_torp_num": self.damage_torp_num() * self.crew_quality.damage_control(),
+                "stability": self.stability_adj(),
+                "stability_standard": self.stability_standard.to_string(),
+                "stability_criteria": self.stability_criteria(),
+                "stability_criteria_pass": self.stability_criteria_pass(),
+                "metacenter": qty(self.metacenter(), LengthLong, Imperial),
+                "kb": qty(self.kb(), LengthLong, Imperial),
+                "bm": qty(self.bm(), LengthLong, Imperial),
+                "kg": qty(self.kg(), LengthLong, Imperial),
+                "roll_period": val(self.roll_period(), "s"),
+                "steadiness": val(self.steadiness(), "%"),
+                "recoil": self.recoil(),
+                "gunnery_modifier": self.gunnery_modifier(),
+                "seakeeping": self.seakeeping(),
+                "head_seas": self.head_seas(),
+                "head_seas_desc": Ship::sea_quality(self.head_seas()),
+                "following_seas": self.following_seas(),
+                "following_seas_desc": Ship::sea_quality(self.following_seas()),
+            },
+
+            "hull_form": {
+                "freeboard_desc": self.hull.freeboard_desc(),
+                "bow_type": self.hull.bow_type.to_string(),
+                "stern_type": self.hull.stern_type.to_string(),
+                "cb": self.hull.cb(),
+                "cb_max": self.cb_max(),
+                "len2beam": self.hull.len2beam(),
+                "vn": val(self.hull.vn(), "kts"),
+                "froude": self.hull.froude(self.engine.vmax),
+                "hump": self.hull.hump(self.engine.vmax),
+                "pw_max": val(self.engine.pw_max(d, lwl, cs, ws) * 100.0, "%"),
+                "trim": self.trim,
+                "bow_angle": val(self.hull.bow_angle, "deg"),
+                "stern_overhang": qty(self.hull.stern_overhang, LengthLong, hull_units),
+                "freeboard": qty(self.hull.freeboard(), LengthLong, hull_units),
+                "fc_fwd_min": qty(self.hull.fc_fwd_min(self.engine.vmax), LengthLong, hull_units),
+                "fc_fwd_margin": qty(self.hull.fc_fwd_margin(self.engine.vmax), LengthLong, hull_units),
+                "wet_fwd": self.hull.fc_fwd_margin(self.engine.vmax) < 0.0,
+            },
+
+            "space": {
+                "hull_room": val(self.hull_room() * 100.0, "%"),
+                "deck_room": val(self.deck_room() * 100.0, "%"),
+                "deck_clear": qty(self.deck_area_clear(), Area, Imperial),
+                "deck_clear_ratio": val(self.deck_area_ratio() * 100.0, "%"),
+                "deck_saturated": self.deck_saturated(),
+                "uptake_deck": qty(self.uptake_deck_area(), Area, Imperial),
+                "uptake_hull": val(self.uptake_hull_space() * 100.0, "%"),
+                "uptakes_crowded": self.uptakes_crowded(),
+                "windage": qty(self.windage(), Area, Imperial),
+                "waterplane": qty(self.wp(), Area, Imperial),
+                "d_factor": val(self.d_factor() * 100.0, "%"),
+                "wgt_struct": qty(self.wgt_struct(), WeightPerArea, Imperial),
+                "str_cross": self.str_cross(),
+                "str_long": self.str_long(),
+                "str_comp": self.str_comp(),
+                "hull_material": self.hull_material().to_string(),
+                "hull_material_factor": self.hull_material().strength_factor(self.year),
+                "comments": self.seakeeping_desc(),
+            },
+
+            "plausibility": self.plausibility(),
+
+            "conversion": match &self.conversion {
+                Some(c) => json!({
+                    "hull_fraction": c.hull_fraction,
+                    "cargo": val(self.wgt_cargo().to_long(), "t"),
+                    "spare": val(self.wgt_spare().to_long(), "t"),
+                }),
+                None => Value::Null,
+            },
+
+            "notes": Notes {
+                text: self.notes.text.iter().map(|s| self.expand(s)).collect(),
+                ss_report: Vec::new(),
+                ..self.notes.clone()
+            },
+        });
+
+        if version < 2 { report["notes"] = report["notes"]["text"].take(); }
+
+        Ok(serde_json::to_string_pretty(&report)?)
+    }
+}
@@ -0,0 +1,742 @@
+use crate::{Units, REPORT_JSON_VERSION};
+
+use std::fmt;
+
+// ReportFormat {{{1
+/// Markup used when writing the report.
+///
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum ReportFormat {
+    /// Plain text, as shown in the GUI.
+    #[default]
+    Text,
+    /// Markdown.
+    Markdown,
+    /// HTML fragment.
+    Html,
+    /// Values from report_json(). Sections do not apply.
+    Json,
+    /// BBCode for forum posts.
+    BBCode,
+}
+
+impl From<String> for ReportFormat { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for ReportFormat {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "md"       => Self::Markdown,
+            "html"     => Self::Html,
+            "json"     => Self::Json,
+            "bbcode"   => Self::BBCode,
+            "text" | _ => Self::Text,
+        }
+    }
+}
+
+impl fmt::Display for ReportFormat { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Text     => "text",
+                Self::Markdown => "md",
+                Self::Html     => "html",
+                Self::Json     => "json",
+                Self::BBCode   => "bbcode",
+            }
+        )
+    }
+}
+
+// ReportSection {{{1
+/// Sections of the text report.
+///
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ReportSection {
+    /// Name, type and design warnings.
+    Header,
+    Displacement,
+    Dimensions,
+    /// Guns, torpedoes, mines and ASW weapons.
+    Armament,
+    Armor,
+    Machinery,
+    Complement,
+    Cost,
+    /// Distribution of weights.
+    Weights,
+    /// Survivability and seakeeping.
+    Survivability,
+    /// Hull form characteristics.
+    HullForm,
+    /// Ship space, strength and comments.
+    Strength,
+    /// Custom notes and metadata.
+    Notes,
+}
+
+impl From<String> for ReportSection { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for ReportSection {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "displacement"  => Self::Displacement,
+            "dimensions"    => Self::Dimensions,
+            "armament"      => Self::Armament,
+            "armor"         => Self::Armor,
+            "machinery"     => Self::Machinery,
+            "complement"    => Self::Complement,
+            "cost"          => Self::Cost,
+            "weights"       => Self::Weights,
+            "survivability" => Self::Survivability,
+            "hull-form"     => Self::HullForm,
+            "strength"      => Self::Strength,
+            "notes"         => Self::Notes,
+            "header" | _    => Self::Header,
+        }
+    }
+}
+
+impl fmt::Display for ReportSection { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Header        => "header",
+                Self::Displacement  => "displacement",
+                Self::Dimensions    => "dimensions",
+                Self::Armament      => "armament",
+                Self::Armor         => "armor",
+                Self::Machinery     => "machinery",
+                Self::Complement    => "complement",
+                Self::Cost          => "cost",
+                Self::Weights       => "weights",
+                Self::Survivability => "survivability",
+                Self::HullForm      => "hull-form",
+                Self::Strength      => "strength",
+                Self::Notes         => "notes",
+            }
+        )
+    }
+}
+
+impl ReportSection { // {{{2
+    pub const ALL: [ReportSection; 13] = [
+        Self::Header,
+        Self::Displacement,
+        Self::Dimensions,
+        Self::Armament,
+        Self::Armor,
+        Self::Machinery,
+        Self::Complement,
+        Self::Cost,
+        Self::Weights,
+        Self::Survivability,
+        Self::HullForm,
+        Self::Strength,
+        Self::Notes,
+    ];
+
+    // affected_by {{{3
+    /// Sections of the report that depend on an input field.
+    ///
+    /// field is a path into the Ship such as `hull.lwl` or
+    /// `batteries[0].num`. Most fields change the weights and so every
+    /// section, only those with a narrower effect are listed. Notes can
+    /// show any field through a placeholder so they are always included.
+    ///
+    pub fn affected_by(field: &str) -> Vec<ReportSection> {
+        let top = field.split(['.', '[']).next().unwrap_or(field);
+
+        match top {
+            "name" | "country" | "kind" => vec![Self::Header, Self::Notes],
+            "notes"                     => vec![Self::Notes],
+            "sea_state"                 => vec![Self::Armament, Self::Notes],
+            "crew_quality"              => vec![Self::Armament, Self::Survivability, Self::Notes],
+            "stability_standard"        => vec![Self::Survivability, Self::Notes],
+            _                           => Self::ALL.to_vec(),
+        }
+    }
+
+    // showing {{{3
+    /// Section of the report that shows an input field, if any.
+    ///
+    /// field is a path into the Ship as for affected_by().
+    ///
+    pub fn showing(field: &str) -> Option<ReportSection> {
+        let mut path = field.split(['.', '[']);
+        let top = path.next().unwrap_or(field);
+
+        match top {
+            "name" | "country" | "kind" | "year"  => Some(Self::Header),
+            "hull" => match path.next() {
+                Some("d")                         => Some(Self::Displacement),
+                Some("lwl" | "loa" | "b" | "bb" | "t") => Some(Self::Dimensions),
+                None                              => Some(Self::Strength),
+                _                                 => Some(Self::HullForm),
+            },
+            "batteries" | "torps" | "mines" | "asw" |
+            "sea_state"                           => Some(Self::Armament),
+            "armor"                               => Some(Self::Armor),
+            "engine"                              => Some(Self::Machinery),
+            "conversion" | "wgts" | "endurance"   => Some(Self::Weights),
+            "compartments" | "stability_standard" |
+            "crew_quality"                        => Some(Self::Survivability),
+            "notes"                               => Some(Self::Notes),
+            _                                     => None,
+        }
+    }
+
+    // affected_by_any {{{3
+    /// Sections of the report that depend on any of the input fields.
+    ///
+    pub fn affected_by_any(fields: &[&str]) -> Vec<ReportSection> {
+        Self::ALL.into_iter()
+            .filter(|s| fields.iter().any(|f| Self::affected_by(f).contains(s)))
+            .collect()
+    }
+}
+
+// ReportOptions {{{1
+/// What to include in the text report.
+///
+#[derive(Clone, Debug)]
+pub struct ReportOptions {
+    /// Include cautions from validate(). Design failures are always shown.
+    pub warnings: bool,
+    /// Include the custom notes and metadata.
+    pub notes: bool,
+    /// Markup to write the report in.
+    pub format: ReportFormat,
+    /// Sections to include. All sections are included if empty.
+    pub sections: Vec<ReportSection>,
+    /// Only show values in these units. Both are shown if not set.
+    pub units: Option<Units>,
+    /// Schema version of JSON reports.
+    pub version: u32,
+    /// Designer named at the foot of the report.
+    pub designer: Option<String>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        ReportOptions {
+            warnings: true, notes: true, format: ReportFormat::default(), sections: Vec::new(), units: None,
+            version: REPORT_JSON_VERSION, designer: None,
+        }
+    }
+}
+
+// ReportWriter {{{1
+/// Write report lines to a fmt::Write, separated by newlines.
+///
+struct ReportWriter<'a, W: fmt::Write> {
+    out: &'a mut W,
+    first: bool,
+    format: ReportFormat,
+    sections: &'a [ReportSection],
+    section: ReportSection,
+    heading: bool,
+}
+
+impl<W: fmt::Write> ReportWriter<'_, W> {
+    // section {{{2
+    /// Start a new section. The first line of the section
+    /// is its heading unless the section has none.
+    ///
+    fn section(&mut self, section: ReportSection) {
+        self.section = section;
+        self.heading = section != ReportSection::Notes;
+    }
+
+    // line {{{2
+    /// Write one line of the report.
+    ///
+    fn line(&mut self, args: fmt::Arguments) -> fmt::Result {
+        if !self.sections.is_empty() && !self.sections.contains(&self.section) { return Ok(()); }
+
+        if !self.first { self.out.write_char('\n')?; }
+        self.first = false;
+
+        if self.format == ReportFormat::Text { return self.out.write_fmt(args); }
+
+        let line = args.to_string();
+        let heading = self.heading && !line.is_empty();
+        if heading { self.heading = false; }
+
+        let text = line.trim_start();
+        let indent = line.len() - text.len();
+        let title = self.section == ReportSection::Header;
+
+        match self.format {
+            ReportFormat::Markdown if heading =>
+                write!(self.out, "{} {}", if title { "#" } else { "##" }, text),
+            ReportFormat::Markdown if text.is_empty() => Ok(()),
+            ReportFormat::Markdown =>
+                write!(self.out, "{}{}  ", "&nbsp;".repeat(indent), text),
+
+            ReportFormat::Html if heading =>
+                write!(self.out, "<{0}>{1}</{0}>", if title { "h1" } else { "h2" }, html_escape(text)),
+            ReportFormat::Html if text.is_empty() => Ok(()),
+            ReportFormat::Html =>
+                write!(self.out, "{}{}<br>", "&nbsp;".repeat(indent), html_escape(text)),
+
+            ReportFormat::BBCode if heading =>
+                write!(self.out, "[b]{}[/b]", text),
+            ReportFormat::BBCode => self.out.write_str(&line),
+
+            ReportFormat::Text | ReportFormat::Json => self.out.write_str(&line),
+        }
+    }
+}
+
+// html_escape {{{1
+/// Escape characters that have a meaning in HTML.
+///
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+// addto {{{1
+/// Pass arguments to format_args!() and write them as a line of the report.
+///
+macro_rules! addto {
+    ($r:ident,$($tts:tt)*) => {
+        $r.line(format_args!($($tts)*))?
+    };
+    ($r:ident) => {
+        $r.line(format_args!(""))?
+    };
+}
+
+// addif {{{1
+/// Return a formatted string if the condition is true.
+/// Otherwise return an empty string.
+///
+macro_rules! addif {
+    ($cond:expr, $($tts:tt)*) => {
+        if $cond {
+            format!($($tts)*)
+        } else {
+            "".into()
+        }
+    }
+}
+
+// dual {{{1
+/// Format a value in imperial and metric units, or only in the
+/// units the report is limited to.
+///
+macro_rules! dual {
+    ($units:expr, ($($imperial:tt)*), ($($metric:tt)*)) => {
+        dual!($units, " / ", ($($imperial)*), ($($metric)*))
+    };
+    ($units:expr, $sep:expr, ($($imperial:tt)*), ($($metric:tt)*)) => {
+        match $units {
+            Some(Imperial) => format!($($imperial)*),
+            Some(Metric)   => format!($($metric)*),
+            None           => format!("{}{}{}", format_args!($($imperial)*), $sep, format_args!($($metric)*)),
+        }
+    };
+}
+
+// num {{{1
+/// Format a number with commas and the specified number of
+/// significant digits.
+///
+// This is a macro instead of a function to avoid having to cast
+// floats to ints or ints to floats
+macro_rules! num {
+    ($val:expr, $digits: expr) => {
+        format_num!(&*format!(",.{}", $digits), $val)
+    }
+}
+
+// plural {{{1
+/// Return an "s" is num is anything other than 1.
+///
+pub(crate) fn plural(num: u32) -> String {
+    match num { 1 => "".to_string(), _ => "s".to_string() }
+}
+
+mod text;
+mod json;
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod report {
+    use super::*;
+    use crate::{Ship, Template, AmmoMix, Condition, CrewQuality, SeaState, StabilityStandard};
+    use crate::units::Units::*;
+    use format_num::format_num;
+    use serde_json::{json, Value};
+    use crate::test_support::*;
+
+    // Test report_json {{{2
+    #[test]
+    fn report_json() {
+        let mut ship = Ship { hull: get_hull(), year: 1920, ..Default::default() };
+        ship.engine.year = 1920;
+
+        let report: Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+
+        assert_eq!(REPORT_JSON_VERSION as u64, report["schema_version"].as_u64().unwrap());
+        assert_eq!(7000.0, report["displacement"]["normal"]["value"].as_f64().unwrap());
+        assert_eq!("ft", report["dimensions"]["lwl"]["unit"].as_str().unwrap());
+        assert_eq!(152.4, to_place(report["dimensions"]["lwl"]["metric"].as_f64().unwrap(), 1));
+    }
+
+    // Test report_json_version {{{2
+    macro_rules! test_report_json_version {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (notes, version) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.year = 1910;
+                    ship.notes.text = vec!["Laid down {{year}}".into()];
+
+                    let report = ship.report_json_version(version);
+                    match notes {
+                        Some(pointer) => {
+                            let report: Value = serde_json::from_str(&report.unwrap()).unwrap();
+
+                            assert_eq!(version as u64, report["schema_version"].as_u64().unwrap());
+                            assert_eq!(Some(&json!(["Laid down 1910"])), report.pointer(pointer));
+                        },
+                        None => assert!(report.is_err()),
+                    }
+                }
+            )*
+        }
+    }
+
+    test_report_json_version! {
+        // name:                    (notes, version)
+        report_json_version_1:      (Some("/notes"), 1),
+        report_json_version_2:      (Some("/notes/text"), 2),
+        report_json_version_3:      (Some("/notes/text"), 3),
+        report_json_version_0:      (None::<&str>, 0),
+        report_json_version_next:   (None::<&str>, REPORT_JSON_VERSION + 1),
+    }
+
+    // Test to_json_versioned {{{2
+    macro_rules! test_to_json_versioned {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (ok, output, version) = $value;
+
+                    let json = crate::to_json_versioned(&output, version);
+                    match ok {
+                        true => {
+                            let json: Value = serde_json::from_str(&json.unwrap()).unwrap();
+
+                            assert_eq!(version as u64, json["schema_version"].as_u64().unwrap());
+                            assert_eq!(output["files"], json["files"]);
+                        },
+                        false => assert!(json.is_err()),
+                    }
+                }
+            )*
+        }
+    }
+
+    test_to_json_versioned! {
+        // name:                    (ok, output, version)
+        to_json_versioned_current:  (true, json!({ "files": ["a.ship"] }), REPORT_JSON_VERSION),
+        to_json_versioned_previous: (true, json!({ "files": ["a.ship"] }), REPORT_JSON_VERSION - 1),
+        to_json_versioned_next:     (false, json!({ "files": ["a.ship"] }), REPORT_JSON_VERSION + 1),
+        to_json_versioned_array:    (false, json!(["a.ship"]), REPORT_JSON_VERSION),
+    }
+
+    // Test report_json_metric {{{2
+    macro_rules! test_report_json_metric {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                #[allow(deprecated)]
+                fn $name() {
+                    let (expected, version, units) = $value;
+
+                    let mut ship = Ship { hull: get_hull(), year: 1920, ..Default::default() };
+                    ship.engine.year = 1920;
+                    ship.hull.units = units;
+
+                    let report: Value = serde_json::from_str(&ship.report_json_version(version).unwrap()).unwrap();
+
+                    assert_eq!(500.0, report["dimensions"]["lwl"]["value"].as_f64().unwrap());
+                    assert_eq!(expected, to_place(report["dimensions"]["lwl"]["metric"].as_f64().unwrap(), 1));
+                }
+            )*
+        }
+    }
+
+    test_report_json_metric! {
+        // name:                        (lwl metric, version, hull units)
+        report_json_metric_2_imperial:  (152.4, 2, Imperial),
+        report_json_metric_2_metric:    (500.0, 2, Metric),
+        report_json_metric_3_imperial:  (152.4, 3, Imperial),
+        report_json_metric_3_metric:    (152.4, 3, Metric),
+    }
+
+    // Test ammo {{{2
+    macro_rules! test_report_ammo {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (safety, year) = $value;
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.year = year;
+                    ship.batteries[0].shells = 100;
+                    ship.batteries[0].ammo = Some(AmmoMix { ap: 60.0, he: 40.0, ..Default::default() });
+
+                    let report = ship.report();
+
+                    assert!(report.contains("60 AP, 40 HE per gun"));
+                    assert_eq!(safety, report.contains("Magazine safety"));
+                }
+            )*
+        }
+    }
+
+    test_report_ammo! {
+        // name:              (safety note, year)
+        report_ammo_1910:     (true, 1910),
+        report_ammo_1918:     (false, 1918),
+    }
+
+    // Test write_report {{{2
+    #[test]
+    fn write_report() {
+        let mut ship = Ship::from_template(Template::CentralBattery1870);
+        ship.notes.text = vec!["Line one".into()];
+
+        let mut all = String::new();
+        ship.write_report(&mut all, &ReportOptions::default()).unwrap();
+
+        let mut bare = String::new();
+        ship.write_report(&mut bare, &ReportOptions { warnings: false, notes: false, ..Default::default() }).unwrap();
+
+        assert_eq!(ship.report(), all);
+        assert!(all.contains("Caution: ") && all.ends_with("Line one"));
+        assert!(!bare.contains("Caution: ") && !bare.contains("Line one"));
+    }
+
+    // Test designer {{{2
+    #[test]
+    fn designer() {
+        let mut ship = Ship::from_template(Template::CentralBattery1870);
+        let options = ReportOptions { designer: Some("E. J. Reed".into()), ..Default::default() };
+
+        assert!(ship.report_with(&options).unwrap().ends_with("\n\nDesigned by E. J. Reed"));
+
+        ship.notes.text = vec!["Line one".into()];
+        assert!(ship.report_with(&options).unwrap().ends_with("Line one\n\nDesigned by E. J. Reed"));
+        assert!(!ship.report().contains("Designed by"));
+    }
+
+    // Test report_with {{{2
+    macro_rules! test_report_with {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((starts, contains), format, sections) = $value;
+
+                    let ship = Ship::from_template(Template::Dreadnought1910);
+                    let options = ReportOptions { format, sections, ..Default::default() };
+                    let report = ship.report_with(&options).unwrap();
+
+                    assert!(report.starts_with(starts), "{}", report);
+                    assert!(report.contains(contains), "{}", report);
+                    assert!(options.sections.is_empty() || !report.contains("Machinery"));
+                }
+            )*
+        }
+    }
+
+    test_report_with! {
+        // name:                 ((starts with, contains), format, sections)
+        report_with_text:        (("Cost:\n    £", "million"), ReportFormat::Text, vec![ReportSection::Cost]),
+        report_with_md:          (("## Cost:\n&nbsp;&nbsp;&nbsp;&nbsp;£", "million  "), ReportFormat::Markdown, vec![ReportSection::Cost]),
+        report_with_md_title:    (("# ", "\n## Cost:"), ReportFormat::Markdown, vec![ReportSection::Header, ReportSection::Cost]),
+        report_with_html:        (("<h2>Cost:</h2>\n&nbsp;", "million<br>"), ReportFormat::Html, vec![ReportSection::Cost]),
+        report_with_bbcode:      (("[b]Cost:[/b]\n    £", "million"), ReportFormat::BBCode, vec![ReportSection::Cost]),
+        report_with_json:        (("{", "\"cost\""), ReportFormat::Json, vec![]),
+        report_with_all:         (("", "Machinery:"), ReportFormat::Text, vec![]),
+    }
+
+    // Test showing {{{2
+    macro_rules! test_showing {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, field) = $value;
+
+                    assert_eq!(expected, ReportSection::showing(field));
+                }
+            )*
+        }
+    }
+
+    test_showing! {
+        // name:                (section, field)
+        showing_name:           (Some(ReportSection::Header), "name"),
+        showing_d:              (Some(ReportSection::Displacement), "hull.d"),
+        showing_beam:           (Some(ReportSection::Dimensions), "hull.b"),
+        showing_freeboard:      (Some(ReportSection::HullForm), "hull.fc_fwd"),
+        showing_hull:           (Some(ReportSection::Strength), "hull"),
+        showing_battery:        (Some(ReportSection::Armament), "batteries[0].diam"),
+        showing_armor:          (Some(ReportSection::Armor), "armor.main.thick"),
+        showing_engine:         (Some(ReportSection::Machinery), "engine.range"),
+        showing_compartments:   (Some(ReportSection::Survivability), "compartments"),
+        showing_endurance:      (Some(ReportSection::Weights), "endurance"),
+        showing_none:           (None, ""),
+    }
+
+    // Test affected_by {{{2
+    macro_rules! test_affected_by {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, field, change): (Vec<ReportSection>, &str, fn(&mut Ship)) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.notes.text.push(format!("{{{{{}}}}}", field));
+                    let mut changed = ship.clone();
+                    change(&mut changed);
+
+                    assert_eq!(expected, ReportSection::affected_by(field));
+
+                    // A note showing the field changes with it
+                    let notes = ReportOptions { sections: vec![ReportSection::Notes], ..Default::default() };
+                    assert_ne!(ship.report_with(&notes).unwrap(), changed.report_with(&notes).unwrap());
+
+                    // Sections that are not affected must not change
+                    let options = ReportOptions {
+                        sections: ReportSection::ALL.into_iter().filter(|s| !expected.contains(s)).collect(),
+                        ..Default::default()
+                    };
+                    if !options.sections.is_empty() {
+                        assert_eq!(ship.report_with(&options).unwrap(), changed.report_with(&options).unwrap());
+                    }
+                }
+            )*
+        }
+    }
+
+    test_affected_by! {
+        // name:                        (sections, field, change)
+        affected_by_name:               (vec![ReportSection::Header, ReportSection::Notes], "name", |s| s.name = "Other".into()),
+        affected_by_notes:              (vec![ReportSection::Notes], "notes.text", |s| s.notes.text.push("Note".into())),
+        affected_by_sea_state:          (vec![ReportSection::Armament, ReportSection::Notes], "sea_state", |s| s.sea_state = SeaState::Heavy),
+        affected_by_crew_quality:       (vec![ReportSection::Armament, ReportSection::Survivability, ReportSection::Notes], "crew_quality",
+                                            |s| s.crew_quality = CrewQuality::Elite),
+        affected_by_stability_standard: (vec![ReportSection::Survivability, ReportSection::Notes], "stability_standard",
+                                            |s| s.stability_standard = StabilityStandard::Both),
+        affected_by_hull:               (ReportSection::ALL.to_vec(), "hull.lwl", |s| { s.hull.set_lwl(600.0); }),
+        affected_by_battery:            (ReportSection::ALL.to_vec(), "batteries[0].num", |s| s.batteries[0].num += 2),
+    }
+
+    #[test]
+    fn affected_by_any() {
+        assert_eq!(vec![ReportSection::Header, ReportSection::Survivability, ReportSection::Notes],
+            ReportSection::affected_by_any(&["notes", "stability_standard", "name"]));
+        assert!(ReportSection::affected_by_any(&[]).is_empty());
+    }
+
+    #[test]
+    fn report_sections() {
+        for s in ReportSection::ALL {
+            assert_eq!(s, ReportSection::from(s.to_string()));
+        }
+    }
+
+    // Test load conditions {{{2
+    #[test]
+    fn load_conditions() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let report = ship.report();
+
+        for c in Condition::ALL {
+            assert!(report.contains(&format!("    {:<18} {:>10} t  ", c.to_string(), num!(ship.condition_d(c).to_long(), 0))), "{}", c);
+        }
+
+        let json: serde_json::Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+        let conditions = json["conditions"].as_array().unwrap();
+        assert_eq!(Condition::ALL.len(), conditions.len());
+        assert_eq!("emergency overload", conditions[4]["condition"]);
+        assert_eq!(ship.hull.d().to_long(), conditions[2]["displacement"]["value"].as_f64().unwrap());
+    }
+
+    // Test report units {{{2
+    macro_rules! test_report_units {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((contains, excludes), units, ship_units) = $value;
+
+                    let ship = Ship { units: ship_units, ..Ship::from_template(Template::Dreadnought1910) };
+                    let options = ReportOptions { units, ..Default::default() };
+                    let report = ship.report_with(&options).unwrap();
+
+                    for s in contains { assert!(report.contains(s), "{}", report); }
+                    for s in excludes { assert!(!report.contains(s), "{}", report); }
+                }
+            )*
+        }
+    }
+
+    test_report_units! {
+        // name:                  ((contains, excludes), units, ship units)
+        report_units_both:        ((vec![" ft / ", " m)", " Square feet or "], Vec::<&str>::new()), None, None),
+        report_units_imperial:    ((vec![" ft", "Square feet", " lbs"], vec![" m ", " m)", " mm", " kg", "Square metres"]), Some(Imperial), None),
+        report_units_metric:      ((vec![" m ", " mm", "Square metres", " Kw"], vec![" ft", "Square feet", " lbs"]), Some(Metric), None),
+        report_units_ship:        ((vec![" m ", "305 mm", "Square metres"], vec![" ft", "Square feet", " lbs"]), None, Some(Metric)),
+        report_units_ship_asked:  ((vec![" ft", "Square feet", " lbs"], vec![" m ", " mm", "Square metres"]), Some(Imperial), Some(Metric)),
+    }
+
+    // Test notes {{{2
+    #[test]
+    fn report_notes() {
+        let mut ship = Ship { hull: get_hull(), year: 1920, ..Default::default() };
+        ship.engine.year = 1920;
+        ship.notes.text = vec!["Line one".into()];
+        ship.notes.tags = vec!["cruiser".into()];
+        ship.notes.author = Some("me".into());
+
+        assert!(ship.report().ends_with("Line one\n\nTags: cruiser\nAuthor: me"));
+    }
+
+    #[test]
+    fn report_notes_expanded() {
+        let mut ship = Ship { hull: get_hull(), year: 1920, ..Default::default() };
+        ship.engine.year = 1920;
+        ship.notes.text = vec!["Laid down {{year}}".into()];
+
+        assert!(ship.report().contains("\nLaid down 1920"));
+
+        let report: Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+        assert_eq!("Laid down 1920", report["notes"]["text"][0].as_str().unwrap());
+        assert_eq!("Laid down {{year}}", ship.notes.text[0]);
+    }
+
+}
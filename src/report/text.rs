@@ -0,0 +1,948 @@
+use crate::{Ship, Units, Condition, Severity, ReportOptions, ReportSection};
+use crate::{AmmoMix, BulkheadType, CrewModel, Equipment, GunDistributionType, MountType, Superstructure};
+use crate::armor;
+use super::{ReportWriter, plural};
+use crate::units::Units::*;
+use crate::units::metric;
+use crate::units::UnitType::*;
+
+use format_num::format_num;
+
+use std::fmt;
+
+impl Ship { // {{{1
+    // percent_calc {{{2
+    /// Return the ratio of a value to the displacement as a percentage.
+    ///
+    fn percent_calc(&self, portion: f64) -> String {
+        format!("{} tons, {:.1} %", format_num!(",.0", portion),
+            if self.hull.d().to_long() > 0.0 {
+                (portion / self.hull.d().to_long()) * 100.0
+            } else {
+                0.0
+            }
+        )
+    }
+
+    // design_failures {{{2
+    /// Reasons the design cannot be built.
+    ///
+    pub(crate) fn design_failures(&self) -> Vec<String> {
+        self.validate().into_iter()
+            .filter(|i| i.severity == Severity::Failure)
+            .map(|i| i.message)
+            .collect()
+    }
+
+    // belt_desc {{{2
+    /// Thickness, length and height columns of a belt in the report.
+    ///
+    fn belt_desc(&self, belt: &armor::Belt, u: Option<Units>) -> String {
+        format!("{}    {}    {}",
+            dual!(u, ("{}\"", num!(belt.thick, if belt.thick < 10.0 { 2 } else { 1 })),
+                ("{:.0} mm", metric(belt.thick, LengthSmall, Imperial))),
+            dual!(u, ("{:.2} ft", belt.len), ("{:.2} m", metric(belt.len, LengthLong, Imperial))),
+            dual!(u, ("{:.2} ft", belt.hgt), ("{:.2} m", metric(belt.hgt, LengthLong, Imperial))),
+        )
+    }
+
+    // freeboard_desc {{{2
+    /// A freeboard height in the report.
+    ///
+    fn freeboard_desc(&self, free: f64, u: Option<Units>) -> String {
+        dual!(u, ("{:.2} ft", free), ("{:.2} m", metric(free, LengthLong, Imperial)))
+    }
+
+    // report {{{2
+    /// Print report.
+    ///
+    pub fn report(&self) -> String {
+        let mut s = String::new();
+        self.write_report(&mut s, &ReportOptions::default())
+            .expect("writing to a String cannot fail");
+
+        s
+    }
+
+    // write_report {{{2
+    /// Write the report to out without building it in memory first.
+    ///
+    pub fn write_report(&self, out: &mut impl fmt::Write, options: &ReportOptions) -> fmt::Result {
+        let mut r = ReportWriter {
+            out,
+            first: true,
+            format: options.format,
+            sections: &options.sections,
+            section: ReportSection::Header,
+            heading: true,
+        };
+        let u = options.units.or(self.units);
+
+        // Header {{{3
+        addto!(r, "{}, {} {} laid down {}{}",
+            self.name,
+            self.country,
+            self.kind,
+            self.year,
+            addif!(self.year != self.engine.year, " (Engine {})", self.engine.year),
+        );
+        if self.ship_type() != "" {
+            addto!(r, "{}", self.ship_type());
+        }
+
+        // Warnings {{{3
+        for i in self.validate() {
+            match i.severity {
+                Severity::Failure => addto!(r, "DESIGN FAILURE: {}", i.message),
+                Severity::Warning => if options.warnings { addto!(r, "Caution: {}", i.message) },
+            }
+        }
+
+        addto!(r);
+
+        r.section(ReportSection::Displacement);
+        addto!(r, "Displacement:"); // {{{3
+        addto!(r, "    {} t light, without fuel, ammunition or stores",
+            num!(self.d_lite().to_long(), 0),
+        );
+        let w = if u.is_none() { 18 } else { 8 };
+        addto!(r, "    {:<18} {:>12}  {:>w$}  {:>5}  {:>w$}  {:>10}",
+            "Condition", "Displacement", "Draught", "Cb", "GM", "Speed",
+        );
+        for c in Condition::ALL {
+            let (t, gm) = (self.condition_t(c), self.condition_gm(c));
+            addto!(r, "    {:<18} {:>10} t  {:>w$}  {:>5.3}  {:>w$}  {:>6.2} kts",
+                c.to_string(),
+                num!(self.condition_d(c).to_long(), 0),
+                dual!(u, ("{:.2} ft", t), ("{:.2} m", metric(t, LengthLong, Imperial))),
+                self.condition_cb(c),
+                dual!(u, ("{:.2} ft", gm), ("{:.2} m", metric(gm, LengthLong, Imperial))),
+                self.trial_speed(c),
+            );
+        }
+        addto!(r);
+
+        r.section(ReportSection::Dimensions);
+        addto!(r, "Dimensions: Length (overall / waterline) x beam x draught (normal/deep)"); // {{{3
+        if u != Some(Metric) { addto!(r, "    ({:.2} ft / {:.2} ft) x {:.2} ft {}x ({:.2} / {:.2} ft)",
+            self.hull.loa(),
+            self.hull.lwl(),
+            self.hull.b,
+            addif!(self.hull.bb > self.hull.b, "(Bulges {:.2} ft) ", self.hull.bb),
+            self.hull.t,
+            self.t_max()
+        ); }
+        if u != Some(Imperial) { addto!(r, "    ({:.2} m / {:.2} m) x {:.2} m {}x ({:.2} / {:.2} m)",
+            metric(self.hull.loa(), LengthLong, Imperial),
+            metric(self.hull.lwl(), LengthLong, Imperial),
+            metric(self.hull.b, LengthLong, Imperial),
+            addif!(self.hull.bb > self.hull.b, "(Bulges {:.2} m) ", metric(self.hull.bb, LengthLong, Imperial)),
+            metric(self.hull.t, LengthLong, Imperial),
+            metric(self.t_max(), LengthLong, Imperial)
+        ); }
+        addto!(r);
+
+        r.section(ReportSection::Armament);
+        let armed = !self.is_gunless() ||
+            self.torps.iter().any(|t| t.num > 0) ||
+            self.mines.num > 0 ||
+            self.asw.iter().any(|a| a.num > 0);
+        if armed { addto!(r, "Armament:"); } // {{{3
+        for (i, b) in self.batteries.iter().enumerate() {
+            let main_gun = i == 0;
+
+            if b.num == 0 { continue; }
+            addto!(r, "    {} - {} {:.1} cal gun{} - {} shells, {} per gun",
+                b.num,
+                dual!(u, ("{:.2}\"", b.diam),
+                    ("{} mm", num!(metric(b.diam, LengthSmall, Imperial), if b.diam * 25.4 < 100.0 { 1 } else { 0 }))),
+                b.len,
+                plural(b.num),
+                dual!(u, ("{}lbs", num!(b.shell_wgt().to_pounds(), 2)), ("{}kg", num!(metric(b.shell_wgt().to_pounds(), Weight, Imperial), 2))),
+                num!(b.shells, 0),
+            );
+            addto!(r, "        {} gun{} in {} mount{}, {} Model",
+                b.kind,
+                plural(b.num),
+                b.mount_kind,
+                plural(b.num),
+                b.year
+            );
+            if b.is_high_angle() {
+                match b.is_anti_aircraft() {
+                    true  => addto!(r, "        {:.0} degrees elevation, effective AA ceiling {}",
+                        b.elevation_max(),
+                        dual!(u, ("{} ft", num!(b.aa_ceiling(), 0)), ("{} m", num!(metric(b.aa_ceiling(), LengthLong, Imperial), 0)))
+                    ),
+                    false => addto!(r, "        {:.0} degrees elevation, cannot engage aircraft", b.elevation_max()),
+                }
+            }
+            if b.ammo.is_some() {
+                addto!(r, "        {} per gun, {} average",
+                    b.rounds().iter()
+                        .map(|(t, n)| format!("{} {}", n, t))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    dual!(u, ("{}lbs", num!(b.shell_wgt_avg(), 2)), ("{}kg", num!(metric(b.shell_wgt_avg(), Weight, Imperial), 2))),
+                );
+                if self.year < AmmoMix::SAFE_CORDITE_YEAR {
+                    addto!(r, "        Magazine safety: Pre-{} cordite handling, at risk of flash fires",
+                        AmmoMix::SAFE_CORDITE_YEAR
+                    );
+                }
+            }
+            if main_gun {
+                let (ahead, broadside, astern) = b.arcs(self.hull.clone());
+                addto!(r, "        {} gun{} {} ahead / {} broadside / {} astern",
+                    ahead,
+                    plural(ahead),
+                    if ahead == 1 { "bears" } else { "bear" },
+                    broadside,
+                    astern
+                );
+            }
+
+            for (i, sb) in b.groups.iter().enumerate() {
+                let sb_super = match i {
+                    0 => sb.above < (b.mount_num - b.groups[1].above),
+                    // TODO: SpringSharp BUG. Correct line is the below commented line:
+                    // 1 => sb.above < (b.mount_num - b.groups[0].above),
+                    _ => sb.above < (2 * sb.num_mounts() - sb.above),
+                };
+
+                if sb.num_mounts() == 0 { continue; }
+                addto!(r, "        {} x {} mount{} on {}",
+                    sb.num_mounts(),
+                    sb.layout,
+                    plural(sb.num_mounts()),
+                    sb.distribution.desc(sb.num_mounts(), self.hull.fc_len + self.hull.fd_len)
+                );
+                if sb.above > 0 {
+                    addto!(r, "        {} {}raised mount{}{}",
+                        sb.above,
+                        match sb.two_mounts_up { true => "double ", false => "", },
+                        if sb.above > 1 { "s" } else if sb.distribution.super_aft() && main_gun { " aft" } else { "" },
+                        if sb_super {
+                            match sb.distribution {
+                                GunDistributionType::CenterlineEven |
+                                GunDistributionType::CenterlineFD |
+                                GunDistributionType::CenterlineAD |
+                                GunDistributionType::SidesEven |
+                                GunDistributionType::SidesFD |
+                                GunDistributionType::SidesAD => "",
+
+                                _ => match b.mount_kind {
+                                    MountType::Broadside => "",
+                                    MountType::ColesTurret => "",
+
+                                    _ => " - superfiring",
+                                    },
+                            }
+                        } else {
+                            ""
+                        }
+                    );
+                }
+
+                if sb.below > 0 {
+                    addto!(r, "        {} hull mount{} {}",
+                        sb.below,
+                        if sb.above > 1 { "s" } else if sb.distribution.super_aft() && main_gun { " aft" } else { "" },
+                        if b.mount_kind == MountType::Broadside {
+                            (match sb.lower_deck { true => "on gundeck", false => "on upperdeck", }).into()
+                        } else {
+                            format!("in {}casemate{}",
+                                addif!(sb.lower_deck, "{}", "lower "),
+                                plural(sb.below),
+                            )
+                        },
+                    );
+                }
+            }
+        }
+        if !self.is_gunless() {
+            addto!(r, "    Weight of broadside {}",
+                dual!(u, ("{} lbs", num!(self.wgt_broad(), 0)), ("{} kg", num!(metric(self.wgt_broad(), Weight, Imperial), 0))),
+            );
+        }
+        if self.sea_state.is_set() && !self.is_gunless() {
+            addto!(r, "    {} seas (gameplay aid): {}",
+                self.sea_state,
+                dual!(u, ("{} lbs", num!(self.wgt_broad_at(self.sea_state), 0)),
+                    ("{} kg", num!(metric(self.wgt_broad_at(self.sea_state), Weight, Imperial), 0))),
+            );
+        }
+        if self.crew_quality.is_set() && !self.is_gunless() {
+            addto!(r, "    {} crew (gameplay aid): {} per minute",
+                self.crew_quality,
+                dual!(u, ("{} lbs", num!(self.throw_wgt(), 0)), ("{} kg", num!(metric(self.throw_wgt(), Weight, Imperial), 0))),
+            );
+        }
+
+        // Weapons {{{3
+        for (i, torp) in self.torps.iter().enumerate() {
+            if torp.num == 0 { continue; }
+
+            addto!(r, "{} Torpedoes",
+                match i { 0 => "Main", 1 => "2nd", _ => "Other", }
+            );
+            addto!(r, "{} - {}, {} torpedo{} {:.3} t total",
+                torp.num,
+                dual!(u, ("{:.1}\"", torp.diam), ("{:.0} mm", metric(torp.diam, LengthSmall, Imperial))),
+                dual!(u, ("{:.2} ft", torp.len), ("{:.2} m", metric(torp.len, LengthLong, Imperial))),
+                match torp.num {
+                    1 => " -".to_string(),
+                    _ => format!("es - {:.3} t each,", torp.wgt_weaps() / torp.num as f64),
+                },
+                torp.wgt_weaps()
+            );
+            addto!(r, "    {}",
+                torp.mount_kind.desc(torp.num, torp.mounts)
+            );
+            if torp.wgt_reload_gear() > 0.0 {
+                addto!(r, "    Powered reload gear {:.3} t", torp.wgt_reload_gear());
+            }
+        }
+        if self.wgt_torp_director().to_long() > 0.0 {
+            addto!(r, "Torpedo director {:.1} t", self.wgt_torp_director().to_long());
+        }
+
+        if self.mines.num != 0 {
+            addto!(r, "Mines");
+            addto!(r, "{} - {} mines{} - {:.3} t total",
+                self.mines.num,
+                dual!(u, ("{:.2} lbs", self.mines.wgt), ("{:.2} kg", metric(self.mines.wgt, Weight, Imperial))),
+                addif!(self.mines.reload > 0, " + {} reloads", self.mines.reload),
+                self.mines.wgt_weaps()
+            );
+            addto!(r, "    {}",
+                self.mines.mount_kind.desc()
+            );
+        }
+
+        for (i, asw) in self.asw.iter().enumerate() {
+            if asw.num == 0 { continue; }
+
+            addto!(r, "{} DC/AS Mortars",
+                match i { 0 => "Main", 1 => "2nd", _ => "Other", }
+            );
+            addto!(r, "{} - {} {}{} - {:.3} t total",
+                asw.num,
+                dual!(u, ("{:.2} lbs", asw.wgt), ("{:.2} kg", metric(asw.wgt, Weight, Imperial))),
+                asw.kind.desc(),
+                addif!(asw.reload > 0, " + {} reloads", asw.reload),
+                asw.wgt_weaps()
+            );
+            if asw.kind.dc_desc() != "" {
+                addto!(r, "    {}",
+                    asw.kind.dc_desc()
+                );
+            }
+        }
+
+        // Armor {{{3
+        if armed { addto!(r); }
+        r.section(ReportSection::Armor);
+        addto!(r, "Armour:");
+
+        if self.armor.main.thick + self.armor.end.thick + self.armor.upper.thick + self.armor.bulkhead.thick > 0.0 {
+            addto!(r, " - Belts:    Width (max)    Length (avg)    Height (avg)");
+            if self.armor.main.thick > 0.0 {
+                addto!(r, "    Main:    {}",
+                    self.belt_desc(&self.armor.main, u)
+                );
+            }
+
+            if self.armor.end.thick > 0.0 {
+                addto!(r, "    Ends:    {}",
+                    self.belt_desc(&self.armor.end, u)
+                );
+                if self.armor.main.len + self.armor.end.len < self.hull.lwl() {
+                    let ends = self.hull.lwl() - self.armor.main.len - self.armor.end.len;
+                    addto!(r, "    {} Unarmoured ends",
+                        dual!(u, ("{:.2} ft", ends), ("{:.2} m", metric(ends, LengthLong, Imperial)))
+                    );
+                }
+            } else if self.armor.main.len < self.hull.lwl() {
+                addto!(r, "    Ends:    Unarmoured");
+            }
+
+            if self.armor.upper.thick > 0.0 {
+                addto!(r, "    Upper:    {}",
+                    self.belt_desc(&self.armor.upper, u)
+                );
+            }
+
+            if let Some(citadel) = &self.armor.citadel {
+                addto!(r, "    Upper belt closed into a citadel by {} end bulkheads{}",
+                    dual!(u, ("{}\"", num!(citadel.thick, if citadel.thick < 10.0 { 2 } else { 1 })),
+                        ("{:.0} mm", metric(citadel.thick, LengthSmall, Imperial))),
+                    if citadel.incline != 0.0 { format!(" sloped {:.2} degrees", citadel.incline) } else { "".into() },
+                );
+            }
+
+            if self.armor.main.thick > 0.0 {
+                addto!(r, "    Main Belt covers {:.0} % of normal length",
+                    self.armor.belt_coverage(self.hull.lwl())*100.0
+                );
+                if self.armor.belt_coverage(self.hull.lwl()) < self.hull_room() {
+                    addto!(r, "    Main belt does not fully cover magazines and engineering spaces");
+                }
+            }
+
+            if self.armor.incline != 0.0 {
+                addto!(r, "    Main Belt inclined {:.2} degrees (positive = in)",
+                    self.armor.incline
+                );
+            }
+
+            if self.armor.bulkhead.thick > 0.0 {
+                addto!(r);
+                addto!(r, "- Torpedo Bulkhead - {} bulkheads:",
+                    match self.armor.bh_kind {
+                        BulkheadType::Strengthened => "Strengthened structural",
+                        BulkheadType::Additional   => "Additional damage containing",
+                    }
+                );
+                addto!(r, "        {}",
+                    self.belt_desc(&self.armor.bulkhead, u)
+                );
+                addto!(r, "    Beam between torpedo bulkheads {}",
+                    dual!(u, ("{:.2} ft", self.armor.bh_beam), ("{:.2} m", metric(self.armor.bh_beam, LengthLong, Imperial)))
+                );
+                addto!(r);
+            }
+
+            if self.armor.bulge.thick > 0.0 || self.wgt_void().to_long() > 0.0 {
+                addto!(r, "- Hull {}:",
+                    if self.hull.b == self.hull.bb { "void" }
+                    else { "Bulges" }
+                );
+                addto!(r, "        {}",
+                    self.belt_desc(&self.armor.bulge, u)
+                );
+            addto!(r);
+            }
+        }
+
+        if self.wgt_gun_armor() > 0.0 {
+            addto!(r, "- Gun armour:    Face (max)    Other gunhouse (avg)    Barbette/hoist (max)");
+
+            for (i, b) in self.batteries.iter().enumerate() {
+                if b.armor_face == 0.0 &&
+                b.armor_back == 0.0 &&
+                b.armor_barb == 0.0 { continue; }
+                addto!(r, "    {}:    {}        {}            {}",
+                    match i { 0 => "Main", 1 => "2nd", 2 => "3rd", 3 => "4th", 4 => "5th", _ => "Other", },
+                    if b.armor_face == 0.0 { "-".into() } else { dual!(u, ("{}\"", num!(b.armor_face, if b.armor_face >= 10.0 { 1 } else { 2 })), ("{:.0} mm", metric(b.armor_face, LengthSmall, Imperial))) },
+                    if b.armor_back == 0.0 { "-".into() } else { dual!(u, ("{}\"", num!(b.armor_back, if b.armor_back >= 10.0 { 1 } else { 2 })), ("{:.0} mm", metric(b.armor_back, LengthSmall, Imperial))) },
+                    if b.armor_barb == 0.0 { "-".into() } else { dual!(u, ("{}\"", num!(b.armor_barb, if b.armor_barb >= 10.0 { 1 } else { 2 })), ("{:.0} mm", metric(b.armor_barb, LengthSmall, Imperial))) },
+                );
+            }
+            addto!(r);
+        }
+
+        if self.armor.deck.is_armored() {
+            addto!(r, "- {}:",
+                self.armor.deck.kind
+            );
+            // TODO: Change spelling to Fore (required to match Springsharp reports)
+            addto!(r, "    For and Aft decks: {}",
+                dual!(u, ("{:.2}\"", self.armor.deck.md), ("{:.0} mm", metric(self.armor.deck.md, LengthSmall, Imperial)))
+            );
+            // TODO: Change spelling to Quarterdeck (required to match Springsharp reports)
+            addto!(r, "    Forecastle: {}    Quarter deck: {}",
+                dual!(u, ("{:.2}\"", self.armor.deck.fc), ("{:.0} mm", metric(self.armor.deck.fc, LengthSmall, Imperial))),
+                dual!(u, ("{:.2}\"", self.armor.deck.qd), ("{:.0} mm", metric(self.armor.deck.qd, LengthSmall, Imperial)))
+            );
+            if let Some(splinter) = &self.armor.deck.splinter {
+                addto!(r, "    Splinter deck: {} ({})",
+                    dual!(u, ("{:.2}\"", splinter.thick), ("{:.0} mm", metric(splinter.thick, LengthSmall, Imperial))),
+                    splinter.kind
+                );
+            }
+            addto!(r);
+        }
+
+        if self.armor.ct_fwd.thick + self.armor.ct_aft.thick > 0.0 {
+            // TODO: Remove stray space before comma (required to match Springsharp reports)
+            addto!(r, "- Conning towers: Forward {}, Aft {}",
+                dual!(u, ("{:.2}\"", self.armor.ct_fwd.thick), ("{:.0} mm", metric(self.armor.ct_fwd.thick, LengthSmall, Imperial))),
+                dual!(u, ("{:.2}\"", self.armor.ct_aft.thick), ("{:.0} mm", metric(self.armor.ct_aft.thick, LengthSmall, Imperial)))
+            );
+            addto!(r);
+        }
+
+        let plates = self.armor_plates();
+        if !plates.is_empty() {
+            addto!(r, "- Plate schedule:    Area    Weight    Weight / area");
+            for p in plates.iter() {
+                addto!(r, "    {}:    {}    {} tons    {}",
+                    p.name,
+                    dual!(u, ("{} sq ft", num!(p.area, 0)), ("{} sq m", num!(metric(p.area, Area, Imperial), 0))),
+                    num!(p.wgt.to_long(), 0),
+                    dual!(u, ("{:.0} lbs/sq ft", p.wgt_per_area()), ("{:.0} Kg/sq metre", metric(p.wgt_per_area(), WeightPerArea, Imperial)))
+                );
+            }
+            addto!(r);
+        }
+
+        r.section(ReportSection::Machinery);
+        addto!(r, "Machinery:"); // {{{3
+        if self.engine.is_powered() {
+            addto!(r, "    {}, {}{},",
+                self.engine.fuel,
+                self.engine.boiler,
+                addif!(self.funnels() > 0, ", {} funnel{}", self.funnels(), plural(self.funnels()))
+            );
+            let hp = self.engine.hp_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws());
+            addto!(r, "    {}, {} shaft{}, {} = {:.2} kts",
+                self.engine.drive,
+                self.engine.shafts(),
+                plural(self.engine.shafts()),
+                dual!(u, ("{} {}", num!(hp, 0), self.engine.boiler.hp_type()), ("{} Kw", num!(metric(hp, Power, Imperial), 0))),
+                self.engine.vmax
+            );
+            let (fast, slow) = self.trial_band();
+            addto!(r, "    Trial speed band {:.2} - {:.2} kts ({:+.2} kts at standard, {:+.2} kts at full load)",
+                self.trial_speed(Condition::Full),
+                self.trial_speed(Condition::Standard),
+                fast,
+                slow
+            );
+            addto!(r, "    Range {}nm at {:.2} kts",
+                num!(self.engine.range, 0),
+                self.engine.vcruise
+            );
+            addto!(r, "    Bunker at max displacement = {} tons{}",
+                num!(self.engine.bunker_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()), 0),
+                if self.engine.pct_coal > 0.0 { format!(" ({:.0}% coal)", self.engine.pct_coal * 100.0) } else { "".into() }
+            );
+            let ratio = self.engine.hp_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()) / self.engine.shafts() as f64;
+
+            if ratio > 20_000.0 && self.engine.boiler.is_reciprocating()
+                { addto!(r, "    Caution: Too much power for reciprocating engines."); }
+            else if ratio > 75_000.0
+                { addto!(r, "    Caution: Too much power for number of propellor shafts."); }
+
+            if self.wgt_engine() < self.engine.d_engine(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()) / 5.0 {
+                addto!(r, "    Caution: Delicate, lightweight machinery.");
+            }
+
+        } else {
+            addto!(r, "    Immobile floating battery");
+        }
+        if self.training_power() > 0.0 {
+            addto!(r, "    Mount training: {} auxiliary power",
+                dual!(u, ("{} hp", num!(self.training_power(), 0)), ("{} Kw", num!(metric(self.training_power(), Power, Imperial), 0))),
+            );
+        }
+        addto!(r);
+
+        r.section(ReportSection::Complement);
+        addto!(r, "Complement:"); // {{{3
+        addto!(r, "    {} - {}{}",
+            self.crew_min(),
+            self.crew_max(),
+            addif!(self.crew_model != CrewModel::Classic, " ({} estimate)", self.crew_model)
+        );
+        addto!(r);
+
+        r.section(ReportSection::Cost);
+        addto!(r, "Cost:"); // {{{3
+        addto!(r, "    £{:.3} million / ${:.3} million",
+            self.cost_lb(),
+            self.cost_dollar()
+        );
+        addto!(r);
+
+        r.section(ReportSection::Weights);
+        addto!(r, "Distribution of weights at normal displacement:"); // {{{3
+        addto!(r, "    Armament: {}",
+            self.percent_calc(self.wgt_guns() + self.wgt_gun_mounts() + self.wgt_weaps()),
+        );
+
+        if self.wgt_guns() > 0.0 {
+            addto!(r, "    - Guns: {}",
+                self.percent_calc(self.wgt_guns() + self.wgt_gun_mounts()),
+            );
+        }
+
+        if self.wgt_weaps() > 0.0 {
+            addto!(r, "    - Weapons: {}",
+                self.percent_calc(self.wgt_weaps()),
+            );
+        }
+
+        if self.wgt_armor() > 0.0 {
+            addto!(r, "    Armour: {}",
+                self.percent_calc(self.wgt_armor()),
+            );
+
+            if self.armor.main.thick + self.armor.end.thick + self.armor.upper.thick > 0.0 {
+                addto!(r, "    - Belts: {}",
+                    self.percent_calc(self.armor.main.wgt(self.hull.lwl(), self.cwp(), self.hull.b) +
+                        self.armor.end.wgt(self.hull.lwl(), self.cwp(), self.hull.b) +
+                        self.armor.upper_wgt(self.hull.lwl(), self.cwp(), self.hull.b) +
+                        self.armor.citadel_wgt(self.hull.lwl(), self.cwp(), self.hull.b)),
+                );
+            }
+
+            if self.armor.bulkhead.thick > 0.0 {
+                addto!(r, "    - Torpedo bulkhead: {}",
+                    self.percent_calc(self.armor.bulkhead.wgt(self.hull.lwl(), self.cwp(), self.hull.b)),
+                );
+            }
+
+            if self.armor.bulge.wgt(self.hull.lwl(), self.cwp(), self.hull.b) > 0.0 {
+                addto!(r, "    - {}: {}",
+                    if self.hull.b == self.hull.bb { "Void" } else { "Bulges" },
+                    self.percent_calc(self.armor.bulge.wgt(self.hull.lwl(), self.cwp(), self.hull.b)),
+                );
+            }
+
+            if self.wgt_gun_armor() > 0.0 {
+                addto!(r, "    - Armament: {}",
+                    self.percent_calc(self.wgt_gun_armor()),
+                );
+            }
+
+            if self.armor.deck.is_armored() {
+                addto!(r, "    - Armour Deck: {}",
+                    self.percent_calc(self.armor.deck.wgt(self.hull.clone(), self.engine.shafts(), self.wgt_mag_room(), self.wgt_engine_est())),
+                );
+            }
+
+            if self.armor.ct_fwd.thick + self.armor.ct_aft.thick > 0.0 {
+                addto!(r, "    - Conning Tower{}: {}",
+                    if self.armor.ct_fwd.thick > 0.0 && self.armor.ct_aft.thick > 0.0 {
+                        "s"
+                    } else { "" },
+                    self.percent_calc(self.armor.ct_fwd.wgt(self.hull.d().to_long()) + self.armor.ct_aft.wgt(self.hull.d().to_long())),
+                );
+            }
+
+            if self.wgt_armor_backing().to_long() > 0.0 {
+                addto!(r, "    - Backing for {} plates: {}",
+                    self.armor_material(),
+                    self.percent_calc(self.wgt_armor_backing().to_long()),
+                );
+            }
+        }
+
+        addto!(r, "    Machinery: {}",
+            self.percent_calc(self.wgt_engine()),
+        );
+        if self.wgt_engine() > 0.0 {
+            if self.wgt_boilers().to_long() > 0.0 {
+                addto!(r, "    - Boilers: {}",
+                    self.percent_calc(self.wgt_boilers().to_long()),
+                );
+            }
+            if self.wgt_engines().to_long() > 0.0 {
+                addto!(r, "    - Engines: {}",
+                    self.percent_calc(self.wgt_engines().to_long()),
+                );
+            }
+            addto!(r, "    - Shafting & auxiliaries: {}",
+                self.percent_calc(self.wgt_shafting().to_long()),
+            );
+        }
+        addto!(r, "    Hull, fittings & equipment: {}{}",
+            self.percent_calc(self.wgt_hull()),
+            addif!(self.conversion.is_some(), "{}", " (merchant hull)"),
+        );
+        addto!(r, "    Fuel, ammunition & stores{}: {}",
+            addif!(self.wgt_cargo().to_long() > 0.0, "{}", ", cargo"),
+            self.percent_calc(self.wgt_load()),
+        );
+        if let Some(days) = self.endurance {
+            addto!(r, "    - Stores for {} days: {}",
+                days,
+                self.percent_calc(self.wgt_stores().to_long()),
+            );
+        }
+        if self.wgt_cargo().to_long() > 0.0 {
+            addto!(r, "    - Cargo: {}",
+                self.percent_calc(self.wgt_cargo().to_long()),
+            );
+        }
+
+        if self.wgt_mag() > 0.0 {
+            addto!(r, "    - Shells: {}",
+                self.percent_calc(self.wgt_mag_shells()),
+            );
+            addto!(r, "    - Propellant: {}",
+                self.percent_calc(self.wgt_mag_propellant()),
+            );
+            addto!(r, "    - Hoists & handling: {}",
+                self.percent_calc(self.wgt_mag_hoists()),
+            );
+        }
+
+        if self.wgt_misc().to_long() > 0.0 {
+            addto!(r, "    Miscellaneous weights: {}",
+                self.percent_calc(self.wgt_misc().to_long()),
+            );
+            if self.wgts.vital > 0 { addto!(r, "    - Hull below water: {} tons", 
+                    num!(self.wgts.vital, 0)
+            ); }
+            if self.wgt_void().to_long() > 0.0 {
+                addto!(r, "    - {} void weights: {} tons{}",
+                    if self.hull.bb > self.hull.b { "Bulge" } else { "Hull" },
+                    num!(self.wgt_void().to_long(), 0),
+                    if self.wgts.void.is_none() { " (estimated)" } else { "" },
+                );
+            }
+            if self.wgts.hull > 0  { addto!(r, "    - Hull above water: {:.0} tons", self.wgts.hull) };
+            if self.wgts.on > 0    { addto!(r, "    - On freeboard deck: {:.0} tons", self.wgts.on) };
+            if self.wgts.above > 0 { addto!(r, "    - Above deck: {:.0} tons", self.wgts.above) };
+            if !self.superstructure.is_empty() {
+                addto!(r, "    - Superstructure: {} tons, {} windage ({})",
+                    num!(self.wgt_superstructure().to_long(), 0),
+                    dual!(u, ("{} sq ft", num!(self.windage(), 0)), ("{} sq m", num!(metric(self.windage(), Area, Imperial), 0))),
+                    Superstructure::desc(&self.superstructure),
+                );
+            }
+            if !self.equipment.is_empty() {
+                addto!(r, "    - Equipment: {} tons ({})",
+                    num!(self.wgt_equipment().to_long(), 0),
+                    Equipment::desc(&self.equipment),
+                );
+            }
+        }
+        if self.conversion.is_some() {
+            addto!(r, "    Unused displacement: {}",
+                self.percent_calc(self.wgt_spare().to_long()),
+            );
+        }
+
+        addto!(r);
+
+        r.section(ReportSection::Survivability);
+        addto!(r, "Overall survivability and seakeeping ability:"); // {{{3
+        addto!(r, "    Survivability (Non-critical penetrating hits needed to sink ship):");
+        addto!(r, "    {} = {:.1} x {} shells or {:.1} torpedoes",
+            dual!(u, ("{:.0} lbs", self.flotation()), ("{:.0} Kg", metric(self.flotation(), Weight, Imperial))),
+            self.damage_shell_num(),
+            dual!(u, ("{:.1} \"", self.damage_shell_size()), ("{:.0} mm", metric(self.damage_shell_size(), LengthSmall, Imperial))),
+            self.damage_torp_num()
+        );
+        if self.compartments.is_some() {
+            addto!(r, "    Subdivision: {} main compartments of {}, {}",
+                self.compartments(),
+                dual!(u, ("{:.1} ft", self.compartment_len()), ("{:.1} m", metric(self.compartment_len(), LengthLong, Imperial))),
+                self.flooding_standard()
+            );
+        }
+        if self.params.is_set() {
+            addto!(r, "    House rules: {}", self.params);
+        }
+        if self.crew_quality.is_set() {
+            addto!(r, "    {} crew (gameplay aid): {:.1} shells or {:.1} torpedoes",
+                self.crew_quality,
+                self.damage_shell_num() * self.crew_quality.damage_control(),
+                self.damage_torp_num() * self.crew_quality.damage_control()
+            );
+        }
+        if self.stability_standard.springsharp() {
+            addto!(r, "    Stability (Unstable if below 1.00): {:.2}",
+                self.stability_adj()
+            );
+        }
+        if self.stability_standard.simplified() {
+            addto!(r, "    Stability (Simplified criteria): {}",
+                if self.stability_criteria_pass() { "Pass" } else { "Fail" }
+            );
+            for c in self.stability_criteria() {
+                addto!(r, "        - {}", c);
+            }
+        }
+        addto!(r, "    Metacentric height {}",
+            dual!(u, ("{:.1} ft", self.metacenter()), ("{:.1} m", metric(self.metacenter(), LengthLong, Imperial)))
+        );
+        addto!(r, "    Roll period: {:.1} seconds",
+            self.roll_period()
+        );
+        addto!(r, "    Steadiness    - As gun platform (Average = 50 %): {:.0} %",
+            self.steadiness()
+        );
+        if !self.is_gunless() {
+            addto!(r, "        - Recoil effect (Restricted arc if above 1.00): {:.2}",
+                self.recoil()
+            );
+            addto!(r, "        - Gunnery modifier (Average = 1.00): {:.2}",
+                self.gunnery_modifier()
+            );
+        }
+        addto!(r, "    Seaboat quality (Average = 1.00): {:.2}",
+            self.seakeeping()
+        );
+        addto!(r, "        - Head seas: {:.2} ({})    Following seas: {:.2} ({})",
+            self.head_seas(),
+            Ship::sea_quality(self.head_seas()),
+            self.following_seas(),
+            Ship::sea_quality(self.following_seas())
+        );
+        addto!(r);
+
+        r.section(ReportSection::HullForm);
+        addto!(r, "Hull form characteristics:"); // {{{3
+        addto!(r, "    Hull has {},",
+            self.hull.freeboard_desc()
+        );
+        addto!(r, "    {} and {}",
+            self.hull.bow_type,
+            self.hull.stern_type
+        );
+        addto!(r, "    Block coefficient (normal/deep): {:.3} / {:.3}",
+            self.hull.cb(), self.cb_max()
+        );
+        addto!(r, "    Length to Beam Ratio: {:.2} : 1",
+            self.hull.len2beam()
+        );
+        addto!(r, "    'Natural speed' for length: {:.2} kts",
+            self.hull.vn()
+        );
+        if self.engine.is_powered() {
+            addto!(r, "    Froude number at top speed: {:.2}",
+                self.hull.froude(self.engine.vmax)
+            );
+        }
+        addto!(r, "    Power going to wave formation at top speed: {:.0} %",
+            self.engine.pw_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.cs(), self.hull.ws()) * 100.0
+        );
+        addto!(r, "    Trim (Max stability = 0, Max steadiness = 100): {}",
+            self.trim
+        );
+        addto!(r, "    Bow angle (Positive = bow angles forward): {:.2} degrees",
+            self.hull.bow_angle
+        );
+        addto!(r, "    Stern overhang: {}",
+            dual!(u, ("{:.2} ft", self.hull.stern_overhang), ("{:.2} m", metric(self.hull.stern_overhang, LengthLong, Imperial)))
+        );
+        addto!(r, "    Freeboard (% = length of deck as a percentage of waterline length):"
+        );
+        addto!(r, "            Fore end, Aft end");
+        addto!(r, "    - Forecastle:    {:.2} %, {}, {}",
+            self.hull.fc_len*100.0,   self.freeboard_desc(self.hull.fc_fwd, u), self.freeboard_desc(self.hull.fc_aft, u)
+        );
+        addto!(r, "    - Forward deck:    {:.2} %, {}, {}",
+            self.hull.fd_len*100.0,   self.freeboard_desc(self.hull.fd_fwd, u), self.freeboard_desc(self.hull.fd_aft, u)
+        );
+        addto!(r, "    - Aft deck:    {:.2} %, {}, {}",
+            self.hull.ad_len()*100.0, self.freeboard_desc(self.hull.ad_fwd, u), self.freeboard_desc(self.hull.ad_aft, u)
+        );
+        addto!(r, "    - Quarter deck:    {:.2} %, {}, {}",
+            self.hull.qd_len*100.0,   self.freeboard_desc(self.hull.qd_fwd, u), self.freeboard_desc(self.hull.qd_aft, u)
+        );
+        addto!(r, "    - Average freeboard:        {}",
+            self.freeboard_desc(self.hull.freeboard(), u)
+        );
+        addto!(r, "    - Bow freeboard needed at {:.2} kts: {} ({}{})",
+            self.engine.vmax,
+            self.freeboard_desc(self.hull.fc_fwd_min(self.engine.vmax), u),
+            if self.hull.fc_fwd_margin(self.engine.vmax) < 0.0 { "short by " } else { "margin " },
+            self.freeboard_desc(self.hull.fc_fwd_margin(self.engine.vmax).abs(), u)
+        );
+        addto!(r);
+
+        r.section(ReportSection::Strength);
+        addto!(r, "Ship space, strength and comments:"); // {{{3
+        addto!(r, "    Space    - Hull below water (magazines/engines, low = better): {:.1} %",
+            self.hull_room() * 100.0
+        );
+        addto!(r, "        - Above water (accommodation/working, high = better): {:.1} %",
+            self.deck_room() * 100.0
+        );
+        addto!(r, "        - Clear deck (boats, rafts and additions): {} ({:.0} %){}",
+            dual!(u, ("{} sq ft", num!(self.deck_area_clear(), 0)), ("{} sq m", num!(metric(self.deck_area_clear(), Area, Imperial), 0))),
+            self.deck_area_ratio() * 100.0,
+            if self.deck_saturated() { ", deck is saturated" } else { "" }
+        );
+        if self.funnels() > 0 {
+            addto!(r, "        - Boiler uptakes: {} taking {} of deck and {:.1} % of hull",
+                dual!(u, ("{} sq ft", num!(self.uptake_area(), 0)), ("{} sq m", num!(metric(self.uptake_area(), Area, Imperial), 0))),
+                dual!(u, ("{} sq ft", num!(self.uptake_deck_area(), 0)), ("{} sq m", num!(metric(self.uptake_deck_area(), Area, Imperial), 0))),
+                self.uptake_hull_space() * 100.0
+            );
+        }
+        addto!(r, "    Waterplane Area: {}",
+            dual!(u, " or ", ("{} Square feet", num!(self.wp(), 0)), ("{} Square metres", num!(metric(self.wp(), Area, Imperial), 0)))
+        );
+        addto!(r, "    Displacement factor (Displacement / loading): {:.0} %",
+            self.d_factor() * 100.0
+        );
+        addto!(r, "    Structure weight / hull surface area: {}",
+            dual!(u, " or ", ("{:.0} lbs/sq ft", self.wgt_struct()), ("{:.0} Kg/sq metre", metric(self.wgt_struct(), WeightPerArea, Imperial)))
+        );
+        addto!(r, "Hull strength (Relative):");
+        addto!(r, "        - Construction: {} ({:.2} x usual for {})",
+            self.hull_material(),
+            self.hull_material().strength_factor(self.year),
+            self.year
+        );
+        addto!(r, "        - Cross-sectional: {:.2}",
+            self.str_cross()
+        );
+        addto!(r, "        - Longitudinal: {:.2}",
+            self.str_long()
+        );
+        addto!(r, "        - Overall: {:.2}",
+            self.str_comp()
+        );
+
+        if self.tender_warn() && !self.capsize_warn() {
+            addto!(r, "Caution: Poor stability - excessive risk of capsizing");
+        }
+        if self.hull_strained() {
+            addto!(r, "Caution: Hull subject to strain in open-sea");
+        }
+        addto!(r, "    {} machinery, storage, compartmentation space",
+            self.hull_room_quality()
+        );
+        addto!(r, "    {} accommodation and workspace room",
+            self.deck_room_quality()
+        );
+        for s in self.seakeeping_desc() {
+            addto!(r, "    {}", s
+            );
+        }
+        let plausibility = self.plausibility();
+        addto!(r, "    Historical plausibility {:.2} (1.00 = usual practice), nearest {}",
+            plausibility.score,
+            plausibility.analogues.iter()
+                .map(|a| format!("{} ({})", a.name, a.year))
+                .collect::<Vec<String>>()
+                .join(", ")
+        );
+
+        addto!(r);
+
+        let notes = options.notes && (!self.notes.text.is_empty() || self.notes.has_metadata());
+        if options.notes {
+            // Custom Notes {{{3
+            r.section(ReportSection::Notes);
+            for s in self.notes.text.iter() {
+                addto!(r, "{}", self.expand(s));
+            }
+
+            // Metadata {{{3
+            if self.notes.has_metadata() {
+                if !self.notes.text.is_empty() { addto!(r); }
+                for s in self.notes.metadata() {
+                    addto!(r, "{}", s);
+                }
+            }
+        }
+
+        // Designer {{{3
+        if let Some(designer) = &options.designer {
+            r.section(ReportSection::Notes);
+            if notes { addto!(r); }
+            addto!(r, "Designed by {}", designer);
+        }
+
+        Ok(())
+    }
+}
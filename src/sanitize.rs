@@ -0,0 +1,288 @@
+use crate::{Ship, DesignIssue, BowType};
+
+// Clamps {{{1
+/// Issues raised while sanitizing a ship.
+///
+#[derive(Default)]
+struct Clamps(Vec<DesignIssue>);
+
+impl Clamps { // {{{2
+    // clamp {{{3
+    /// Clamp a value into a range, noting the old and new values if it
+    /// was outside it.
+    ///
+    fn clamp(&mut self, field: &'static str, path: &str, v: &mut f64, min: f64, max: f64, why: &str) {
+        let old = *v;
+        if old.is_nan() {
+            *v = min;
+        } else if old < min || old > max {
+            *v = old.clamp(min, max);
+        } else {
+            return;
+        }
+
+        self.0.push(DesignIssue::warning("input-clamped", field,
+            format!("{} of {} {}, set to {}", path, old, why, *v)));
+    }
+
+    // not_negative {{{3
+    /// Clamp a value that cannot be negative to zero.
+    ///
+    fn not_negative(&mut self, field: &'static str, path: &str, v: &mut f64) {
+        self.clamp(field, path, v, 0.0, f64::INFINITY, "is negative");
+    }
+}
+
+impl Ship { // {{{1
+    // sanitize {{{2
+    /// Clamp inputs that a ship file can hold but that have no meaning,
+    /// and return a warning for each one changed.
+    ///
+    /// This is done when a ship is loaded or edited so that calculations
+    /// can trust the design instead of each guarding against these.
+    /// Designs that are merely impossible, such as a Block Coefficient
+    /// over one, are left for validate() to report.
+    ///
+    /// | Input                                   | Clamped to                |
+    /// |-----------------------------------------|---------------------------|
+    /// | Negative dimensions, freeboards, ram,   | 0                         |
+    /// | armour, calibres, weights or speeds     |                           |
+    /// | Beam at bulges less than the beam       | The beam                  |
+    /// | Deck section lengths outside 0 to 1     | 0 to 1                    |
+    /// | Deck section lengths summing over 1     | Scaled down to sum to 1   |
+    /// | Belt lengths longer than the waterline  | The waterline length      |
+    /// | Armour on batteries of no guns          | 0                         |
+    /// | Cruising speed over the maximum speed   | The maximum speed         |
+    /// | Share of coal outside 0 to 1            | 0 to 1                    |
+    ///
+    /// The warnings are also kept and reported by validate().
+    ///
+    pub fn sanitize(&mut self) -> Vec<DesignIssue> {
+        let mut c = Clamps::default();
+
+        // Hull {{{3
+        for (path, given) in self.hull.given_mut() {
+            if let Some(v) = given { c.not_negative(path, path, v); }
+        }
+
+        let hull = &mut self.hull;
+        for (path, v) in [
+            ("hull.b", &mut hull.b), ("hull.bb", &mut hull.bb), ("hull.t", &mut hull.t),
+            ("hull.stern_overhang", &mut hull.stern_overhang),
+            ("hull.fc_fwd", &mut hull.fc_fwd), ("hull.fc_aft", &mut hull.fc_aft),
+            ("hull.fd_fwd", &mut hull.fd_fwd), ("hull.fd_aft", &mut hull.fd_aft),
+            ("hull.ad_fwd", &mut hull.ad_fwd), ("hull.ad_aft", &mut hull.ad_aft),
+            ("hull.qd_fwd", &mut hull.qd_fwd), ("hull.qd_aft", &mut hull.qd_aft),
+        ] {
+            c.not_negative(path, path, v);
+        }
+        if let BowType::Ram(len) = &mut hull.bow_type {
+            c.not_negative("hull.bow_type", "hull.bow_type.Ram", len);
+        }
+
+        let b = hull.b;
+        c.clamp("hull.bb", "hull.bb", &mut hull.bb, b, f64::INFINITY, "is less than the beam");
+
+        for (path, v) in [("hull.fc_len", &mut hull.fc_len), ("hull.fd_len", &mut hull.fd_len), ("hull.qd_len", &mut hull.qd_len)] {
+            c.clamp(path, path, v, 0.0, 1.0, "is not a fraction of the deck");
+        }
+        let sections = hull.fc_len + hull.fd_len + hull.qd_len;
+        if sections > 1.0 {
+            for (path, v) in [("hull.fc_len", &mut hull.fc_len), ("hull.fd_len", &mut hull.fd_len), ("hull.qd_len", &mut hull.qd_len)] {
+                let scaled = *v / sections;
+                c.clamp(path, path, v, 0.0, scaled, "leaves no afterdeck");
+            }
+        }
+
+        // Armor {{{3
+        let lwl = self.hull.lwl();
+        let armor = &mut self.armor;
+        for (path, belt) in [
+            ("armor.main", &mut armor.main), ("armor.end", &mut armor.end), ("armor.upper", &mut armor.upper),
+            ("armor.bulge", &mut armor.bulge), ("armor.bulkhead", &mut armor.bulkhead),
+        ] {
+            c.not_negative(path, &format!("{}.thick", path), &mut belt.thick);
+            c.not_negative(path, &format!("{}.hgt", path), &mut belt.hgt);
+            c.clamp(path, &format!("{}.len", path), &mut belt.len, 0.0, lwl, "is not within the waterline length");
+        }
+        c.not_negative("armor.bh_beam", "armor.bh_beam", &mut armor.bh_beam);
+        if let Some(citadel) = &mut armor.citadel {
+            c.not_negative("armor.citadel", "armor.citadel.thick", &mut citadel.thick);
+        }
+
+        let deck = &mut armor.deck;
+        for (path, v) in [("armor.deck.fc", &mut deck.fc), ("armor.deck.md", &mut deck.md), ("armor.deck.qd", &mut deck.qd)] {
+            c.not_negative(path, path, v);
+        }
+        if let Some(splinter) = &mut deck.splinter {
+            c.not_negative("armor.deck.splinter", "armor.deck.splinter.thick", &mut splinter.thick);
+        }
+        for (path, given) in [("armor.deck.box_len", &mut deck.box_len), ("armor.deck.box_beam", &mut deck.box_beam)] {
+            if let Some(v) = given { c.not_negative(path, path, v); }
+        }
+        c.not_negative("armor.ct_fwd", "armor.ct_fwd.thick", &mut armor.ct_fwd.thick);
+        c.not_negative("armor.ct_aft", "armor.ct_aft.thick", &mut armor.ct_aft.thick);
+
+        // Weapons {{{3
+        for (i, btry) in self.batteries.iter_mut().enumerate() {
+            c.not_negative("batteries", &format!("batteries[{}].diam", i), &mut btry.diam);
+            c.not_negative("batteries", &format!("batteries[{}].len", i), &mut btry.len);
+
+            let max = if btry.num == 0 { 0.0 } else { f64::INFINITY };
+            for (name, v) in [("armor_face", &mut btry.armor_face), ("armor_back", &mut btry.armor_back), ("armor_barb", &mut btry.armor_barb)] {
+                let why = if *v < 0.0 { "is negative" } else { "is on a battery of no guns" };
+                c.clamp("batteries", &format!("batteries[{}].{}", i, name), v, 0.0, max, why);
+            }
+        }
+        for (i, t) in self.torps.iter_mut().enumerate() {
+            c.not_negative("torps", &format!("torps[{}].diam", i), &mut t.diam);
+            c.not_negative("torps", &format!("torps[{}].len", i), &mut t.len);
+        }
+        c.not_negative("mines.wgt", "mines.wgt", &mut self.mines.wgt);
+        for (i, a) in self.asw.iter_mut().enumerate() {
+            c.not_negative("asw", &format!("asw[{}].wgt", i), &mut a.wgt);
+        }
+
+        // Machinery {{{3
+        let engine = &mut self.engine;
+        c.not_negative("engine.vmax", "engine.vmax", &mut engine.vmax);
+        let vmax = engine.vmax;
+        let why = if engine.vcruise < 0.0 { "is negative" } else { "is over the maximum speed" };
+        c.clamp("engine.vcruise", "engine.vcruise", &mut engine.vcruise, 0.0, vmax, why);
+        c.clamp("engine.pct_coal", "engine.pct_coal", &mut engine.pct_coal, 0.0, 1.0, "is not a fraction of the bunker");
+
+        self.clamped = c.0.clone();
+        c.0
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod sanitize {
+    use super::*;
+    use crate::Template;
+    use crate::test_support::*;
+
+    fn clamped(ship: &mut Ship) -> Vec<&'static str> {
+        ship.sanitize().iter().map(|i| i.field).collect()
+    }
+
+    // Test sanitize {{{2
+    macro_rules! test_sanitize {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, edit, check) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    edit(&mut ship);
+
+                    assert_eq!(expected, clamped(&mut ship));
+                    check(&ship);
+
+                    // Sanitizing again changes nothing
+                    assert!(ship.sanitize().is_empty());
+                }
+            )*
+        }
+    }
+
+    test_sanitize! {
+        // name:                (fields, edit, check)
+        sanitize_none:          (Vec::<&str>::new(), |_: &mut Ship| (), |_: &Ship| ()),
+        sanitize_lwl:           (vec!["hull.lwl", "armor.main", "armor.end", "armor.upper"],
+                                    |s: &mut Ship| { s.hull.set_lwl(-500.0); },
+                                    |s: &Ship| assert_eq!((0.0, 0.0), (s.hull.lwl(), s.armor.main.len))),
+        sanitize_beam:          (vec!["hull.b", "hull.bb"],
+                                    |s: &mut Ship| { s.hull.b = -80.0; s.hull.bb = -80.0; },
+                                    |s: &Ship| assert_eq!((0.0, 0.0), (s.hull.b, s.hull.bb))),
+        sanitize_bulges:        (vec!["hull.bb"],
+                                    |s: &mut Ship| { s.hull.bb = s.hull.b - 5.0; },
+                                    |s: &Ship| assert_eq!(s.hull.b, s.hull.bb)),
+        sanitize_ram:           (vec!["hull.bow_type"],
+                                    |s: &mut Ship| { s.hull.bow_type = BowType::Ram(-5.0); },
+                                    |s: &Ship| assert_eq!(0.0, s.hull.bow_type.ram_len())),
+        sanitize_sections:      (vec!["hull.fc_len", "hull.fd_len", "hull.qd_len"],
+                                    |s: &mut Ship| { s.hull.fc_len = 0.5; s.hull.fd_len = 0.5; s.hull.qd_len = 0.5; },
+                                    |s: &Ship| assert_eq!(0.0, to_place(s.hull.ad_len(), 6))),
+        sanitize_section_neg:   (vec!["hull.fd_len"],
+                                    |s: &mut Ship| { s.hull.fd_len = -0.1; },
+                                    |s: &Ship| assert_eq!(0.0, s.hull.fd_len)),
+        sanitize_belt_len:      (vec!["armor.main"],
+                                    |s: &mut Ship| { s.armor.main.len = s.hull.lwl() * 1.5; },
+                                    |s: &Ship| assert_eq!(s.hull.lwl(), s.armor.main.len)),
+        sanitize_belt_thick:    (vec!["armor.end"],
+                                    |s: &mut Ship| { s.armor.end.thick = -3.0; },
+                                    |s: &Ship| assert_eq!(0.0, s.armor.end.thick)),
+        sanitize_deck:          (vec!["armor.deck.md"],
+                                    |s: &mut Ship| { s.armor.deck.md = -1.0; },
+                                    |s: &Ship| assert_eq!(0.0, s.armor.deck.md)),
+        sanitize_no_guns:       (vec!["batteries", "batteries", "batteries"],
+                                    |s: &mut Ship| { s.batteries[0].num = 0; s.batteries[0].armor_face = 11.0; s.batteries[0].armor_back = 8.0; s.batteries[0].armor_barb = 10.0; },
+                                    |s: &Ship| assert_eq!((0.0, 0.0), (s.batteries[0].armor_face, s.batteries[0].armor_barb))),
+        sanitize_calibre:       (vec!["batteries"],
+                                    |s: &mut Ship| { s.batteries[1].diam = -4.0; },
+                                    |s: &Ship| assert_eq!(0.0, s.batteries[1].diam)),
+        sanitize_cruise:        (vec!["engine.vcruise"],
+                                    |s: &mut Ship| { s.engine.vcruise = s.engine.vmax + 5.0; },
+                                    |s: &Ship| assert_eq!(s.engine.vmax, s.engine.vcruise)),
+        sanitize_coal:          (vec!["engine.pct_coal"],
+                                    |s: &mut Ship| { s.engine.pct_coal = 1.5; },
+                                    |s: &Ship| assert_eq!(1.0, s.engine.pct_coal)),
+        sanitize_nan:           (vec!["hull.t"],
+                                    |s: &mut Ship| { s.hull.t = f64::NAN; },
+                                    |s: &Ship| assert_eq!(0.0, s.hull.t)),
+    }
+
+    // Test templates {{{2
+    #[test]
+    fn sanitize_templates() {
+        for template in Template::ALL {
+            assert!(Ship::from_template(template).sanitize().is_empty(), "{}", template);
+        }
+    }
+
+    // Test fixtures {{{2
+    macro_rules! test_sanitize_fixture {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let file = $value;
+
+                    let (mut ship, _) = Ship::open_any(file.to_string()).unwrap();
+                    assert!(ship.clamped.is_empty());
+                    assert!(ship.sanitize().is_empty());
+                }
+            )*
+        }
+    }
+
+    test_sanitize_fixture! {
+        // name:                        file
+        sanitize_aa_cruiser:            "tests/fixtures/aa_cruiser_1942.ship",
+        sanitize_central_battery:       "tests/fixtures/central_battery_1870.ship",
+        sanitize_destroyer:             "tests/fixtures/destroyer_1935.ship",
+        sanitize_dreadnought:           "tests/fixtures/dreadnought_1915.ship",
+        sanitize_dreadnought_ss:        "tests/fixtures/dreadnought_1915.sship",
+        sanitize_protected_cruiser:     "tests/fixtures/protected_cruiser_1895.ship",
+    }
+
+    // Test load {{{2
+    #[test]
+    fn sanitize_on_load() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.engine.pct_coal = -0.5;
+
+        let file = std::env::temp_dir().join(format!("sharpie-sanitize-{}.ship", std::process::id()));
+        let path = file.to_string_lossy().into_owned();
+        ship.save(path.clone()).unwrap();
+        let ship = Ship::load(path).unwrap();
+        std::fs::remove_file(file).unwrap();
+
+        assert_eq!(0.0, ship.engine.pct_coal);
+        assert!(ship.validate().iter().any(|i| i.code == "input-clamped" && i.field == "engine.pct_coal"));
+    }
+}
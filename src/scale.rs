@@ -0,0 +1,120 @@
+use crate::{Ship, EquipmentKind};
+
+use std::error::Error;
+
+// ScaleOptions {{{1
+/// What to scale along with the hull.
+///
+#[derive(Clone, Debug, Default)]
+pub struct ScaleOptions {
+    /// Scale the calibre of the guns.
+    pub armament: bool,
+    /// Scale the thickness of the armor.
+    pub armor: bool,
+}
+
+impl Ship { // {{{1
+    // scaled {{{2
+    /// Geometrically similar ship scaled by factor.
+    ///
+    /// Lengths grow with the factor and fixed weights with its cube. The
+    /// top and cruising speeds are kept and the power needed for them
+    /// follows from the new hull. Gun calibres and armor thickness only
+    /// grow if the options ask for them.
+    ///
+    pub fn scaled(&self, factor: f64, options: &ScaleOptions) -> Result<Ship, Box<dyn Error>> {
+        if !(factor > 0.0 && factor.is_finite()) {
+            return Err(format!("Scale factor must be greater than zero: {}", factor).into());
+        }
+
+        let mut ship = self.clone();
+        let wgt = |w: u32| (w as f64 * factor.powi(3)).round() as u32;
+
+        ship.hull.scale(factor);
+        ship.armor.scale(factor);
+        ship.vcg.scale(factor);
+        for b in ship.batteries.iter_mut() { b.vcg = b.vcg.map(|h| h * factor); }
+
+        ship.wgts.vital = wgt(ship.wgts.vital);
+        ship.wgts.hull  = wgt(ship.wgts.hull);
+        ship.wgts.on    = wgt(ship.wgts.on);
+        ship.wgts.above = wgt(ship.wgts.above);
+        ship.wgts.void  = ship.wgts.void.map(wgt);
+
+        for s in ship.superstructure.iter_mut() {
+            s.wgt = s.wgt.map(|w| w * factor.powi(3));
+        }
+        for e in ship.equipment.iter_mut().filter(|e| e.kind == EquipmentKind::Boats) {
+            e.wgt = e.wgt.map(|w| w * factor.powi(3));
+        }
+
+        if options.armament {
+            for b in ship.batteries.iter_mut() { b.scale(factor); }
+        }
+        if options.armor {
+            ship.armor.scale_thick(factor);
+            for b in ship.batteries.iter_mut() { b.scale_armor(factor); }
+        }
+
+        ship.notes.text.push(format!("Scaled {} times from {}", factor,
+            if self.name.is_empty() { "an unnamed design" } else { &self.name }));
+
+        Ok(ship)
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod scale {
+    use super::*;
+    use crate::Template;
+    use crate::test_support::*;
+
+    // Test scaled {{{2
+    macro_rules! test_scaled {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((diam, belt), (armament, armor)) = $value;
+
+                    let ship = Ship::from_template(Template::Dreadnought1910);
+                    let scaled = ship.scaled(2.0, &ScaleOptions { armament, armor }).unwrap();
+
+                    assert_eq!(to_place(ship.hull.lwl() * 2.0, 2), to_place(scaled.hull.lwl(), 2));
+                    assert_eq!(to_place(ship.hull.b * 2.0, 2), to_place(scaled.hull.b, 2));
+                    assert_eq!(to_place(ship.hull.d().to_long() * 8.0, 0), to_place(scaled.hull.d().to_long(), 0));
+                    assert_eq!(to_place(ship.hull.cb(), 4), to_place(scaled.hull.cb(), 4));
+                    assert_eq!(to_place(ship.armor.main.len * 2.0, 2), to_place(scaled.armor.main.len, 2));
+                    assert_eq!(ship.engine.vmax, scaled.engine.vmax);
+
+                    assert_eq!(ship.batteries[0].diam * diam, scaled.batteries[0].diam);
+                    assert_eq!(ship.armor.main.thick * belt, scaled.armor.main.thick);
+                }
+            )*
+        }
+    }
+
+    test_scaled! {
+        // name:              ((diam, belt), (armament, armor))
+        scaled_hull:          ((1.0, 1.0), (false, false)),
+        scaled_armament:      ((2.0, 1.0), (true, false)),
+        scaled_armor:         ((1.0, 2.0), (false, true)),
+        scaled_all:           ((2.0, 2.0), (true, true)),
+    }
+
+    // Test scaled factor {{{2
+    #[test]
+    fn scaled_factor() {
+        let ship = Ship::from_template(Template::Destroyer1935);
+
+        assert!(ship.scaled(0.0, &ScaleOptions::default()).is_err());
+        assert!(ship.scaled(-1.0, &ScaleOptions::default()).is_err());
+        assert!(ship.scaled(f64::NAN, &ScaleOptions::default()).is_err());
+
+        let scaled = ship.scaled(1.0, &ScaleOptions::default()).unwrap();
+        assert_eq!(ship.hull.d(), scaled.hull.d());
+        assert!(scaled.notes.text.last().unwrap().starts_with("Scaled 1 times from "));
+    }
+}
@@ -0,0 +1,114 @@
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+// SeaState {{{1
+/// Sea conditions the ship is fighting in.
+///
+/// This is a gameplay aid for wargames and only affects which hull
+/// mounts count towards the broadside. Calm seas have no effect at all.
+///
+#[derive(PartialEq, PartialOrd, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum SeaState {
+    #[default]
+    Calm,
+    Light,
+    Moderate,
+    Heavy,
+}
+
+impl From<String> for SeaState { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for SeaState {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "1"     => Self::Light,
+            "2"     => Self::Moderate,
+            "3"     => Self::Heavy,
+            "0" | _ => Self::Calm,
+        }
+    }
+}
+
+impl fmt::Display for SeaState { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Calm     => "Calm",
+                Self::Light    => "Light",
+                Self::Moderate => "Moderate",
+                Self::Heavy    => "Heavy",
+            }
+        )
+    }
+}
+
+impl SeaState { // {{{2
+    // is_set {{{3
+    /// True if the sea state changes anything.
+    ///
+    pub fn is_set(&self) -> bool {
+        *self != Self::Calm
+    }
+
+    // hull_mount_limit {{{3
+    /// Lowest sea state that makes hull mounts unusable.
+    ///
+    /// Mounts with less freeboard are swamped in lighter seas and mounts
+    /// on the lower deck need more freeboard than those on the upper deck.
+    ///
+    pub fn hull_mount_limit(free: f64, lower_deck: bool) -> Self {
+        if free < 12.0 || (free < 19.0 && lower_deck) {
+            Self::Light
+        } else if free < 16.0 || (free < 24.0 && lower_deck) {
+            Self::Moderate
+        } else {
+            Self::Heavy
+        }
+    }
+
+    // limit_desc {{{3
+    /// Report text for the seas that limit hull mounts.
+    ///
+    pub fn limit_desc(&self) -> &'static str {
+        match self {
+            Self::Calm | Self::Light => "any sea",
+            Self::Moderate           => "all but light seas",
+            Self::Heavy              => "heavy seas",
+        }
+    }
+}
+
+// Testing SeaState {{{2
+#[cfg(test)]
+mod sea_state {
+    use super::*;
+
+    // Test hull_mount_limit {{{3
+    macro_rules! test_hull_mount_limit {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, free, lower_deck) = $value;
+
+                    assert_eq!(expected, SeaState::hull_mount_limit(free, lower_deck));
+                }
+            )*
+        }
+    }
+
+    test_hull_mount_limit! {
+        // name:                  (sea state, free, lower_deck)
+        limit_light:              (SeaState::Light, 11.0, false),
+        limit_light_lower:        (SeaState::Light, 18.0, true),
+        limit_moderate:           (SeaState::Moderate, 15.0, false),
+        limit_moderate_lower:     (SeaState::Moderate, 23.0, true),
+        limit_heavy:              (SeaState::Heavy, 16.0, false),
+        limit_heavy_lower:        (SeaState::Heavy, 24.0, true),
+    }
+}
@@ -0,0 +1,2602 @@
+use crate::{Hull, HullMaterial, Armor, ArmorMaterial, ArmorPlate, Engine, Battery, Torpedoes, Mines, ASW, MiscWgts};
+use crate::{Superstructure, Equipment, CrewQuality, CrewModel, SeaState, StabilityStandard, Conversion, Vcg, Params, Notes, Snapshot};
+use crate::{FuelType, BulkheadType, ReportOptions, DesignIssue, SuperstructureKind, Trim, SeaType, Tons, Units, MountType, GunDistributionType};
+use crate::armor;
+use crate::plausibility::{Plausibility, Ratios};
+use crate::validate::battery_name;
+
+use serde::{Serialize, Deserialize};
+use serde_json::Value;
+
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+
+// Ship {{{1
+/// All the parts of a ship.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Ship {
+    /// Name of ship.
+    pub name: String,
+    /// Country of ship.
+    pub country: String,
+    /// Type of ship.
+    ///
+    /// This is informative only and does not affect any calculations.
+    pub kind: String,
+    /// Year ship laid down
+    pub year: u32,
+
+    /// Balance between stability and seakeeping.
+    pub trim: Trim,
+
+    /// Hull configuration.
+    pub hull: Hull,
+    /// Armor configuration.
+    pub armor: Armor,
+    /// Engine configuration.
+    pub engine: Engine,
+    /// Gun batteries.
+    pub batteries: Vec<Battery>,
+    /// Torpedo mounts.
+    pub torps: Vec<Torpedoes>,
+    /// Torpedoes are aimed from a director rather than at each mount.
+    #[serde(default)]
+    pub torp_director: bool,
+    /// Mines.
+    pub mines: Mines,
+    /// ASW gear.
+    pub asw: Vec<ASW>,
+    /// Miscellaneous weights.
+    pub wgts: MiscWgts,
+    /// Bridge, funnels and masts.
+    #[serde(default)]
+    pub superstructure: Vec<Superstructure>,
+    /// Boats, paravanes, smoke generators and minesweeping gear.
+    #[serde(default)]
+    pub equipment: Vec<Equipment>,
+
+    /// Crew quality (gameplay aid only).
+    #[serde(default)]
+    pub crew_quality: CrewQuality,
+    /// How the size of the crew is estimated.
+    #[serde(default)]
+    pub crew_model: CrewModel,
+    /// Sea state used to leave unusable hull mounts out of the broadside
+    /// (gameplay aid only).
+    #[serde(default)]
+    pub sea_state: SeaState,
+    /// Standard stability is judged against.
+    #[serde(default)]
+    pub stability_standard: StabilityStandard,
+    /// Deliberate fantasy design. Gun mounts and design ratios are not
+    /// checked against what could be built in their era.
+    #[serde(default)]
+    pub fantasy: bool,
+    /// Merchant hull the ship was converted from, if it is an auxiliary.
+    #[serde(default)]
+    pub conversion: Option<Conversion>,
+    /// Heights of centres of gravity that replace the stability
+    /// calculation's fixed levers.
+    #[serde(default)]
+    pub vcg: Vcg,
+    /// Constants of the damage model changed by house rules.
+    #[serde(default)]
+    pub params: Params,
+    /// Number of main transverse watertight compartments, estimated from
+    /// the length and year if not given.
+    #[serde(default)]
+    pub compartments: Option<u32>,
+    /// Days of provisions carried for the full complement. If not given
+    /// stores are a fixed share of the displacement.
+    #[serde(default)]
+    pub endurance: Option<u32>,
+    /// Units the ship is reported in when none are asked for. Every
+    /// value is held in imperial units whatever this is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<Units>,
+
+    /// Version of sharpie that saved the ship file if it has been edited
+    /// by hand since.
+    #[serde(skip)]
+    pub(crate) edited_since: Option<String>,
+    /// Values calculated when the ship was archived, kept after the ship
+    /// in its file.
+    #[serde(skip)]
+    pub snapshot: Option<Snapshot>,
+    /// Inputs that were clamped by sanitize() when the ship was loaded.
+    #[serde(skip)]
+    pub(crate) clamped: Vec<DesignIssue>,
+    /// Fields of the ship file this version does not know, kept to be
+    /// written back when the ship is saved.
+    #[serde(skip)]
+    pub(crate) unknown: Value,
+
+    /// Custom notes and design metadata
+    pub notes: Notes,
+}
+
+impl Default for Ship { // {{{2
+    fn default() -> Ship {
+        Ship {
+            name: "".into(),
+            country: "".into(),
+            kind: "".into(),
+            year: 0,
+
+            trim: Trim::default(),
+
+            hull: Hull::default(),
+            wgts: MiscWgts::default(),
+            superstructure: Vec::new(),
+            equipment: Vec::new(),
+            engine: Engine::default(),
+            armor: Armor::default(),
+            torps: vec![Torpedoes::default(), Torpedoes::default()],
+            torp_director: false,
+            mines: Mines::default(),
+            asw: vec![ASW::default(), ASW::default()],
+            batteries: vec![
+                Battery::default(),
+                Battery::default(),
+                Battery::default(),
+                Battery::default(),
+                Battery::default(),
+            ],
+
+            crew_quality: CrewQuality::default(),
+            crew_model: CrewModel::default(),
+            sea_state: SeaState::default(),
+            stability_standard: StabilityStandard::default(),
+            fantasy: false,
+            conversion: None,
+            vcg: Vcg::default(),
+            params: Params::default(),
+            compartments: None,
+            endurance: None,
+            units: None,
+            edited_since: None,
+            snapshot: None,
+            clamped: Vec::new(),
+            unknown: Value::Null,
+
+            notes: Notes::default(),
+        }
+    }
+}
+
+impl Ship { // {{{2
+    /// Pounds in a long ton.
+    pub(crate) const POUND2TON: f64 = 2240.0;
+    /// Share of the deck taken up by superstructure, ventilators and
+    /// walkways.
+    const DECK_FITTINGS: f64 = 0.5;
+    /// Deck area taken up by each square foot of uptake, allowing for
+    /// casings and fiddleys.
+    const UPTAKE_CASING: f64 = 3.0;
+    /// Width of the uptake casings as a share of the beam.
+    const UPTAKE_WIDTH: f64 = 0.3;
+    /// Share of the waterline length amidships that uptakes and centreline
+    /// mounts can share.
+    pub const MIDSHIPS: f64 = 0.4;
+    /// Share of the free deck that must stay clear of weapons before the
+    /// deck is saturated.
+    pub const DECK_SATURATED: f64 = 0.2;
+    /// Weight (t) of generating plant per hp of auxiliary power.
+    const AUX_PLANT_WGT: f64 = 0.025;
+
+    // year_adj {{{3
+    /// Year adjustment factor for various calculations.
+    ///
+    pub fn year_adj(year: u32) -> f64 {
+             if year <= 1890 { 1.0 - (1890 - year) as f64 / 66.666664 }
+        else if year <= 1950 { 1.0 }
+        else                 { 0.0 }
+    }
+
+    // deck_space {{{3
+    /// Relative measure of hull space based on waterplane area, freeboard and
+    /// displacement adjusted for above water torpedoes.
+    ///
+    pub fn deck_space(&self) -> f64 {
+        let mut space = 0.0;
+        for w in self.torps.iter() {
+            space += w.deck_space(self.hull.b); 
+        }
+
+        space / self.wp()
+    }
+
+    // deck_area {{{3
+    /// Deck area free of superstructure and fittings for weapons, boats
+    /// and later additions, estimated from the waterplane area.
+    ///
+    pub fn deck_area(&self) -> f64 {
+        f64::max(self.wp() * (1.0 - Self::DECK_FITTINGS) - self.uptake_deck_area(), 0.0)
+    }
+
+    // uptake_area {{{3
+    /// Cross section of the boiler uptakes. Machinery without boilers
+    /// needs none.
+    ///
+    pub fn uptake_area(&self) -> f64 {
+        if self.wgt_boilers().to_long() == 0.0 { return 0.0; }
+
+        self.engine.uptake_area(
+            self.engine.hp_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws())
+        )
+    }
+
+    // funnels {{{3
+    /// Number of funnels, as listed in the superstructure or else as
+    /// needed to carry the boiler uptakes.
+    ///
+    pub fn funnels(&self) -> u32 {
+        let listed = self.superstructure.iter()
+            .filter(|s| s.kind == SuperstructureKind::Funnel)
+            .count() as u32;
+
+        if listed > 0 { return listed; }
+
+        self.engine.funnels(
+            self.engine.hp_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()),
+            self.hull.b
+        )
+    }
+
+    // uptake_deck_area {{{3
+    /// Deck area taken up by the uptakes of the funnels() with their
+    /// casings, each funnel as wide as the most it can carry.
+    ///
+    pub fn uptake_deck_area(&self) -> f64 {
+        self.funnels() as f64 * Engine::funnel_area(self.hull.b) * Self::UPTAKE_CASING
+    }
+
+    // uptake_len {{{3
+    /// Centreline deck length taken up by the uptake casings.
+    ///
+    pub fn uptake_len(&self) -> f64 {
+        if self.hull.b == 0.0 { return 0.0; } // Catch divide by zero
+
+        self.uptake_deck_area() / (self.hull.b * Self::UPTAKE_WIDTH)
+    }
+
+    // uptake_hull_space {{{3
+    /// Proportion of the hull volume taken up by the uptakes on their way
+    /// up from the boiler rooms.
+    ///
+    /// The machinery weight already charges the space for hull_room(), so
+    /// this is not added to it.
+    ///
+    pub fn uptake_hull_space(&self) -> f64 {
+        if self.hull.d().to_long() == 0.0 { return 0.0; } // Catch divide by zero
+
+        self.uptake_area() * (self.hull.t + self.hull.freeboard_dist()) /
+            (self.hull.d().to_long() * Hull::FT3_PER_TON_SEA)
+    }
+
+    // uptakes_crowded {{{3
+    /// True if the uptakes and the centreline mounts amidships do not fit
+    /// in the middle of the ship together.
+    ///
+    pub fn uptakes_crowded(&self) -> bool {
+        let mounts: f64 = self.batteries.iter().map(|b| b.amidships_len()).sum();
+
+        mounts > 0.0 && self.uptake_len() + mounts > self.hull.lwl() * Self::MIDSHIPS
+    }
+
+    // deck_area_weapons {{{3
+    /// Deck area taken up by gun mounts and above water torpedoes.
+    ///
+    pub fn deck_area_weapons(&self) -> f64 {
+        self.batteries.iter().map(|b| b.deck_area()).sum::<f64>() +
+            self.torps.iter().map(|w| w.deck_space(self.hull.b)).sum::<f64>()
+    }
+
+    // deck_area_clear {{{3
+    /// Deck area left for boats, rafts and wartime additions such as
+    /// light AA guns.
+    ///
+    pub fn deck_area_clear(&self) -> f64 {
+        f64::max(self.deck_area() - self.deck_area_weapons(), 0.0)
+    }
+
+    // deck_area_ratio {{{3
+    /// Proportion of the free deck area left clear of weapons.
+    ///
+    pub fn deck_area_ratio(&self) -> f64 {
+        if self.deck_area() == 0.0 { return 0.0; } // Catch divide by zero
+
+        self.deck_area_clear() / self.deck_area()
+    }
+
+    // deck_saturated {{{3
+    /// True if there is effectively no deck left for boats or additions.
+    ///
+    pub fn deck_saturated(&self) -> bool {
+        self.deck_area_ratio() < Self::DECK_SATURATED
+    }
+
+    // hull_space {{{3
+    /// Proportional measure of weights of engines, guns, magazines,
+    /// miscellaneous weights, ships stores, torpedo bulkheads and hull mounted
+    /// torpedoes to displacement to estimate the minimum length of the
+    /// "vitalspace" needed to contain these relative to a norm of 65% of water
+    /// length.
+    ///
+    pub fn hull_space(&self) -> f64 {
+        let mut space = 0.0;
+        for w in self.torps.iter() {
+            space += w.hull_space(); 
+        }
+        space / (self.hull.d().to_long() * Hull::FT3_PER_TON_SEA)
+    }
+
+    // wgt_bunker {{{3
+    /// Convenience function to get bunkerage weight from the engine.
+    ///
+    pub(crate) fn wgt_bunker(&self) -> f64 {
+        self.engine.bunker(
+            self.hull.d().to_long(),
+            self.hull.lwl(),
+            self.hull.leff(),
+            self.hull.cs(),
+            self.hull.ws()
+        )
+    }
+
+    // wgt_load {{{3
+    /// Weight of bunkerage, magazine, stores and cargo.
+    ///
+    pub(crate) fn wgt_load(&self) -> f64 {
+        self.wgt_stores().to_long() + self.wgt_bunker() + self.wgt_mag() + self.wgt_cargo().to_long()
+    }
+
+    // d_lite {{{3
+    /// Light Displacement (t): Displacement without bunkerage, magazine or
+    /// stores.
+    ///
+    pub fn d_lite(&self) -> Tons {
+        self.hull.d() - Tons::long(self.wgt_load())
+    }
+
+    // d_std {{{3
+    /// Standard Displacement (t): Standardized displacement per the Washington
+    /// and London Naval Treaties. Does not include bunkerage or reserve
+    /// feedwater.
+    ///
+    pub fn d_std(&self) -> Tons {
+        self.hull.d() - Tons::long(self.wgt_bunker())
+    }
+
+    // d_max {{{3
+    /// Maximum Displacement (t): Displacement including full bunker, magazines,
+    /// feedwater and stores.
+    ///
+    pub fn d_max(&self) -> Tons {
+        self.hull.d() + Tons::long(0.8 * self.wgt_bunker())
+    }
+
+    // cwp {{{3
+    /// Waterplane Area Coefficient for the hull and engine.
+    ///
+    pub fn cwp(&self) -> f64 {
+        self.hull.cwp(self.engine.shafts())
+    }
+
+    // wp {{{3
+    /// Waterplane Area for the hull and engine.
+    ///
+    pub fn wp(&self) -> f64 {
+        self.hull.wp(self.engine.shafts())
+    }
+
+    // t_max {{{3
+    /// Draft at maximum displacement.
+    ///
+    pub fn t_max(&self) -> f64 {
+        self.hull.t_calc(self.d_max(), self.engine.shafts())
+    }
+
+    // cb_max {{{3
+    /// Block coeficcient at maximum displacement.
+    ///
+    pub fn cb_max(&self) -> f64 {
+        self.hull.cb_calc(self.d_max(), self.t_max())
+    }
+
+    // crew_max {{{3
+    /// Estimated maximum crew size using the selected crew model.
+    ///
+    pub fn crew_max(&self) -> u32 {
+        self.crew_model.crew_max(self.hull.d().to_long(), self.year, self.engine.fuel.contains(FuelType::Coal))
+    }
+
+    // crew_min {{{3
+    /// Estimated minimum crew size based on displacement.
+    ///
+    pub fn crew_min(&self) -> u32 {
+        (self.crew_max() as f64 * CrewModel::MIN_RATIO) as u32
+    }
+
+    // vitalspace {{{3
+    /// Forecastle and Quarterdeck length required
+    /// to cover engine and magazine spaces.
+    ///
+    pub fn vitalspace(&self) -> f64 {
+        (1.0 - 0.65 * self.hull_room()) * 50.0 - 0.01
+    }
+
+    // vitalspace_length {{{3
+    /// Minimum armor belt length to cover
+    /// engine and magazine spaces.
+    ///
+    pub fn vitalspace_length(&self) -> f64 {
+        self.hull.lwl() * 0.65 * self.hull_room() + 0.01
+    }
+
+    // room {{{3
+    /// Share of the hull taken by the weights stowed below, each weighted
+    /// by the space it needs. Above 1 the hull is crowded.
+    ///
+    fn room(&self) -> f64 {
+        (
+            self.wgt_mag() +
+            self.wgt_stores().to_long() +
+            self.wgt_borne() * 6.4 +
+            self.wgt_engine() * 3.0 +
+            self.wgts.vital as f64 +
+            self.wgts.hull as f64
+        ) / (self.hull.d().to_long() * 0.94) / (1.0 - self.hull_space())
+    }
+
+    // hull_room {{{3
+    /// Ratio of the sum of weights of the engine, magazines, ship's stores, torpedo
+    /// bulkheads, hull mounted torpedoes and miscellaneous weights to displacement.
+    ///
+    pub fn hull_room(&self) -> f64 {
+        self.room() * if self.armor.bulkhead.wgt(self.hull.lwl(), self.cwp(), self.hull.b) > 0.1 {
+            self.hull.b / self.armor.bh_beam
+        } else { 1.0 }
+    }
+
+    // deck_room {{{3
+    /// Deck analog of hull_room(): room on deck per man of the minimum
+    /// crew. Below 0.9 the deck is cramped.
+    ///
+    pub fn deck_room(&self) -> f64 {
+        self.wp() /
+            Hull::FT3_PER_TON_SEA /
+            15.0 * (1.0 - self.deck_space()) /
+            self.crew_min() as f64 * self.hull.freeboard_dist()
+    }
+
+    // deck_room_quality {{{3
+    /// Return a string describing the deck space.
+    ///
+    pub fn deck_room_quality(&self) -> String {
+        let sp = self.deck_room();
+
+               if sp > 1.2 {
+            "Excellent".into()
+        } else if sp > 0.9 {
+            "Adequate".into()
+        } else if sp >= 0.5 {
+            "Cramped".into()
+        } else {
+            "Poor".into()
+        }
+    }
+
+    // hull_room_quality {{{3
+    /// Return a string describing the hull space.
+    ///
+    pub fn hull_room_quality(&self) -> String {
+        let sp = self.hull_room();
+
+               if sp < 5.0/6.0 {
+            "Excellent".into()
+        } else if sp < 1.1111112 {
+            "Adequate".into()
+        } else if sp <= 2.0 {
+            "Cramped".into()
+        } else {
+            "Extremely poor".into()
+        }
+    }
+
+    // cost_dollar {{{3
+    /// Cost in millions of US dollars.
+    ///
+    pub fn cost_dollar(&self) -> f64 {
+        // Crews larger or smaller than SpringSharp's estimate need more or less accommodation
+        let crew = self.crew_max() as f64 - CrewModel::classic(self.hull.d().to_long()) as f64;
+
+        ((self.hull.d().to_long()-self.wgt_load())*0.00014+self.wgt_engine()*0.00056+(self.wgt_borne()*8.0)*0.00042+crew*0.0002)*
+            self.cost_inflation()
+    }
+
+    // cost_inflation {{{3
+    /// Rise in the cost of construction after 1912.
+    ///
+    pub(crate) fn cost_inflation(&self) -> f64 {
+        if self.year as f64 +2.0>1914.0 {
+            1.0+(self.year as f64 +1.5-1914.0)/5.5
+        } else { 1.0 }
+    }
+
+    // cost_lb {{{3
+    /// Cost in millions of British pounds
+    ///
+    pub fn cost_lb(&self) -> f64 {
+        self.cost_dollar() / 4.0
+    }
+
+    // plausibility {{{3
+    /// Compare the design against real ships of similar year and type.
+    ///
+    pub fn plausibility(&self) -> Plausibility {
+        let (d, lwl, cwp, b) = (self.hull.d().to_long(), self.hull.lwl(), self.cwp(), self.hull.b);
+
+        let ratios = Ratios::new(
+            d,
+            self.engine.hp_max(d, lwl, self.hull.leff(), self.hull.cs(), self.hull.ws()),
+            self.armor.main.wgt(lwl, cwp, b) +
+                self.armor.end.wgt(lwl, cwp, b) +
+                self.armor.upper.wgt(lwl, cwp, b) +
+                self.armor.citadel_wgt(lwl, cwp, b),
+            self.wgt_broad(),
+            self.crew_max() as f64,
+        );
+
+        Plausibility::new(self.year, &self.kind, &ratios)
+    }
+
+    // recoil {{{3
+    /// A relative calculation of the ability of the ship to handle her weight of gunfire.
+    ///
+    pub fn recoil(&self) -> f64 {
+        (
+            (self.wgt_broad()/self.hull.d().to_long() * self.hull.freeboard_dist() * self.gun_super_factor() / self.hull.bb) *
+
+            ( self.hull.d().to_long().powf(1.0 / 3.0) / self.hull.bb * 3.0 ).powf(2.0) * 7.0
+        ) /
+            if self.stability_adj() > 0.0 {
+                self.stability_adj() * ((50.0 - self.steadiness()) / 150.0 + 1.0)
+            } else { 1.0 }
+    }
+
+    // metacenter {{{3
+    /// A measure of vertical equilibrium.
+    ///
+    pub fn metacenter(&self) -> f64 {
+        self.hull.b.powf(1.5) * (self.stability_adj() - 0.5) / 0.5 / 200.0
+    }
+
+    // kb {{{3
+    /// Estimated height of the center of buoyancy above the keel.
+    ///
+    pub fn kb(&self) -> f64 {
+        self.hull.kb(self.engine.shafts())
+    }
+
+    // bm {{{3
+    /// Estimated height of the metacenter above the center of buoyancy.
+    ///
+    pub fn bm(&self) -> f64 {
+        self.hull.bm(self.engine.shafts())
+    }
+
+    // kg {{{3
+    /// Estimated height of the center of gravity above the keel,
+    /// found by working back from metacenter().
+    ///
+    pub fn kg(&self) -> f64 {
+        self.kb() + self.bm() - self.metacenter()
+    }
+
+    // seaboat {{{3
+    /// Intermediate calculations for seakeeping() and steadiness().
+    ///
+    pub(crate) fn seaboat(&self) -> f64 {
+        let a = (self.hull.free_cap(self.cap_calc_broadside()) / (2.4 * self.hull.d().to_long().powf(0.2))).sqrt() *
+            (
+                (self.stability() * 5.0 * (self.hull.bb / self.hull.lwl())).powf(0.2) *
+                (self.hull.free_cap(self.cap_calc_broadside()) / self.hull.lwl() * 20.0).sqrt() *
+                (
+                    self.hull.d().to_long() /
+                        (
+                            self.hull.d().to_long() +
+                            self.armor.end.wgt(self.hull.lwl(), self.cwp(), self.hull.b) * 3.0 +
+                            self.wgt_hull_plus() / 3.0 +
+                            (
+                                self.wgt_borne() +
+                                self.wgt_gun_armor()
+                            ) * self.super_factor_long()
+                        )
+                )
+            ) * 8.0;
+
+        let b = a * if (self.hull.t / self.hull.bb) < 0.3 {
+                (self.hull.t / self.hull.bb / 0.3).sqrt()
+            } else {
+                1.0
+            };
+
+        let c = b *
+            if (self.engine.rf_max(self.hull.ws()) / (self.engine.rf_max(self.hull.ws()) + self.engine.rw_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.cs()))) < 0.55 &&
+                self.engine.is_powered()
+            {
+                (self.engine.rf_max(self.hull.ws()) / (self.engine.rf_max(self.hull.ws()) + self.engine.rw_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.cs()))).powf(2.0)
+            } else {
+                0.3025
+            };
+
+        f64::min(c, 2.0)
+    }
+
+    // seakeeping {{{3
+    /// The sea keeping ability of the ship.
+    ///
+    pub fn seakeeping(&self) -> f64 {
+        self.seaboat() * f64::min(self.steadiness(), 50.0) / 50.0
+    }
+
+    // head_seas {{{3
+    /// The sea keeping ability of the ship heading into the seas.
+    ///
+    pub fn head_seas(&self) -> f64 {
+        self.seakeeping() * self.hull.head_sea_factor()
+    }
+
+    // following_seas {{{3
+    /// The sea keeping ability of the ship running before the seas.
+    ///
+    pub fn following_seas(&self) -> f64 {
+        self.seakeeping() * self.hull.following_sea_factor(self.engine.vmax)
+    }
+
+    // sea_quality {{{3
+    /// Describe a seakeeping value.
+    ///
+    pub(crate) fn sea_quality(seakeeping: f64) -> &'static str {
+               if seakeeping < 0.7 {
+            "very wet, dangerous"
+        } else if seakeeping < 0.995 {
+            "wet"
+        } else if seakeeping < 1.2 {
+            "fair"
+        } else if seakeeping < 1.5 {
+            "good"
+        } else {
+            "excellent"
+        }
+    }
+
+    // tender_warn {{{3
+    /// If ship has an excessive risk of capsizing.
+    ///
+    #[allow(clippy::needless_bool)]
+    pub(crate) fn tender_warn(&self) -> bool {
+        if self.stability_adj() <= 0.995 {
+            true
+        } else {
+            false
+        }
+    }
+
+    // capsize_warn {{{3
+    /// If ship will capsize.
+    ///
+    #[allow(clippy::needless_bool)]
+    pub(crate) fn capsize_warn(&self) -> bool {
+        if self.metacenter() <= 0.0 {
+            true
+        } else {
+            false
+        }
+    }
+
+    // hull_strained {{{3
+    /// If hull will be subject to strain in the open sea.
+    ///
+    #[allow(clippy::needless_bool)]
+    pub(crate) fn hull_strained(&self) -> bool {
+        if self.str_comp() >= 0.5 && self.str_comp() < 0.885 && (
+            self.engine.vmax < 24.0 || self.hull.d().to_long() > 4000.0)
+        {
+            true
+        } else {
+            false
+        }
+    }
+
+    // is_steady {{{3
+    /// If ship is a steady gun platform.
+    ///
+    #[allow(clippy::needless_bool)]
+    fn is_steady(&self) -> bool {
+        if self.steadiness() >= 69.5 {
+            true
+        } else {
+            false
+        }
+    }
+
+    // is_unsteady {{{3
+    /// If ship is not a steady gun platform.
+    ///
+    #[allow(clippy::needless_bool)]
+    fn is_unsteady(&self) -> bool {
+        if self.steadiness() < 30.0 {
+            true
+        } else {
+            false
+        }
+    }
+
+    // type_sea {{{3
+    /// Convert seakeeping() value into SeaType.
+    ///
+    fn type_sea(&self) -> SeaType {
+               if self.seakeeping() < 0.7 {
+            SeaType::BadSea
+        } else if self.seakeeping() < 0.995 {
+            SeaType::PoorSea
+        } else if self.seakeeping() >= 1.5 {
+            SeaType::FineSea
+        } else if self.seakeeping() >= 1.2 {
+            SeaType::GoodSea
+        } else {
+            SeaType::Error
+        }
+    }
+
+    // seakeeping desc {{{3
+    /// Return a string describing risk of capsizing,
+    /// hull strain, steadiness and seaworthiness.
+    ///
+    pub fn seakeeping_desc(&self) -> Vec<String> {
+        let mut s: Vec<String> = Vec::new();
+        
+        if self.is_steady() {
+            s.push("Ship has slow easy roll, a good steady, gun platform".into());
+        } else if self.is_unsteady() {
+            s.push("Ship has quick, lively roll, not a steady gun platform".into());
+        }
+
+        let sea = match self.type_sea() {
+            SeaType::BadSea  => "Caution: Lacks seaworthiness - very limited seakeeping ability".into(),
+            SeaType::PoorSea => "Poor seaboat, wet and uncomfortable, reduced performance in heavy weather".into(),
+            SeaType::GoodSea => "Good seaboat, rides out heavy weather easily".into(),
+            SeaType::FineSea => format!("Excellent seaboat, comfortable, {}",
+                    if self.wgt_guns() > 0.0 {
+                        "can fire her guns in the heaviest weather"
+                    } else {
+                        "rides out heavy weather easily"
+                    }),
+            SeaType::Error   => "Invalid SeaType".into(),
+        };
+
+        s.push(sea);
+
+        s
+    }
+
+    // roll_period {{{3
+    /// Roll period of the ship.
+    ///
+    pub fn roll_period(&self) -> f64 {
+        0.42 * self.hull.bb / self.metacenter().sqrt()
+    }
+
+    // steadiness {{{3
+    /// Dynamic hull steadiness in open sea based
+    /// on trim adjustment and seakeeping value.
+    ///
+    pub fn steadiness(&self) -> f64 {
+        f64::min(self.trim.value() * self.seaboat(), 100.0)
+    }
+
+    // gunnery_modifier {{{3
+    /// Accuracy of the ship's gunfire against an average gun platform,
+    /// from her steadiness and reduced by recoil that restricts the arcs.
+    ///
+    pub fn gunnery_modifier(&self) -> f64 {
+        self.steadiness() / 50.0 / f64::max(self.recoil(), 1.0)
+    }
+
+    // stability {{{3
+    /// Inherent stability of the ship before applying
+    /// the trim adjustment.
+    ///
+    pub(crate) fn stability(&self) -> f64 {
+        let a =
+            (self.armor.ct_fwd.wgt(self.hull.d().to_long()) + self.armor.ct_aft.wgt(self.hull.d().to_long())) * 5.0 +
+            self.gun_moment() +
+            self.wgts.hull as f64 * 2.0 +
+            self.wgts.on as f64 * 3.0 +
+            self.wgts.above as f64 * 4.0 +
+            self.superstructure.iter()
+                .map(|s| (s.wgt(self.hull.d().to_long(), self.year) * s.kind.lever()).to_long())
+                .sum::<f64>() +
+            self.equipment.iter()
+                .map(|e| (e.wgt(self.hull.d().to_long(), self.year) * e.kind.lever()).to_long())
+                .sum::<f64>() +
+            // Only count the part of each belt that fits on the hull side
+            self.vcg_moment(
+                (self.armor.upper.wgt(self.hull.d().to_long(), self.cwp(), self.hull.b) + self.armor.citadel_wgt(self.hull.d().to_long(), self.cwp(), self.hull.b)) *
+                    self.armor.upper_fit(self.hull.t, self.hull.freeboard_dist()),
+                self.vcg.upper_belt, 2.0) +
+            self.vcg_moment(
+                self.armor.main.wgt(self.hull.d().to_long(), self.cwp(), self.hull.b) * self.armor.main_fit(self.hull.t, self.hull.freeboard_dist()),
+                self.vcg.main_belt, 1.0) +
+            self.vcg_moment(
+                self.armor.end.wgt(self.hull.d().to_long(), self.cwp(), self.hull.b) * self.armor.end_fit(self.hull.t, self.hull.freeboard_dist()),
+                self.vcg.end_belt, 1.0) +
+            // Backing sits behind the belts and battery armor
+            self.wgt_armor_backing().to_long() * 1.5 +
+            self.wgt_torp_director().to_long() * 5.0 +
+            self.armor.deck.wgt(self.hull.clone(), self.engine.shafts(), self.wgt_mag_room(), self.wgt_engine_est()) +
+            (self.wgt_hull_plus() + self.wgt_guns() + self.wgt_gun_mounts() - self.wgt_borne()) * 1.5 * self.hull.freeboard() / self.hull.t;
+
+        // Weights below decks only count when the deck is cramped
+        let cramped = if self.deck_room() < 1.0 { 1.0 - self.deck_room().powf(2.0) } else { 0.0 };
+        let b = a +
+            (self.wgts.vital as f64 + self.wgt_void().to_long()) * cramped +
+            self.vcg_moment(self.wgt_engine(), self.vcg.machinery, cramped);
+
+        if b > 0.0 {
+            ((self.hull.d().to_long() * (self.hull.bb / self.hull.t) / b) * 0.5).sqrt() *
+            (8.76755 / self.hull.len2beam()).powf(0.25)
+        } else {
+            b
+        }
+    }
+
+    // stability_adj {{{3
+    /// A measure of the effect of vertical weights
+    /// on the stability of the ship.
+    ///
+    pub fn stability_adj(&self) -> f64 {
+        self.stability() * ((50.0 - self.trim.value()) / 150.0 + 1.0)
+    }
+
+    // d_factor {{{3
+    /// Adjustment factor to reduce engine weight in a highly
+    /// stressed ship of less than 5,000 tons.
+    ///
+    pub fn d_factor(&self) -> f64 {
+        f64::min(
+            self.hull.d().to_long() /
+            (
+                self.engine.d_engine(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()) +
+                    8.0 * self.wgt_borne() + self.wgt_armor() + self.wgt_misc().to_long()
+            ),
+            10.0
+        )
+    }
+
+    // cap_calc_broadside {{{3
+    /// True if every battery is mounted on the broadside below deck.
+    ///
+    pub fn cap_calc_broadside(&self) -> bool {
+        for b in self.batteries.iter() {
+            if ! b.broad_and_below() { return false; }
+        }
+
+        true
+    }
+
+    // flotation {{{3
+    /// Estimate of the pounds of non-critical shell
+    /// hits required to sink or destroy the ship.
+    ///
+    pub fn flotation(&self) -> f64 {
+        let a = if self.cap_calc_broadside() {
+                self.hull.free_cap(self.cap_calc_broadside())
+            } else {
+                self.hull.freeboard_dist()
+            };
+
+        let b = (a * self.wp() / Hull::FT3_PER_TON_SEA + self.hull.d().to_long()) / 2.0;
+
+        let c = b * self.stability_adj().powf(
+            if self.stability_adj() > 1.0 { 0.5 } else { 4.0 }
+            );
+
+        let d = c * if self.str_comp() < 1.0 { self.str_comp() } else { 1.0 };
+
+        let e = d / self.room().powf(if self.room() > 1.0 { 2.0 } else { 1.0 });
+
+        f64::max(e * Self::year_adj(self.year), 0.0)
+    }
+
+    // str_cross {{{3
+    /// Cross-sectional strength.
+    ///
+    pub fn str_cross(&self) -> f64 {
+        let mut concentration: f64 = 1.0;
+
+        if self.wgt_broad() > 0.0 {
+            concentration = 1.0 + self.gun_concentration();
+        }
+
+        let mut str_cross = self.wgt_struct() / f64::sqrt(self.hull.bb * (self.hull.t + self.hull.freeboard_dist())) /
+            ((self.hull.d().to_long() + ((self.wgt_broad() + self.wgt_borne() + self.wgt_gun_armor() + self.armor.ct_fwd.wgt(self.hull.d().to_long()) + self.armor.ct_aft.wgt(self.hull.d().to_long())) * (concentration * self.gun_super_factor()) + f64::max(self.engine.hp_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()), 0.0) / 100.0)) / self.hull.d().to_long()) * 0.6;
+
+        if self.year < 1900 {
+            str_cross *= 1.0 - (1900.0 - self.year as f64) / 100.0;
+        }
+
+        str_cross * self.hull.size_factor() * self.hull_material().strength_factor(self.year)
+    }
+
+    // str_long {{{3
+    /// Longitudinal strength.
+    ///
+    pub fn str_long(&self) -> f64 {
+        (
+            self.wgt_hull_plus() + match self.armor.bh_kind {
+                BulkheadType::Strengthened =>
+                    self.armor.bulkhead.wgt(self.hull.lwl(), self.cwp(), self.hull.b),
+                BulkheadType::Additional => 0.0,
+            }
+        ) /
+            (
+                (self.hull.lwl() / (self.hull.t + self.hull.free_cap(self.cap_calc_broadside()))).powf(2.0) *
+                (
+                    self.hull.d().to_long() +
+                    self.armor.end.wgt(self.hull.lwl(), self.cwp(), self.hull.b) *
+                    3.0 + (
+                        self.wgt_borne() +
+                        self.wgt_gun_armor()
+                        ) * self.super_factor_long() * 2.0
+                )
+            ) *
+            850.0 * if self.year < 1900 { 1 - (1900 - self.year) / 100 } else { 1 } as f64 *
+            self.hull.size_factor() *
+            self.hull_material().strength_factor(self.year)
+    }
+
+    // hull_material {{{3
+    /// How the hull is built, as set or else as usual for the year.
+    ///
+    pub fn hull_material(&self) -> HullMaterial {
+        self.hull.material.unwrap_or(HullMaterial::for_year(self.year))
+    }
+
+    // str_comp {{{3
+    /// Composite strength.
+    ///
+    pub fn str_comp(&self) -> f64 {
+        if self.str_cross() > self.str_long() {
+            self.str_long() * (self.str_cross() / self.str_long()).powf(0.25)
+        } else {
+            self.str_cross() * (self.str_long() / self.str_cross()).powf(0.1)
+        }
+    }
+
+    // gun_concentration {{{3
+    /// How concentrated the broadside is in a few heavy mounts, weighted
+    /// by each battery's share of the broadside weight.
+    ///
+    pub(crate) fn gun_concentration(&self) -> f64 {
+        let mut concentration = 0.0;
+        for b in self.batteries.iter() {
+            concentration += b.concentration(self.wgt_broad());
+        }
+        concentration
+    }
+
+    // damage_shell_size {{{3
+    /// Size of shells used to calculate flotation(): the calibre of the
+    /// main battery, or the damage model's shell size (6") if it has no
+    /// guns.
+    ///
+    pub fn damage_shell_size(&self) -> f64 {
+        match self.batteries.first() {
+            Some(b) if b.num > 0 && b.diam > 0.0 => b.diam,
+            _                                    => self.params.shell_size(),
+        }
+    }
+
+    // damage_shell_num {{{3
+    /// Number of non-critical shell hits of the same caliber as the
+    /// main battery or 6" shells if the ship has no main battery.
+    ///
+    pub fn damage_shell_num(&self) -> f64 {
+        self.flotation() / (
+            self.params.shell_damage(self.damage_shell_size()) *
+            Self::year_adj(self.year)
+            )
+    }
+
+    // throw_wgt {{{3
+    /// Weight of shells fired per minute by all guns, adjusted for
+    /// crew quality.
+    ///
+    pub fn throw_wgt(&self) -> f64 {
+        let mut throw = 0.0;
+        for b in self.batteries.iter() {
+            throw += b.broadside_wgt() * b.rate_of_fire();
+        }
+        throw * self.crew_quality.rate_of_fire()
+    }
+
+    // damage_shell_torp_num {{{3
+    /// Number of non-critical 20" torpedo hits required to sink the ship,
+    /// counted in the ship's own torpedoes if it carries any, which are
+    /// weighed against the damage model's torpedo.
+    ///
+    pub fn damage_torp_num(&self) -> f64 {
+        (
+            (
+                (
+                    (self.flotation() / 10_000.0).powf(1.0/3.0) +
+                    (self.torp_beam() / 75.0).powf(2.0) +
+                    (
+                        (self.armor.bulkhead.thick / 2.0 * self.armor.bulkhead.len / self.hull.lwl()) /
+                        0.65 * self.armor.bulkhead.hgt / self.hull.t
+                    ).powf(1.0/3.0) *
+                    self.flotation() / 35_000.0 * self.torp_beam() / 50.0
+                ) / self.room() * self.hull.lwl() / (self.hull.lwl() + self.hull.bb)
+            ) * if self.stability_adj() < 1.0 {
+                    self.stability_adj().powf(4.0)
+                } else {
+                    1.0
+                } * (1.0 - self.hull_space())
+        ) * match self.torps.first() {
+                Some(t) if t.wgt_weaps() > 0.0 => self.params.torp_wgt() / (t.wgt_weaps() / t.num as f64),
+                _                              => 1.0,
+            }
+    }
+
+    // wgt_engine {{{3
+    /// Weight of the machinery: the engine, adjusted by the displacement
+    /// factor (d_factor()), and the auxiliary plant.
+    ///
+    pub(crate) fn wgt_engine(&self) -> f64 {
+        self.wgt_propulsion() + self.wgt_aux_plant().to_long()
+    }
+
+    // wgt_propulsion {{{3
+    /// Weight of the engine, adjusted by the displacement factor (d_factor()).
+    ///
+    fn wgt_propulsion(&self) -> f64 {
+
+        let p =
+            if (self.hull.d().to_long() < 5000.0) && (self.hull.d().to_long() >= 600.0) && (self.d_factor() < 1.0)
+            {
+                1.0 - self.hull.d().to_long() / 5000.0
+            } else if (self.hull.d().to_long() < 600.0) && (self.d_factor() < 1.0) {
+                    0.88
+                } else {
+                    0.0
+            };
+
+        (self.engine.d_engine(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()) / 2.0) *
+            self.d_factor().powf(p)
+    }
+
+    // wgt_engine_est {{{3
+    /// Weight of the engine before the displacement factor, which depends
+    /// on the weight of the armour. Armour that is sized to the machinery
+    /// uses this to avoid depending on its own weight.
+    ///
+    pub(crate) fn wgt_engine_est(&self) -> f64 {
+        self.engine.d_engine(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()) / 2.0
+    }
+
+    // wgt_boilers {{{3
+    /// Weight of the boilers.
+    ///
+    pub fn wgt_boilers(&self) -> Tons {
+        Tons::long(self.wgt_propulsion() * self.engine.wgt_shares().0)
+    }
+
+    // wgt_engines {{{3
+    /// Weight of the engines with their gearing or motors.
+    ///
+    pub fn wgt_engines(&self) -> Tons {
+        Tons::long(self.wgt_propulsion() * self.engine.wgt_shares().1)
+    }
+
+    // wgt_shafting {{{3
+    /// Weight of the shafting, propellers and auxiliary machinery.
+    ///
+    pub fn wgt_shafting(&self) -> Tons {
+        Tons::long(self.wgt_propulsion() * self.engine.wgt_shares().2) + self.wgt_aux_plant()
+    }
+
+    // wgt_aux_plant {{{3
+    /// Weight of the generating plant that supplies the auxiliary power.
+    ///
+    pub fn wgt_aux_plant(&self) -> Tons {
+        Tons::long(self.training_power() * Self::AUX_PLANT_WGT)
+    }
+
+    // wgt_struct {{{3
+    /// Weight per square feet of hull.
+    ///
+    pub fn wgt_struct(&self) -> f64 {
+        (
+            self.wgt_hull_plus() +
+            match self.armor.bh_kind {
+                BulkheadType::Strengthened => 
+                    self.armor.bulkhead.wgt(self.hull.lwl(), self.cwp(), self.hull.b),
+                BulkheadType::Additional => 0.0,
+            }
+        ) * Self::POUND2TON / (
+            self.hull.ws() +
+            2.0 * self.hull.lwl() * self.hull.free_cap(self.cap_calc_broadside()) +
+            self.wp()
+            )
+    }
+
+    // wgt_hull {{{3
+    /// Weight of the hull.
+    ///
+    /// A warship's hull is whatever the other weights leave of the
+    /// displacement while a merchant hull is a fixed share of it.
+    ///
+    pub(crate) fn wgt_hull(&self) -> f64 {
+        if let Some(c) = &self.conversion { return self.hull.d().to_long() * c.hull_fraction; }
+
+        self.hull.d().to_long() -
+            self.wgt_guns() -
+            self.wgt_gun_mounts() -
+            self.wgt_weaps() -
+            self.wgt_armor() -
+            self.wgt_engine() -
+            self.wgt_load() -
+            self.wgt_misc().to_long()
+    }
+
+    // wgt_fitted {{{3
+    /// Weight of everything but the hull.
+    ///
+    pub(crate) fn wgt_fitted(&self) -> f64 {
+        self.wgt_guns() +
+            self.wgt_gun_mounts() +
+            self.wgt_weaps() +
+            self.wgt_armor() +
+            self.wgt_engine() +
+            self.wgt_load() +
+            self.wgt_misc().to_long()
+    }
+
+    // wgt_hull_plus {{{3
+    /// Weight of the hull plus weight of guns and mounts
+    /// (excluding wgt_borne()).
+    ///
+    pub(crate) fn wgt_hull_plus(&self) -> f64 {
+        self.wgt_hull() +
+        self.wgt_guns() +
+        self.wgt_gun_mounts() -
+        self.wgt_borne()
+    }
+
+    // wgt_borne {{{3
+    /// Weight of the guns scaled by how their mounts carry them, doubled.
+    ///
+    pub(crate) fn wgt_borne(&self) -> f64 {
+        let mut wgt = 0.0;
+        for b in self.batteries.iter() {
+            wgt += b.gun_wgt() * b.mount_kind.wgt_adj();
+        }
+        wgt * 2.0
+    }
+
+    // wgt_misc {{{3
+    /// Weight of miscellaneous weights including void space.
+    ///
+    pub fn wgt_misc(&self) -> Tons {
+        Tons::long(self.wgts.wgt() as f64) + self.wgt_superstructure() + self.wgt_equipment() + match self.wgts.void {
+            Some(_) => Tons::default(),
+            None    => self.wgt_void(),
+        }
+    }
+
+    // wgt_superstructure {{{3
+    /// Weight of the bridge, funnels and masts.
+    ///
+    pub fn wgt_superstructure(&self) -> Tons {
+        self.superstructure.iter()
+            .map(|s| s.wgt(self.hull.d().to_long(), self.year))
+            .sum()
+    }
+
+    // wgt_equipment {{{3
+    /// Weight of the boats, paravanes and other minor equipment.
+    ///
+    pub fn wgt_equipment(&self) -> Tons {
+        self.equipment.iter()
+            .map(|e| e.wgt(self.hull.d().to_long(), self.year))
+            .sum()
+    }
+
+    // windage {{{3
+    /// Side area of the bridge, funnels and masts exposed to the wind.
+    ///
+    pub fn windage(&self) -> f64 {
+        let mut area = 0.0;
+        for s in self.superstructure.iter() {
+            area += s.windage(self.hull.lwl(), self.hull.b, self.year);
+        }
+        area
+    }
+
+    // wgt_void {{{3
+    /// Weight given to void space, estimated from the
+    /// bulges unless it has been set.
+    ///
+    pub fn wgt_void(&self) -> Tons {
+        Tons::long(match self.wgts.void {
+            Some(void) => void as f64,
+            None       => self.hull.bulge_void(self.bulge_len()),
+        })
+    }
+
+    // bulge_len {{{3
+    /// Length of the torpedo bulges, defaulting to
+    /// the length of the main belt.
+    ///
+    pub fn bulge_len(&self) -> f64 {
+        if self.armor.bulge.len > 0.0 {
+            self.armor.bulge.len
+        } else if self.armor.main.len > 0.0 {
+            self.armor.main.len
+        } else {
+            self.hull.lwl() * 0.6
+        }.min(self.hull.lwl())
+    }
+
+    // torp_beam {{{3
+    /// Beam used for torpedo protection. Bulges
+    /// only count along the length they cover.
+    ///
+    fn torp_beam(&self) -> f64 {
+        let covered = if self.armor.main.len > 0.0 {
+            self.armor.main.len
+        } else {
+            self.hull.lwl() * 0.6
+        };
+
+        self.hull.b + (self.hull.bb - self.hull.b) * f64::min(self.bulge_len() / covered, 1.0)
+    }
+
+    // wgt_weaps {{{3
+    /// Weight of torpedos, mines and ASW weapons
+    ///
+    pub(crate) fn wgt_weaps(&self) -> f64 {
+        let mut wgt = 0.0;
+        for w in self.torps.iter() { wgt += w.wgt(); }
+        for w in self.asw.iter()   { wgt += w.wgt(); }
+        wgt += self.mines.wgt();
+        wgt += self.wgt_torp_director().to_long();
+
+        wgt
+    }
+
+    // wgt_torp_director {{{3
+    /// Weight of the torpedo director, if the ship has torpedoes and
+    /// aims them from one.
+    ///
+    pub fn wgt_torp_director(&self) -> Tons {
+        if !self.torp_director || self.torps.iter().all(|t| t.num == 0) { return Tons::default(); }
+
+        Tons::long(Torpedoes::wgt_director(self.year))
+    }
+
+    // wgt_guns {{{3
+    /// Weight of guns (excluding mounts).
+    ///
+    pub(crate) fn wgt_guns(&self) -> f64 {
+        let mut wgt = 0.0;
+        for b in self.batteries.iter() {
+            wgt += b.gun_wgt();
+        }
+        wgt
+    }
+
+    // wgt_gun_mounts {{{3
+    /// Weight of gun mounts.
+    ///
+    pub(crate) fn wgt_gun_mounts(&self) -> f64 {
+        let mut wgt = 0.0;
+        for b in self.batteries.iter() {
+            wgt += b.mount_wgt() + b.training_wgt();
+        }
+        wgt
+    }
+
+    // training_power {{{3
+    /// Auxiliary power needed to train the gun mounts.
+    ///
+    pub fn training_power(&self) -> f64 {
+        self.batteries.iter().map(|b| b.training_power()).sum()
+    }
+
+    // wgt_gun_armor {{{3
+    /// Weight of gun mount armor.
+    ///
+    pub(crate) fn wgt_gun_armor(&self) -> f64 {
+        let mut wgt = 0.0;
+        for b in self.batteries.iter() {
+            wgt += b.armor_wgt(self.hull.clone());
+        }
+        wgt
+    }
+
+    // wgt_mag {{{3
+    /// Weight of the ship's magazines.
+    ///
+    pub(crate) fn wgt_mag(&self) -> f64 {
+        let mut wgt = 0.0;
+        for b in self.batteries.iter() {
+            wgt += b.mag_wgt();
+        }
+        wgt
+    }
+
+    // wgt_mag_shells {{{3
+    /// Weight of the shells in the ship's shell rooms.
+    ///
+    pub(crate) fn wgt_mag_shells(&self) -> f64 {
+        self.batteries.iter().map(|b| b.mag_shell_wgt()).sum()
+    }
+
+    // wgt_mag_propellant {{{3
+    /// Weight of the propellant in the ship's magazines.
+    ///
+    pub(crate) fn wgt_mag_propellant(&self) -> f64 {
+        self.batteries.iter().map(|b| b.mag_propellant_wgt()).sum()
+    }
+
+    // wgt_mag_hoists {{{3
+    /// Weight of the ship's ammunition hoists and handling gear.
+    ///
+    pub(crate) fn wgt_mag_hoists(&self) -> f64 {
+        self.batteries.iter().map(|b| b.mag_hoist_wgt()).sum()
+    }
+
+    // wgt_mag_room {{{3
+    /// Weight stowed in the magazines and shell rooms. This sets their
+    /// extent and leaves out the hoists that run up to the mounts.
+    ///
+    pub(crate) fn wgt_mag_room(&self) -> f64 {
+        self.wgt_mag_shells() + self.wgt_mag_propellant()
+    }
+
+    // wgt_broad {{{3
+    /// Sum of the broadside weights of all batteries.
+    ///
+    pub(crate) fn wgt_broad(&self) -> f64 {
+        let mut broad = 0.0;
+        for b in self.batteries.iter() {
+            broad += b.broadside_wgt();
+        }
+        broad
+    }
+
+    // is_gunless {{{3
+    /// True if the ship carries no guns, as many torpedo boats and
+    /// minelayers do.
+    ///
+    pub fn is_gunless(&self) -> bool {
+        self.batteries.iter().all(|b| b.num == 0 || b.diam == 0.0)
+    }
+
+    // wgt_broad_at {{{3
+    /// Weight of broadside leaving out hull mounts that are unusable in the
+    /// given sea state.
+    ///
+    pub(crate) fn wgt_broad_at(&self, sea: SeaState) -> f64 {
+        self.batteries.iter()
+            .map(|b| b.broadside_wgt_at(self.hull.clone(), sea))
+            .sum()
+    }
+
+    // wgt_armor {{{3
+    /// Weight of ship and battery armor.
+    ///
+    pub(crate) fn wgt_armor(&self) -> f64 {
+        self.wgt_armor_plates() * (1.0 + self.armor_material().backing())
+    }
+
+    // wgt_armor_plates {{{3
+    /// Weight of ship and battery armor plates without their backing.
+    ///
+    fn wgt_armor_plates(&self) -> f64 {
+        self.armor.wgt(self.hull.clone(), self.engine.shafts(), self.wgt_mag_room(), self.wgt_engine_est()) + self.wgt_gun_armor()
+    }
+
+    // wgt_armor_backing {{{3
+    /// Weight of the timber backing and fastenings iron and compound
+    /// armor plates are carried on.
+    ///
+    pub fn wgt_armor_backing(&self) -> Tons {
+        Tons::long(self.wgt_armor_plates() * self.armor_material().backing())
+    }
+
+    // armor_material {{{3
+    /// What the armor is made of, as set or else as usual for the year.
+    ///
+    pub fn armor_material(&self) -> ArmorMaterial {
+        self.armor.material.unwrap_or(ArmorMaterial::for_year(self.year))
+    }
+
+    // armor_plates {{{3
+    /// Thickness, area and weight of each armored element.
+    ///
+    pub fn armor_plates(&self) -> Vec<ArmorPlate> {
+        let (lwl, cwp, b) = (self.hull.lwl(), self.cwp(), self.hull.b);
+        let belt = |name: &str, belt: &armor::Belt| ArmorPlate {
+            name: name.into(), thick: belt.thick, area: belt.area(lwl, cwp, b), wgt: Tons::long(belt.wgt(lwl, cwp, b))
+        };
+
+        let mut plates = vec![
+            belt("Main belt", &self.armor.main),
+            belt("End belts", &self.armor.end),
+            ArmorPlate {
+                name: "Upper belt".into(), thick: self.armor.upper.thick,
+                area: self.armor.upper_area(lwl, cwp, b), wgt: Tons::long(self.armor.upper_wgt(lwl, cwp, b)),
+            },
+            belt("Torpedo bulkhead", &self.armor.bulkhead),
+            belt(if self.hull.b == self.hull.bb { "Void" } else { "Bulges" }, &self.armor.bulge),
+        ];
+
+        if let Some(citadel) = &self.armor.citadel {
+            plates.push(ArmorPlate {
+                name: "Citadel bulkheads".into(), thick: citadel.thick,
+                area: self.armor.citadel_area(lwl, cwp, b), wgt: Tons::long(self.armor.citadel_wgt(lwl, cwp, b)),
+            });
+        }
+
+        let (md, fc, qd, splinter) = self.armor.deck.areas(self.hull.clone(), self.engine.shafts(), self.wgt_mag_room(), self.wgt_engine_est());
+        let deck = &self.armor.deck;
+        for (name, thick, area) in [
+            ("Main deck", deck.md, md),
+            ("Forecastle deck", deck.fc, fc),
+            ("Quarter deck", deck.qd, qd),
+            ("Splinter deck", deck.splinter_thick(), splinter),
+        ] {
+            plates.push(ArmorPlate { name: name.into(), thick, area, wgt: Tons::long(area * thick * Armor::INCH) });
+        }
+
+        for (i, btry) in self.batteries.iter().enumerate() {
+            plates.push(ArmorPlate::implied(&format!("{} battery face", battery_name(i)), btry.armor_face, Tons::long(btry.armor_face_wgt())));
+            plates.push(ArmorPlate::implied(&format!("{} battery gunhouse", battery_name(i)), btry.armor_back, Tons::long(btry.armor_back_wgt())));
+            plates.push(ArmorPlate::implied(&format!("{} battery barbette", battery_name(i)), btry.armor_barb, Tons::long(btry.armor_barb_wgt(self.hull.clone()))));
+        }
+
+        plates.push(ArmorPlate::implied("Forward conning tower", self.armor.ct_fwd.thick, Tons::long(self.armor.ct_fwd.wgt(self.hull.d().to_long()))));
+        plates.push(ArmorPlate::implied("Aft conning tower", self.armor.ct_aft.thick, Tons::long(self.armor.ct_aft.wgt(self.hull.d().to_long()))));
+
+        plates.retain(|p| p.thick > 0.0 && p.wgt > Tons::default());
+        plates
+    }
+
+    // gun_wtf {{{3
+    /// Weight of the guns, mounts and gun armor times the superfiring
+    /// factor of each battery.
+    ///
+    pub(crate) fn gun_wtf(&self) -> f64 {
+        let mut wtf = 0.0;
+        for b in self.batteries.iter() {
+            if b.diam == 0.0 { continue; }
+            wtf += (
+                b.gun_wgt() +
+                b.mount_wgt() +
+                b.armor_wgt(self.hull.clone())
+             ) *
+                b.super_(self.hull.clone()) *
+                b.mount_kind.wgt_adj();
+        }
+        wtf
+    }
+
+    // gun_super_factor {{{3
+    /// Average superfiring factor of the guns by weight: 1 if every gun
+    /// is on the deck and more the higher they are mounted.
+    ///
+    /// Ships without guns have a neutral factor of 1.
+    ///
+    pub(crate) fn gun_super_factor(&self) -> f64 {
+        let wgt = self.wgt_gun_armor() + self.wgt_guns() + self.wgt_gun_mounts();
+        if wgt == 0.0 { return 1.0; } // catch divide by zero
+
+        self.gun_wtf() / wgt
+    }
+
+    // super_factor_long {{{3
+    /// Lever of the main battery in the longitudinal strength and
+    /// seakeeping calculations, smaller for guns grouped amidships.
+    ///
+    pub fn super_factor_long(&self) -> f64 {
+        let main = self.batteries.first().cloned().unwrap_or_default();
+
+        let a = self.hull_room() *
+            if (
+                    main.groups[0].distribution == GunDistributionType::CenterlineEven ||
+                    main.groups[0].distribution == GunDistributionType::SidesEven ||
+                    main.groups[1].distribution == GunDistributionType::CenterlineEven ||
+                    main.groups[1].distribution == GunDistributionType::SidesEven
+                ) && (
+                    main.mount_num == 3 ||
+                    main.mount_num == 4
+                )
+            {
+                self.gun_super_factor()
+            } else {
+                1.0
+            };
+        a *
+            if (
+                    main.groups[0].num_mounts() > 0 &&
+                    main.groups[1].num_mounts() == 0 &&
+                    main.groups[0].distribution.super_factor_long()
+                ) || (
+                    main.groups[1].num_mounts() > 0 &&
+                    main.groups[0].num_mounts() == 0 &&
+                    main.groups[1].distribution.super_factor_long()
+                ) || (
+                    main.groups[0].num_mounts() > 0 &&
+                    main.groups[1].num_mounts() > 0 &&
+                    (main.groups[0].distribution.g1_gun_position(self.hull.fd_len, self.hull.ad_len()) -
+                     main.groups[1].distribution.g2_gun_position(self.hull.fd_len, self.hull.ad_len())).abs() < 0.2
+                )
+            {
+                0.8 * self.gun_super_factor()
+            } else {
+                2.0 * self.gun_super_factor() - 1.0
+            }
+    }
+
+    // ship_type {{{3
+    /// Get a string describing the type of ship based 
+    /// on gun distribution, mounts and armor.
+    ///
+    pub(crate) fn ship_type(&self) -> String {
+        let mut s: Vec<String> = Vec::new();
+
+        let main = self.batteries.first().cloned().unwrap_or_default();
+        let sec = self.batteries.get(1).cloned().unwrap_or_default();
+        let ter = self.batteries.get(2).cloned().unwrap_or_default();
+
+        if main.mount_kind == MountType::OpenBarbette ||
+            sec.mount_kind == MountType::OpenBarbette
+        { s.push("Barbette Ship".into()); }
+
+        if main.groups[0].distribution == GunDistributionType::CenterlineFD ||
+            main.groups[0].distribution == GunDistributionType::SidesEndsFD
+        { s.push("Central Citadel Ship".into()); }
+
+        let main_broad = main.mount_kind == MountType::Broadside;
+        let sec_broad  = sec.mount_kind == MountType::Broadside;
+        let ter_broad  = ter.mount_kind == MountType::Broadside;
+
+        let main_below = (main.groups[0].below + main.groups[1].below) > 0;
+        let sec_below  = (sec.groups[0].below + main.groups[1].below) > 0;
+        let ter_below  = (ter.groups[0].below + main.groups[1].below) > 0;
+
+        let main_broad_below = main_broad && main_below;
+        let sec_broad_below  = sec_broad  && sec_below;
+        let ter_broad_below  = ter_broad  && ter_below;
+
+        let main_no_back = main.armor_face > 0.0;
+        let sec_no_back  = sec.armor_face > 0.0;
+        let ter_no_back  = ter.armor_face > 0.0;
+
+        let main_broad_no_back = main_broad && main_no_back;
+        let sec_broad_no_back  = sec_broad && sec_no_back;
+        let ter_broad_no_back  = ter_broad && ter_no_back;
+
+        let has_belt = (
+            self.armor.main.wgt(self.hull.lwl(), self.cwp(), self.hull.b) +
+            self.armor.end.wgt(self.hull.lwl(), self.cwp(), self.hull.b) +
+            self.armor.upper.wgt(self.hull.lwl(), self.cwp(), self.hull.b)
+        ) > 0.0;
+
+        if main_broad || sec_broad || ter_broad {
+            if has_belt {
+                if main_broad_no_back || sec_broad_no_back || ter_broad_no_back {
+                    s.push("Armoured Casemate Ship".into());
+                } else if self.hull.fc_len + self.hull.fd_len < 0.5 {
+                    if main_broad_below || sec_broad_below || ter_broad_below {
+                        s.push("Armoured Frigate (Broadside Ironclad)".into());
+                    } else {
+                        s.push("Armoured Corvette (Broadside Ironclad)".into());
+                    }
+                } else if main_broad_below || sec_broad_below || ter_broad_below {
+                    s.push("Armoured Frigate (Central Battery Ironclad)".into());
+                } else {
+                    s.push("Armoured Corvette (Central Battery Ironclad)".into());
+                }
+            } else if main_broad_below || sec_broad_below || ter_broad_below {
+                s.push("Frigate (Unarmoured)".into());
+            } else {
+                s.push("Corvette (Unarmoured)".into());
+            }
+        }
+
+        s.join("\n")
+    }
+}
+
+// Computed snapshot {{{1
+/// Immutable snapshot of the values calculated from a Ship.
+///
+/// Calculating a snapshot only needs a shared reference to the Ship so
+/// it can be done on a background thread while the Ship is shared
+/// through an `Arc` instead of being cloned.
+///
+#[derive(Clone, Debug)]
+pub struct ShipComputed {
+    /// Ship the values were calculated from.
+    pub ship: Arc<Ship>,
+
+    /// Text report.
+    pub report: String,
+    /// Design failures.
+    pub failures: Vec<String>,
+    /// Design failures and warnings.
+    pub issues: Vec<DesignIssue>,
+
+    /// Light displacement.
+    pub d_lite: Tons,
+    /// Standard displacement.
+    pub d_std: Tons,
+    /// Normal displacement.
+    pub d_normal: Tons,
+    /// Full load displacement.
+    pub d_max: Tons,
+
+    /// Minimum crew.
+    pub crew_min: u32,
+    /// Maximum crew.
+    pub crew_max: u32,
+
+    /// Cost in millions of pounds.
+    pub cost_lb: f64,
+    /// Cost in millions of dollars.
+    pub cost_dollar: f64,
+
+    /// Stability (unstable if below 1.00).
+    pub stability: f64,
+    /// Seaboat quality (average = 1.00).
+    pub seakeeping: f64,
+    /// Gunnery modifier (average = 1.00).
+    pub gunnery_modifier: f64,
+    /// Overall hull strength.
+    pub str_comp: f64,
+}
+
+impl Ship { // {{{2
+    // compute {{{3
+    /// Calculate a snapshot of the ship.
+    ///
+    pub fn compute(ship: &Arc<Ship>) -> ShipComputed {
+        Ship::compute_with(ship, &ReportOptions::default())
+    }
+
+    // compute_with {{{3
+    /// Calculate a snapshot of the ship with the text report written
+    /// using options.
+    ///
+    pub fn compute_with(ship: &Arc<Ship>, options: &ReportOptions) -> ShipComputed {
+        let mut report = String::new();
+        ship.write_report(&mut report, options)
+            .expect("writing to a String cannot fail");
+
+        ShipComputed {
+            ship: Arc::clone(ship),
+
+            report,
+            failures: ship.design_failures(),
+            issues: ship.validate(),
+
+            d_lite: ship.d_lite(),
+            d_std: ship.d_std(),
+            d_normal: ship.hull.d(),
+            d_max: ship.d_max(),
+
+            crew_min: ship.crew_min(),
+            crew_max: ship.crew_max(),
+
+            cost_lb: ship.cost_lb(),
+            cost_dollar: ship.cost_dollar(),
+
+            stability: ship.stability_adj(),
+            seakeeping: ship.seakeeping(),
+            gunnery_modifier: ship.gunnery_modifier(),
+            str_comp: ship.str_comp(),
+        }
+    }
+
+    // compute_in_background {{{3
+    /// Calculate a snapshot of the ship on a background thread.
+    ///
+    pub fn compute_in_background(ship: Arc<Ship>) -> JoinHandle<ShipComputed> {
+        thread::spawn(move || Ship::compute(&ship))
+    }
+}
+
+// Testing Ship {{{2
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod ship {
+    use super::*;
+    use crate::{Template, BoilerType, Condition, EquipmentKind, ScaleOptions, TrainingDrive};
+    use crate::test_support::*;
+    use crate::weapons::{GunLayoutType, TorpedoMountType};
+
+    // Test year_adj {{{3
+    macro_rules! test_year_adj {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year) = $value;
+
+                    assert_eq!(expected, to_place(Ship::year_adj(year), 5));
+                }
+            )*
+        }
+    }
+
+    test_year_adj! {
+        // name:    (year_adj, year)
+        year_adj_1: (0.985, 1889),
+        year_adj_2: (1.0, 1890),
+        year_adj_3: (1.0, 1949),
+        year_adj_4: (1.0, 1950),
+        year_adj_5: (0.0, 1951),
+    }
+
+    // Test deck_space {{{3
+    macro_rules! test_deck_space {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, kind) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull().clone();
+
+                    ship.torps[0].year = 1920;
+                    ship.torps[0].num = 3;
+                    ship.torps[0].mounts = 2;
+                    ship.torps[0].diam = 20.0;
+                    ship.torps[0].len = 10.0;
+                    ship.torps[0].mount_kind = kind;
+
+                    ship.torps[1].num = 0;
+
+                    assert_eq!(expected, to_place(ship.deck_space(), 4));
+                }
+            )*
+        }
+    }
+
+    test_deck_space! {
+        // name:    (deck_space, kind)
+        deck_space_1: (0.002, TorpedoMountType::FixedTubes),
+        deck_space_2: (0.0039, TorpedoMountType::DeckSideTubes),
+        deck_space_3: (0.0415, TorpedoMountType::CenterTubes),
+        deck_space_4: (0.0039, TorpedoMountType::DeckReloads),
+        deck_space_5: (0.0, TorpedoMountType::BowTubes),
+        deck_space_6: (0.0, TorpedoMountType::SternTubes),
+        deck_space_7: (0.0, TorpedoMountType::BowAndSternTubes),
+        deck_space_8: (0.0, TorpedoMountType::SubmergedSideTubes),
+        deck_space_9: (0.0, TorpedoMountType::SubmergedReloads),
+    }
+
+    // Test deck_area_clear {{{3
+    macro_rules! test_deck_area_clear {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((clear, saturated), (diam, mounts)) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull();
+                    ship.year = 1910;
+
+                    ship.batteries[0].diam = diam;
+                    ship.batteries[0].len = 45.0;
+                    ship.batteries[0].year = 1910;
+                    ship.batteries[0].mount_kind = MountType::Deck;
+                    ship.batteries[0].set_layout((0, mounts, 0), GunLayoutType::Single, GunDistributionType::SidesEven);
+
+                    assert_eq!(clear, to_place(ship.deck_area_clear(), 0));
+                    assert_eq!(saturated, ship.deck_saturated());
+                }
+            )*
+        }
+    }
+
+    test_deck_area_clear! {
+        // name:                  ((clear, saturated), (diam, mounts))
+        deck_clear_none:          ((12500.0, false), (0.0, 0)),
+        deck_clear_light:         ((11311.0, false), (4.0, 12)),
+        deck_clear_heavy:         ((10008.0, false), (8.0, 12)),
+        deck_clear_saturated:     ((38.0, true), (8.0, 60)),
+    }
+
+    // Test hull_space {{{3
+    macro_rules! test_hull_space {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, kind) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull().clone();
+
+                    ship.torps[0].year = 1920;
+                    ship.torps[0].num = 3;
+                    ship.torps[0].mounts = 2;
+                    ship.torps[0].diam = 20.0;
+                    ship.torps[0].len = 10.0;
+                    ship.torps[0].mount_kind = kind;
+
+                    ship.torps[1].num = 0;
+
+                    assert_eq!(expected, to_place(ship.hull_space(), 4));
+                }
+            )*
+        }
+    }
+
+    test_hull_space! {
+        // name:    (hull_space, kind)
+        hull_space_1: (0.0, TorpedoMountType::FixedTubes),
+        hull_space_2: (0.0, TorpedoMountType::DeckSideTubes),
+        hull_space_3: (0.0, TorpedoMountType::CenterTubes),
+        hull_space_4: (0.0, TorpedoMountType::DeckReloads),
+        hull_space_5: (0.0064, TorpedoMountType::BowTubes),
+        hull_space_6: (0.0064, TorpedoMountType::SternTubes),
+        hull_space_7: (0.0064, TorpedoMountType::BowAndSternTubes),
+        hull_space_8: (0.0064, TorpedoMountType::SubmergedSideTubes),
+        hull_space_9: (0.0011, TorpedoMountType::SubmergedReloads),
+    }
+
+
+    // Test crew_max {{{3
+    macro_rules! test_crew_max {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let mut ship = Ship::default();
+
+                    let (expected, d) = $value;
+                    ship.hull.set_d(Tons::long(d));
+
+                    assert_eq!(expected, ship.crew_max());
+                }
+            )*
+        }
+    }
+
+    test_crew_max! {
+        // name:            (crew, d)
+        crew_max_d_eq_zero: (0, 0.0),
+        crew_max_d_eq_1000: (115, 1000.0),
+    }
+
+    // Test crew_min {{{3
+    macro_rules! test_crew_min {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let mut ship = Ship::default();
+
+                    let (expected, d) = $value;
+                    ship.hull.set_d(Tons::long(d));
+
+                    assert_eq!(expected, ship.crew_min());
+                }
+            )*
+        }
+    }
+
+    test_crew_min! {
+        // name:            (crew, d)
+        crew_min_d_eq_zero: (0, 0.0),
+        crew_min_d_eq_1000: (88, 1000.0),
+    }
+
+    // Test crew_model {{{3
+    macro_rules! test_crew_model {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((crew, deck_room, cost, noted), (model, year)) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull();
+                    ship.year = year;
+                    ship.crew_model = model;
+
+                    assert_eq!(crew, ship.crew_max());
+                    assert_eq!(deck_room, to_place(ship.deck_room(), 4));
+                    assert_eq!(cost, to_place(ship.cost_dollar(), 3));
+                    assert_eq!(noted, ship.report().contains(&format!("({} estimate)", ship.crew_model)));
+                }
+            )*
+        }
+    }
+
+    test_crew_model! {
+        // name:                ((crew, deck_room, cost, noted), (model, year))
+        crew_model_classic:     ((497, 0.0249, 0.960, false), (CrewModel::Classic, 1910)),
+        crew_model_adjusted:    ((497, 0.0249, 0.960, true), (CrewModel::Adjusted, 1910)),
+        crew_model_early:       ((571, 0.0217, 0.975, true), (CrewModel::Adjusted, 1880)),
+        crew_model_override:    ((1000, 0.0124, 1.061, true), (CrewModel::Override(1000), 1910)),
+    }
+
+    // Test stability_standard {{{3
+    macro_rules! test_report_stability {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((springsharp, simplified), standard) = $value;
+
+                    let ship = Ship {
+                        stability_standard: standard,
+                        ..Ship::from_template(Template::Dreadnought1910)
+                    };
+                    let report = ship.report();
+
+                    assert_eq!(springsharp, report.contains("Stability (Unstable if below 1.00)"));
+                    assert_eq!(simplified, report.contains("Stability (Simplified criteria): Pass"));
+                    assert_eq!(simplified, report.contains("- Metacentric height / beam:"));
+                }
+            )*
+        }
+    }
+
+    test_report_stability! {
+        // name:                      ((springsharp, simplified), standard)
+        report_stability_springsharp: ((true, false), StabilityStandard::SpringSharp),
+        report_stability_simplified:  ((false, true), StabilityStandard::Simplified),
+        report_stability_both:        ((true, true), StabilityStandard::Both),
+    }
+
+    // Test armor_plates {{{3
+    #[test]
+    fn armor_plates() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let plates = ship.armor_plates();
+
+        // The plates account for all of the armor
+        let wgt: Tons = plates.iter().map(|p| p.wgt).sum();
+        assert_eq!(to_place(ship.wgt_armor(), 6), to_place(wgt.to_long(), 6));
+
+        let main = plates.iter().find(|p| p.name == "Main belt").unwrap();
+        assert_eq!(to_place(ship.armor.main.wgt(ship.hull.lwl(), ship.cwp(), ship.hull.b), 6), to_place(main.wgt.to_long(), 6));
+        assert_eq!(to_place(main.thick * Armor::INCH * 2240.0, 6), to_place(main.wgt_per_area(), 6));
+
+        assert!(plates.iter().all(|p| p.thick > 0.0 && p.area > 0.0));
+        assert!(ship.report().contains("- Plate schedule:"));
+        assert!(!Ship::from_template(Template::Destroyer1935).report().contains("- Plate schedule:"));
+    }
+
+    // Test citadel {{{3
+    #[test]
+    fn citadel() {
+        let open = Ship::from_template(Template::Dreadnought1910);
+        let mut ship = open.clone();
+        ship.armor.citadel = Some(armor::Citadel { thick: 6.0, incline: 0.0 });
+
+        let (lwl, cwp, b) = (ship.hull.lwl(), ship.cwp(), ship.hull.b);
+        let wgt = ship.armor.citadel_wgt(lwl, cwp, b);
+        assert!(wgt > 0.0);
+
+        // The citadel bulkheads replace the upper belt's own
+        let own = ship.armor.upper.wgt(lwl, cwp, b) - ship.armor.upper_wgt(lwl, cwp, b);
+        assert!(own > 0.0);
+        assert_eq!(to_place(open.wgt_armor() + wgt - own, 6), to_place(ship.wgt_armor(), 6));
+
+        let plate = ship.armor_plates().into_iter().find(|p| p.name == "Citadel bulkheads").unwrap();
+        assert_eq!(to_place(wgt, 6), to_place(plate.wgt.to_long(), 6));
+
+        assert!(ship.report().contains("    Upper belt closed into a citadel by 6.00\" / 152 mm end bulkheads"));
+        assert!(!open.report().contains("citadel"));
+
+        let json: serde_json::Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+        assert_eq!(6.0, json["armor"]["citadel"]["thick"]["value"].as_f64().unwrap());
+    }
+
+    // Test machinery weights {{{3
+    #[test]
+    fn machinery_wgt() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+
+        assert!(ship.wgt_boilers().to_long() > 0.0);
+        assert_eq!(
+            to_place(ship.wgt_engine(), 6),
+            to_place(ship.wgt_boilers().to_long() + ship.wgt_engines().to_long() + ship.wgt_shafting().to_long(), 6)
+        );
+        assert!(ship.report().contains("    - Shafting & auxiliaries: "));
+
+        let mut hand = ship.clone();
+        for b in hand.batteries.iter_mut() { b.training = Some(TrainingDrive::Hand); }
+        assert!(ship.wgt_aux_plant().to_long() > 0.0);
+        assert_eq!(0.0, hand.wgt_aux_plant().to_long());
+        assert_eq!(
+            to_place(ship.wgt_engine() - ship.wgt_aux_plant().to_long(), 6),
+            to_place(hand.wgt_engine(), 6)
+        );
+
+        let mut motor = ship.clone();
+        motor.engine.fuel = FuelType::Diesel;
+        assert_eq!(0.0, motor.wgt_boilers().to_long());
+        assert!(!motor.report().contains("    - Boilers: "));
+    }
+
+    // Test trial speed {{{3
+    #[test]
+    fn trial_speed() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let report = ship.report();
+
+        assert!(report.contains(&format!("    Trial speed band {:.2} - ", ship.trial_speed(Condition::Full))));
+        assert!(report.contains(" kts at full load)"));
+
+        let json: serde_json::Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+        assert_eq!(ship.engine.vmax, json["machinery"]["trial_speed"]["normal"]["value"].as_f64().unwrap());
+    }
+
+    // Test compartments {{{3
+    #[test]
+    fn compartments() {
+        let plain = Ship::from_template(Template::Dreadnought1910);
+        let mut ship = plain.clone();
+        ship.compartments = Some(16);
+
+        assert!(ship.report().contains("    Subdivision: 16 main compartments of "));
+        assert!(!plain.report().contains("Subdivision"));
+
+        let json: serde_json::Value = serde_json::from_str(&plain.report_json().unwrap()).unwrap();
+        assert_eq!(plain.compartments_default() as u64, json["survivability"]["compartments"].as_u64().unwrap());
+    }
+
+    // Test endurance {{{3
+    #[test]
+    fn endurance() {
+        let plain = Ship::from_template(Template::Dreadnought1910);
+        let mut ship = plain.clone();
+        ship.endurance = Some(90);
+
+        assert!(ship.report().contains("    - Stores for 90 days: "));
+        assert!(!plain.report().contains("Stores for"));
+
+        let json: serde_json::Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+        assert_eq!(90.0, json["complement"]["endurance_days"].as_f64().unwrap());
+        assert_eq!(to_place(ship.wgt_stores().to_long(), 3), to_place(json["weights"]["stores"]["value"].as_f64().unwrap(), 3));
+    }
+
+    // Test superstructure {{{3
+    #[test]
+    fn superstructure() {
+        let block = |kind, wgt| Superstructure { kind, wgt };
+
+        let plain = Ship::from_template(Template::Dreadnought1910);
+        let ship = Ship {
+            superstructure: vec![
+                block(SuperstructureKind::Bridge, Some(Tons::long(100.0))),
+                block(SuperstructureKind::Funnel, Some(Tons::long(50.0))),
+                block(SuperstructureKind::Funnel, Some(Tons::long(50.0))),
+                block(SuperstructureKind::Mast, Some(Tons::long(40.0))),
+            ],
+            ..plain.clone()
+        };
+        let mut anonymous = plain.clone();
+        anonymous.wgts.above += 240;
+
+        assert_eq!(240.0, ship.wgt_superstructure().to_long());
+        assert_eq!(plain.wgt_misc().to_long() + 240.0, ship.wgt_misc().to_long());
+        assert_eq!(anonymous.wgt_misc(), ship.wgt_misc());
+        // The mast carries its weight higher than anonymous weights above deck
+        assert!(ship.stability() < anonymous.stability());
+        assert!(ship.report().contains("    - Superstructure: 240 tons"));
+        assert!(ship.report().contains("windage (bridge, 2 funnels, mast)"));
+        assert!(!plain.report().contains("Superstructure:"));
+    }
+
+    // Test armor backing {{{3
+    macro_rules! test_armor_backing {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, material, year) = $value;
+
+                    let mut ship = Ship::from_template(Template::CentralBattery1870);
+                    (ship.year, ship.armor.material) = (year, material);
+
+                    assert_eq!(expected, to_place(ship.wgt_armor_backing().to_long() / ship.wgt_armor_plates(), 2));
+                }
+            )*
+        }
+    }
+
+    test_armor_backing! {
+        // name:                    (share of plates, material, year)
+        armor_backing_iron:         (0.2, None, 1866),
+        armor_backing_compound:     (0.1, None, 1880),
+        armor_backing_steel:        (0.0, None, 1895),
+        armor_backing_set:          (0.0, Some(ArmorMaterial::Steel), 1866),
+        armor_backing_set_iron:     (0.2, Some(ArmorMaterial::WroughtIron), 1895),
+    }
+
+    #[test]
+    fn armor_backing_central_battery() {
+        let ship = Ship::from_template(Template::CentralBattery1870);
+        let mut steel = ship.clone();
+        steel.armor.material = Some(ArmorMaterial::Steel);
+
+        // Central battery ironclads such as Hercules and Sultan put about
+        // a sixth to a fifth of their displacement into armor and backing
+        let share = ship.wgt_armor() / ship.hull.d().to_long();
+        assert!((0.16..0.22).contains(&share), "{}", share);
+
+        // The backing comes out of the hull weight and is reported
+        assert_eq!(to_place(steel.wgt_hull() - ship.wgt_hull(), 3), to_place(ship.wgt_armor_backing().to_long(), 3));
+        assert!(ship.report().contains("    - Backing for wrought iron plates: "));
+        assert!(!steel.report().contains("Backing"));
+    }
+
+    // Test hull material {{{3
+    #[test]
+    fn hull_material() {
+        let usual = Ship::from_template(Template::Dreadnought1910);
+        let mut welded = usual.clone();
+        welded.hull.material = Some(HullMaterial::Welded);
+
+        assert_eq!(HullMaterial::RivetedSteel, usual.hull_material());
+        assert_eq!(to_place(usual.str_cross() / 0.9, 6), to_place(welded.str_cross(), 6));
+        assert_eq!(to_place(usual.str_long() / 0.9, 6), to_place(welded.str_long(), 6));
+        assert!(welded.str_comp() > usual.str_comp());
+
+        assert!(usual.report().contains("        - Construction: riveted steel (1.00 x usual for 1910)"));
+        assert!(welded.report().contains("        - Construction: welded steel (1.11 x usual for 1910)"));
+
+        let json: serde_json::Value = serde_json::from_str(&welded.report_json().unwrap()).unwrap();
+        assert_eq!("welded steel", json["space"]["hull_material"]);
+    }
+
+    // Test gunnery modifier {{{3
+    macro_rules! test_gunnery_modifier {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, (num, diam)) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull();
+                    ship.year = 1910;
+
+                    ship.batteries[0].diam = diam;
+                    ship.batteries[0].len = 45.0;
+                    ship.batteries[0].year = 1910;
+                    ship.batteries[0].set_layout((1, num / 2 - 1, 0), GunLayoutType::Twin, GunDistributionType::CenterlineEven);
+
+                    assert_eq!(expected, to_place(ship.gunnery_modifier(), 3));
+                }
+            )*
+        }
+    }
+
+    test_gunnery_modifier! {
+        // name:                    (modifier, (guns, diam))
+        gunnery_modifier_gunless:   (0.182, (2, 0.0)),
+        gunnery_modifier_light:     (0.171, (4, 6.0)),
+        gunnery_modifier_medium:    (0.127, (10, 12.0)),
+        gunnery_modifier_heavy:     (0.058, (10, 16.0)),
+        // Recoil restricts the arcs
+        gunnery_modifier_recoil:    (0.019, (12, 18.0)),
+    }
+
+    #[test]
+    fn gunnery_modifier_report() {
+        let mut ship = Ship { hull: get_hull(), year: 1910, ..Default::default() };
+
+        ship.batteries[0].diam = 12.0;
+        ship.batteries[0].len = 45.0;
+        ship.batteries[0].year = 1910;
+        ship.batteries[0].set_layout((1, 4, 0), GunLayoutType::Twin, GunDistributionType::CenterlineEven);
+
+        assert!(ship.report().contains("        - Gunnery modifier (Average = 1.00): 0.13"));
+
+        let json: serde_json::Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+        assert_eq!(ship.gunnery_modifier(), json["survivability"]["gunnery_modifier"]);
+    }
+
+    // Test equipment {{{3
+    #[test]
+    fn equipment() {
+        let item = |kind, wgt| Equipment { kind, wgt };
+
+        let plain = Ship::from_template(Template::Dreadnought1910);
+        let ship = Ship {
+            equipment: vec![
+                item(EquipmentKind::Boats, Some(Tons::long(60.0))),
+                item(EquipmentKind::Paravanes, None),
+                item(EquipmentKind::Minesweeping, Some(Tons::long(8.0))),
+            ],
+            ..plain.clone()
+        };
+        let mut anonymous = plain.clone();
+        anonymous.wgts.on += 70;
+
+        assert_eq!(70.0, ship.wgt_equipment().to_long());
+        assert_eq!(plain.wgt_misc().to_long() + 70.0, ship.wgt_misc().to_long());
+        assert_eq!(anonymous.wgt_misc(), ship.wgt_misc());
+        // Boats carry their weight higher than anonymous weights on deck
+        assert!(ship.stability() < anonymous.stability());
+        assert!(ship.report().contains("    - Equipment: 70 tons (boats, paravanes, minesweeping gear)"));
+        assert!(!plain.report().contains("Equipment:"));
+
+        let json: serde_json::Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+        assert_eq!("paravanes", json["equipment"][1]["kind"]);
+        assert_eq!(70.0, json["weights"]["equipment"]["value"]);
+    }
+
+    // Test torpedo gear {{{3
+    #[test]
+    fn torpedo_gear() {
+        let plain = Ship::from_template(Template::Destroyer1935);
+        let mut ship = plain.clone();
+        ship.torp_director = true;
+        ship.torps[1] = Torpedoes { mount_kind: TorpedoMountType::DeckReloads, reload_gear: true, ..plain.torps[0].clone() };
+        let mut by_hand = ship.clone();
+        by_hand.torps[1].reload_gear = false;
+
+        assert_eq!(5.0, ship.wgt_torp_director().to_long());
+        assert_eq!(to_place(plain.wgt_weaps() - plain.torps[1].wgt() + by_hand.torps[1].wgt() + 5.0, 3), to_place(by_hand.wgt_weaps(), 3));
+        assert_eq!(to_place(by_hand.wgt_weaps() + ship.torps[1].wgt_reload_gear(), 3), to_place(ship.wgt_weaps(), 3));
+        assert!(ship.deck_area_weapons() > by_hand.deck_area_weapons());
+        // The director sits high on the bridge
+        let directed = Ship { torp_director: true, ..plain.clone() };
+        assert!(directed.stability() < plain.stability());
+
+        let report = ship.report();
+        assert!(report.contains("Powered reload gear"));
+        assert!(report.contains("Torpedo director 5.0 t"));
+        assert!(!plain.report().contains("Torpedo director"));
+
+        // No director without torpedoes
+        for t in ship.torps.iter_mut() { t.num = 0; }
+        assert_eq!(0.0, ship.wgt_torp_director().to_long());
+    }
+
+    // Test funnels {{{3
+    #[test]
+    fn funnels() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+
+        assert_eq!(2, ship.funnels());
+        assert!(ship.report().contains("steam turbines, 2 funnels,"));
+        assert!(ship.report().contains("        - Boiler uptakes: "));
+
+        ship.superstructure = vec![
+            Superstructure { kind: SuperstructureKind::Funnel, wgt: None },
+            Superstructure { kind: SuperstructureKind::Funnel, wgt: None },
+            Superstructure { kind: SuperstructureKind::Funnel, wgt: None },
+        ];
+        assert_eq!(3, ship.funnels());
+        assert_eq!(to_place(ship.uptake_len(), 6), to_place(3.0 * Engine::funnel_area(ship.hull.b) * Ship::UPTAKE_CASING / (ship.hull.b * Ship::UPTAKE_WIDTH), 6));
+
+        ship.engine.vmax = 0.0;
+        ship.superstructure.clear();
+        assert_eq!(0, ship.funnels());
+        assert!(!ship.report().contains("Boiler uptakes"));
+    }
+
+    // Test vertical centers {{{3
+    macro_rules! test_vertical_centers {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let template = $value;
+                    let ship = Ship::from_template(template);
+
+                    assert!(ship.kb() > 0.0 && ship.kb() < ship.hull.t);
+                    assert!(ship.kg() > ship.kb());
+                    assert!(ship.kg() < ship.hull.t + ship.hull.freeboard_dist());
+                    assert_eq!(
+                        crate::test_support::to_place(ship.metacenter(), 6),
+                        crate::test_support::to_place(ship.kb() + ship.bm() - ship.kg(), 6)
+                    );
+                }
+            )*
+        }
+    }
+
+    test_vertical_centers! {
+        // name:                       template
+        vertical_centers_ironclad:     Template::CentralBattery1870,
+        vertical_centers_dreadnought:  Template::Dreadnought1910,
+        vertical_centers_destroyer:    Template::Destroyer1935,
+        vertical_centers_fast_bb:      Template::FastBattleship1940,
+    }
+
+    // Test void {{{3
+    macro_rules! test_void {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, bulge_len, void) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.hull = get_hull();
+                    ship.hull.bb = ship.hull.b + 12.0;
+                    ship.armor.main.len = 300.0;
+                    ship.armor.bulge.len = bulge_len;
+                    ship.wgts.void = void;
+
+                    assert_eq!(expected, crate::test_support::to_place(ship.wgt_void().to_long(), 0));
+                    assert_eq!(ship.wgt_misc(), ship.wgt_void());
+                }
+            )*
+        }
+    }
+
+    test_void! {
+        // name:          (void, bulge_len, void override)
+        void_estimated:   (257.0, 0.0, None),
+        void_short:       (171.0, 200.0, None),
+        void_override:    (500.0, 0.0, Some(500)),
+        void_no_void:     (0.0, 0.0, Some(0)),
+    }
+
+    #[test]
+    fn void_bulges() {
+        let mut plain = Ship { hull: get_hull(), year: 1910, ..Default::default() };
+        plain.armor.main.len = 300.0;
+
+        let mut ship = plain.clone();
+        ship.hull.bb = ship.hull.b + 12.0;
+
+        assert_eq!(0.0, plain.wgt_void().to_long());
+        assert!(ship.wgt_hull() < Ship { wgts: MiscWgts { void: Some(0), ..Default::default() }, ..ship.clone() }.wgt_hull());
+        assert!(ship.damage_torp_num() > plain.damage_torp_num());
+        assert!(ship.report().contains("Bulge void weights: 257 tons (estimated)"));
+
+        // Short bulges protect less of the hull
+        let mut short = ship.clone();
+        short.armor.bulge.len = 100.0;
+        assert!(short.damage_torp_num() < ship.damage_torp_num());
+    }
+
+    // Test magazine weights {{{3
+    #[test]
+    fn magazine_extent() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.armor.deck.kind = armor::DeckType::BoxOverMagazine;
+
+        let deck = |wgt_mag| ship.armor.deck.wgt(ship.hull.clone(), ship.engine.shafts(), wgt_mag, 0.0);
+
+        assert_eq!(to_place(ship.wgt_mag(), 6), to_place(ship.wgt_mag_room() + ship.wgt_mag_hoists(), 6));
+        assert!(ship.wgt_mag_hoists() > 0.0);
+        assert!(deck(ship.wgt_mag_room()) < deck(ship.wgt_mag()));
+        assert!(ship.report().contains("    - Hoists & handling: "));
+    }
+
+    // Test box_extent {{{3
+    #[test]
+    fn box_extent() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.armor.deck.kind = armor::DeckType::BoxOverBoth;
+
+        let (d, lwl) = (ship.hull.d().to_long(), ship.hull.lwl());
+        let len = ship.armor.deck.kind.box_len(d, lwl, ship.wgt_engine_est(), ship.wgt_mag_room()).unwrap();
+        let deck = |ship: &Ship| ship.armor.deck.wgt(ship.hull.clone(), ship.engine.shafts(), ship.wgt_mag_room(), ship.wgt_engine_est());
+
+        // The box covers the machinery and magazines, inside the vitals
+        assert!(len > ship.armor.deck.kind.box_len(d, lwl, 0.0, ship.wgt_mag_room()).unwrap());
+        assert!(len < ship.vitalspace_length());
+
+        // Giving the extent overrides it
+        let mut given = ship.clone();
+        given.armor.deck.box_len = Some(len);
+        assert_eq!(to_place(deck(&ship), 6), to_place(deck(&given), 6));
+
+        given.armor.deck.box_len = Some(len * 2.0);
+        given.armor.deck.box_beam = Some(ship.hull.b / 2.0);
+        let (main, ..) = given.armor.deck.areas(given.hull.clone(), given.engine.shafts(), given.wgt_mag_room(), given.wgt_engine_est());
+        assert_eq!(to_place(armor::DeckType::box_area(len * 2.0, ship.hull.b / 2.0), 6), to_place(main, 6));
+
+        // Other decks ignore the extent
+        given.armor.deck.kind = armor::DeckType::MultipleArmored;
+        ship.armor.deck.kind = armor::DeckType::MultipleArmored;
+        assert_eq!(deck(&ship), deck(&given));
+    }
+
+    // Test box_independent {{{3
+    #[test]
+    fn box_independent() {
+        // Small and overweight, so the machinery weight depends on the
+        // displacement factor
+        let mut ship = Ship::from_template(Template::Destroyer1935);
+        ship.armor.deck = armor::Deck { kind: armor::DeckType::BoxOverMachinery, md: 3.0, ..Default::default() };
+
+        let mut thicker = ship.clone();
+        thicker.armor.deck.md = ship.armor.deck.md * 2.0;
+
+        // A thicker deck changes the machinery weight through the
+        // displacement factor but not the box it is sized from
+        assert_ne!(ship.wgt_engine(), thicker.wgt_engine());
+        assert_eq!(ship.wgt_engine_est(), thicker.wgt_engine_est());
+
+        let deck = |ship: &Ship| ship.armor.deck.wgt(ship.hull.clone(), ship.engine.shafts(), ship.wgt_mag_room(), ship.wgt_engine_est());
+        assert_eq!(to_place(deck(&ship) * 2.0, 6), to_place(deck(&thicker), 6));
+    }
+
+    // Test compute {{{3
+    #[test]
+    fn ship_is_sync() {
+        fn is_sync<T: Send + Sync>() {}
+
+        is_sync::<Ship>();
+        is_sync::<ShipComputed>();
+    }
+
+    #[test]
+    fn compute_in_background() {
+        let mut ship = Ship { hull: get_hull(), year: 1920, ..Default::default() };
+        ship.engine.year = 1920;
+
+        let ship = Arc::new(ship);
+        let computed = Ship::compute_in_background(Arc::clone(&ship)).join().unwrap();
+
+        assert_eq!(ship.report(), computed.report);
+        assert_eq!(ship.d_max(), computed.d_max);
+        assert!(Arc::ptr_eq(&ship, &computed.ship));
+    }
+
+    // Test no propulsion {{{3
+    macro_rules! test_no_propulsion {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (template, engine_year, fuel, boiler, vcruise, range) = $value;
+                    let mut ship = Ship::from_template(template);
+                    ship.engine.vmax = 0.0;
+                    ship.engine.year = engine_year;
+                    ship.engine.fuel = fuel;
+                    ship.engine.boiler = boiler;
+                    ship.engine.vcruise = vcruise;
+                    ship.engine.range = range;
+
+                    let report: Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+
+                    assert!(ship.report().contains("Immobile floating battery"));
+                    assert_eq!("None", report["machinery"]["propulsion"].as_str().unwrap());
+                    assert_eq!(ship.wgt_aux_plant().to_long(), ship.wgt_engine());
+                    assert_eq!(0.0, ship.wgt_bunker());
+                    assert!(ship.d_max().to_long().is_finite());
+                    assert!(ship.seakeeping().is_finite());
+                    assert!(ship.str_comp().is_finite());
+                }
+            )*
+        }
+    }
+
+    test_no_propulsion! {
+        // name:                 (template, engine_year, fuel, boiler, vcruise, range)
+        no_propulsion_battery:   (Template::CentralBattery1870, 0, FuelType::empty(), BoilerType::empty(), 0.0, 0),
+        no_propulsion_hulk:      (Template::Dreadnought1910, 1910, FuelType::Coal, BoilerType::Turbine, 10.0, 6500),
+    }
+
+    // Test sea_state {{{3
+    macro_rules! test_sea_state {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (excluded, sea) = $value;
+                    let mut ship = Ship::from_template(Template::CentralBattery1870);
+                    ship.sea_state = sea;
+
+                    assert_eq!(excluded, ship.wgt_broad_at(ship.sea_state) < ship.wgt_broad());
+                    assert!(ship.validate().iter().any(|i| i.code == "casemates-unusable"));
+                }
+            )*
+        }
+    }
+
+    test_sea_state! {
+        // name:              (excluded, sea state)
+        sea_state_calm:       (false, SeaState::Calm),
+        sea_state_light:      (false, SeaState::Light),
+        sea_state_moderate:   (true, SeaState::Moderate),
+        sea_state_heavy:      (true, SeaState::Heavy),
+    }
+
+    // Test gunless {{{3
+    /// Destroyer with its guns removed and the weapons given.
+    ///
+    fn gunless(torps: u32, mines: u32, factor: f64) -> Ship {
+        let mut ship = Ship::from_template(Template::Destroyer1935)
+            .scaled(factor, &ScaleOptions::default()).unwrap();
+
+        for b in ship.batteries.iter_mut() { *b = Battery::default(); }
+        ship.default_years();
+        ship.torps[0].num = torps;
+        ship.torps[0].mounts = torps.min(2);
+        ship.mines.num = mines;
+        ship.mines.wgt = 500.0;
+        ship.asw[0].num = 0;
+
+        ship
+    }
+
+    macro_rules! test_gunless {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (armament, ship) = $value;
+
+                    assert!(ship.is_gunless());
+                    assert_eq!(1.0, ship.gun_super_factor());
+                    assert_eq!(6.0, ship.damage_shell_size());
+                    assert!(ship.stability_adj().is_finite());
+                    assert!(ship.str_comp().is_finite());
+                    assert!(ship.recoil().is_finite());
+
+                    let report = ship.report();
+                    assert!(!report.contains("NaN"), "{}", report);
+                    assert!(!report.contains("Weight of broadside"));
+                    assert!(!report.contains("Recoil effect"));
+                    assert_eq!(armament, report.contains("\nArmament:"));
+
+                    let json: Value = serde_json::from_str(&ship.report_json().unwrap()).unwrap();
+                    assert!(json["survivability"]["stability"].is_f64());
+                    assert!(json["survivability"]["recoil"].is_f64());
+                    assert!(json["space"]["deck_room"]["value"].is_f64());
+                    assert!(json["space"]["str_comp"].is_f64());
+
+                    let codes: Vec<_> = ship.validate().into_iter().map(|i| i.code).collect();
+                    assert!(!codes.iter().any(|c| ["guns-too-heavy", "gun-over-length", "casemates-unusable",
+                        "infeasible-mount", "mount-count-mismatch", "hand-trained-mount"].contains(c)));
+                }
+            )*
+        }
+    }
+
+    test_gunless! {
+        // name:              (armament, ship)
+        gunless_minelayer:    (true, gunless(0, 200, 1.0)),
+        gunless_mtb:          (true, gunless(2, 0, 0.5)),
+        gunless_unarmed:      (false, gunless(0, 0, 1.0)),
+    }
+
+    #[test]
+    fn gunless_empty_main() {
+        // Calibre without guns is not a main battery
+        let mut ship = gunless(2, 0, 1.0);
+        ship.batteries[0].diam = 12.0;
+
+        assert!(ship.is_gunless());
+        assert_eq!(6.0, ship.damage_shell_size());
+        assert!(!Ship::from_template(Template::Destroyer1935).is_gunless());
+    }
+
+}
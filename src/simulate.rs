@@ -0,0 +1,345 @@
+use crate::Ship;
+use crate::weapons::Battery;
+
+use serde::Serialize;
+
+// Constants {{{1
+/// Length of a simulation step (minutes).
+const STEP: f64 = 1.0;
+/// Longest engagement simulated (minutes).
+const MAX_TIME: f64 = 180.0;
+/// Fraction of damage done by shells that do not penetrate the armor.
+const NON_PEN_DAMAGE: f64 = 0.25;
+
+// DuelStep {{{1
+/// State of the engagement at a point in time.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct DuelStep {
+    /// Minutes since the engagement started.
+    pub time: f64,
+    /// Fraction of ship a's flotation lost.
+    pub a_damage: f64,
+    /// Fraction of ship b's flotation lost.
+    pub b_damage: f64,
+}
+
+// Outcome {{{1
+/// Result of the engagement.
+///
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    /// Ship a sinks ship b first.
+    AWins,
+    /// Ship b sinks ship a first.
+    BWins,
+    /// Both ships sink at the same time.
+    MutualDestruction,
+    /// Neither ship sinks within the time limit.
+    Draw,
+}
+
+// Duel {{{1
+/// Expected exchange between two ships at a fixed range.
+///
+#[derive(Serialize, Clone, Debug)]
+pub struct Duel {
+    /// Range of the engagement (yards).
+    pub range: f64,
+    /// Damage dealt to ship b per minute (fraction of flotation).
+    pub a_rate: f64,
+    /// Damage dealt to ship a per minute (fraction of flotation).
+    pub b_rate: f64,
+    /// Minutes for ship a to sink ship b, if it can.
+    pub a_time_to_sink: Option<f64>,
+    /// Minutes for ship b to sink ship a, if it can.
+    pub b_time_to_sink: Option<f64>,
+    /// Result of the engagement.
+    pub outcome: Outcome,
+    /// Damage over time.
+    pub timeline: Vec<DuelStep>,
+}
+
+// duel {{{1
+/// Simulate a gunnery duel between two ships at a given range (yards).
+///
+/// This is a coarse model: each ship fires every gun that bears on the
+/// broadside at a rate set by caliber and hits a fraction of the time set
+/// by range and its gunnery modifier, which allows for how steady a gun
+/// platform it is and for recoil that restricts its arcs. Damage is measured the
+/// same way as the survivability numbers in the report, and shells that
+/// cannot penetrate the target's main belt or decks do reduced damage.
+/// The longer the range the more hits land on the deck.
+///
+pub fn duel(a: &Ship, b: &Ship, range: f64) -> Duel {
+    let a_rate = damage_rate(a, b, range);
+    let b_rate = damage_rate(b, a, range);
+
+    let time_to_sink = |rate: f64| if rate > 0.0 { Some(1.0 / rate) } else { None };
+    let a_time_to_sink = time_to_sink(a_rate);
+    let b_time_to_sink = time_to_sink(b_rate);
+
+    let mut timeline = vec![DuelStep { time: 0.0, a_damage: 0.0, b_damage: 0.0 }];
+    let mut time = 0.0;
+    while time < MAX_TIME {
+        time += STEP;
+
+        let step = DuelStep {
+            time,
+            a_damage: (b_rate * time).min(1.0),
+            b_damage: (a_rate * time).min(1.0),
+        };
+        let done = step.a_damage >= 1.0 || step.b_damage >= 1.0;
+
+        timeline.push(step);
+        if done { break; }
+    }
+
+    let sinks = |t: Option<f64>| t.filter(|t| *t <= MAX_TIME);
+    let outcome = match (sinks(a_time_to_sink), sinks(b_time_to_sink)) {
+        (Some(ta), Some(tb)) if ta < tb => Outcome::AWins,
+        (Some(ta), Some(tb)) if tb < ta => Outcome::BWins,
+        (Some(_), Some(_))              => Outcome::MutualDestruction,
+        (Some(_), None)                 => Outcome::AWins,
+        (None, Some(_))                 => Outcome::BWins,
+        (None, None)                    => Outcome::Draw,
+    };
+
+    Duel { range, a_rate, b_rate, a_time_to_sink, b_time_to_sink, outcome, timeline }
+}
+
+// damage_rate {{{2
+/// Fraction of the target's flotation destroyed per minute by the shooter.
+///
+fn damage_rate(shooter: &Ship, target: &Ship, range: f64) -> f64 {
+    let flotation = target.flotation() * target.crew_quality.damage_control();
+    if flotation <= 0.0 { return 0.0; }
+
+    let accuracy = shooter.gunnery_modifier() * shooter.crew_quality.rate_of_fire();
+
+    // A belt that is entirely under water does not keep out shells
+    let belt = if target.armor.belt_above_water(target.hull.t, target.hull.freeboard_dist()) > 0.0 {
+        target.armor.main.thick
+    } else {
+        0.0
+    };
+
+    shooter.batteries.iter()
+        .filter(|b| b.num > 0 && b.diam > 0.0)
+        .map(|b| {
+            let (_, guns, _) = b.arcs(shooter.hull.clone());
+
+            let hits = guns as f64 * b.rate_of_fire() * hit_chance(b, range) * accuracy;
+            let damage = target.params.shell_damage(b.diam) * Ship::year_adj(target.year);
+            let belt_pen = if penetration(b, range) >= belt { 1.0 } else { NON_PEN_DAMAGE };
+            let deck_pen = if deck_penetration(b, range) >= target.armor.deck.horizontal() { 1.0 } else { NON_PEN_DAMAGE };
+            let plunging = plunging(b, range);
+            let pen = belt_pen * (1.0 - plunging) + deck_pen * plunging;
+
+            hits * damage * pen
+        })
+        .sum::<f64>() / flotation
+}
+
+// max_range {{{2
+/// Maximum range of the gun (yards).
+///
+fn max_range(b: &Battery) -> f64 {
+    b.diam * 2000.0
+}
+
+// hit_chance {{{2
+/// Chance of each round hitting at the given range (yards).
+///
+fn hit_chance(b: &Battery, range: f64) -> f64 {
+    if range > max_range(b) { return 0.0; }
+
+    0.1 * (-range / 8000.0).exp()
+}
+
+// penetration {{{2
+/// Belt armor penetrated at the given range (inches).
+///
+fn penetration(b: &Battery, range: f64) -> f64 {
+    b.diam * 1.5 * (-range / (b.diam * 2500.0)).exp()
+}
+
+// deck_penetration {{{2
+/// Deck armor penetrated at the given range (inches).
+///
+fn deck_penetration(b: &Battery, range: f64) -> f64 {
+    b.diam * 0.5 * (range / max_range(b)).min(1.0)
+}
+
+// plunging {{{2
+/// Fraction of hits at the given range that strike the deck rather than
+/// the belt.
+///
+fn plunging(b: &Battery, range: f64) -> f64 {
+    (range / max_range(b)).min(1.0).powf(2.0)
+}
+
+// immune_zone {{{1
+/// Band of ranges (yards) where the battery can penetrate neither the
+/// target's main belt nor its decks.
+///
+/// The inner edge is set by the belt and the outer edge by the combined
+/// thickness of the main and splinter decks.
+///
+pub fn immune_zone(b: &Battery, target: &Ship) -> Option<(f64, f64)> {
+    let belt = target.armor.main.thick;
+    let deck = target.armor.deck.horizontal();
+    if b.diam <= 0.0 || belt <= 0.0 || deck <= 0.0 { return None; }
+
+    let inner = (b.diam * 2500.0 * (b.diam * 1.5 / belt).ln()).max(0.0);
+    let outer = (max_range(b) * deck / (b.diam * 0.5)).min(max_range(b));
+
+    if inner < outer { Some((inner, outer)) } else { None }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod simulate {
+    use super::*;
+    use crate::Tons;
+    use crate::test_support::*;
+    use crate::armor::SplinterDeck;
+    use crate::weapons::{GunDistributionType, GunLayoutType, MountType};
+
+    fn get_ship(diam: f64, belt: f64) -> Ship {
+        let mut ship = Ship { year: 1910, ..Default::default() };
+
+        ship.hull.set_d(Tons::long(20000.0));
+        ship.hull.set_lwl(550.0);
+        ship.hull.b = 85.0;
+        ship.hull.bb = ship.hull.b;
+        ship.hull.t = 27.0;
+        ship.hull.fc_len = 0.2;
+        ship.hull.fd_len = 0.3;
+        ship.hull.qd_len = 0.15;
+        ship.hull.fc_fwd = 28.0;
+        ship.hull.fc_aft = 28.0;
+        ship.hull.fd_fwd = 20.0;
+        ship.hull.fd_aft = 20.0;
+        ship.hull.ad_fwd = 20.0;
+        ship.hull.ad_aft = 20.0;
+        ship.hull.qd_fwd = 20.0;
+        ship.hull.qd_aft = 20.0;
+
+        ship.engine.year = 1910;
+
+        ship.armor.main.thick = belt;
+        ship.armor.main.len = 300.0;
+        ship.armor.main.hgt = 8.0;
+
+        let b = &mut ship.batteries[0];
+        b.num = 8;
+        b.diam = diam;
+        b.len = 45.0;
+        b.year = 1910;
+        b.mount_kind = MountType::ClosedBarbette;
+        b.mount_num = 4;
+        b.groups[0].layout = GunLayoutType::Twin;
+        b.groups[0].distribution = GunDistributionType::CenterlineEven;
+        b.groups[0].on = 4;
+
+        ship
+    }
+
+    // Test penetration {{{2
+    macro_rules! test_penetration {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, diam, range) = $value;
+
+                    let mut b = Battery::default();
+                    b.diam = diam;
+
+                    assert_eq!(expected, to_place(penetration(&b, range), 2));
+                }
+            )*
+        }
+    }
+
+    test_penetration! {
+        // name:            (penetration, diam, range)
+        penetration_12_0:   (18.0, 12.0, 0.0),
+        penetration_12_10k: (12.9, 12.0, 10000.0),
+        penetration_6_10k:  (4.62, 6.0, 10000.0),
+    }
+
+    // Test immune_zone {{{2
+    macro_rules! test_immune_zone {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, belt, md, splinter) = $value;
+
+                    let shooter = get_ship(12.0, 0.0);
+                    let mut target = get_ship(12.0, belt);
+                    target.armor.deck.md = md;
+                    if splinter > 0.0 {
+                        target.armor.deck.splinter = Some(SplinterDeck { thick: splinter, ..Default::default() });
+                    }
+
+                    let zone = immune_zone(&shooter.batteries[0], &target)
+                        .map(|(i, o)| (to_place(i, 0), to_place(o, 0)));
+
+                    assert_eq!(expected, zone);
+                }
+            )*
+        }
+    }
+
+    test_immune_zone! {
+        // name:                 (zone, belt, md, splinter)
+        immune_zone_none:        (None, 12.0, 0.0, 0.0),
+        immune_zone_no_belt:     (None, 0.0, 3.0, 0.0),
+        immune_zone_thin_deck:   (None, 12.0, 3.0, 0.0),
+        immune_zone_main:        (Some((12164.0, 16000.0)), 12.0, 4.0, 0.0),
+        immune_zone_splinter:    (Some((12164.0, 16000.0)), 12.0, 3.0, 1.0),
+    }
+
+    // Test duel {{{2
+    #[test]
+    fn duel_equal_ships() {
+        let a = get_ship(12.0, 10.0);
+        let d = duel(&a, &a, 10000.0);
+
+        assert_eq!(d.a_rate, d.b_rate);
+        assert_eq!(Outcome::MutualDestruction, d.outcome);
+    }
+
+    #[test]
+    fn duel_belt_keeps_out_shells() {
+        let a = get_ship(12.0, 13.0);
+        let b = get_ship(12.0, 6.0);
+        let d = duel(&a, &b, 12000.0);
+
+        assert!(d.a_rate > d.b_rate);
+        assert_eq!(Outcome::AWins, d.outcome);
+    }
+
+    #[test]
+    fn duel_belt_below_water() {
+        let a = get_ship(12.0, 13.0);
+        let mut b = get_ship(12.0, 13.0);
+        b.armor.main.hgt = 4.0;
+        let d = duel(&a, &b, 12000.0);
+
+        assert!(d.a_rate > d.b_rate);
+    }
+
+    #[test]
+    fn duel_out_of_range() {
+        let a = get_ship(12.0, 10.0);
+        let d = duel(&a, &a, 50000.0);
+
+        assert_eq!(None, d.a_time_to_sink);
+        assert_eq!(Outcome::Draw, d.outcome);
+    }
+}
@@ -0,0 +1,181 @@
+use crate::Ship;
+
+use serde::{Serialize, Deserialize};
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+// Snapshot {{{1
+/// Key values calculated from a ship, kept in its file so the numbers
+/// a design was published with survive changes to the formulas.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    /// Version of sharpie that calculated the values.
+    pub sharpie: String,
+    /// Checksum of the ship the values were calculated from.
+    pub checksum: String,
+    /// Values by the name `docs::formula()` knows them by.
+    pub values: BTreeMap<String, f64>,
+}
+
+impl Snapshot { // {{{2
+    /// Relative change in a value that counts as drift, enough to show
+    /// in the report.
+    const DRIFT: f64 = 0.001;
+
+    // is_of {{{3
+    /// True if the snapshot was taken of the ship as it is now rather
+    /// than before it was changed.
+    ///
+    pub fn is_of(&self, ship: &Ship) -> Result<bool, Box<dyn Error>> {
+        Ok(self.checksum == ship.checksum()?)
+    }
+
+    // drift {{{3
+    /// Values that have changed from this snapshot to a later one.
+    ///
+    pub fn drift(&self, now: &Snapshot) -> Vec<Drift> {
+        self.values.iter()
+            .filter_map(|(name, saved)| {
+                let now = *now.values.get(name)?;
+                ((now - saved).abs() > saved.abs() * Self::DRIFT)
+                    .then(|| Drift { name: name.clone(), saved: *saved, now })
+            })
+            .collect()
+    }
+}
+
+// Drift {{{1
+/// A value that has changed since a snapshot was taken.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Drift {
+    /// Name of the value.
+    pub name: String,
+    /// Value in the snapshot.
+    pub saved: f64,
+    /// Value calculated now.
+    pub now: f64,
+}
+
+impl fmt::Display for Drift { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {:.2} saved, {:.2} now ({:+.1}%)",
+            self.name, self.saved, self.now,
+            if self.saved != 0.0 { (self.now / self.saved - 1.0) * 100.0 } else { 100.0 })
+    }
+}
+
+impl Ship { // {{{1
+    // take_snapshot {{{2
+    /// Calculate the ship's key values along with the version of sharpie
+    /// and a checksum of the ship.
+    ///
+    pub fn take_snapshot(&self) -> Result<Snapshot, Box<dyn Error>> {
+        let values = [
+            ("d_lite",           self.d_lite().to_long()),
+            ("d_std",            self.d_std().to_long()),
+            ("d_normal",         self.hull.d().to_long()),
+            ("d_max",            self.d_max().to_long()),
+            ("vmax",             self.engine.vmax),
+            ("crew_min",         self.crew_min() as f64),
+            ("crew_max",         self.crew_max() as f64),
+            ("cost_dollar",      self.cost_dollar()),
+            ("flotation",        self.flotation()),
+            ("damage_shell_num", self.damage_shell_num()),
+            ("damage_torp_num",  self.damage_torp_num()),
+            ("stability_adj",    self.stability_adj()),
+            ("seakeeping",       self.seakeeping()),
+            ("gunnery_modifier", self.gunnery_modifier()),
+            ("str_comp",         self.str_comp()),
+        ];
+
+        Ok(Snapshot {
+            sharpie: env!("CARGO_PKG_VERSION").into(),
+            checksum: self.checksum()?,
+            values: values.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        })
+    }
+
+    // snapshot_drift {{{2
+    /// Values that have changed since the snapshot in the ship file was
+    /// taken, or None if it has none.
+    ///
+    pub fn snapshot_drift(&self) -> Result<Option<Vec<Drift>>, Box<dyn Error>> {
+        match &self.snapshot {
+            Some(saved) => Ok(Some(saved.drift(&self.take_snapshot()?))),
+            None        => Ok(None),
+        }
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod snapshot {
+    use super::*;
+    use crate::Template;
+
+    // Test drift {{{2
+    macro_rules! test_drift {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, change) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    let mut snapshot = ship.take_snapshot().unwrap();
+                    change(&mut snapshot);
+                    ship.snapshot = Some(snapshot);
+
+                    assert_eq!(expected, ship.snapshot_drift().unwrap().unwrap().iter().map(|d| d.name.as_str()).collect::<Vec<_>>());
+                }
+            )*
+        }
+    }
+
+    test_drift! {
+        // name:            (drifted, change(snapshot))
+        drift_none:         (Vec::<&str>::new(), |_: &mut Snapshot| ()),
+        drift_rounding:     (Vec::<&str>::new(), |s: &mut Snapshot| *s.values.get_mut("str_comp").unwrap() *= 1.0005),
+        drift_value:        (vec!["str_comp"], |s: &mut Snapshot| *s.values.get_mut("str_comp").unwrap() *= 1.01),
+        drift_two:          (vec!["cost_dollar", "d_std"], |s: &mut Snapshot| {
+                                *s.values.get_mut("cost_dollar").unwrap() = 1.0;
+                                *s.values.get_mut("d_std").unwrap() += 100.0;
+                            }),
+        drift_new_value:    (Vec::<&str>::new(), |s: &mut Snapshot| { s.values.remove("seakeeping"); }),
+        drift_old_value:    (Vec::<&str>::new(), |s: &mut Snapshot| { s.values.insert("retired".into(), 1.0); }),
+    }
+
+    #[test]
+    fn documented() {
+        let snapshot = Ship::from_template(Template::Dreadnought1910).take_snapshot().unwrap();
+
+        assert!(snapshot.values.keys().all(|k| crate::docs::formula(k).is_some()));
+    }
+
+    #[test]
+    fn is_of() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        let snapshot = ship.take_snapshot().unwrap();
+        assert!(snapshot.is_of(&ship).unwrap());
+
+        ship.hull.b += 1.0;
+        assert!(!snapshot.is_of(&ship).unwrap());
+    }
+
+    #[test]
+    fn drift_missing() {
+        assert_eq!(None, Ship::from_template(Template::Dreadnought1910).snapshot_drift().unwrap());
+    }
+
+    #[test]
+    fn drift_display() {
+        let drift = Drift { name: "str_comp".into(), saved: 1.0, now: 1.1 };
+
+        assert_eq!("str_comp: 1.00 saved, 1.10 now (+10.0%)", drift.to_string());
+    }
+}
@@ -0,0 +1,279 @@
+use crate::Ship;
+
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+// StabilityStandard {{{1
+/// Standard the ship's stability is judged against.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum StabilityStandard {
+    /// SpringSharp's relative stability index.
+    #[default]
+    SpringSharp,
+    /// Simplified intact stability criteria in the style of the IMO code.
+    Simplified,
+    /// Report against both standards.
+    Both,
+}
+
+impl From<String> for StabilityStandard { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for StabilityStandard {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "1"     => Self::Simplified,
+            "2"     => Self::Both,
+            "0" | _ => Self::SpringSharp,
+        }
+    }
+}
+
+impl fmt::Display for StabilityStandard { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::SpringSharp => "SpringSharp",
+                Self::Simplified  => "Simplified",
+                Self::Both        => "Both",
+            }
+        )
+    }
+}
+
+impl StabilityStandard { // {{{2
+    // springsharp {{{3
+    /// True if the SpringSharp index is reported.
+    ///
+    pub fn springsharp(&self) -> bool {
+        *self != Self::Simplified
+    }
+
+    // simplified {{{3
+    /// True if the simplified criteria are reported.
+    ///
+    pub fn simplified(&self) -> bool {
+        *self != Self::SpringSharp
+    }
+}
+
+// StabilityCriterion {{{1
+/// One check of the simplified stability criteria.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct StabilityCriterion {
+    /// What is being checked.
+    pub name: &'static str,
+    /// Value for the ship.
+    pub value: f64,
+    /// Smallest passing value.
+    pub min: f64,
+    /// Largest passing value.
+    pub max: Option<f64>,
+}
+
+impl fmt::Display for StabilityCriterion { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {:.2} ({}) - {}",
+            self.name,
+            self.value,
+            match self.max {
+                Some(max) => format!("{:.2} to {:.2}", self.min, max),
+                None      => format!("at least {:.2}", self.min),
+            },
+            if self.pass() { "Pass" } else { "Fail" }
+        )
+    }
+}
+
+impl StabilityCriterion { // {{{2
+    // pass {{{3
+    /// True if the value is within limits.
+    ///
+    pub fn pass(&self) -> bool {
+        self.value >= self.min && self.max.is_none_or(|max| self.value <= max)
+    }
+}
+
+impl Ship { // {{{1
+    /// Smallest metacentric height (ft), 0.15 m.
+    const MIN_GM: f64 = 0.49;
+    /// Metacentric height to beam ratio below which a ship is tender.
+    const MIN_GM_B: f64 = 0.04;
+    /// Metacentric height to beam ratio above which a ship rolls violently.
+    const MAX_GM_B: f64 = 0.12;
+    /// Smallest heel (degrees) that puts the deck edge under.
+    const MIN_DECK_EDGE: f64 = 15.0;
+    /// Pressure (lb/sq ft) of a steady gale on the ship's side.
+    const WIND_PRESSURE: f64 = 10.5;
+    /// Largest heel (degrees) a steady gale may cause.
+    const MAX_WIND_HEEL: f64 = 16.0;
+
+    // stability_criteria {{{2
+    /// Check the estimated metacentric height and freeboard against
+    /// simplified intact stability criteria.
+    ///
+    pub fn stability_criteria(&self) -> Vec<StabilityCriterion> {
+        let gm = self.metacenter();
+        let gm_b = if self.hull.bb > 0.0 { gm / self.hull.bb } else { 0.0 };
+        let deck_edge = self.deck_edge_angle();
+
+        vec![
+            StabilityCriterion { name: "Metacentric height (ft)", value: gm, min: Self::MIN_GM, max: None },
+            StabilityCriterion { name: "Metacentric height / beam", value: gm_b, min: Self::MIN_GM_B, max: Some(Self::MAX_GM_B) },
+            StabilityCriterion { name: "Deck edge immersion (degrees)", value: deck_edge, min: Self::MIN_DECK_EDGE, max: None },
+            StabilityCriterion { name: "Wind heel (degrees)", value: self.wind_heel(), min: 0.0, max: Some(Self::MAX_WIND_HEEL) },
+        ]
+    }
+
+    // wind_heel {{{2
+    /// Heel (degrees) a steady gale on the beam causes.
+    ///
+    /// The wind acts on the hull side above water and on the windage of
+    /// the superstructure, each at its centre of area, with the lever
+    /// taken from half the draught.
+    ///
+    pub fn wind_heel(&self) -> f64 {
+        let gm = self.metacenter();
+        if gm <= 0.0 || self.hull.d().to_long() == 0.0 { return 90.0; }
+
+        let (t, fb) = (self.hull.t, self.hull.freeboard_dist());
+        let moment = self.hull.lwl() * fb * (t + fb) / 2.0 +
+            self.superstructure.iter()
+                .map(|s| s.windage(self.hull.lwl(), self.hull.b, self.year) * (t / 2.0 + fb + s.hgt(self.hull.b, self.year) / 2.0))
+                .sum::<f64>();
+
+        (moment * Self::WIND_PRESSURE / Self::POUND2TON / self.hull.d().to_long() / gm).atan().to_degrees()
+    }
+
+    // deck_edge_angle {{{2
+    /// Heel (degrees) that puts the deck edge under.
+    ///
+    pub fn deck_edge_angle(&self) -> f64 {
+        if self.hull.bb > 0.0 {
+            (2.0 * self.hull.freeboard_dist() / self.hull.bb).atan().to_degrees()
+        } else {
+            0.0
+        }
+    }
+
+    // stability_criteria_pass {{{2
+    /// True if the ship meets all of the simplified stability criteria.
+    ///
+    pub fn stability_criteria_pass(&self) -> bool {
+        self.stability_criteria().iter().all(|c| c.pass())
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod stability {
+    use super::*;
+    use crate::{Template, Superstructure, SuperstructureKind, Tons};
+    use crate::test_support::*;
+
+    // Test standard {{{2
+    macro_rules! test_standard {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((springsharp, simplified), standard) = $value;
+
+                    assert_eq!(springsharp, standard.springsharp());
+                    assert_eq!(simplified, standard.simplified());
+                }
+            )*
+        }
+    }
+
+    test_standard! {
+        // name:               ((springsharp, simplified), standard)
+        standard_springsharp:  ((true, false), StabilityStandard::SpringSharp),
+        standard_simplified:   ((false, true), StabilityStandard::Simplified),
+        standard_both:         ((true, true), StabilityStandard::Both),
+    }
+
+    // Test pass {{{2
+    macro_rules! test_pass {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, value, max) = $value;
+
+                    let c = StabilityCriterion { name: "", value, min: 1.0, max };
+
+                    assert_eq!(expected, c.pass());
+                }
+            )*
+        }
+    }
+
+    test_pass! {
+        // name:          (pass, value, max)
+        pass_below_min:   (false, 0.5, None),
+        pass_min:         (true, 1.0, None),
+        pass_no_max:      (true, 100.0, None),
+        pass_in_range:    (true, 1.5, Some(2.0)),
+        pass_above_max:   (false, 2.5, Some(2.0)),
+    }
+
+    // Test stability_criteria {{{2
+    macro_rules! test_criteria {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, template, b) = $value;
+
+                    let mut ship = Ship::from_template(template);
+                    ship.hull.b *= b;
+                    ship.hull.bb *= b;
+
+                    assert_eq!(expected, ship.stability_criteria_pass());
+                }
+            )*
+        }
+    }
+
+    test_criteria! {
+        // name:                  (pass, template, beam factor)
+        criteria_dreadnought:     (true, Template::Dreadnought1910, 1.0),
+        criteria_destroyer:       (true, Template::Destroyer1935, 1.0),
+        criteria_fast_bb:         (true, Template::FastBattleship1940, 1.0),
+        criteria_narrow:          (false, Template::Dreadnought1910, 0.8),
+    }
+
+    // Test wind_heel {{{2
+    macro_rules! test_wind_heel {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, template, kinds) = $value;
+
+                    let mut ship = Ship::from_template(template);
+                    ship.superstructure = kinds.iter()
+                        .map(|&kind| Superstructure { kind, wgt: Some(Tons::long(0.0)) })
+                        .collect();
+
+                    assert_eq!(expected, to_place(ship.wind_heel(), 2));
+                }
+            )*
+        }
+    }
+
+    test_wind_heel! {
+        // name:                  (heel, template, superstructure)
+        wind_heel_bare:           (0.79, Template::Dreadnought1910, Vec::<SuperstructureKind>::new()),
+        wind_heel_bridge:         (0.93, Template::Dreadnought1910, vec![SuperstructureKind::Bridge]),
+        wind_heel_destroyer:      (5.86, Template::Destroyer1935, vec![SuperstructureKind::Bridge, SuperstructureKind::Funnel, SuperstructureKind::Mast]),
+    }
+}
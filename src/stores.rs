@@ -0,0 +1,162 @@
+use crate::{Ship, Tons};
+
+impl Ship { // {{{1
+    /// Share of the displacement SpringSharp gives to stores when no
+    /// endurance is given.
+    pub const STORES_SHARE: f64 = 0.02;
+
+    // stores_rate {{{2
+    /// Weight of provisions, fresh water and consumables (t) each man
+    /// needs per day, rising with the era's standard of living aboard.
+    ///
+    pub fn stores_rate(year: u32) -> f64 {
+             if year < 1900 { 0.004 }
+        else if year < 1930 { 0.005 }
+        else                { 0.006 }
+    }
+
+    // wgt_stores {{{2
+    /// Weight of stores (t): enough for the full complement for the
+    /// endurance if one is given, otherwise a fixed share of the
+    /// displacement.
+    ///
+    pub fn wgt_stores(&self) -> Tons {
+        match self.endurance {
+            Some(days) => Tons::long(self.crew_max() as f64 * days as f64 * Self::stores_rate(self.year)),
+            None       => self.hull.d() * Self::STORES_SHARE,
+        }
+    }
+
+    // endurance_days {{{2
+    /// Days the stores last the full complement.
+    ///
+    pub fn endurance_days(&self) -> f64 {
+        if let Some(days) = self.endurance { return days as f64; }
+
+        let per_day = self.crew_max() as f64 * Self::stores_rate(self.year);
+        if per_day == 0.0 { return 0.0; } // catch divide by zero
+
+        self.wgt_stores().to_long() / per_day
+    }
+
+    // voyage_days {{{2
+    /// Days taken to steam the range at cruising speed.
+    ///
+    pub fn voyage_days(&self) -> f64 {
+        if self.engine.vcruise <= 0.0 { return 0.0; } // catch divide by zero
+
+        self.engine.range as f64 / self.engine.vcruise / 24.0
+    }
+
+    // stores_short {{{2
+    /// True if the stores run out before the fuel does at cruising speed.
+    ///
+    pub fn stores_short(&self) -> bool {
+        self.engine.is_powered() && self.endurance_days() < self.voyage_days()
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod stores {
+    use super::*;
+    use crate::Template;
+    use crate::test_support::*;
+
+    // Test stores_rate {{{2
+    macro_rules! test_stores_rate {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year) = $value;
+
+                    assert_eq!(expected, Ship::stores_rate(year));
+                }
+            )*
+        }
+    }
+
+    test_stores_rate! {
+        // name:            (rate, year)
+        stores_rate_1870:   (0.004, 1870),
+        stores_rate_1900:   (0.005, 1900),
+        stores_rate_1929:   (0.005, 1929),
+        stores_rate_1940:   (0.006, 1940),
+    }
+
+    // Test wgt_stores {{{2
+    #[test]
+    fn wgt_stores_default() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+
+        assert_eq!(to_place(ship.hull.d().to_long() * 0.02, 3), to_place(ship.wgt_stores().to_long(), 3));
+    }
+
+    #[test]
+    fn wgt_stores_endurance() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.endurance = Some(90);
+
+        assert_eq!(
+            to_place(ship.crew_max() as f64 * 90.0 * 0.005, 3),
+            to_place(ship.wgt_stores().to_long(), 3)
+        );
+        assert_eq!(90.0, ship.endurance_days());
+
+        // Longer endurance is heavier and sinks the light ship deeper
+        let light = ship.d_lite().to_long();
+        ship.endurance = Some(180);
+        assert!(ship.d_lite().to_long() < light);
+    }
+
+    // Test endurance_days {{{2
+    #[test]
+    fn endurance_days_default() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+        let days = ship.endurance_days();
+
+        // The 2% share works out to a few months for a battleship
+        assert!(days > 30.0 && days < 150.0, "{}", days);
+    }
+
+    // Test stores_short {{{2
+    macro_rules! test_stores_short {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, endurance, range) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.endurance = endurance;
+                    ship.engine.range = range;
+
+                    assert_eq!(expected, ship.stores_short());
+                }
+            )*
+        }
+    }
+
+    test_stores_short! {
+        // name:                (short, endurance, range)
+        stores_short_default:   (false, None, 7000),
+        stores_short_coastal:   (true, Some(10), 7000),
+        stores_short_raider:    (false, Some(120), 20000),
+        stores_short_too_far:   (true, Some(30), 20000),
+    }
+
+    // Test voyage_days {{{2
+    #[test]
+    fn voyage_days() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.engine.range = 2400;
+        ship.engine.vcruise = 10.0;
+
+        assert_eq!(10.0, ship.voyage_days());
+
+        ship.engine.vcruise = 0.0;
+        assert_eq!(0.0, ship.voyage_days());
+    }
+}
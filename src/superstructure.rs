@@ -0,0 +1,201 @@
+use crate::{Tons, Choice};
+
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+// SuperstructureKind {{{1
+/// Kind of superstructure block.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum SuperstructureKind {
+    #[default]
+    Bridge,
+    Funnel,
+    Mast,
+}
+
+impl From<String> for SuperstructureKind { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for SuperstructureKind {
+    fn from(label: &str) -> Self {
+        Self::from_label(label).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for SuperstructureKind { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Bridge => "bridge",
+                Self::Funnel => "funnel",
+                Self::Mast   => "mast",
+            }
+        )
+    }
+}
+
+impl SuperstructureKind { // {{{2
+    // lever {{{3
+    /// Multiplier for the block's weight in the stability calculation.
+    ///
+    /// Weights on the freeboard deck count 3 times and weights above it 4
+    /// times. Masts carry their weight higher still.
+    ///
+    pub fn lever(&self) -> f64 {
+        match self {
+            Self::Bridge => 4.0,
+            Self::Funnel => 4.0,
+            Self::Mast   => 5.0,
+        }
+    }
+}
+
+// Superstructure {{{1
+/// A block of superstructure above the freeboard deck.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Superstructure {
+    /// Kind of block.
+    pub kind: SuperstructureKind,
+    /// Weight of the block. Estimated from the displacement and year if
+    /// not set.
+    #[serde(default)]
+    pub wgt: Option<Tons>,
+}
+
+impl Superstructure { // {{{2
+    /// Year tripod masts and enlarged bridges came into use.
+    const TRIPOD_YEAR: u32 = 1905;
+    /// Year bridges grew to carry fire control.
+    const TOWER_YEAR: u32 = 1920;
+
+    // wgt {{{3
+    /// Weight of the block.
+    ///
+    pub fn wgt(&self, d: f64, year: u32) -> Tons {
+        self.wgt.unwrap_or(self.wgt_est(d, year))
+    }
+
+    // wgt_est {{{3
+    /// Usual weight of a block of this kind in a ship of this
+    /// displacement and year.
+    ///
+    pub fn wgt_est(&self, d: f64, year: u32) -> Tons {
+        Tons::long(d) * match self.kind {
+            SuperstructureKind::Bridge => if year >= Self::TOWER_YEAR { 0.006 } else { 0.004 },
+            SuperstructureKind::Funnel => 0.0025,
+            SuperstructureKind::Mast   => if year >= Self::TRIPOD_YEAR { 0.002 } else { 0.0008 },
+        }
+    }
+
+    // hgt {{{3
+    /// Height of the block above the deck.
+    ///
+    pub fn hgt(&self, b: f64, year: u32) -> f64 {
+        match self.kind {
+            SuperstructureKind::Bridge => if year >= Self::TOWER_YEAR { 32.0 } else { 24.0 },
+            SuperstructureKind::Funnel => b * 0.7,
+            SuperstructureKind::Mast   => b * 1.2,
+        }
+    }
+
+    // windage {{{3
+    /// Side area of the block exposed to the wind.
+    ///
+    pub fn windage(&self, lwl: f64, b: f64, year: u32) -> f64 {
+        self.hgt(b, year) * match self.kind {
+            SuperstructureKind::Bridge => lwl * 0.08,
+            SuperstructureKind::Funnel => b * 0.18,
+            SuperstructureKind::Mast   => b * if year >= Self::TRIPOD_YEAR { 0.06 } else { 0.02 },
+        }
+    }
+
+    // desc {{{3
+    /// Description of a list of blocks, e.g. "bridge, 2 funnels, mast".
+    ///
+    pub fn desc(blocks: &[Superstructure]) -> String {
+        [SuperstructureKind::Bridge, SuperstructureKind::Funnel, SuperstructureKind::Mast].iter()
+            .map(|k| (k, blocks.iter().filter(|s| s.kind == *k).count()))
+            .filter(|(_, n)| *n > 0)
+            .map(|(k, n)| if n == 1 { k.to_string() } else { format!("{} {}s", n, k) })
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod superstructure {
+    use super::*;
+    use crate::test_support::*;
+
+    // Test wgt {{{2
+    macro_rules! test_wgt {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, kind, wgt, year) = $value;
+
+                    let s = Superstructure { kind, wgt };
+
+                    assert_eq!(expected, to_place(s.wgt(10_000.0, year).to_long(), 1));
+                }
+            )*
+        }
+    }
+
+    test_wgt! {
+        // name:             (wgt, kind, wgt, year)
+        wgt_bridge:          (40.0, SuperstructureKind::Bridge, None, 1910),
+        wgt_bridge_tower:    (60.0, SuperstructureKind::Bridge, None, 1930),
+        wgt_funnel:          (25.0, SuperstructureKind::Funnel, None, 1910),
+        wgt_pole_mast:       (8.0, SuperstructureKind::Mast, None, 1900),
+        wgt_tripod_mast:     (20.0, SuperstructureKind::Mast, None, 1910),
+        wgt_set:             (100.0, SuperstructureKind::Funnel, Some(Tons::long(100.0)), 1910),
+    }
+
+    // Test windage {{{2
+    macro_rules! test_windage {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, kind, year) = $value;
+
+                    let s = Superstructure { kind, wgt: None };
+
+                    assert_eq!(expected, to_place(s.windage(500.0, 80.0, year), 1));
+                }
+            )*
+        }
+    }
+
+    test_windage! {
+        // name:                 (windage, kind, year)
+        windage_bridge:          (960.0, SuperstructureKind::Bridge, 1910),
+        windage_bridge_tower:    (1280.0, SuperstructureKind::Bridge, 1930),
+        windage_funnel:          (806.4, SuperstructureKind::Funnel, 1910),
+        windage_pole_mast:       (153.6, SuperstructureKind::Mast, 1900),
+        windage_tripod_mast:     (460.8, SuperstructureKind::Mast, 1910),
+    }
+
+    // Test desc {{{2
+    #[test]
+    fn desc() {
+        let block = |kind| Superstructure { kind, wgt: None };
+
+        assert_eq!("", Superstructure::desc(&[]));
+        assert_eq!("bridge, 2 funnels, mast", Superstructure::desc(&[
+            block(SuperstructureKind::Funnel),
+            block(SuperstructureKind::Mast),
+            block(SuperstructureKind::Bridge),
+            block(SuperstructureKind::Funnel),
+        ]));
+    }
+}
@@ -0,0 +1,609 @@
+use crate::{Ship, Tons};
+use crate::hull::{Hull, BowType, SternType};
+use crate::armor::DeckType;
+use crate::engine::{FuelType, BoilerType, DriveType};
+use crate::weapons::{Battery, Torpedoes, ASW, GunType, MountType, GunLayoutType, GunDistributionType, TorpedoMountType, ASWType};
+
+use serde::{Serialize, Deserialize};
+use std::fmt;
+
+// Template {{{1
+/// Typical designs to start a new ship from.
+///
+/// Each template is a complete, working design for its type and year
+/// that can be adjusted instead of starting from an empty ship.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum Template {
+    /// Central battery ironclad (1870).
+    CentralBattery1870,
+    /// Protected cruiser (1895).
+    ProtectedCruiser1895,
+    /// All big gun battleship (1910).
+    #[default]
+    Dreadnought1910,
+    /// Washington Treaty heavy cruiser (1930).
+    TreatyCruiser1930,
+    /// Fleet destroyer (1935).
+    Destroyer1935,
+    /// Fast battleship (1940).
+    FastBattleship1940,
+}
+
+impl From<String> for Template { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for Template {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "0"     => Self::CentralBattery1870,
+            "1"     => Self::ProtectedCruiser1895,
+            "3"     => Self::TreatyCruiser1930,
+            "4"     => Self::Destroyer1935,
+            "5"     => Self::FastBattleship1940,
+            "2" | _ => Self::Dreadnought1910,
+        }
+    }
+}
+
+impl fmt::Display for Template { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::CentralBattery1870   => "Central battery ironclad (1870)",
+                Self::ProtectedCruiser1895 => "Protected cruiser (1895)",
+                Self::Dreadnought1910      => "Dreadnought battleship (1910)",
+                Self::TreatyCruiser1930    => "Treaty heavy cruiser (1930)",
+                Self::Destroyer1935        => "Fleet destroyer (1935)",
+                Self::FastBattleship1940   => "Fast battleship (1940)",
+            }
+        )
+    }
+}
+
+impl Template { // {{{2
+    /// All available templates.
+    pub const ALL: [Template; 6] = [
+        Template::CentralBattery1870,
+        Template::ProtectedCruiser1895,
+        Template::Dreadnought1910,
+        Template::TreatyCruiser1930,
+        Template::Destroyer1935,
+        Template::FastBattleship1940,
+    ];
+
+    // year {{{3
+    /// Year the template is laid down.
+    ///
+    pub fn year(&self) -> u32 {
+        match self {
+            Self::CentralBattery1870   => 1870,
+            Self::ProtectedCruiser1895 => 1895,
+            Self::Dreadnought1910      => 1910,
+            Self::TreatyCruiser1930    => 1930,
+            Self::Destroyer1935        => 1935,
+            Self::FastBattleship1940   => 1940,
+        }
+    }
+}
+
+// ShipKind {{{1
+/// Kind of ship, used to set up an empty design with the weapons a
+/// ship of that kind usually has.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ShipKind {
+    Battleship,
+    Cruiser,
+    Destroyer,
+    /// Room for every kind of weapon.
+    #[default]
+    Other,
+}
+
+impl From<String> for ShipKind { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for ShipKind {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "0"     => Self::Battleship,
+            "1"     => Self::Cruiser,
+            "2"     => Self::Destroyer,
+            "3" | _ => Self::Other,
+        }
+    }
+}
+
+impl fmt::Display for ShipKind { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Battleship => "Battleship",
+                Self::Cruiser    => "Cruiser",
+                Self::Destroyer  => "Destroyer",
+                Self::Other      => "Other",
+            }
+        )
+    }
+}
+
+impl ShipKind { // {{{2
+    // skeleton {{{3
+    /// Number of gun batteries, torpedo mounts and ASW gear.
+    ///
+    pub fn skeleton(&self) -> (usize, usize, usize) {
+        match self {
+            Self::Battleship => (3, 1, 0),
+            Self::Cruiser    => (4, 2, 1),
+            Self::Destroyer  => (1, 2, 2),
+            Self::Other      => (5, 2, 2),
+        }
+    }
+}
+
+// Helpers {{{1
+// hull {{{2
+/// Set the main hull dimensions.
+///
+fn hull(hull: &mut Hull, d: f64, lwl: f64, b: f64, t: f64) {
+    hull.set_d(Tons::long(d));
+    hull.set_lwl(lwl);
+    hull.b = b;
+    hull.bb = b;
+    hull.t = t;
+}
+
+// decks {{{2
+/// Set deck lengths and freeboards.
+///
+/// Each deck is (fraction of length, height forward, height aft), the
+/// aft deck length being whatever is left over.
+///
+fn decks(hull: &mut Hull, fc: (f64, f64, f64), fd: (f64, f64, f64), ad: (f64, f64), qd: (f64, f64, f64)) {
+    (hull.fc_len, hull.fc_fwd, hull.fc_aft) = fc;
+    (hull.fd_len, hull.fd_fwd, hull.fd_aft) = fd;
+    (hull.ad_fwd, hull.ad_aft) = ad;
+    (hull.qd_len, hull.qd_fwd, hull.qd_aft) = qd;
+}
+
+// battery {{{2
+/// Build a gun battery with all mounts in the first group.
+///
+/// Mounts are (above, on, below) the deck.
+///
+#[allow(clippy::too_many_arguments)]
+fn battery(num: u32, diam: f64, len: f64, year: u32, kind: GunType, mount_kind: MountType,
+           layout: GunLayoutType, distribution: GunDistributionType, mounts: (u32, u32, u32)) -> Battery {
+    let mut b = Battery::default();
+
+    b.diam = diam;
+    b.len = len;
+    b.year = year;
+    b.kind = kind;
+    b.mount_kind = mount_kind;
+    b.set_layout(mounts, layout, distribution);
+    debug_assert_eq!(num, b.num);
+
+    b
+}
+
+impl Ship { // {{{1
+    // new_for_kind {{{2
+    /// Create an empty ship with the weapons a ship of this kind usually
+    /// has.
+    ///
+    pub fn new_for_kind(kind: ShipKind) -> Ship {
+        let (batteries, torps, asw) = kind.skeleton();
+
+        Ship {
+            kind: if kind == ShipKind::Other { "".into() } else { kind.to_string() },
+            batteries: vec![Battery::default(); batteries],
+            torps: vec![Torpedoes::default(); torps],
+            asw: vec![ASW::default(); asw],
+            ..Default::default()
+        }
+    }
+
+    // from_template {{{2
+    /// Create a ship from a template.
+    ///
+    pub fn from_template(template: Template) -> Ship {
+        let year = template.year();
+        let mut ship = Ship { year, ..Default::default() };
+        ship.engine.year = year;
+
+        match template {
+            Template::CentralBattery1870 => { // {{{3
+                ship.kind = "Ironclad".into();
+
+                hull(&mut ship.hull, 8680.0, 325.0, 59.0, 24.5);
+                decks(&mut ship.hull, (0.15, 18.0, 16.0), (0.35, 16.0, 15.0), (15.0, 15.0), (0.15, 15.0, 16.0));
+                ship.hull.bow_type = BowType::Ram(10.0);
+                ship.hull.stern_type = SternType::Round;
+
+                ship.engine.fuel = FuelType::Coal;
+                ship.engine.boiler = BoilerType::Simple;
+                ship.engine.drive = DriveType::Direct;
+                ship.engine.vmax = 14.0;
+                ship.engine.vcruise = 10.0;
+                ship.engine.range = 2000;
+                ship.engine.pct_coal = 1.0;
+                ship.engine.set_shafts(1);
+
+                ship.armor.main.thick = 9.0;
+                ship.armor.main.len = 200.0;
+                ship.armor.main.hgt = 10.0;
+                ship.armor.upper.thick = 6.0;
+                ship.armor.upper.len = 120.0;
+                ship.armor.upper.hgt = 8.0;
+                ship.armor.ct_fwd.thick = 6.0;
+
+                ship.batteries[0] = battery(8, 10.0, 14.0, 1868, GunType::MuzzleLoading, MountType::Broadside,
+                    GunLayoutType::Single, GunDistributionType::SidesEven, (0, 0, 8));
+                ship.batteries[0].shells = 60;
+            },
+
+            Template::ProtectedCruiser1895 => { // {{{3
+                ship.kind = "Protected Cruiser".into();
+
+                hull(&mut ship.hull, 5870.0, 340.0, 53.0, 21.5);
+                decks(&mut ship.hull, (0.25, 24.0, 22.0), (0.25, 18.0, 17.0), (17.0, 17.0), (0.15, 17.0, 18.0));
+                ship.hull.bow_type = BowType::Ram(8.0);
+
+                ship.engine.fuel = FuelType::Coal;
+                ship.engine.boiler = BoilerType::Complex;
+                ship.engine.drive = DriveType::Direct;
+                ship.engine.vmax = 21.0;
+                ship.engine.vcruise = 10.0;
+                ship.engine.range = 6000;
+                ship.engine.pct_coal = 1.0;
+                ship.engine.set_shafts(2);
+
+                ship.armor.deck.md = 4.0;
+                ship.armor.deck.fc = 2.0;
+                ship.armor.deck.qd = 2.0;
+                ship.armor.deck.kind = DeckType::MultipleProtected;
+                ship.armor.ct_fwd.thick = 5.0;
+
+                ship.batteries[0] = battery(4, 8.0, 35.0, 1890, GunType::BreechLoading, MountType::ClosedBarbette,
+                    GunLayoutType::Twin, GunDistributionType::CenterlineEndsFD, (0, 2, 0));
+                ship.batteries[0].shells = 125;
+                ship.batteries[0].armor_face = 3.5;
+                ship.batteries[0].armor_back = 2.0;
+                ship.batteries[0].armor_barb = 4.5;
+
+                ship.batteries[1] = battery(10, 5.0, 40.0, 1890, GunType::QuickFiring, MountType::Casemate,
+                    GunLayoutType::Single, GunDistributionType::SidesEven, (0, 0, 10));
+                ship.batteries[1].shells = 200;
+                ship.batteries[1].armor_face = 4.0;
+
+                ship.torps[0].num = 4;
+                ship.torps[0].mounts = 4;
+                ship.torps[0].diam = 18.0;
+                ship.torps[0].len = 11.5;
+                ship.torps[0].mount_kind = TorpedoMountType::DeckSideTubes;
+            },
+
+            Template::Dreadnought1910 => { // {{{3
+                ship.kind = "Battleship".into();
+
+                hull(&mut ship.hull, 20000.0, 540.0, 86.0, 27.5);
+                decks(&mut ship.hull, (0.25, 28.0, 26.0), (0.3, 22.0, 21.0), (21.0, 21.0), (0.15, 21.0, 22.0));
+
+                ship.engine.fuel = FuelType::Coal | FuelType::Oil;
+                ship.engine.boiler = BoilerType::Turbine;
+                ship.engine.drive = DriveType::Direct;
+                ship.engine.vmax = 21.0;
+                ship.engine.vcruise = 10.0;
+                ship.engine.range = 6500;
+                ship.engine.pct_coal = 0.8;
+                ship.engine.set_shafts(4);
+
+                ship.armor.main.thick = 11.0;
+                ship.armor.main.len = 330.0;
+                ship.armor.main.hgt = 12.0;
+                ship.armor.end.thick = 6.0;
+                ship.armor.end.len = 150.0;
+                ship.armor.end.hgt = 8.0;
+                ship.armor.upper.thick = 8.0;
+                ship.armor.upper.len = 260.0;
+                ship.armor.upper.hgt = 8.0;
+                ship.armor.deck.md = 2.0;
+                ship.armor.deck.fc = 1.0;
+                ship.armor.deck.qd = 1.0;
+                ship.armor.ct_fwd.thick = 11.0;
+
+                ship.batteries[0] = battery(10, 12.0, 45.0, 1908, GunType::BreechLoading, MountType::ClosedBarbette,
+                    GunLayoutType::Twin, GunDistributionType::CenterlineEven, (1, 4, 0));
+                ship.batteries[0].shells = 100;
+                ship.batteries[0].armor_face = 11.0;
+                ship.batteries[0].armor_back = 3.0;
+                ship.batteries[0].armor_barb = 10.0;
+
+                ship.batteries[1] = battery(20, 4.0, 50.0, 1908, GunType::QuickFiring, MountType::Deck,
+                    GunLayoutType::Single, GunDistributionType::SidesEven, (8, 12, 0));
+                ship.batteries[1].shells = 150;
+
+                ship.torps[0].num = 4;
+                ship.torps[0].mounts = 4;
+                ship.torps[0].diam = 18.0;
+                ship.torps[0].len = 17.0;
+                ship.torps[0].mount_kind = TorpedoMountType::SubmergedSideTubes;
+            },
+
+            Template::TreatyCruiser1930 => { // {{{3
+                ship.kind = "Heavy Cruiser".into();
+
+                hull(&mut ship.hull, 10000.0, 570.0, 64.0, 19.5);
+                decks(&mut ship.hull, (0.3, 30.0, 28.0), (0.25, 24.0, 22.0), (22.0, 21.0), (0.15, 21.0, 21.0));
+
+                ship.engine.fuel = FuelType::Oil;
+                ship.engine.boiler = BoilerType::Turbine;
+                ship.engine.drive = DriveType::Geared;
+                ship.engine.vmax = 32.0;
+                ship.engine.vcruise = 15.0;
+                ship.engine.range = 10000;
+                ship.engine.set_shafts(4);
+
+                ship.armor.main.thick = 4.0;
+                ship.armor.main.len = 280.0;
+                ship.armor.main.hgt = 12.0;
+                ship.armor.deck.md = 1.5;
+                ship.armor.deck.kind = DeckType::SingleArmored;
+                ship.armor.ct_fwd.thick = 3.0;
+
+                ship.batteries[0] = battery(8, 8.0, 55.0, 1928, GunType::BreechLoading, MountType::ClosedBarbette,
+                    GunLayoutType::Twin, GunDistributionType::CenterlineEndsFD, (2, 2, 0));
+                ship.batteries[0].shells = 150;
+                ship.batteries[0].armor_face = 2.0;
+                ship.batteries[0].armor_back = 1.0;
+                ship.batteries[0].armor_barb = 2.0;
+
+                ship.batteries[1] = battery(8, 4.0, 45.0, 1928, GunType::DualPurpose, MountType::Deck,
+                    GunLayoutType::Single, GunDistributionType::SidesEven, (0, 8, 0));
+                ship.batteries[1].shells = 250;
+
+                ship.torps[0].num = 12;
+                ship.torps[0].mounts = 4;
+                ship.torps[0].diam = 21.0;
+                ship.torps[0].len = 23.0;
+                ship.torps[0].mount_kind = TorpedoMountType::DeckSideTubes;
+            },
+
+            Template::Destroyer1935 => { // {{{3
+                ship.kind = "Destroyer".into();
+
+                hull(&mut ship.hull, 2200.0, 355.0, 36.0, 11.0);
+                decks(&mut ship.hull, (0.3, 24.0, 21.0), (0.2, 16.0, 15.0), (15.0, 14.0), (0.15, 14.0, 14.0));
+                ship.hull.stern_type = SternType::TransomSm;
+
+                ship.engine.fuel = FuelType::Oil;
+                ship.engine.boiler = BoilerType::Turbine;
+                ship.engine.drive = DriveType::Geared;
+                ship.engine.vmax = 34.0;
+                ship.engine.vcruise = 15.0;
+                ship.engine.range = 5500;
+                ship.engine.set_shafts(2);
+
+                ship.batteries[0] = battery(4, 4.7, 45.0, 1930, GunType::QuickFiring, MountType::Deck,
+                    GunLayoutType::Single, GunDistributionType::CenterlineEndsFD, (2, 2, 0));
+                ship.batteries[0].shells = 200;
+
+                ship.batteries[1] = battery(4, 0.5, 62.0, 1930, GunType::MachineGun, MountType::Deck,
+                    GunLayoutType::Quad, GunDistributionType::CenterlineEven, (0, 1, 0));
+                ship.batteries[1].shells = 2500;
+
+                ship.torps[0].num = 8;
+                ship.torps[0].mounts = 2;
+                ship.torps[0].diam = 21.0;
+                ship.torps[0].len = 23.0;
+                ship.torps[0].mount_kind = TorpedoMountType::CenterTubes;
+
+                ship.asw[0].num = 20;
+                ship.asw[0].wgt = 300.0;
+                ship.asw[0].kind = ASWType::SternRacks;
+            },
+
+            Template::FastBattleship1940 => { // {{{3
+                ship.kind = "Battleship".into();
+
+                hull(&mut ship.hull, 42000.0, 740.0, 108.0, 32.0);
+                decks(&mut ship.hull, (0.3, 34.0, 30.0), (0.25, 28.0, 26.0), (26.0, 26.0), (0.15, 26.0, 26.0));
+
+                ship.engine.fuel = FuelType::Oil;
+                ship.engine.boiler = BoilerType::Turbine;
+                ship.engine.drive = DriveType::Geared;
+                ship.engine.vmax = 28.0;
+                ship.engine.vcruise = 15.0;
+                ship.engine.range = 15000;
+                ship.engine.set_shafts(4);
+
+                ship.armor.main.thick = 13.5;
+                ship.armor.main.len = 420.0;
+                ship.armor.main.hgt = 16.0;
+                ship.armor.incline = 15.0;
+                ship.armor.bulkhead.thick = 1.5;
+                ship.armor.bulkhead.len = 420.0;
+                ship.armor.bulkhead.hgt = 30.0;
+                ship.armor.bh_beam = 100.0;
+                ship.armor.deck.md = 5.0;
+                ship.armor.deck.fc = 1.5;
+                ship.armor.deck.qd = 1.5;
+                ship.armor.deck.kind = DeckType::SingleArmored;
+                ship.armor.ct_fwd.thick = 16.0;
+
+                ship.batteries[0] = battery(9, 16.0, 45.0, 1937, GunType::BreechLoading, MountType::ClosedBarbette,
+                    GunLayoutType::Triple, GunDistributionType::CenterlineEndsFD, (1, 2, 0));
+                ship.batteries[0].shells = 100;
+                ship.batteries[0].armor_face = 16.0;
+                ship.batteries[0].armor_back = 7.0;
+                ship.batteries[0].armor_barb = 16.0;
+
+                ship.batteries[1] = battery(20, 5.0, 38.0, 1934, GunType::DualPurpose, MountType::DeckAndHoist,
+                    GunLayoutType::Twin, GunDistributionType::SidesEven, (4, 6, 0));
+                ship.batteries[1].shells = 400;
+                ship.batteries[1].armor_face = 2.0;
+                ship.batteries[1].armor_back = 1.0;
+
+                ship.batteries[2] = battery(40, 1.57, 56.0, 1938, GunType::AntiAir, MountType::Deck,
+                    GunLayoutType::Quad, GunDistributionType::SidesEven, (0, 10, 0));
+                ship.batteries[2].shells = 2000;
+            },
+        }
+
+        ship.default_years();
+
+        ship
+    }
+
+    // new {{{2
+    /// Create an empty ship laid down in year, with its machinery and
+    /// weapons from the same year.
+    ///
+    pub fn new(year: u32) -> Ship {
+        let mut ship = Ship { year, ..Default::default() };
+        ship.engine.year = year;
+        ship.default_years();
+
+        ship
+    }
+
+    // default_years {{{2
+    /// Give every empty battery and all torpedoes, mines and ASW gear the
+    /// year the ship was laid down.
+    ///
+    pub fn default_years(&mut self) {
+        let year = self.year;
+
+        for b in self.batteries.iter_mut().filter(|b| b.num == 0) { b.year = year; }
+        for t in self.torps.iter_mut() { t.year = year; }
+        self.mines.year = year;
+        for a in self.asw.iter_mut() { a.year = year; }
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod template {
+    use super::*;
+
+    // Test from_template {{{2
+    macro_rules! test_from_template {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let template = $value;
+                    let ship = Ship::from_template(template);
+
+                    assert_eq!(template.year(), ship.year);
+                    assert_eq!(Vec::<String>::new(), ship.design_failures());
+                    assert!(!ship.report().is_empty());
+                }
+            )*
+        }
+    }
+
+    test_from_template! {
+        // name:                      template
+        from_template_ironclad:       Template::CentralBattery1870,
+        from_template_prot_cruiser:   Template::ProtectedCruiser1895,
+        from_template_dreadnought:    Template::Dreadnought1910,
+        from_template_treaty_cruiser: Template::TreatyCruiser1930,
+        from_template_destroyer:      Template::Destroyer1935,
+        from_template_fast_bb:        Template::FastBattleship1940,
+    }
+
+    // Test new_for_kind {{{2
+    macro_rules! test_new_for_kind {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((batteries, torps, asw), kind) = $value;
+                    let ship = Ship::new_for_kind(kind);
+
+                    assert_eq!(batteries, ship.batteries.len());
+                    assert_eq!(torps, ship.torps.len());
+                    assert_eq!(asw, ship.asw.len());
+                }
+            )*
+        }
+    }
+
+    test_new_for_kind! {
+        // name:                  ((batteries, torps, asw), kind)
+        new_for_kind_battleship:  ((3, 1, 0), ShipKind::Battleship),
+        new_for_kind_cruiser:     ((4, 2, 1), ShipKind::Cruiser),
+        new_for_kind_destroyer:   ((1, 2, 2), ShipKind::Destroyer),
+        new_for_kind_other:       ((5, 2, 2), ShipKind::Other),
+    }
+
+    // Test new {{{2
+    #[test]
+    fn new() {
+        let ship = Ship::new(1938);
+
+        assert_eq!(1938, ship.year);
+        assert_eq!(1938, ship.engine.year);
+        assert!(ship.batteries.iter().all(|b| b.year == 1938));
+        assert!(ship.torps.iter().all(|t| t.year == 1938));
+        assert_eq!(1938, ship.mines.year);
+        assert!(ship.asw.iter().all(|a| a.year == 1938));
+    }
+
+    // Test reports with fewer weapons {{{2
+    macro_rules! test_skeleton_report {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (template, kind) = $value;
+                    let (batteries, torps, asw) = kind.skeleton();
+
+                    let mut ship = Ship::from_template(template);
+                    ship.batteries.truncate(batteries);
+                    ship.torps.truncate(torps);
+                    ship.asw.truncate(asw);
+
+                    assert!(!ship.report().is_empty());
+                    assert!(ship.report_json().is_ok());
+                }
+            )*
+        }
+    }
+
+    test_skeleton_report! {
+        // name:                     (template, kind)
+        skeleton_report_battleship:  (Template::FastBattleship1940, ShipKind::Battleship),
+        skeleton_report_cruiser:     (Template::TreatyCruiser1930, ShipKind::Cruiser),
+        skeleton_report_destroyer:   (Template::Destroyer1935, ShipKind::Destroyer),
+    }
+
+    #[test]
+    fn skeleton_report_unarmed() {
+        let mut ship = Ship::from_template(Template::Destroyer1935);
+        ship.batteries.clear();
+        ship.torps.clear();
+        ship.asw.clear();
+
+        assert!(!ship.report().is_empty());
+    }
+
+    #[test]
+    fn all_templates() {
+        for (i, t) in Template::ALL.iter().enumerate() {
+            assert_eq!(*t, Template::from(i.to_string()));
+        }
+    }
+}
@@ -0,0 +1,303 @@
+use crate::{Ship, Tons};
+
+use std::fmt;
+
+// Treaty {{{1
+/// Naval arms limitation treaties.
+///
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum Treaty {
+    /// Washington Naval Treaty (1922).
+    #[default]
+    Washington,
+    /// London Naval Treaty (1930).
+    London,
+}
+
+impl From<String> for Treaty { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for Treaty {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "london"         => Self::London,
+            "washington" | _ => Self::Washington,
+        }
+    }
+}
+
+impl fmt::Display for Treaty { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Washington => "Washington Naval Treaty",
+                Self::London     => "London Naval Treaty",
+            }
+        )
+    }
+}
+
+impl Treaty { // {{{2
+    /// Signatories and their capital ship tonnage (t).
+    const CAPITAL: [(&str, Tons); 5] = [
+        ("United States", Tons::long(525_000.0)),
+        ("United Kingdom", Tons::long(525_000.0)),
+        ("Japan", Tons::long(315_000.0)),
+        ("France", Tons::long(175_000.0)),
+        ("Italy", Tons::long(175_000.0)),
+    ];
+    /// Heavy cruiser, light cruiser and destroyer tonnage (t) of the
+    /// signatories of the London Naval Treaty that accepted them.
+    const LONDON: [(&str, [Tons; 3]); 3] = [
+        ("United States", [Tons::long(180_000.0), Tons::long(143_500.0), Tons::long(150_000.0)]),
+        ("United Kingdom", [Tons::long(146_800.0), Tons::long(192_200.0), Tons::long(150_000.0)]),
+        ("Japan", [Tons::long(108_400.0), Tons::long(100_450.0), Tons::long(105_500.0)]),
+    ];
+
+    /// Largest standard displacement (t) of a capital ship.
+    pub const CAPITAL_MAX_D: Tons = Tons::long(35_000.0);
+    /// Largest gun (in) of a capital ship.
+    pub const CAPITAL_MAX_GUN: f64 = 16.0;
+    /// Largest standard displacement (t) of a cruiser.
+    pub const CRUISER_MAX_D: Tons = Tons::long(10_000.0);
+    /// Largest gun (in) of a cruiser.
+    pub const CRUISER_MAX_GUN: f64 = 8.0;
+    /// Largest gun (in) of a light cruiser.
+    pub const LIGHT_MAX_GUN: f64 = 6.1;
+    /// Largest standard displacement (t) of a destroyer.
+    pub const DESTROYER_MAX_D: Tons = Tons::long(1_850.0);
+    /// Largest gun (in) of a destroyer.
+    pub const DESTROYER_MAX_GUN: f64 = 5.1;
+    /// Largest standard displacement (t) of a vessel free of limits.
+    pub const EXEMPT_MAX_D: Tons = Tons::long(600.0);
+
+    // signatory {{{3
+    /// Name of the signatory a country name refers to, or None if the
+    /// country did not sign the treaty.
+    ///
+    pub fn signatory(&self, country: &str) -> Option<&'static str> {
+        let country = country.trim().to_lowercase();
+
+        Some(match country.as_str() {
+            "us" | "usa" | "u.s." | "u.s.a." | "america" => "United States",
+            "uk" | "u.k." | "britain" | "great britain" | "england" => "United Kingdom",
+            c if c.contains("united states") => "United States",
+            c if c.contains("united kingdom") || c.contains("british") => "United Kingdom",
+            c if c.contains("japan") => "Japan",
+            c if c.contains("france") || c.contains("french") => "France",
+            c if c.contains("italy") || c.contains("italian") => "Italy",
+            _ => return None,
+        })
+    }
+
+    // category {{{3
+    /// Category the treaty places a ship in.
+    ///
+    pub fn category(&self, ship: &Ship) -> TreatyCategory {
+        let (d, gun) = (ship.d_std(), Self::gun(ship));
+
+        if d > Self::CRUISER_MAX_D || gun > Self::CRUISER_MAX_GUN {
+            return TreatyCategory::CapitalShip;
+        }
+
+        match self {
+            Self::Washington => TreatyCategory::Auxiliary,
+
+            Self::London =>
+                       if gun > Self::LIGHT_MAX_GUN {
+                    TreatyCategory::HeavyCruiser
+                } else if d > Self::DESTROYER_MAX_D || gun > Self::DESTROYER_MAX_GUN {
+                    TreatyCategory::LightCruiser
+                } else if d > Self::EXEMPT_MAX_D {
+                    TreatyCategory::Destroyer
+                } else {
+                    TreatyCategory::Exempt
+                },
+        }
+    }
+
+    // limit {{{3
+    /// Total tonnage a country may have in a category, or None if the
+    /// treaty does not limit it.
+    ///
+    pub fn limit(&self, country: &str, category: TreatyCategory) -> Option<Tons> {
+        let name = self.signatory(country)?;
+
+        let london = |i: usize| match self {
+            Self::London => Self::LONDON.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, l)| l[i]),
+            Self::Washington => None,
+        };
+
+        match category {
+            TreatyCategory::CapitalShip  => Self::CAPITAL.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, l)| *l),
+            TreatyCategory::HeavyCruiser => london(0),
+            TreatyCategory::LightCruiser => london(1),
+            TreatyCategory::Destroyer    => london(2),
+            TreatyCategory::Auxiliary |
+            TreatyCategory::Exempt       => None,
+        }
+    }
+
+    // check {{{3
+    /// Limits on individual ships that the ship breaks.
+    ///
+    pub fn check(&self, ship: &Ship) -> Vec<String> {
+        let (d, gun) = (ship.d_std(), Self::gun(ship));
+        let mut v = Vec::new();
+
+        if d > Self::CAPITAL_MAX_D {
+            v.push(format!("{} standard displacement is over the {} limit", d, Self::CAPITAL_MAX_D));
+        }
+        if gun > Self::CAPITAL_MAX_GUN {
+            v.push(format!("{}\" guns are over the {}\" limit", gun, Self::CAPITAL_MAX_GUN));
+        }
+
+        v
+    }
+
+    // gun {{{3
+    /// Largest gun carried by the ship.
+    ///
+    fn gun(ship: &Ship) -> f64 {
+        ship.batteries.iter()
+            .filter(|b| b.num > 0)
+            .fold(0.0, |gun, b| f64::max(gun, b.diam))
+    }
+}
+
+// TreatyCategory {{{1
+/// Categories of warship limited by the treaties.
+///
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum TreatyCategory {
+    /// Ships over 10,000 t or with guns over 8".
+    CapitalShip,
+    /// Cruisers with guns over 6.1" (London).
+    HeavyCruiser,
+    /// Cruisers with guns up to 6.1" (London).
+    LightCruiser,
+    /// Ships up to 1,850 t with guns up to 5.1" (London).
+    Destroyer,
+    /// Ships up to 10,000 t with guns up to 8" (Washington).
+    Auxiliary,
+    /// Ships up to 600 t (London).
+    Exempt,
+}
+
+impl fmt::Display for TreatyCategory { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::CapitalShip  => "Capital ships",
+                Self::HeavyCruiser => "Heavy cruisers",
+                Self::LightCruiser => "Light cruisers",
+                Self::Destroyer    => "Destroyers",
+                Self::Auxiliary    => "Auxiliary ships",
+                Self::Exempt       => "Exempt ships",
+            }
+        )
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod treaty {
+    use super::*;
+    use crate::Template;
+
+    // Test signatory {{{2
+    macro_rules! test_signatory {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, country) = $value;
+
+                    assert_eq!(expected, Treaty::Washington.signatory(country));
+                }
+            )*
+        }
+    }
+
+    test_signatory! {
+        // name:              (signatory, country)
+        signatory_usa:        (Some("United States"), "USA"),
+        signatory_us_long:    (Some("United States"), "United States of America"),
+        signatory_britain:    (Some("United Kingdom"), " Britain "),
+        signatory_japan:      (Some("Japan"), "Imperial Japan"),
+        signatory_italy:      (Some("Italy"), "italy"),
+        signatory_none:       (None, "Germany"),
+        signatory_empty:      (None, ""),
+    }
+
+    // Test category {{{2
+    macro_rules! test_category {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((washington, london), template) = $value;
+                    let ship = Ship::from_template(template);
+
+                    assert_eq!(washington, Treaty::Washington.category(&ship));
+                    assert_eq!(london, Treaty::London.category(&ship));
+                }
+            )*
+        }
+    }
+
+    test_category! {
+        // name:              ((washington, london), template)
+        category_battleship:  ((TreatyCategory::CapitalShip, TreatyCategory::CapitalShip), Template::Dreadnought1910),
+        category_cruiser:     ((TreatyCategory::Auxiliary, TreatyCategory::HeavyCruiser), Template::TreatyCruiser1930),
+        category_8in_cruiser: ((TreatyCategory::Auxiliary, TreatyCategory::HeavyCruiser), Template::ProtectedCruiser1895),
+        // Too large to count as a destroyer
+        category_destroyer:   ((TreatyCategory::Auxiliary, TreatyCategory::LightCruiser), Template::Destroyer1935),
+    }
+
+    // Test limit {{{2
+    macro_rules! test_limit {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, treaty, country, category) = $value;
+
+                    assert_eq!(expected, Treaty::from(treaty).limit(country, category));
+                }
+            )*
+        }
+    }
+
+    test_limit! {
+        // name:                    (limit, treaty, country, category)
+        limit_capital_us:           (Some(Tons::long(525_000.0)), "washington", "USA", TreatyCategory::CapitalShip),
+        limit_capital_france:       (Some(Tons::long(175_000.0)), "london", "France", TreatyCategory::CapitalShip),
+        limit_heavy_japan:          (Some(Tons::long(108_400.0)), "london", "Japan", TreatyCategory::HeavyCruiser),
+        limit_light_uk:             (Some(Tons::long(192_200.0)), "london", "UK", TreatyCategory::LightCruiser),
+        limit_destroyer_italy:      (None, "london", "Italy", TreatyCategory::Destroyer),
+        limit_destroyer_washington: (None, "washington", "USA", TreatyCategory::Destroyer),
+        limit_auxiliary:            (None, "washington", "USA", TreatyCategory::Auxiliary),
+        limit_not_signatory:        (None, "washington", "Germany", TreatyCategory::CapitalShip),
+    }
+
+    // Test check {{{2
+    #[test]
+    fn check() {
+        let mut ship = Ship::from_template(Template::FastBattleship1940);
+        ship.batteries[0].diam = 16.0;
+        assert!(Treaty::Washington.check(&ship).iter().all(|s| !s.contains("guns")));
+
+        ship.batteries[0].diam = 18.0;
+        assert!(Treaty::Washington.check(&ship).iter().any(|s| s.contains("18\" guns")));
+    }
+}
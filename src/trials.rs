@@ -0,0 +1,299 @@
+use crate::{Ship, Hull, Tons};
+
+use std::fmt;
+
+// Condition {{{1
+/// Loading condition a ship is run at.
+///
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Condition {
+    /// Standard displacement, with no fuel aboard.
+    Standard,
+    /// Trial displacement, with two thirds of the normal fuel aboard.
+    Trial,
+    /// Normal displacement, the condition the design is worked out at.
+    Normal,
+    /// Maximum displacement, with the bunkers full.
+    Full,
+    /// Emergency overload, with fuel in the spare bunker space too.
+    Overload,
+}
+
+impl Condition { // {{{2
+    /// Every condition from lightest to heaviest.
+    pub const ALL: [Condition; 5] = [Self::Standard, Self::Trial, Self::Normal, Self::Full, Self::Overload];
+}
+
+impl From<String> for Condition { // {{{2
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+impl From<&str> for Condition { // {{{2
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(s: &str) -> Self {
+        match s {
+            "standard" => Self::Standard,
+            "trial"    => Self::Trial,
+            "full"     => Self::Full,
+            "overload" => Self::Overload,
+            "normal" | _ => Self::Normal,
+        }
+    }
+}
+
+impl fmt::Display for Condition { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Standard => "standard",
+                Self::Trial    => "trial",
+                Self::Normal   => "normal",
+                Self::Full     => "full load",
+                Self::Overload => "emergency overload",
+            }
+        )
+    }
+}
+
+impl Ship { // {{{1
+    /// Speeds the trial speed is found between (kts).
+    const TRIAL_SPEEDS: (f64, f64) = (0.0, 100.0);
+    /// Trial speeds are found to within this (kts).
+    const TRIAL_TOLERANCE: f64 = 0.001;
+    /// Height above the keel of the fuel taken on or burnt between
+    /// conditions, as a share of the normal draught.
+    const LOAD_VCG: f64 = 0.5;
+    /// Most fuel (as a share of the normal displacement) that can be
+    /// crammed into the spare bunker space for an emergency overload.
+    const OVERLOAD_MAX: f64 = 0.05;
+
+    // condition_d {{{2
+    /// Displacement (t) in a loading condition.
+    ///
+    pub fn condition_d(&self, condition: Condition) -> Tons {
+        match condition {
+            Condition::Standard => self.d_std(),
+            Condition::Trial    => self.hull.d() - Tons::long(self.wgt_bunker() / 3.0),
+            Condition::Normal   => self.hull.d(),
+            Condition::Full     => self.d_max(),
+            Condition::Overload if !self.engine.is_powered() => self.d_max(),
+            Condition::Overload => self.d_max() + Tons::long(f64::min(
+                f64::max(self.bunker_space() - self.bunker_volume(), 0.0) / self.engine.stowage(),
+                self.hull.d().to_long() * Self::OVERLOAD_MAX
+            )),
+        }
+    }
+
+    // condition_hull {{{2
+    /// Hull sunk or raised to the draught of a loading condition.
+    ///
+    fn condition_hull(&self, condition: Condition) -> Hull {
+        let mut hull = self.hull.clone();
+        if condition == Condition::Normal { return hull; }
+
+        let d = self.condition_d(condition);
+        hull.t = self.hull.t_calc(d, self.engine.shafts());
+        hull.set_d(d);
+
+        hull
+    }
+
+    // condition_t {{{2
+    /// Draught (ft) in a loading condition.
+    ///
+    pub fn condition_t(&self, condition: Condition) -> f64 {
+        self.condition_hull(condition).t
+    }
+
+    // condition_cb {{{2
+    /// Block coefficient in a loading condition.
+    ///
+    pub fn condition_cb(&self, condition: Condition) -> f64 {
+        self.condition_hull(condition).cb()
+    }
+
+    // condition_gm {{{2
+    /// Metacentric height (ft) in a loading condition.
+    ///
+    /// The centre of gravity at normal displacement is shifted by the fuel
+    /// taken on or burnt, which sits low in the hull. The result is only a
+    /// guide to how the stability changes between conditions.
+    ///
+    pub fn condition_gm(&self, condition: Condition) -> f64 {
+        if condition == Condition::Normal { return self.metacenter(); }
+
+        let hull = self.condition_hull(condition);
+        let (d, dc) = (self.hull.d().to_long(), hull.d().to_long());
+        if dc <= 0.0 { return 0.0; } // catch divide by zero
+
+        let kg = (self.kg() * d + (dc - d) * self.hull.t * Self::LOAD_VCG) / dc;
+
+        hull.kb(self.engine.shafts()) + hull.bm(self.engine.shafts()) - kg
+    }
+
+    // trial_speed {{{2
+    /// Speed (kts) the machinery drives the ship at in a loading condition.
+    ///
+    /// The power needed for the top speed at normal displacement is
+    /// applied to the hull sunk or raised to the condition's draught.
+    ///
+    pub fn trial_speed(&self, condition: Condition) -> f64 {
+        if !self.engine.is_powered() || self.engine.vmax <= 0.0 { return 0.0; }
+        if condition == Condition::Normal { return self.engine.vmax; }
+
+        let hp = self.engine.hp_max(self.hull.d().to_long(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws());
+
+        let hull = self.condition_hull(condition);
+        let d = hull.d().to_long();
+
+        let (mut lo, mut hi) = Self::TRIAL_SPEEDS;
+        while hi - lo > Self::TRIAL_TOLERANCE {
+            let v = (lo + hi) / 2.0;
+            if self.engine.hp(v, d, hull.lwl(), hull.leff(), hull.cs(), hull.ws()) < hp {
+                lo = v;
+            } else {
+                hi = v;
+            }
+        }
+
+        (lo + hi) / 2.0
+    }
+
+    // trial_band {{{2
+    /// Difference (kts) from the top speed of the speed at standard
+    /// displacement and at full load.
+    ///
+    pub fn trial_band(&self) -> (f64, f64) {
+        (
+            self.trial_speed(Condition::Standard) - self.engine.vmax,
+            self.trial_speed(Condition::Full) - self.engine.vmax,
+        )
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod trials {
+    use super::*;
+    use crate::Template;
+    use crate::test_support::*;
+
+    // Test condition {{{2
+    macro_rules! test_condition {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, s) = $value;
+
+                    assert_eq!(expected, Condition::from(s));
+                }
+            )*
+        }
+    }
+
+    test_condition! {
+        // name:            (condition, string)
+        condition_standard: (Condition::Standard, "standard"),
+        condition_normal:   (Condition::Normal, "normal"),
+        condition_trial:    (Condition::Trial, "trial"),
+        condition_full:     (Condition::Full, "full"),
+        condition_overload: (Condition::Overload, "overload"),
+        condition_default:  (Condition::Normal, "half"),
+    }
+
+    // Test trial_speed {{{2
+    macro_rules! test_trial_speed {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let template = $value;
+
+                    let ship = Ship::from_template(template);
+                    let vmax = ship.engine.vmax;
+
+                    assert_eq!(to_place(vmax, 2), to_place(ship.trial_speed(Condition::Normal), 2));
+
+                    // Lighter ships are faster on the same power
+                    assert!(ship.trial_speed(Condition::Standard) > vmax);
+                    assert!(ship.trial_speed(Condition::Full) < vmax);
+
+                    // But not by much
+                    let (fast, slow) = ship.trial_band();
+                    assert!(fast < 1.0 && slow > -1.5, "{} {}", fast, slow);
+                }
+            )*
+        }
+    }
+
+    test_trial_speed! {
+        // name:                template
+        trial_dreadnought:      Template::Dreadnought1910,
+        trial_destroyer:        Template::Destroyer1935,
+    }
+
+    #[test]
+    fn trial_speed_unpowered() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.engine.vmax = 0.0;
+
+        assert_eq!(0.0, ship.trial_speed(Condition::Full));
+    }
+
+    // Test condition_d {{{2
+    #[test]
+    fn condition_d() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+
+        assert!(Condition::ALL.windows(2).all(|c| ship.condition_d(c[0]) < ship.condition_d(c[1])));
+    }
+
+    #[test]
+    fn condition_d_unpowered() {
+        let mut ship = Ship::from_template(Template::CentralBattery1870);
+        ship.engine.vmax = 0.0;
+
+        assert_eq!(ship.condition_d(Condition::Full), ship.condition_d(Condition::Overload));
+    }
+
+    // Test conditions {{{2
+    macro_rules! test_conditions {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let template = $value;
+
+                    let ship = Ship::from_template(template);
+
+                    // Normal matches the design figures
+                    assert_eq!(ship.hull.t, ship.condition_t(Condition::Normal));
+                    assert_eq!(ship.hull.cb(), ship.condition_cb(Condition::Normal));
+                    assert_eq!(ship.metacenter(), ship.condition_gm(Condition::Normal));
+                    assert_eq!(to_place(ship.t_max(), 3), to_place(ship.condition_t(Condition::Full), 3));
+
+                    // Heavier ships sit deeper, fuller and slower
+                    for c in Condition::ALL.windows(2) {
+                        assert!(ship.condition_t(c[0]) < ship.condition_t(c[1]));
+                        assert!(ship.condition_cb(c[0]) < ship.condition_cb(c[1]));
+                        assert!(ship.trial_speed(c[0]) > ship.trial_speed(c[1]));
+                    }
+
+                    // Fuel low in the hull steadies the ship
+                    assert!(ship.condition_gm(Condition::Standard) < ship.condition_gm(Condition::Full));
+                }
+            )*
+        }
+    }
+
+    test_conditions! {
+        // name:                    template
+        conditions_dreadnought:     Template::Dreadnought1910,
+        conditions_cruiser:         Template::TreatyCruiser1930,
+        conditions_destroyer:       Template::Destroyer1935,
+    }
+}
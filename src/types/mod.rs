@@ -0,0 +1,5 @@
+mod trim;
+pub use trim::Trim;
+
+mod sea_type;
+pub use sea_type::SeaType;
@@ -0,0 +1,14 @@
+use serde::{Serialize, Deserialize};
+
+// SeaType {{{1
+/// Levels of seakeeping ability.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub enum SeaType {
+    #[default]
+    BadSea,
+    PoorSea,
+    FineSea,
+    GoodSea,
+    Error, // This is an...error if it shows up anywhere
+}
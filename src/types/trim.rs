@@ -0,0 +1,122 @@
+use serde::{Serialize, Deserialize};
+
+use std::fmt;
+
+// Trim {{{1
+/// Balance between stability (0) and steadiness (100).
+///
+/// Older files store whole numbers which are read unchanged. Values
+/// outside 0 to 100 are rejected.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, PartialOrd)]
+#[serde(try_from = "f64", into = "f64")]
+pub struct Trim(f64);
+
+impl Trim { // {{{2
+    /// Smallest trim.
+    pub const MIN: f64 = 0.0;
+    /// Largest trim.
+    pub const MAX: f64 = 100.0;
+
+    // new {{{3
+    /// Create a trim, failing if it is out of range.
+    ///
+    pub fn new(trim: f64) -> Result<Trim, String> {
+        if (Self::MIN..=Self::MAX).contains(&trim) {
+            Ok(Trim(trim))
+        } else {
+            Err(format!("Trim must be between {} and {}, not {}", Self::MIN, Self::MAX, trim))
+        }
+    }
+
+    // value {{{3
+    /// Trim as a number.
+    ///
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl Default for Trim { // {{{2
+    fn default() -> Self {
+        Trim(50.0)
+    }
+}
+
+impl TryFrom<f64> for Trim { // {{{2
+    type Error = String;
+
+    fn try_from(trim: f64) -> Result<Self, Self::Error> {
+        Trim::new(trim)
+    }
+}
+
+impl From<Trim> for f64 {
+    fn from(trim: Trim) -> Self {
+        trim.0
+    }
+}
+
+impl fmt::Display for Trim { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// Testing Trim {{{2
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod trim {
+    use super::*;
+
+    // Test new {{{3
+    macro_rules! test_trim {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, trim) = $value;
+
+                    assert_eq!(expected, Trim::new(trim).is_ok());
+                }
+            )*
+        }
+    }
+
+    test_trim! {
+        // name:        (valid, trim)
+        trim_min:       (true, 0.0),
+        trim_max:       (true, 100.0),
+        trim_fraction:  (true, 37.5),
+        trim_negative:  (false, -0.5),
+        trim_too_large: (false, 100.5),
+        trim_nan:       (false, f64::NAN),
+    }
+
+    // Test serde {{{3
+    macro_rules! test_trim_serde {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, json) = $value;
+
+                    assert_eq!(expected, serde_json::from_str::<Trim>(json).ok().map(|t| t.value()));
+                }
+            )*
+        }
+    }
+
+    test_trim_serde! {
+        // name:               (trim, json)
+        trim_serde_integer:    (Some(50.0), "50"),
+        trim_serde_fraction:   (Some(62.5), "62.5"),
+        trim_serde_range:      (None, "150"),
+    }
+
+    #[test]
+    fn trim_display() {
+        assert_eq!("50", Trim::default().to_string());
+        assert_eq!("62.5", Trim::new(62.5).unwrap().to_string());
+    }
+}
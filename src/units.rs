@@ -1,5 +1,9 @@
+use format_num::format_num;
 use serde::{Serialize, Deserialize};
+
 use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Sub};
 
 // Units {{{1
 #[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
@@ -9,6 +13,15 @@ pub enum Units {
     Metric
 }
 
+impl Units { // {{{2
+    // is_imperial {{{3
+    /// True for imperial units, which ship files leave out.
+    ///
+    pub(crate) fn is_imperial(&self) -> bool {
+        *self == Units::Imperial
+    }
+}
+
 impl From<String> for Units { // {{{2
     fn from(index: String) -> Self {
         index.as_str().into()
@@ -16,6 +29,7 @@ impl From<String> for Units { // {{{2
 }
 
 impl From<&str> for Units {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1"     => Self::Metric,
@@ -39,15 +53,45 @@ pub enum UnitType { // {{{1
     LengthSmall,
     LengthLong,
     Area,
+    Volume,
     Weight,
     Power, 
     WeightPerArea,
 }
 
+impl UnitType { // {{{2
+    // abbr {{{3
+    /// Abbreviation used when reporting a value of this type.
+    ///
+    pub fn abbr(&self, units: Units) -> &'static str {
+        match units {
+            Units::Imperial => match self {
+                Self::LengthSmall   => "in",
+                Self::LengthLong    => "ft",
+                Self::Area          => "sq ft",
+                Self::Volume        => "cu ft",
+                Self::Weight        => "lbs",
+                Self::Power         => "hp",
+                Self::WeightPerArea => "lbs/sq ft",
+            },
+            Units::Metric => match self {
+                Self::LengthSmall   => "mm",
+                Self::LengthLong    => "m",
+                Self::Area          => "sq m",
+                Self::Volume        => "cu m",
+                Self::Weight        => "kg",
+                Self::Power         => "kW",
+                Self::WeightPerArea => "kg/sq m",
+            },
+        }
+    }
+}
+
 // Conversion constants {{{2
 const INCH2MM: f64         = 25.4;
 const FEET2METERS: f64     = 0.3048;
 const SQFEET2SQMETERS: f64 = 0.092903;
+const CUFEET2CUMETERS: f64 = 0.0283168;
 const POUND2KG: f64        = 0.45359236;
 const HP2KW: f64           = 0.746;
 
@@ -60,9 +104,223 @@ pub fn metric(imperial: f64, unit_type: UnitType, units: Units) -> f64 { // {{{3
         UnitType::LengthSmall => imperial * INCH2MM,
         UnitType::LengthLong => imperial * FEET2METERS,
         UnitType::Area => imperial * SQFEET2SQMETERS,
+        UnitType::Volume => imperial * CUFEET2CUMETERS,
         UnitType::Weight => imperial * POUND2KG,
         UnitType::Power => imperial * HP2KW,
         UnitType::WeightPerArea => imperial / SQFEET2SQMETERS * POUND2KG,
     }
 }
 
+
+pub fn imperial(metric: f64, unit_type: UnitType, units: Units) -> f64 { // {{{3
+    if units == Units::Imperial { return metric; }
+
+    match unit_type {
+        UnitType::LengthSmall => metric / INCH2MM,
+        UnitType::LengthLong => metric / FEET2METERS,
+        UnitType::Area => metric / SQFEET2SQMETERS,
+        UnitType::Volume => metric / CUFEET2CUMETERS,
+        UnitType::Weight => metric / POUND2KG,
+        UnitType::Power => metric / HP2KW,
+        UnitType::WeightPerArea => metric * SQFEET2SQMETERS / POUND2KG,
+    }
+}
+
+// convert {{{3
+/// Convert a value in one system of units to another.
+///
+pub fn convert(value: f64, unit_type: UnitType, from: Units, to: Units) -> f64 {
+    match to {
+        Units::Imperial => imperial(value, unit_type, from),
+        Units::Metric   => metric(value, unit_type, from),
+    }
+}
+
+// Tons {{{1
+/// A weight, held in long tons.
+///
+/// Weights are built from and read back in an explicit unit so that
+/// short tons or tonnes cannot be mistaken for the long tons used
+/// throughout the calculations.
+///
+/// Displacements, targets, weight overrides and the Ship::wgt_*
+/// breakdown are given as Tons. The whole-ton MiscWgts of the ship file
+/// and the weights of single components, such as a Battery's, stay in
+/// long tons.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[serde(transparent)]
+pub struct Tons(f64);
+
+impl Tons { // {{{2
+    /// Pounds in a long ton.
+    const LONG: f64 = 2240.0;
+    /// Pounds in a short ton.
+    const SHORT: f64 = 2000.0;
+    /// Pounds in a tonne.
+    const TONNE: f64 = 1000.0 / POUND2KG;
+
+    // long {{{3
+    /// Weight of a number of long tons.
+    ///
+    pub const fn long(t: f64) -> Tons { Tons(t) }
+
+    // short {{{3
+    /// Weight of a number of short tons.
+    ///
+    pub fn short(t: f64) -> Tons { Tons(t * Self::SHORT / Self::LONG) }
+
+    // tonnes {{{3
+    /// Weight of a number of metric tonnes.
+    ///
+    pub fn tonnes(t: f64) -> Tons { Tons(t * Self::TONNE / Self::LONG) }
+
+    // pounds {{{3
+    /// Weight of a number of pounds.
+    ///
+    pub fn pounds(lbs: f64) -> Tons { Tons(lbs / Self::LONG) }
+
+    // to_long {{{3
+    /// Weight in long tons.
+    ///
+    pub fn to_long(self) -> f64 { self.0 }
+
+    // to_pounds {{{3
+    /// Weight in pounds.
+    ///
+    pub fn to_pounds(self) -> f64 { self.0 * Self::LONG }
+
+    // to_short {{{3
+    /// Weight in short tons.
+    ///
+    pub fn to_short(self) -> f64 { self.0 * Self::LONG / Self::SHORT }
+
+    // to_tonnes {{{3
+    /// Weight in metric tonnes.
+    ///
+    pub fn to_tonnes(self) -> f64 { self.0 * Self::LONG / Self::TONNE }
+
+    // to_units {{{3
+    /// Weight in long tons or metric tonnes.
+    ///
+    pub fn to_units(self, units: Units) -> f64 {
+        match units {
+            Units::Imperial => self.to_long(),
+            Units::Metric   => self.to_tonnes(),
+        }
+    }
+
+    // desc {{{3
+    /// Weight in long tons or metric tonnes, e.g. "27,500 t" or
+    /// "27,941 tonnes".
+    ///
+    pub fn desc(self, units: Units) -> String {
+        match units {
+            Units::Imperial => self.to_string(),
+            Units::Metric   => format!("{} tonnes", format_num!(",.0", self.to_tonnes())),
+        }
+    }
+}
+
+impl fmt::Display for Tons { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} t", format_num!(",.0", self.0))
+    }
+}
+
+impl Add for Tons { // {{{2
+    type Output = Tons;
+
+    fn add(self, other: Tons) -> Tons { Tons(self.0 + other.0) }
+}
+
+impl AddAssign for Tons {
+    fn add_assign(&mut self, other: Tons) { self.0 += other.0; }
+}
+
+impl Sub for Tons {
+    type Output = Tons;
+
+    fn sub(self, other: Tons) -> Tons { Tons(self.0 - other.0) }
+}
+
+impl Mul<f64> for Tons {
+    type Output = Tons;
+
+    fn mul(self, factor: f64) -> Tons { Tons(self.0 * factor) }
+}
+
+impl Sum for Tons {
+    fn sum<I: Iterator<Item = Tons>>(iter: I) -> Tons {
+        let mut t = Tons::default();
+        for w in iter { t += w; }
+
+        t
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod units {
+    use super::*;
+
+    // Test convert {{{2
+    macro_rules! test_convert {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, value, from, to) = $value;
+
+                    assert_eq!(expected, (convert(value, UnitType::LengthSmall, from, to) * 100.0).round() / 100.0);
+                }
+            )*
+        }
+    }
+
+    test_convert! {
+        // name:                   (expected, value, from, to)
+        convert_to_metric:         (25.4, 1.0, Units::Imperial, Units::Metric),
+        convert_to_imperial:       (1.0, 25.4, Units::Metric, Units::Imperial),
+        convert_imperial_imperial: (12.0, 12.0, Units::Imperial, Units::Imperial),
+        convert_metric_metric:     (305.0, 305.0, Units::Metric, Units::Metric),
+    }
+
+    // Test Tons {{{2
+    macro_rules! test_tons {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((long, short, tonnes), tons) = $value;
+
+                    let round = |v: f64| (v * 100.0).round() / 100.0;
+
+                    assert_eq!(long, round(tons.to_long()));
+                    assert_eq!(short, round(tons.to_short()));
+                    assert_eq!(tonnes, round(tons.to_tonnes()));
+                }
+            )*
+        }
+    }
+
+    test_tons! {
+        // name:          ((long, short, tonnes), tons)
+        tons_long:        ((1.0, 1.12, 1.02), Tons::long(1.0)),
+        tons_short:       ((0.89, 1.0, 0.91), Tons::short(1.0)),
+        tons_tonnes:      ((0.98, 1.1, 1.0), Tons::tonnes(1.0)),
+        tons_pounds:      ((1.0, 1.12, 1.02), Tons::pounds(2240.0)),
+    }
+
+    // Test Tons desc {{{2
+    #[test]
+    fn tons_desc() {
+        let tons = Tons::long(27_500.0);
+
+        assert_eq!("27,500 t", tons.to_string());
+        assert_eq!("27,500 t", tons.desc(Units::Imperial));
+        assert_eq!("27,941 tonnes", tons.desc(Units::Metric));
+        assert_eq!(Tons::long(3.0), [Tons::long(1.0), Tons::long(2.0)].into_iter().sum());
+    }
+}
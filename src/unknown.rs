@@ -0,0 +1,195 @@
+use crate::Ship;
+
+use serde_json::{Map, Value};
+
+impl Ship { // {{{1
+    // unknown_fields {{{2
+    /// Paths of the fields of the ship file this version of sharpie does
+    /// not know, such as those added by a newer version. They are kept
+    /// and written back when the ship is saved.
+    ///
+    pub fn unknown_fields(&self) -> Vec<String> {
+        let mut paths = Vec::new();
+        if let Ok(known) = serde_json::to_value(self) { field_paths(&self.unknown, &known, "", &mut paths); }
+
+        paths
+    }
+}
+
+// unknown {{{1
+/// Parts of a ship file that are not in the ship read from it, as a tree
+/// of the objects and arrays that lead to them, or Null if there are none.
+///
+/// Fields that are null or empty are not kept as they mean the same as
+/// leaving the field out, which is how some known fields are written.
+///
+pub(crate) fn unknown(file: &Value, known: &Value) -> Value {
+    match (file, known) {
+        (Value::Object(file), Value::Object(known)) => {
+            let mut found = Map::new();
+
+            for (k, v) in file {
+                let u = match known.get(k) {
+                    Some(known) => unknown(v, known),
+                    None        => v.clone(),
+                };
+                if !is_empty(&u) { found.insert(k.clone(), u); }
+            }
+
+            if found.is_empty() { Value::Null } else { Value::Object(found) }
+        },
+
+        (Value::Array(file), Value::Array(known)) => {
+            let found: Vec<Value> = file.iter().zip(known).map(|(f, k)| unknown(f, k)).collect();
+
+            if found.iter().all(Value::is_null) { Value::Null } else { Value::Array(found) }
+        },
+
+        _ => Value::Null,
+    }
+}
+
+// is_empty {{{1
+/// True if a value holds no data.
+///
+fn is_empty(v: &Value) -> bool {
+    match v {
+        Value::Null           => true,
+        Value::Array(items)   => items.is_empty(),
+        Value::Object(fields) => fields.is_empty(),
+        _                     => false,
+    }
+}
+
+// merge {{{1
+/// Put the unknown parts of a ship file back into a ship written out to
+/// JSON. Parts whose place in the ship no longer exists are dropped.
+///
+pub(crate) fn merge(known: &mut Value, unknown: &Value) {
+    match (known, unknown) {
+        (Value::Object(known), Value::Object(unknown)) => {
+            for (k, u) in unknown {
+                match known.get_mut(k) {
+                    Some(known) => merge(known, u),
+                    None        => { known.insert(k.clone(), u.clone()); },
+                }
+            }
+        },
+
+        (Value::Array(known), Value::Array(unknown)) => {
+            for (k, u) in known.iter_mut().zip(unknown) { merge(k, u); }
+        },
+
+        _ => (),
+    }
+}
+
+// field_paths {{{1
+/// Add the path to each field in a tree from unknown() that is not in
+/// the known ship.
+///
+fn field_paths(unknown: &Value, known: &Value, path: &str, paths: &mut Vec<String>) {
+    match (unknown, known) {
+        (Value::Object(unknown), Value::Object(known)) => for (k, u) in unknown {
+            let path = if path.is_empty() { k.clone() } else { format!("{}.{}", path, k) };
+
+            match known.get(k) {
+                Some(known) => field_paths(u, known, &path, paths),
+                None        => paths.push(path),
+            }
+        },
+
+        (Value::Array(unknown), Value::Array(known)) => for (i, (u, k)) in unknown.iter().zip(known).enumerate() {
+            field_paths(u, k, &format!("{}[{}]", path, i), paths);
+        },
+
+        _ => (),
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod unknown {
+    use super::*;
+    use crate::Template;
+
+    use serde_json::json;
+
+    // Test unknown {{{2
+    macro_rules! test_unknown {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, file, known) = $value;
+
+                    assert_eq!(expected, unknown(&file, &known));
+                }
+            )*
+        }
+    }
+
+    test_unknown! {
+        // name:                (unknown, file, known)
+        unknown_none:           (Value::Null, json!({"a": 1, "b": {"c": 2}}), json!({"a": 1, "b": {"c": 2}})),
+        unknown_top:            (json!({"z": [1, 2]}), json!({"a": 1, "z": [1, 2]}), json!({"a": 1})),
+        unknown_nested:         (json!({"b": {"z": "x"}}), json!({"a": 1, "b": {"c": 2, "z": "x"}}), json!({"a": 1, "b": {"c": 2}})),
+        unknown_array:          (json!({"b": [null, {"z": 3}]}), json!({"b": [{"c": 1}, {"c": 2, "z": 3}]}), json!({"b": [{"c": 1}, {"c": 2}]})),
+        unknown_null:           (Value::Null, json!({"a": 1, "z": null}), json!({"a": 1})),
+        unknown_empty:          (Value::Null, json!({"a": 1, "y": [], "z": {}}), json!({"a": 1})),
+    }
+
+    // Test merge {{{2
+    #[test]
+    fn merge_back() {
+        let file = json!({"a": 1, "b": [{"c": 1}, {"c": 2, "z": 3}], "d": {"e": 4, "y": {"f": 5}}, "x": true});
+        let mut known = json!({"a": 2, "b": [{"c": 1}, {"c": 3}], "d": {"e": 4}});
+
+        let found = unknown(&file, &known);
+        merge(&mut known, &found);
+
+        // Unknown fields come back without undoing changes to known ones
+        assert_eq!(json!({"a": 2, "b": [{"c": 1}, {"c": 3, "z": 3}], "d": {"e": 4, "y": {"f": 5}}, "x": true}), known);
+    }
+
+    // Test round trip {{{2
+    #[test]
+    fn round_trip() {
+        let mut value = serde_json::to_value(Ship::from_template(Template::Dreadnought1910)).unwrap();
+        value["rigging"] = json!({"masts": 2, "sails": false});
+        value["hull"]["sheer"] = json!(1.5);
+        value["batteries"][1]["director"] = json!("Dreyer table");
+
+        let ship = Ship::parse(&format!("{{\"version\":1}}\n{}", value)).unwrap();
+        assert_eq!(vec!["batteries[1].director", "hull.sheer", "rigging"], ship.unknown_fields());
+        assert!(ship.validate().iter().any(|i| i.code == "unknown-fields"));
+
+        // Saving keeps the unknown fields and the checksum agrees with them
+        let saved = Ship::parse(&ship.file_contents().unwrap()).unwrap();
+        assert_eq!(ship.unknown_fields(), saved.unknown_fields());
+        assert!(!saved.validate().iter().any(|i| i.code == "file-edited"));
+
+        let contents = ship.file_contents().unwrap();
+        let file: Value = serde_json::from_str(contents.lines().nth(1).unwrap()).unwrap();
+        assert_eq!(json!({"masts": 2, "sails": false}), file["rigging"]);
+        assert_eq!(json!("Dreyer table"), file["batteries"][1]["director"]);
+
+        // Ships without unknown fields are saved as before
+        let plain = Ship::from_template(Template::Dreadnought1910);
+        assert!(plain.unknown_fields().is_empty());
+        assert!(plain.file_contents().unwrap().contains(&serde_json::to_string(&plain).unwrap()));
+    }
+
+    // Test set {{{2
+    #[test]
+    fn set_keeps_unknown() {
+        let mut value = serde_json::to_value(Ship::from_template(Template::Dreadnought1910)).unwrap();
+        value["rigging"] = json!(2);
+
+        let mut ship = Ship::parse(&format!("{{\"version\":1}}\n{}", value)).unwrap();
+        ship.set("name", json!("Warspite")).unwrap();
+
+        assert_eq!(vec!["rigging"], ship.unknown_fields());
+    }
+}
@@ -0,0 +1,1150 @@
+use crate::{Ship, Conversion, ReportSection};
+use crate::armor::Citadel;
+use crate::plausibility::Plausibility;
+use crate::hull::{Hull, Hump};
+use crate::units::{metric, imperial};
+use crate::units::UnitType::{Area, LengthLong, LengthSmall, Volume};
+use crate::units::Units::{Imperial, Metric};
+use crate::sea::SeaState;
+use crate::report::plural;
+use crate::weapons::{Battery, TrainingDrive};
+
+use serde::Serialize;
+use std::fmt;
+
+// Severity {{{1
+/// How serious a design issue is.
+///
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// The design works but something is questionable.
+    Warning,
+    /// The design does not work.
+    Failure,
+}
+
+impl fmt::Display for Severity { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Warning => "Warning",
+                Self::Failure => "Failure",
+            }
+        )
+    }
+}
+
+impl From<String> for Severity { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for Severity {
+    #[allow(clippy::wildcard_in_or_patterns)]
+    fn from(index: &str) -> Self {
+        match index {
+            "warning"     => Self::Warning,
+            "failure" | _ => Self::Failure,
+        }
+    }
+}
+
+// DesignIssue {{{1
+/// A problem found when validating a design.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct DesignIssue {
+    /// Stable identifier for the issue.
+    pub code: &'static str,
+    /// How serious the issue is.
+    pub severity: Severity,
+    /// Path to the Ship field most responsible for the issue.
+    pub field: &'static str,
+    /// Description of the issue.
+    pub message: String,
+}
+
+impl DesignIssue { // {{{2
+    // failure {{{3
+    /// Create a design failure.
+    ///
+    fn failure(code: &'static str, field: &'static str, message: String) -> DesignIssue {
+        DesignIssue { code, severity: Severity::Failure, field, message }
+    }
+
+    // warning {{{3
+    /// Create a design warning.
+    ///
+    pub(crate) fn warning(code: &'static str, field: &'static str, message: String) -> DesignIssue {
+        DesignIssue { code, severity: Severity::Warning, field, message }
+    }
+
+    // section {{{3
+    /// Section of the report that shows the field the issue is about.
+    ///
+    pub fn section(&self) -> Option<ReportSection> {
+        ReportSection::showing(self.field)
+    }
+}
+
+impl Ship { // {{{1
+    /// Share of the minimum bow freeboard below which the bow is buried.
+    const BOW_BURIED: f64 = 0.5;
+    /// Share of the minimum bow freeboard a ship may fall short by before
+    /// it is very wet.
+    const VERY_WET: f64 = 0.2;
+    /// Years after the ship was laid down that a weapon may date from
+    /// before it is anachronistic.
+    const YEAR_MARGIN: u32 = 5;
+    /// Weakest composite strength a warship hull can have.
+    pub const MIN_STR: f64 = 0.5;
+    /// Largest gun calibre (in) that is not a probable unit error.
+    const CALIBRE_MAX: f64 = 20.0;
+    /// Thickest armour (in) that is not a probable unit error.
+    const ARMOR_MAX: f64 = 26.0;
+    /// Length to beam ratios that are not probable unit errors.
+    const LENGTH_BEAM: (f64, f64) = (2.5, 15.0);
+    /// Beam to draught ratios that are not probable unit errors.
+    const BEAM_DRAUGHT: (f64, f64) = (1.2, 9.0);
+
+    // validate {{{2
+    /// Check the design for problems.
+    ///
+    pub fn validate(&self) -> Vec<DesignIssue> {
+        let mut v = Vec::new();
+
+        // Failures {{{3
+        if self.hull.cb() <= 0.0 || self.hull.cb() > 1.0 {
+            v.push(DesignIssue::failure("displacement-impossible", "hull.d",
+                "Displacement impossible with given dimensions".into()));
+        }
+        if self.hull.d().to_long() < (self.wgt_broad() / 4.0) {
+            v.push(DesignIssue::failure("guns-too-heavy", "batteries",
+                "Gun weight too much for hull".into()));
+        }
+        if self.wgt_armor() > self.hull.d().to_long() {
+            v.push(DesignIssue::failure("armor-too-heavy", "armor",
+                "Armour weight too much for hull".into()));
+        }
+        if self.str_comp() < self.min_str() {
+            v.push(DesignIssue::failure("hull-overloaded", "hull",
+                "Overall load weight too much for hull".into()));
+        }
+        if self.capsize_warn() {
+            v.push(DesignIssue::failure("capsize", "hull.b",
+                "Ship will capsize".into()));
+        }
+        let (fc_fwd, fc_min) = (self.hull.fc_fwd, self.hull.fc_fwd_min(self.engine.vmax));
+        if fc_fwd < fc_min * Self::BOW_BURIED {
+            v.push(DesignIssue::failure("bow-buried", "hull.fc_fwd",
+                format!("Bow freeboard of {:.2} ft / {:.2} m is less than half the {:.2} ft / {:.2} m needed at {:.2} kts, ship will bury its bow",
+                    fc_fwd, metric(fc_fwd, LengthLong, Imperial), fc_min, metric(fc_min, LengthLong, Imperial), self.engine.vmax)));
+        }
+
+        // Ship file {{{3
+        if let Some(saved_by) = &self.edited_since {
+            v.push(DesignIssue::warning("file-edited", "",
+                format!("Ship file has been edited by hand since sharpie {} saved it, check that its values agree with each other",
+                    if saved_by.is_empty() { "(unknown version)" } else { saved_by })));
+        }
+        let unknown = self.unknown_fields();
+        if !unknown.is_empty() {
+            v.push(DesignIssue::warning("unknown-fields", "",
+                format!("Ship file has fields this version of sharpie does not know ({}), they are kept when it is saved but not used",
+                    unknown.join(", "))));
+        }
+        v.extend(self.clamped.iter().cloned());
+        if self.hull.is_overdetermined() {
+            v.push(DesignIssue::warning("hull-overdetermined", "hull.cb",
+                format!("Both the block coefficient ({:.3}) and the displacement ({:.0} t) are set, remove one of them from the ship file",
+                    self.hull.cb(), self.hull.d().to_long())));
+        }
+
+        // Units {{{3
+        let (lwl, b, t) = (self.hull.lwl(), self.hull.b, self.hull.t);
+        let ratio_in = |r: f64, (min, max): (f64, f64)| (min..=max).contains(&r);
+        let in_metres = |what: &str, ft: f64, ratio: &str, r: f64| format!(
+            "{} of {:.2} ft gives a {} ratio of {:.2}, it may have been entered in m ({:.2} ft)",
+            what, ft, ratio, r, imperial(ft, LengthLong, Metric));
+
+        if b > 0.0 && !ratio_in(lwl / b, Self::LENGTH_BEAM) {
+            let ratio = lwl / b;
+            if ratio_in(lwl / imperial(b, LengthLong, Metric), Self::LENGTH_BEAM) {
+                v.push(DesignIssue::warning("probable-unit-error", "hull.b", in_metres("Beam", b, "length to beam", ratio)));
+            } else if ratio_in(imperial(lwl, LengthLong, Metric) / b, Self::LENGTH_BEAM) {
+                v.push(DesignIssue::warning("probable-unit-error", "hull.lwl", in_metres("Length", lwl, "length to beam", ratio)));
+            }
+        }
+        if t > 0.0 && !ratio_in(b / t, Self::BEAM_DRAUGHT) {
+            let ratio = b / t;
+            if ratio_in(b / imperial(t, LengthLong, Metric), Self::BEAM_DRAUGHT) {
+                v.push(DesignIssue::warning("probable-unit-error", "hull.t", in_metres("Draught", t, "beam to draught", ratio)));
+            } else if ratio_in(imperial(b, LengthLong, Metric) / t, Self::BEAM_DRAUGHT) {
+                v.push(DesignIssue::warning("probable-unit-error", "hull.b", in_metres("Beam", b, "beam to draught", ratio)));
+            }
+        }
+
+        for (i, b) in self.batteries.iter().enumerate().filter(|(_, b)| b.num > 0) {
+            if let Some(guess) = small_units(b.diam, Self::CALIBRE_MAX) {
+                v.push(DesignIssue::warning("probable-unit-error", "batteries",
+                    format!("{} battery calibre of {}\" is larger than any gun built, it may have been entered in {}",
+                        battery_name(i), b.diam, guess)));
+            }
+        }
+
+        let armor = [
+            ("armor.main.thick", "Main belt", self.armor.main.thick),
+            ("armor.end.thick", "End belt", self.armor.end.thick),
+            ("armor.upper.thick", "Upper belt", self.armor.upper.thick),
+            ("armor.deck.md", "Deck", self.armor.deck.md),
+            ("armor.ct_fwd.thick", "Conning tower", self.armor.ct_fwd.thick),
+        ];
+        for (field, what, thick) in armor {
+            if let Some(guess) = small_units(thick, Self::ARMOR_MAX) {
+                v.push(DesignIssue::warning("probable-unit-error", field,
+                    format!("{} armour of {}\" is thicker than any fitted, it may have been entered in {}",
+                        what, thick, guess)));
+            }
+        }
+        for (i, b) in self.batteries.iter().enumerate().filter(|(_, b)| b.num > 0) {
+            for (what, thick) in [("face", b.armor_face), ("barbette", b.armor_barb)] {
+                if let Some(guess) = small_units(thick, Self::ARMOR_MAX) {
+                    v.push(DesignIssue::warning("probable-unit-error", "batteries",
+                        format!("{} battery {} armour of {}\" is thicker than any fitted, it may have been entered in {}",
+                            battery_name(i), what, thick, guess)));
+                }
+            }
+        }
+
+        // Merchant conversion {{{3
+        if let Some(c) = &self.conversion {
+            let (min, max) = Conversion::HULL_FRACTIONS;
+
+            if c.hull_fraction < min || c.hull_fraction > max {
+                v.push(DesignIssue::warning("hull-fraction-unusual", "conversion.hull_fraction",
+                    format!("Merchant hulls take {:.0}% to {:.0}% of the displacement, not {:.0}%",
+                        min * 100.0, max * 100.0, c.hull_fraction * 100.0)));
+            }
+            if self.wgt_spare().to_long() < 0.0 {
+                v.push(DesignIssue::warning("conversion-overweight", "conversion.cargo",
+                    format!("Conversion is {:.0} t heavier than the merchant hull can carry, land cargo or weapons",
+                        -self.wgt_spare().to_long())));
+            }
+            if self.str_comp() >= self.min_str() && self.str_comp() < Ship::MIN_STR {
+                v.push(DesignIssue::warning("merchant-hull-weak", "conversion.hull_fraction",
+                    format!("Merchant hull strength of {:.2} is below warship standards, it will not stand up to heavy weather or action damage",
+                        self.str_comp())));
+            }
+            if self.armor.main.thick > 0.0 || self.armor.deck.is_armored() {
+                v.push(DesignIssue::warning("merchant-hull-armored", "armor",
+                    "Merchant hulls were rarely armoured, belt and deck armour has to be added to a structure not built for it".into()));
+            }
+        }
+
+        // Belt geometry {{{3
+        let (t, dist) = (self.hull.t, self.hull.freeboard_dist());
+        let side = self.armor.max_belt_hgt(t, dist);
+
+        if self.armor.main.thick > 0.0 && self.armor.main_fit(t, dist) < 1.0 {
+            v.push(DesignIssue::warning("belt-too-tall", "armor.main.hgt",
+                format!("Main belt is {:.2} ft high but the hull side is only {:.2} ft", self.armor.main.hgt, side)));
+        }
+        if self.armor.end.thick > 0.0 && self.armor.end_fit(t, dist) < 1.0 {
+            v.push(DesignIssue::warning("belt-too-tall", "armor.end.hgt",
+                format!("End belt is {:.2} ft high but the hull side is only {:.2} ft", self.armor.end.hgt, side)));
+        }
+        if self.armor.upper.thick > 0.0 && self.armor.upper_fit(t, dist) < 1.0 {
+            v.push(DesignIssue::warning("belt-too-tall", "armor.upper.hgt",
+                format!("Upper belt does not fit on the hull side above the main belt ({:.2} ft available)",
+                    f64::max(side - self.armor.main.hgt, 0.0))));
+        }
+        if let Some(citadel) = &self.armor.citadel {
+            if self.armor.upper.thick <= 0.0 || self.armor.upper.len <= 0.0 || self.armor.upper.hgt <= 0.0 {
+                v.push(DesignIssue::warning("citadel-no-upper", "armor.citadel",
+                    "Citadel bulkheads have no upper belt to close and add no weight".into()));
+            }
+            if citadel.incline.abs() > Citadel::MAX_INCLINE {
+                v.push(DesignIssue::warning("citadel-incline", "armor.citadel.incline",
+                    format!("Citadel bulkheads sloped {:.2} degrees are treated as sloped {:.0} degrees",
+                        citadel.incline, Citadel::MAX_INCLINE)));
+            }
+        }
+        if self.armor.main.thick > 0.0 && self.armor.belt_above_water(t, dist) <= 0.0 {
+            v.push(DesignIssue::warning("belt-below-water", "armor.main.hgt",
+                format!("Main belt is entirely below the waterline (it must be over {:.2} ft high)",
+                    self.armor.belt_below_water(t))));
+        }
+
+        // Centres of gravity {{{3
+        let depth = self.hull.t + self.hull.freeboard();
+        for (field, name, hgt) in [
+            ("vcg.machinery", "Machinery", self.vcg.machinery),
+            ("vcg.main_belt", "Main belt", self.vcg.main_belt),
+            ("vcg.end_belt", "End belt", self.vcg.end_belt),
+            ("vcg.upper_belt", "Upper belt", self.vcg.upper_belt),
+        ] {
+            if let Some(h) = hgt && !(0.0..=depth).contains(&h) {
+                v.push(DesignIssue::warning("vcg-outside-hull", field,
+                    format!("{} centre of gravity of {:.2} ft is outside the hull (keel to deck is {:.2} ft)", name, h, depth)));
+            }
+        }
+        for (i, b) in self.batteries.iter().enumerate() {
+            if let Some(h) = b.vcg && h < 0.0 {
+                v.push(DesignIssue::warning("vcg-outside-hull", "batteries",
+                    format!("{} battery centre of gravity of {:.2} ft is below the keel", battery_name(i), h)));
+            }
+        }
+
+        // Bunkers {{{3
+        if self.engine.is_powered() && !self.bunkers_fit() {
+            let (need, space) = (self.bunker_volume(), self.bunker_space());
+            v.push(DesignIssue::warning("bunkers-too-large", "engine.range",
+                format!("Fuel for {} nm needs {:.0} cu ft / {:.0} cu m of bunkers but the hull has room for {:.0} cu ft / {:.0} cu m, range is at most {} nm",
+                    self.engine.range, need, metric(need, Volume, Imperial), space, metric(space, Volume, Imperial), self.range_max())));
+        }
+
+        // Stores {{{3
+        if self.stores_short() {
+            v.push(DesignIssue::warning("stores-short", "endurance",
+                format!("Stores last {:.0} days but steaming {} nm at {} kts takes {:.0} days, carry more stores or cut the range",
+                    self.endurance_days(), self.engine.range, self.engine.vcruise, self.voyage_days())));
+        }
+
+        // Subdivision {{{3
+        if self.compartments.is_some() && self.floodable_compartments() == 0 {
+            v.push(DesignIssue::warning("flooding-one-compartment", "compartments",
+                format!("Flooding one {:.1} ft compartment sinks the ship {:.2} ft, more than its {:.2} ft freeboard, add bulkheads or freeboard",
+                    self.compartment_len(), self.flood_sinkage(1), self.hull.freeboard_dist())));
+        }
+
+        // Speed {{{3
+        let hump = self.hull.hump(self.engine.vmax);
+        if self.engine.is_powered() && hump != Hump::Clear {
+            let lwl = Hull::lwl_for_froude(self.engine.vmax, Hull::ECONOMIC_FROUDE);
+
+            v.push(DesignIssue::warning("speed-near-hump", "engine.vmax",
+                format!("Top speed is {} the resistance hump (Froude number {:.2}), {:.0} ft / {:.0} m waterline needed to reach it economically",
+                    hump, self.hull.froude(self.engine.vmax), lwl, metric(lwl, LengthLong, Imperial))));
+        }
+
+        // Bow freeboard {{{3
+        if fc_fwd < fc_min && fc_fwd >= fc_min * Self::BOW_BURIED {
+            let short = fc_min - fc_fwd;
+
+            v.push(DesignIssue::warning("wet-forward", "hull.fc_fwd",
+                format!("Bow freeboard is {:.2} ft / {:.2} m short of the {:.2} ft / {:.2} m needed at {:.2} kts, ship is {}wet forward",
+                    short, metric(short, LengthLong, Imperial), fc_min, metric(fc_min, LengthLong, Imperial), self.engine.vmax,
+                    if short > fc_min * Self::VERY_WET { "very " } else { "" })));
+        }
+
+        // Hull size {{{3
+        if self.hull.size_factor() < 1.0 {
+            v.push(DesignIssue::warning("hull-beyond-calibration", "hull.lwl",
+                format!("Waterline is longer than {:.0} ft / {:.0} m, strength reduced {:.0}% to allow for the hull's size",
+                    Hull::STRENGTH_LWL, metric(Hull::STRENGTH_LWL, LengthLong, Imperial),
+                    (1.0 - self.hull.size_factor()) * 100.0)));
+        }
+
+        // Deck space {{{3
+        if self.deck_saturated() {
+            v.push(DesignIssue::warning("deck-saturated", "batteries",
+                format!("Weapons leave only {:.0} sq ft / {:.0} sq m ({:.0}%) of deck for boats, rafts and additions",
+                    self.deck_area_clear(), metric(self.deck_area_clear(), Area, Imperial), self.deck_area_ratio() * 100.0)));
+        }
+
+        // Boiler uptakes {{{3
+        if self.uptakes_crowded() {
+            v.push(DesignIssue::warning("uptakes-crowded", "batteries",
+                format!("{} funnel{} and the centreline mounts amidships need more than {:.0}% of the ship's length",
+                    self.funnels(), plural(self.funnels()), Ship::MIDSHIPS * 100.0)));
+        }
+
+        // Mount tiers {{{3
+        for b in self.batteries.iter() {
+            for g in b.groups.iter() {
+                if let Some(t) = &g.tiers && t.num_mounts() != g.above + g.on {
+                    v.push(DesignIssue::warning("tiers-mismatch", "batteries",
+                        format!("Mount tiers list {} mounts but the group has {} above the waterline",
+                            t.num_mounts(), g.above + g.on)));
+                }
+            }
+        }
+
+        // Gun length {{{3
+        for (i, b) in self.batteries.iter().enumerate().filter(|(_, b)| b.num > 0 && b.over_length()) {
+            v.push(DesignIssue::warning("gun-over-length", "batteries",
+                format!("{} battery guns are {:.1} calibres long but no guns over {:.0} calibres were in service in {}, they are heavy, wear quickly and need reduced charges to stay accurate",
+                    battery_name(i), b.len, b.max_len(), b.year)));
+        }
+
+        // Mount layouts {{{3
+        if !self.fantasy {
+            for (i, b) in self.batteries.iter().enumerate().filter(|(_, b)| b.num > 0) {
+                for s in b.infeasible_layouts() {
+                    v.push(DesignIssue::warning("infeasible-mount", "batteries",
+                        format!("{} battery: {}", battery_name(i), s)));
+                }
+            }
+        }
+
+        // Mount widths {{{3
+        for (i, b) in self.batteries.iter().enumerate().filter(|(_, b)| b.num > 0 && b.beam_req() > self.hull.b) {
+            v.push(DesignIssue::warning("mounts-too-wide", "batteries",
+                format!("{} battery mounts and barbettes need {:.2} ft / {:.2} m of beam to fit but the ship is {:.2} ft / {:.2} m wide",
+                    battery_name(i), b.beam_req(), metric(b.beam_req(), LengthLong, Imperial),
+                    self.hull.b, metric(self.hull.b, LengthLong, Imperial))));
+        }
+
+        // Historical ratios {{{3
+        if !self.fantasy {
+            let plausibility = self.plausibility();
+            let nearest: Vec<&str> = plausibility.analogues.iter().map(|a| a.name).collect();
+
+            for c in plausibility.checks.iter().filter(|c| c.divergence > Plausibility::DIVERGENCE_MAX) {
+                let range = match c.min == c.max {
+                    true  => format!("{:.2}", c.min),
+                    false => format!("{:.2} to {:.2}", c.min, c.max),
+                };
+                v.push(DesignIssue::warning("implausible-ratio", c.field,
+                    format!("{} is {:.2} against {} for comparable real ships such as {}",
+                        c.name, c.value, range, nearest.join(", "))));
+            }
+        }
+
+        // Mount counts {{{3
+        for (i, b) in self.batteries.iter().enumerate() {
+            if let Some(mismatch) = b.count_mismatch() {
+                v.push(DesignIssue::warning("mount-count-mismatch", "batteries",
+                    format!("{} battery has {}", battery_name(i), mismatch)));
+            }
+        }
+
+        // Weapon years {{{3
+        let late = |year: u32| year > self.year + Self::YEAR_MARGIN;
+        let anachronism = |field, what: String, year: u32| DesignIssue::warning("anachronistic-weapon", field,
+            format!("{} date from {}, {} years after the ship was laid down in {}",
+                what, year, year - self.year, self.year));
+
+        for (i, b) in self.batteries.iter().enumerate().filter(|(_, b)| b.num > 0 && late(b.year)) {
+            v.push(anachronism("batteries", format!("{} battery guns", battery_name(i)), b.year));
+        }
+        for t in self.torps.iter().filter(|t| t.num > 0 && late(t.year)) {
+            v.push(anachronism("torps", "Torpedoes".into(), t.year));
+        }
+        if self.mines.num > 0 && late(self.mines.year) {
+            v.push(anachronism("mines", "Mines".into(), self.mines.year));
+        }
+        for a in self.asw.iter().filter(|a| a.num > 0 && late(a.year)) {
+            v.push(anachronism("asw", "ASW weapons".into(), a.year));
+        }
+
+        // Mount training {{{3
+        for b in self.batteries.iter().filter(|b| b.num > 0) {
+            if b.training_drive() == TrainingDrive::Hand && b.mount_wgt_each() > TrainingDrive::HAND_MAX_WGT {
+                v.push(DesignIssue::warning("hand-trained-mount", "batteries",
+                    format!("{}\" mounts weigh {:.0} t each and are too heavy to train by hand",
+                        b.diam, b.mount_wgt_each())));
+            }
+        }
+
+        // Turret weights {{{3
+        for (i, b) in self.batteries.iter().enumerate() {
+            let Some((name, hist)) = b.turret_wgt_hist() else { continue; };
+
+            let ratio = b.turret_wgt() / hist;
+            if !(1.0 / Battery::TURRET_WGT_MARGIN..=Battery::TURRET_WGT_MARGIN).contains(&ratio) {
+                v.push(DesignIssue::warning("turret-weight", "batteries",
+                    format!("{} battery turrets weigh {:.0} t each against about {:.0} t for those of {}, check the calibre is in inches",
+                        battery_name(i), b.turret_wgt(), hist, name)));
+            }
+        }
+
+        // Hull mounts {{{3
+        for (i, b) in self.batteries.iter().enumerate().filter(|(_, b)| b.num > 0) {
+            let free = b.free(self.hull.clone());
+
+            for g in b.groups.iter().filter(|g| g.below > 0) {
+                let limit = SeaState::hull_mount_limit(free, g.lower_deck);
+                if limit > SeaState::Moderate { continue; }
+
+                v.push(DesignIssue::warning("casemates-unusable", "batteries",
+                    format!("{} battery hull mounts are unusable in {}",
+                        battery_name(i), limit.limit_desc())));
+            }
+        }
+
+        v
+    }
+}
+
+// battery_name {{{1
+/// Name of a battery in messages.
+///
+pub(crate) fn battery_name(i: usize) -> &'static str {
+    match i { 0 => "Main", 1 => "2nd", 2 => "3rd", 3 => "4th", 4 => "5th", _ => "Other", }
+}
+
+// small_units {{{1
+/// Describe the metric units a gun calibre or armour thickness (in) above
+/// max may have been entered in, with what it is in inches from each.
+///
+fn small_units(value: f64, max: f64) -> Option<String> {
+    if value <= max { return None; }
+
+    let guesses: Vec<String> = [("cm", 10.0), ("mm", 1.0)].iter()
+        .map(|(unit, mm)| (unit, imperial(value * mm, LengthSmall, Metric)))
+        .filter(|(_, v)| *v <= max)
+        .map(|(unit, v)| format!("{} ({:.2}\")", unit, v))
+        .collect();
+
+    match guesses.len() {
+        0 => None,
+        _ => Some(guesses.join(" or ")),
+    }
+}
+
+// FileIssues {{{1
+/// Design issues found in a ship file.
+///
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct FileIssues {
+    /// Ship file that was checked.
+    pub file: String,
+    /// Why the file could not be loaded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Design issues found in the ship.
+    pub issues: Vec<DesignIssue>,
+}
+
+impl FileIssues { // {{{2
+    // check {{{3
+    /// Load a ship file in any format sharpie recognizes and validate it.
+    ///
+    pub fn check(file: String) -> FileIssues {
+        match Ship::open_any(file.clone()) {
+            Ok((ship, _)) => FileIssues { file, error: None, issues: ship.validate() },
+            Err(error)    => FileIssues { file, error: Some(error.to_string()), issues: Vec::new() },
+        }
+    }
+
+    // fails {{{3
+    /// True if the file could not be loaded or has an issue at least as
+    /// serious as fail_on.
+    ///
+    pub fn fails(&self, fail_on: Severity) -> bool {
+        self.error.is_some() || self.issues.iter().any(|i| i.severity >= fail_on)
+    }
+}
+
+impl fmt::Display for FileIssues { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(error) = &self.error {
+            return write!(f, "{}: Error: {}", self.file, error);
+        }
+        if self.issues.is_empty() {
+            return write!(f, "{}: OK", self.file);
+        }
+
+        let lines: Vec<String> = self.issues.iter()
+            .map(|i| format!("{}: {}: {} [{}]", self.file, i.severity, i.message, i.code))
+            .collect();
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod validate {
+    use super::*;
+    use crate::{Template, Tons};
+    use crate::weapons::{MountTiers, GunDistributionType};
+
+    fn warnings(ship: &Ship) -> Vec<&'static str> {
+        ship.validate().iter()
+            .filter(|i| i.severity == Severity::Warning)
+            .map(|i| i.code)
+            .collect()
+    }
+
+    // Test FileIssues {{{2
+    macro_rules! test_file_issues {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((warning, failure), file) = $value;
+
+                    let issues = FileIssues::check(file.into());
+
+                    assert_eq!(warning, issues.fails(Severity::from("warning")));
+                    assert_eq!(failure, issues.fails(Severity::from("failure")));
+                }
+            )*
+        }
+    }
+
+    test_file_issues! {
+        // name:                  ((fails on warning, fails on failure), file)
+        file_issues_warnings:     ((true, false), "tests/fixtures/central_battery_1870.ship"),
+        file_issues_missing:      ((true, true), "tests/fixtures/missing.ship"),
+        file_issues_not_a_ship:   ((true, true), "tests/fixtures/README.md"),
+    }
+
+    #[test]
+    fn file_issues_display() {
+        let ok = FileIssues { file: "a.ship".into(), error: None, issues: Vec::new() };
+        assert_eq!("a.ship: OK", ok.to_string());
+
+        let issues = FileIssues::check("tests/fixtures/central_battery_1870.ship".into());
+        assert!(issues.to_string().lines()
+            .all(|l| l.starts_with("tests/fixtures/central_battery_1870.ship: Warning: ") && l.ends_with(']')));
+
+        let json = serde_json::to_value(&issues).unwrap();
+        assert!(json.get("error").is_none());
+        assert_eq!("Warning", json["issues"][0]["severity"]);
+    }
+
+    // Test belt geometry {{{2
+    macro_rules! test_belt {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, main, upper) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.armor.main.hgt = main;
+                    ship.armor.upper.hgt = upper;
+
+                    assert_eq!(expected, warnings(&ship));
+                }
+            )*
+        }
+    }
+
+    test_belt! {
+        // name:             (codes, main, upper)
+        belt_ok:             (Vec::<&str>::new(), 12.0, 8.0),
+        belt_main_too_tall:  (vec!["belt-too-tall", "belt-too-tall", "implausible-ratio"], 60.0, 8.0),
+        belt_upper_too_tall: (vec!["belt-too-tall", "implausible-ratio"], 12.0, 40.0),
+        belt_below_water:    (vec!["belt-below-water"], 5.0, 8.0),
+    }
+
+    // Test citadel {{{2
+    macro_rules! test_citadel {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, upper, incline) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.armor.upper.thick = upper;
+                    ship.armor.citadel = Some(Citadel { thick: 6.0, incline });
+
+                    assert_eq!(expected, warnings(&ship));
+                }
+            )*
+        }
+    }
+
+    test_citadel! {
+        // name:             (codes, upper, incline)
+        citadel_ok:          (Vec::<&str>::new(), 8.0, 30.0),
+        citadel_no_upper:    (vec!["citadel-no-upper"], 0.0, 0.0),
+        citadel_too_steep:   (vec!["citadel-incline"], 8.0, 75.0),
+    }
+
+    // Test vcg {{{2
+    macro_rules! test_vcg {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, machinery, battery) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.vcg.machinery = machinery;
+                    ship.batteries[0].vcg = battery;
+
+                    assert_eq!(expected, warnings(&ship));
+                }
+            )*
+        }
+    }
+
+    test_vcg! {
+        // name:                (codes, machinery, battery)
+        vcg_ok:                 (Vec::<&str>::new(), Some(12.0), Some(40.0)),
+        vcg_machinery_below:    (vec!["vcg-outside-hull"], Some(-1.0), None),
+        vcg_machinery_above:    (vec!["vcg-outside-hull"], Some(200.0), None),
+        vcg_battery_below:      (vec!["vcg-outside-hull"], None, Some(-5.0)),
+    }
+
+    // Test bunkers {{{2
+    macro_rules! test_bunkers {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, range) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.engine.range = range;
+
+                    assert_eq!(expected, warnings(&ship));
+                }
+            )*
+        }
+    }
+
+    test_bunkers! {
+        // name:            (codes, range)
+        bunkers_ok:         (Vec::<&str>::new(), 7000),
+        bunkers_too_large:  (vec!["bunkers-too-large", "stores-short"], 60000),
+    }
+
+    // Test stores {{{2
+    macro_rules! test_stores {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, endurance) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.endurance = endurance;
+
+                    assert_eq!(expected, warnings(&ship));
+                }
+            )*
+        }
+    }
+
+    test_stores! {
+        // name:            (codes, endurance)
+        stores_default:     (Vec::<&str>::new(), None),
+        stores_ok:          (Vec::<&str>::new(), Some(60)),
+        stores_short:       (vec!["stores-short"], Some(14)),
+    }
+
+    // Test compartments {{{2
+    macro_rules! test_compartments {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, compartments) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.compartments = compartments;
+
+                    assert_eq!(expected, warnings(&ship));
+                }
+            )*
+        }
+    }
+
+    test_compartments! {
+        // name:                (codes, compartments)
+        compartments_default:   (Vec::<&str>::new(), None),
+        compartments_ok:        (Vec::<&str>::new(), Some(16)),
+        compartments_too_few:   (vec!["flooding-one-compartment"], Some(1)),
+    }
+
+    // Test mount tiers {{{2
+    #[test]
+    fn tiers() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        let tiers = ship.batteries[0].groups[0].mount_tiers();
+        let stability = ship.stability();
+
+        ship.batteries[0].groups[0].set_tiers(MountTiers { super1_hgt: Some(30.0), ..tiers });
+        assert!(ship.stability() < stability);
+        assert!(warnings(&ship).is_empty());
+
+        ship.batteries[0].groups[0].on += 1;
+        ship.batteries[0].sync_counts();
+        assert_eq!(vec!["tiers-mismatch"], warnings(&ship));
+    }
+
+    // Test deck space {{{2
+    #[test]
+    fn deck_saturated() {
+        let mut ship = Ship::from_template(Template::Destroyer1935);
+        assert!(!warnings(&ship).contains(&"deck-saturated"));
+
+        ship.batteries[1].groups[0].on += 20;
+        assert!(warnings(&ship).contains(&"deck-saturated"));
+    }
+
+    // Test bow freeboard {{{2
+    macro_rules! test_bow_freeboard {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((code, severity, very), fc_fwd) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.hull.fc_fwd = fc_fwd;
+
+                    let issue = ship.validate().into_iter()
+                        .find(|i| i.field == "hull.fc_fwd");
+
+                    assert_eq!(code, issue.as_ref().map(|i| i.code));
+                    assert_eq!(severity, issue.as_ref().map(|i| i.severity));
+                    assert_eq!(very, issue.is_some_and(|i| i.message.contains("very wet")));
+                }
+            )*
+        }
+    }
+
+    test_bow_freeboard! {
+        // name:              ((code, severity, very wet), fc_fwd)
+        bow_freeboard_dry:    ((None, None, false), 28.0),
+        bow_freeboard_wet:    ((Some("wet-forward"), Some(Severity::Warning), false), 24.0),
+        bow_freeboard_very:   ((Some("wet-forward"), Some(Severity::Warning), true), 18.0),
+        bow_freeboard_buried: ((Some("bow-buried"), Some(Severity::Failure), false), 12.0),
+    }
+
+    // Test uptakes {{{2
+    #[test]
+    fn uptakes_crowded() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        assert!(!warnings(&ship).contains(&"uptakes-crowded"));
+
+        ship.batteries[1].groups[0].distribution = GunDistributionType::CenterlineEven;
+        assert!(warnings(&ship).contains(&"uptakes-crowded"));
+    }
+
+    // Test gun length {{{2
+    #[test]
+    fn gun_over_length() {
+        let mut ship = Ship::from_template(Template::ProtectedCruiser1895);
+        assert!(!warnings(&ship).contains(&"gun-over-length"));
+
+        ship.batteries[0].len = 80.0;
+        assert!(warnings(&ship).contains(&"gun-over-length"));
+    }
+
+    // Test merchant conversion {{{2
+    macro_rules! test_conversion {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, hull_fraction, cargo, armored) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    if !armored { ship.armor = Default::default(); }
+                    ship.conversion = Some(Conversion { hull_fraction, cargo });
+
+                    let codes: Vec<&str> = warnings(&ship).into_iter()
+                        .filter(|c| ["hull-fraction-unusual", "conversion-overweight", "merchant-hull-weak", "merchant-hull-armored"].contains(c))
+                        .collect();
+
+                    assert_eq!(expected, codes);
+                    assert!(!ship.design_failures().iter().any(|f| f.contains("load weight")));
+                }
+            )*
+        }
+    }
+
+    test_conversion! {
+        // name:                  (warnings, hull_fraction, cargo, armored)
+        conversion_liner:         (Vec::<&str>::new(), 0.3, 0.0, false),
+        conversion_cargo:         (Vec::<&str>::new(), 0.3, 1000.0, false),
+        conversion_overweight:    (vec!["conversion-overweight"], 0.3, 20000.0, false),
+        conversion_light:         (vec!["hull-fraction-unusual", "merchant-hull-weak"], 0.15, 0.0, false),
+        conversion_heavy:         (vec!["hull-fraction-unusual"], 0.6, 0.0, false),
+        conversion_armored:       (vec!["merchant-hull-armored"], 0.3, 0.0, true),
+    }
+
+    #[test]
+    fn conversion_strength() {
+        // Too weak for a warship but built to merchant standards
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.armor = Default::default();
+        ship.conversion = Some(Conversion { hull_fraction: 0.15, cargo: 0.0 });
+
+        assert!(ship.str_comp() < Ship::MIN_STR);
+        assert!(!ship.validate().iter().any(|i| i.code == "hull-overloaded"));
+
+        ship.conversion = None;
+        assert!(!warnings(&ship).contains(&"merchant-hull-weak"));
+    }
+
+    // Test mount layouts {{{2
+    #[test]
+    fn infeasible_mount() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        assert!(!warnings(&ship).contains(&"infeasible-mount"));
+
+        ship.batteries[0].diam = 20.0;
+        assert!(warnings(&ship).contains(&"infeasible-mount"));
+
+        ship.fantasy = true;
+        assert!(!warnings(&ship).contains(&"infeasible-mount"));
+    }
+
+    #[test]
+    fn infeasible_mount_templates() {
+        for t in Template::ALL.iter() {
+            assert!(!warnings(&Ship::from_template(*t)).contains(&"infeasible-mount"), "{}", t);
+        }
+    }
+
+    // Test mount counts {{{2
+    #[test]
+    fn mount_count_mismatch() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        assert!(!warnings(&ship).contains(&"mount-count-mismatch"));
+
+        ship.batteries[0].num += 1;
+        assert!(warnings(&ship).contains(&"mount-count-mismatch"));
+
+        ship.batteries[0].sync_counts();
+        assert!(!warnings(&ship).contains(&"mount-count-mismatch"));
+    }
+
+    // Test weapon years {{{2
+    macro_rules! test_weapon_year {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.batteries[0].year = ship.year + year;
+                    ship.torps[0].year = ship.year + year;
+
+                    let n = warnings(&ship).iter().filter(|c| **c == "anachronistic-weapon").count();
+                    assert_eq!(expected, n);
+                }
+            )*
+        }
+    }
+
+    test_weapon_year! {
+        // name:                  (warnings, years after laid down)
+        weapon_year_same:         (0, 0),
+        weapon_year_margin:       (0, Ship::YEAR_MARGIN),
+        weapon_year_anachronism:  (2, Ship::YEAR_MARGIN + 1),
+    }
+
+    #[test]
+    fn weapon_year_empty() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        for b in ship.batteries.iter_mut().filter(|b| b.num == 0) { b.year = 1940; }
+        ship.mines.num = 0;
+        ship.mines.year = 1940;
+
+        assert!(!warnings(&ship).contains(&"anachronistic-weapon"));
+    }
+
+    // Test mount training {{{2
+    #[test]
+    fn hand_trained_mount() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        assert!(!warnings(&ship).contains(&"hand-trained-mount"));
+
+        ship.batteries[0].training = Some(TrainingDrive::Hand);
+        assert!(warnings(&ship).contains(&"hand-trained-mount"));
+    }
+
+    // Test unit errors {{{2
+    macro_rules! test_unit_errors {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, edit) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    edit(&mut ship);
+
+                    let fields: Vec<&str> = ship.validate().iter()
+                        .filter(|i| i.code == "probable-unit-error")
+                        .map(|i| i.field)
+                        .collect();
+
+                    assert_eq!(expected, fields);
+                }
+            )*
+        }
+    }
+
+    test_unit_errors! {
+        // name:                (fields, edit)
+        unit_errors_none:       (Vec::<&str>::new(), |_: &mut Ship| ()),
+        unit_errors_beam:       (vec!["hull.b", "hull.b"], |s: &mut Ship| s.hull.b *= 0.3048),
+        unit_errors_length:     (vec!["hull.lwl"], |s: &mut Ship| { s.hull.set_lwl(s.hull.lwl() * 0.3048); }),
+        unit_errors_draught:    (vec!["hull.t"], |s: &mut Ship| s.hull.t *= 0.3048),
+        unit_errors_calibre:    (vec!["batteries"], |s: &mut Ship| s.batteries[0].diam = 305.0),
+        unit_errors_belt:       (vec!["armor.main.thick"], |s: &mut Ship| s.armor.main.thick = 279.0),
+        unit_errors_deck:       (vec!["armor.deck.md"], |s: &mut Ship| s.armor.deck.md = 76.0),
+        unit_errors_barbette:   (vec!["batteries"], |s: &mut Ship| s.batteries[0].armor_barb = 280.0),
+        unit_errors_fantasy:    (Vec::<&str>::new(), |s: &mut Ship| s.batteries[0].diam = 600.0),
+    }
+
+    #[test]
+    fn unit_errors_templates() {
+        for template in Template::ALL {
+            let ship = Ship::from_template(template);
+            assert!(!warnings(&ship).contains(&"probable-unit-error"), "{}", template);
+        }
+    }
+
+    // Test small_units {{{2
+    macro_rules! test_small_units {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, value) = $value;
+
+                    assert_eq!(expected, small_units(value, 20.0).as_deref());
+                }
+            )*
+        }
+    }
+
+    test_small_units! {
+        // name:            (guess, value)
+        small_units_inches: (None, 15.0),
+        small_units_mm:     (Some("mm (14.96\")"), 380.0),
+        small_units_both:   (Some("cm (15.00\") or mm (1.50\")"), 38.1),
+        small_units_none:   (None, 1000.0),
+    }
+
+    // Test turret weight {{{2
+    macro_rules! test_turret_weight {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, template, diam) = $value;
+
+                    let mut ship = Ship::from_template(template);
+                    ship.batteries[0].diam = diam;
+
+                    assert_eq!(expected, warnings(&ship).contains(&"turret-weight"));
+                }
+            )*
+        }
+    }
+
+    test_turret_weight! {
+        // name:                    (warning, template, diam)
+        turret_weight_dreadnought:  (false, Template::Dreadnought1910, 12.0),
+        turret_weight_battleship:   (false, Template::FastBattleship1940, 16.0),
+        turret_weight_cruiser:      (false, Template::TreatyCruiser1930, 8.0),
+        turret_weight_predreadnought:(false, Template::ProtectedCruiser1895, 8.0),
+        turret_weight_mm:           (true, Template::Dreadnought1910, 305.0),
+        turret_weight_mm_cruiser:   (true, Template::TreatyCruiser1930, 203.0),
+    }
+
+    // Test historical ratios {{{2
+    macro_rules! test_implausible {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, vmax, fantasy) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.engine.vmax = vmax;
+                    ship.fantasy = fantasy;
+
+                    let issues = ship.validate();
+                    let issue = issues.iter().find(|i| i.code == "implausible-ratio");
+                    assert_eq!(expected, issue.map(|i| i.field));
+                }
+            )*
+        }
+    }
+
+    test_implausible! {
+        // name:                (field, vmax, fantasy)
+        implausible_usual:      (None, 21.0, false),
+        implausible_fast:       (Some("engine.vmax"), 40.0, false),
+        implausible_fantasy:    (None, 40.0, true),
+    }
+
+    // Test mount widths {{{2
+    macro_rules! test_mounts_too_wide {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, b, barb) = $value;
+
+                    let mut ship = Ship::from_template(Template::Dreadnought1910);
+                    ship.hull.b = b;
+                    ship.batteries[0].armor_barb = barb;
+
+                    assert_eq!(expected, warnings(&ship).contains(&"mounts-too-wide"));
+                }
+            )*
+        }
+    }
+
+    test_mounts_too_wide! {
+        // name:                    (warning, beam, barbette)
+        mounts_too_wide_fit:        (false, 86.0, 10.0),
+        mounts_too_wide_narrow:     (true, 20.0, 10.0),
+        mounts_too_wide_barbette:   (true, 24.0, 12.0),
+        mounts_too_wide_bare:       (false, 24.0, 0.0),
+    }
+
+    // Test large hulls {{{2
+    // Reference designs are the fast battleship template stretched to
+    // the dimensions of the Montana, H-39 and H-44 designs.
+    macro_rules! test_large_hull {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((str_comp, codes), (d, lwl, b, t, free)) = $value;
+
+                    let mut ship = Ship::from_template(Template::FastBattleship1940);
+                    ship.hull.set_lwl(lwl);
+                    ship.hull.b = b;
+                    ship.hull.bb = b;
+                    ship.hull.t = t;
+                    ship.hull.set_d(Tons::long(d));
+                    ship.hull.fc_fwd = free + 8.0; ship.hull.fc_aft = free + 4.0;
+                    ship.hull.fd_fwd = free + 2.0; ship.hull.fd_aft = free;
+                    ship.hull.ad_fwd = free;       ship.hull.ad_aft = free;
+                    ship.hull.qd_fwd = free;       ship.hull.qd_aft = free;
+
+                    assert_eq!(str_comp, crate::test_support::to_place(ship.str_comp(), 2));
+                    assert_eq!(codes, warnings(&ship));
+                }
+            )*
+        }
+    }
+
+    test_large_hull! {
+        // name:           ((str_comp, codes), (d, lwl, b, t, free))
+        large_hull_h39:     ((1.16, vec!["hull-beyond-calibration", "implausible-ratio"]), (56444.0, 870.0, 122.0, 33.0, 30.0)),
+        large_hull_montana: ((1.27, vec!["hull-beyond-calibration", "implausible-ratio"]), (60500.0, 890.0, 121.0, 36.0, 30.0)),
+        large_hull_h44:     ((1.56, vec!["hull-beyond-calibration", "implausible-ratio", "implausible-ratio", "implausible-ratio"]), (131000.0, 1132.0, 169.0, 41.0, 36.0)),
+    }
+
+    #[test]
+    fn displacement_impossible() {
+        let mut ship = Ship::from_template(Template::Dreadnought1910);
+        ship.hull.set_cb(1.5);
+
+        let issues = ship.validate();
+
+        assert_eq!("displacement-impossible", issues[0].code);
+        assert_eq!(Severity::Failure, issues[0].severity);
+    }
+}
@@ -0,0 +1,176 @@
+use crate::Ship;
+
+use serde::{Serialize, Deserialize};
+
+// Vcg {{{1
+/// Heights of the centres of gravity of parts of the ship above the keel
+/// (ft), for designers who know them better than the stability
+/// calculation's fixed levers.
+///
+/// Parts without a height keep the lever the calculation gives them.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Vcg {
+    /// Machinery.
+    #[serde(default)]
+    pub machinery: Option<f64>,
+    /// Main belt.
+    #[serde(default)]
+    pub main_belt: Option<f64>,
+    /// End belts.
+    #[serde(default)]
+    pub end_belt: Option<f64>,
+    /// Upper belt and any citadel bulkheads closing it.
+    #[serde(default)]
+    pub upper_belt: Option<f64>,
+}
+
+impl Vcg { // {{{2
+    // scale {{{3
+    /// Scale the heights by factor.
+    ///
+    pub fn scale(&mut self, factor: f64) {
+        for hgt in [&mut self.machinery, &mut self.main_belt, &mut self.end_belt, &mut self.upper_belt] {
+            *hgt = hgt.map(|h| h * factor);
+        }
+    }
+
+    // is_set {{{3
+    /// True if any height is given.
+    ///
+    pub fn is_set(&self) -> bool {
+        *self != Vcg::default()
+    }
+}
+
+impl Ship { // {{{1
+    // vcg_lever {{{2
+    /// Lever of a weight at a height above the keel in the stability
+    /// calculation.
+    ///
+    /// The fixed levers count weights at the waterline, such as the main
+    /// belt, as 1 and those on the deck as 3. A height is placed on the
+    /// same scale using the average freeboard. Weights low enough to
+    /// have no lever count for nothing, as roomy machinery does.
+    ///
+    pub fn vcg_lever(&self, hgt: f64) -> f64 {
+        let free = self.hull.freeboard();
+        if free <= 0.0 { return 1.0; } // catch divide by zero
+
+        f64::max(1.0 + 2.0 * (hgt - self.hull.t) / free, 0.0)
+    }
+
+    // vcg_moment {{{2
+    /// Weight times its lever in the stability calculation, using the
+    /// height if one is given and the fixed lever if not.
+    ///
+    pub(crate) fn vcg_moment(&self, wgt: f64, hgt: Option<f64>, lever: f64) -> f64 {
+        wgt * match hgt {
+            Some(h) => self.vcg_lever(h),
+            None    => lever,
+        }
+    }
+
+    // gun_moment {{{2
+    /// Weight of the guns borne by the mounts and the gun armor times
+    /// their lever in the stability calculation.
+    ///
+    /// Batteries without a height share the lever of the superfiring
+    /// factor of all the guns.
+    ///
+    pub(crate) fn gun_moment(&self) -> f64 {
+        let lever = (2.0 * self.gun_super_factor() - 1.0) * 4.0;
+
+        self.batteries.iter()
+            .map(|b| self.vcg_moment(
+                b.gun_wgt() * b.mount_kind.wgt_adj() * 2.0 + b.armor_wgt(self.hull.clone()),
+                b.vcg,
+                lever
+            ))
+            .sum()
+    }
+}
+
+// Testing {{{1
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+mod vcg {
+    use super::*;
+    use crate::Template;
+    use crate::test_support::*;
+
+    // Test vcg_lever {{{2
+    macro_rules! test_vcg_lever {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, hgt) = $value;
+
+                    let ship = Ship::from_template(Template::Dreadnought1910);
+                    let (t, free) = (ship.hull.t, ship.hull.freeboard());
+
+                    assert_eq!(expected, to_place(ship.vcg_lever(hgt(t, free)), 2));
+                }
+            )*
+        }
+    }
+
+    test_vcg_lever! {
+        // name:            (lever, hgt(t, freeboard))
+        vcg_lever_water:    (1.0, |t: f64, _: f64| t),
+        vcg_lever_deck:     (3.0, |t: f64, f: f64| t + f),
+        vcg_lever_above:    (4.0, |t: f64, f: f64| t + f * 1.5),
+        vcg_lever_keel:     (0.0, |_: f64, _: f64| 0.0),
+    }
+
+    // Test stability {{{2
+    #[test]
+    fn defaults_unchanged() {
+        let ship = Ship::from_template(Template::Dreadnought1910);
+
+        let mut same = ship.clone();
+        same.vcg = Vcg::default();
+        for b in same.batteries.iter_mut() { b.vcg = None; }
+
+        assert!(!ship.vcg.is_set());
+        assert_eq!(ship.stability(), same.stability());
+    }
+
+    macro_rules! test_stability {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (higher, set) = $value;
+
+                    let ship = Ship::from_template(Template::Dreadnought1910);
+                    let (mut low, mut high) = (ship.clone(), ship.clone());
+                    let t = ship.hull.t;
+                    set(&mut low, t);
+                    set(&mut high, t + higher);
+
+                    // Raising a weight makes the ship less stable
+                    assert!(high.stability() < low.stability());
+                }
+            )*
+        }
+    }
+
+    test_stability! {
+        // name:                (higher, set(ship, hgt))
+        stability_machinery:    (5.0, |s: &mut Ship, h: f64| s.vcg.machinery = Some(h)),
+        stability_main_belt:    (5.0, |s: &mut Ship, h: f64| s.vcg.main_belt = Some(h)),
+        stability_upper_belt:   (5.0, |s: &mut Ship, h: f64| s.vcg.upper_belt = Some(h)),
+        stability_battery:      (10.0, |s: &mut Ship, h: f64| s.batteries[0].vcg = Some(h)),
+    }
+
+    // Test scale {{{2
+    #[test]
+    fn scale() {
+        let mut vcg = Vcg { machinery: Some(10.0), main_belt: Some(20.0), ..Default::default() };
+        vcg.scale(1.5);
+
+        assert_eq!(Vcg { machinery: Some(15.0), main_belt: Some(30.0), ..Default::default() }, vcg);
+    }
+}
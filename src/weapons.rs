@@ -1,6 +1,7 @@
-use crate::{Ship, Armor};
+use crate::{Ship, Armor, Choice};
 use crate::Hull;
-use crate::units::Units;
+use crate::sea::SeaState;
+use crate::units::{Units, Tons};
 
 use serde::{Serialize, Deserialize};
 
@@ -12,7 +13,9 @@ use std::fmt;
 ///
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Battery {
-    /// Units
+    /// Units SpringSharp flagged the battery as entered in.
+    #[deprecated(note = "only used by version 1 and 2 JSON reports, use Ship::units for how values are shown")]
+    #[serde(default, skip_serializing_if = "Units::is_imperial")]
     pub units: Units,
 
     /// Number of guns in the battery.
@@ -28,9 +31,13 @@ pub struct Battery {
 
     /// Number of shells in the magazine
     pub shells: u32,
-    /// Weight of each shell.
+    /// Weight of each shell (lbs).
         shell_wgt: Option<f64>,
 
+    /// Mix of shell types in the magazine.
+    #[serde(default)]
+    pub ammo: Option<AmmoMix>,
+
     /// Type of gun.
     pub kind: GunType,
 
@@ -47,14 +54,24 @@ pub struct Battery {
     /// Armor thickness on barbette.
     pub armor_barb: f64,
 
+    /// How the mounts are trained. Chosen by year and mount if not set.
+    #[serde(default)]
+    pub training: Option<TrainingDrive>,
+
+    /// Height of the battery's centre of gravity above the keel (ft),
+    /// replacing its lever in the stability calculation.
+    #[serde(default)]
+    pub vcg: Option<f64>,
+
     /// Separate groups of guns within the Battery
     pub groups: Vec<SubBattery>,
 }
 
 impl Default for Battery { // {{{2
+    #[allow(deprecated)]
     fn default() -> Self {
         Self {
-            units: Units::Imperial, 
+            units: Units::Imperial,
 
             num: 0,
             diam: 0.0,
@@ -62,6 +79,7 @@ impl Default for Battery { // {{{2
             year: 1920,
             shells: 0,
             shell_wgt: None,
+            ammo: None,
             kind: GunType::default(),
 
             mount_num: 0,
@@ -69,6 +87,8 @@ impl Default for Battery { // {{{2
             armor_face: 0.0,
             armor_back: 0.0,
             armor_barb: 0.0,
+            training: None,
+            vcg: None,
 
             groups: vec![
                 SubBattery::default(),
@@ -82,6 +102,28 @@ impl Battery { // {{{2
     /// Factor to account for powder, etc. when calculating the magazine weight.
     ///
     const CORDITE_FACTOR: f64 = 0.2444444;
+    /// Turret weights more than this many times that of comparable
+    /// historical turrets, or less than its inverse, are suspect.
+    pub const TURRET_WGT_MARGIN: f64 = 3.0;
+
+    // scale {{{3
+    /// Scale the calibre of the guns by factor.
+    ///
+    /// A set shell weight grows with the cube of the factor.
+    ///
+    pub fn scale(&mut self, factor: f64) {
+        self.diam *= factor;
+        self.shell_wgt = self.shell_wgt.map(|wgt| wgt * factor.powi(3));
+    }
+
+    // scale_armor {{{3
+    /// Scale the thickness of the mount and barbette armor by factor.
+    ///
+    pub fn scale_armor(&mut self, factor: f64) {
+        self.armor_face *= factor;
+        self.armor_back *= factor;
+        self.armor_barb *= factor;
+    }
 
     // broad_and_below {{{3
     /// Returns true if the battery has Broadside mounts
@@ -97,13 +139,14 @@ impl Battery { // {{{2
     }
 
     // concentration {{{3
-    /// XXX: I do not know what this does.
+    /// This battery's share of the broadside weight times a bonus for
+    /// few heavy mounts, or a penalty for light ones.
     ///
     pub fn concentration(&self, wgt_broad: f64) -> f64 {
         // Catch divide by zero
         if self.mount_num == 0 || wgt_broad == 0.0 { return 0.0; }
 
-        (self.shell_wgt() * self.num as f64 / wgt_broad) *
+        (self.shell_wgt().to_pounds() * self.num as f64 / wgt_broad) *
             if self.mount_kind.wgt_adj() > 0.6 {
                 (4.0 / self.mount_num as f64).powf(0.25) - 1.0
             } else {
@@ -112,24 +155,33 @@ impl Battery { // {{{2
     }
 
     // super_ {{{3
-    /// XXX: I do not know what this does.
+    /// Superfiring factor: the height of the guns above the waterline,
+    /// including the average superfiring height, as a multiple of the
+    /// freeboard at their mounts.
     ///
     pub fn super_(&self, hull: Hull) -> f64 {
         if self.num == 0 { return 0.0 } // catch divide by zero
 
-        let mut super_ = 0;
+        let mut hgt = 0.0;
         for g in self.groups.iter() {
-            super_ += g.super_()
+            hgt += g.super_hgt(self.tier_hgt())
         }
 
         match self.free(hull) {
             0.0 => 0.0, // Catch divide by zero
-            free => ((super_ as f64 / self.num as f64) * (self.diam * 0.6).max(7.5) + free) / free,
+            free => (hgt / self.num as f64 + free) / free,
         }
     }
 
+    // tier_hgt {{{3
+    /// Default height of one superfiring tier above the deck (ft).
+    ///
+    pub fn tier_hgt(&self) -> f64 {
+        (self.diam * 0.6).max(7.5)
+    }
+
     // free {{{3
-    /// XXX: I do not know what this does.
+    /// Average freeboard (ft) at the battery's mounts.
     ///
     pub fn free(&self, hull: Hull) -> f64 {
         if self.mount_num == 0 { return 0.0 } // Catch divide by zero
@@ -142,6 +194,67 @@ impl Battery { // {{{2
         f / self.mount_num as f64
     }
 
+    // arcs {{{3
+    /// Number of guns that bear ahead, on one broadside and astern.
+    ///
+    pub fn arcs(&self, hull: Hull) -> (u32, u32, u32) {
+        let hull_mounts = matches!(self.mount_kind, MountType::Broadside | MountType::Casemate);
+
+        self.groups.iter()
+            .map(|g| g.arcs(hull.fc_len + hull.fd_len, hull_mounts))
+            .fold((0, 0, 0), |a, g| (a.0 + g.0, a.1 + g.1, a.2 + g.2))
+    }
+
+    // beam_req {{{3
+    /// Beam needed to fit the widest group of mounts across the ship,
+    /// with the barbette armour around mounts that stand on one.
+    ///
+    /// Broadside and casemate guns are mounted in the hull and do not
+    /// need to fit across the deck.
+    ///
+    pub fn beam_req(&self) -> f64 {
+        let barb = if self.mount_kind.armor_barb_wgt() > 0.0 { self.armor_barb } else { 0.0 };
+
+        match self.mount_kind {
+            MountType::Broadside | MountType::Casemate => 0.0,
+
+            _ => self.groups.iter()
+                    .map(|g| g.beam_req(self.diam, barb))
+                    .fold(0.0, f64::max),
+        }
+    }
+
+    // deck_area {{{3
+    /// Deck area taken up by the deck mounts of the battery.
+    ///
+    /// Each mount needs a circle as wide as the mount. Broadside and
+    /// casemate guns are mounted in the hull and take no deck area.
+    ///
+    pub fn deck_area(&self) -> f64 {
+        use std::f64::consts::PI;
+
+        match self.mount_kind {
+            MountType::Broadside | MountType::Casemate => 0.0,
+
+            _ => self.groups.iter()
+                    .map(|g| PI * (g.diameter_calc(self.diam) / 2.0).powf(2.0) * (g.above + g.on) as f64)
+                    .sum(),
+        }
+    }
+
+    // amidships_len {{{3
+    /// Centreline deck length taken up by mounts amidships.
+    ///
+    pub fn amidships_len(&self) -> f64 {
+        match self.mount_kind {
+            MountType::Broadside | MountType::Casemate => 0.0,
+
+            _ => self.groups.iter()
+                    .map(|g| g.diameter_calc(self.diam) * g.distribution.amidships(g.above + g.on) as f64)
+                    .sum(),
+        }
+    }
+
     // armor_face_wgt {{{3
     /// Weight of battery face armor.
     ///
@@ -159,7 +272,7 @@ impl Battery { // {{{2
     }
 
     // house_hgt {{{3
-    /// XXX: I do not know what this does.
+    /// Height (ft) of the gunhouse the face and back armor cover.
     ///
     fn house_hgt(&self) -> f64 {
         f64::max(
@@ -228,7 +341,8 @@ impl Battery { // {{{2
     }
 
     // wgt_adj {{{3
-    /// XXX: I do not know what this does.
+    /// Share of a full mount's weight each of the battery's mounts
+    /// weighs, averaged over the mounts.
     ///
     pub fn wgt_adj(&self) -> f64 {
         if self.mount_num == 0 { return 0.0; } // Catch divide by zero
@@ -251,9 +365,9 @@ impl Battery { // {{{2
     // set_shell_wgt {{{3
     /// Set the shell weight.
     ///
-    pub fn set_shell_wgt(&mut self, wgt: f64) -> f64 {
-        self.shell_wgt = Some(wgt);
-        
+    pub fn set_shell_wgt(&mut self, wgt: Tons) -> Tons {
+        self.shell_wgt = Some(wgt.to_pounds());
+
         wgt
     }
 
@@ -263,11 +377,11 @@ impl Battery { // {{{2
     /// Return the value previously set by set_shell_wgt()
     /// or the estimated shell weight if unset.
     ///
-    pub fn shell_wgt(&self) -> f64 {
-        match self.shell_wgt {
+    pub fn shell_wgt(&self) -> Tons {
+        Tons::pounds(match self.shell_wgt {
             Some(wgt) => wgt,
             None      => self.shell_wgt_est(),
-        }
+        })
     }
 
     // shell_wgt_est {{{3
@@ -284,8 +398,8 @@ impl Battery { // {{{2
     pub fn gun_wgt(&self) -> f64 {
         if self.diam == 0.0 { return 0.0; }
 
-        self.shell_wgt_est() * (self.len as f64 / 812.289434917877 *
-            (1.0 + (1.0 / self.diam as f64).powf(2.3297949327695))
+        self.shell_wgt_est() * (self.len / 812.289434917877 *
+            (1.0 + (1.0 / self.diam).powf(2.3297949327695))
             ) * self.num as f64
     }
 
@@ -316,62 +430,313 @@ impl Battery { // {{{2
         wgt * self.wgt_adj()
     }
 
+    // max_len {{{3
+    /// Longest barrel (calibres) in service in the battery's year.
+    ///
+    pub fn max_len(&self) -> f64 {
+             if self.year < 1880 { 30.0 }
+        else if self.year < 1895 { 45.0 }
+        else if self.year < 1910 { 50.0 }
+        else if self.year < 1925 { 55.0 }
+        else                     { 80.0 }
+    }
+
+    // over_length {{{3
+    /// True if the barrels are longer than any in service in the
+    /// battery's year. Such guns are heavy, wear out quickly and need
+    /// reduced charges to stay accurate.
+    ///
+    pub fn over_length(&self) -> bool {
+        self.num > 0 && self.len > self.max_len()
+    }
+
+    // training_drive {{{3
+    /// How the mounts are trained.
+    ///
+    pub fn training_drive(&self) -> TrainingDrive {
+        self.training.unwrap_or(TrainingDrive::for_mount(self.year, &self.mount_kind, self.diam))
+    }
+
+    // training_wgt {{{3
+    /// Weight of the training gear beyond that of the usual drive for the
+    /// battery's year and mount. Mount weights already allow for the usual
+    /// drive.
+    ///
+    pub fn training_wgt(&self) -> f64 {
+        let usual = TrainingDrive::for_mount(self.year, &self.mount_kind, self.diam);
+
+        self.mount_wgt() * (self.training_drive().wgt_factor() - usual.wgt_factor())
+    }
+
+    // training_power {{{3
+    /// Power needed to train the mounts.
+    ///
+    pub fn training_power(&self) -> f64 {
+        self.mount_wgt() * self.training_drive().power_factor()
+    }
+
+    // mount_wgt_each {{{3
+    /// Weight of each mount.
+    ///
+    pub fn mount_wgt_each(&self) -> f64 {
+        if self.mount_num == 0 { return 0.0; } // Catch divide by zero
+
+        self.mount_wgt() / self.mount_num as f64
+    }
+
+    // turret_wgt {{{3
+    /// Revolving weight of each mount: the guns, mounting, training gear
+    /// and gunhouse armour, but not the barbette.
+    ///
+    pub fn turret_wgt(&self) -> f64 {
+        if self.mount_num == 0 { return 0.0; } // Catch divide by zero
+
+        (self.gun_wgt() + self.mount_wgt() + self.training_wgt() + self.armor_face_wgt() + self.armor_back_wgt()) /
+            self.mount_num as f64
+    }
+
+    // turret_wgt_hist {{{3
+    /// Name of the historical turret nearest the battery's in calibre and
+    /// year with the same number of guns, and its weight scaled to the
+    /// battery's calibre.
+    ///
+    /// Only turrets and other gunhouse mounts of two to four guns are
+    /// compared. Weight grows with the cube of the calibre, but not beyond
+    /// the largest turrets built.
+    ///
+    pub fn turret_wgt_hist(&self) -> Option<(&'static str, f64)> {
+        match self.mount_kind {
+            MountType::ColesTurret | MountType::ClosedBarbette | MountType::DeckAndHoist => (),
+            _ => return None,
+        }
+        if self.num == 0 || self.mount_num == 0 || self.diam <= 0.0 { return None; }
+
+        let guns = self.num / self.mount_num;
+        let distance = |t: &HistoricalTurret| (self.diam / t.diam).ln().abs() + (self.year as f64 - t.year as f64).abs() / 100.0;
+
+        let turret = TURRETS.iter()
+            .filter(|t| t.guns == guns)
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)))?;
+
+        let (min, max) = TURRETS.iter().fold((f64::INFINITY, 0.0_f64), |(min, max), t| (min.min(t.diam), max.max(t.diam)));
+        if self.diam < min { return None; }
+
+        Some((turret.name, turret.wgt * (self.diam.min(max) / turret.diam).powi(3)))
+    }
+
     // broadside_wgt {{{3
     /// Weight of shells if each barrel fires a single shell.
     ///
     pub fn broadside_wgt(&self) -> f64 {
-        self.num as f64 * self.shell_wgt()
+        self.num as f64 * self.shell_wgt().to_pounds()
+    }
+
+    // broadside_wgt_at {{{3
+    /// Weight of broadside leaving out hull mounts that are unusable in the
+    /// given sea state.
+    ///
+    pub fn broadside_wgt_at(&self, hull: Hull, sea: SeaState) -> f64 {
+        let free = self.free(hull);
+
+        let unusable: u32 = self.groups.iter()
+            .filter(|g| g.below > 0 && SeaState::hull_mount_limit(free, g.lower_deck) <= sea)
+            .map(|g| g.below * g.layout.guns_per())
+            .sum();
+
+        self.num.saturating_sub(unusable) as f64 * self.shell_wgt().to_pounds()
+    }
+
+    // rate_of_fire {{{3
+    /// Estimated rounds per gun per minute.
+    ///
+    pub fn rate_of_fire(&self) -> f64 {
+        if self.diam == 0.0 { return 0.0; } // Catch divide by zero
+
+        (20.0 / self.diam).clamp(0.5, 20.0)
     }
 
     // mag_wgt {{{3
     /// Weight of the battery magazine.
     ///
     pub fn mag_wgt(&self) -> f64 {
-        (self.num * self.shells) as f64 * self.shell_wgt() / Ship::POUND2TON * (1.0 + Self::CORDITE_FACTOR)
+        self.mag_shell_wgt() + self.mag_propellant_wgt() + self.mag_hoist_wgt()
+    }
+
+    // mag_shell_wgt {{{3
+    /// Weight of the shells in the battery's shell rooms.
+    ///
+    pub fn mag_shell_wgt(&self) -> f64 {
+        (self.num * self.shells) as f64 * self.shell_wgt_avg() / Ship::POUND2TON
+    }
+
+    // mag_propellant_wgt {{{3
+    /// Weight of the propellant in the battery's magazines.
+    ///
+    pub fn mag_propellant_wgt(&self) -> f64 {
+        self.mag_shell_wgt() * Self::CORDITE_FACTOR * (1.0 - self.hoist_share())
+    }
+
+    // mag_hoist_wgt {{{3
+    /// Weight of the hoists and handling gear between the magazines and
+    /// the mounts.
+    ///
+    pub fn mag_hoist_wgt(&self) -> f64 {
+        self.mag_shell_wgt() * Self::CORDITE_FACTOR * self.hoist_share()
+    }
+
+    // hoist_share {{{3
+    /// Share of the propellant and handling allowance taken up by hoists
+    /// and handling gear.
+    ///
+    /// Heavier guns and enclosed mounts need more mechanised handling.
+    ///
+    fn hoist_share(&self) -> f64 {
+        f64::min(self.mount_kind.hoist_share() * (self.diam / 12.0).clamp(0.5, 1.5), 0.5)
+    }
+
+    // shell_wgt_avg {{{3
+    /// Average weight of the shells in the magazine.
+    ///
+    pub fn shell_wgt_avg(&self) -> f64 {
+        match &self.ammo {
+            Some(mix) => self.shell_wgt().to_pounds() * mix.wgt_factor(),
+            None      => self.shell_wgt().to_pounds(),
+        }
+    }
+
+    // rounds {{{3
+    /// Number of rounds per gun of each shell type.
+    ///
+    pub fn rounds(&self) -> Vec<(AmmoType, u32)> {
+        match &self.ammo {
+            Some(mix) => AmmoType::ALL.iter()
+                .map(|t| (*t, (self.shells as f64 * mix.share(*t)).round() as u32))
+                .filter(|(_, n)| *n > 0)
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    // set_layout {{{3
+    /// Put every mount of the battery in the first group, with mounts
+    /// giving the number (above, on, below) the waterline, and update
+    /// the number of guns and mounts to match.
+    ///
+    pub fn set_layout(&mut self, mounts: (u32, u32, u32), layout: GunLayoutType, distribution: GunDistributionType) {
+        for g in self.groups.iter_mut().skip(1) {
+            (g.above, g.on, g.below) = (0, 0, 0);
+            g.tiers = None;
+        }
+
+        self.set_group(0, mounts, layout, distribution);
+    }
+
+    // set_group {{{3
+    /// Set the layout, distribution and mounts (above, on, below) the
+    /// waterline of one group and update the number of guns and mounts
+    /// in the battery to match.
+    ///
+    pub fn set_group(&mut self, i: usize, mounts: (u32, u32, u32), layout: GunLayoutType, distribution: GunDistributionType) {
+        let g = &mut self.groups[i];
+
+        g.layout = layout;
+        g.distribution = distribution;
+        (g.above, g.on, g.below) = mounts;
+        g.tiers = None;
+
+        self.sync_counts();
+    }
+
+    // sync_counts {{{3
+    /// Set the number of guns and mounts from the groups.
+    ///
+    pub fn sync_counts(&mut self) {
+        self.mount_num = self.groups.iter().map(|g| g.num_mounts()).sum();
+        self.num = self.groups.iter().map(|g| g.num_guns()).sum();
+    }
+
+    // infeasible_layouts {{{3
+    /// Why the mounts of each group could not have been built for the
+    /// battery's guns.
+    ///
+    pub fn infeasible_layouts(&self) -> Vec<String> {
+        let mut v = Vec::new();
+
+        for g in self.groups.iter().filter(|g| g.num_mounts() > 0) {
+            if let Some(s) = g.layout.infeasible(self.diam, self.year) {
+                v.push(s);
+            }
+            if let Some(max) = self.mount_kind.max_guns() && g.layout.guns_per() > max {
+                v.push(format!("{} mounts never held more than {} gun{}", self.mount_kind, max, if max == 1 { "" } else { "s" }));
+            }
+        }
+
+        v
+    }
+
+    // count_mismatch {{{3
+    /// Description of how the number of guns or mounts differs from the
+    /// groups, or None if they agree.
+    ///
+    pub fn count_mismatch(&self) -> Option<String> {
+        let mounts: u32 = self.groups.iter().map(|g| g.num_mounts()).sum();
+        let guns: u32 = self.groups.iter().map(|g| g.num_guns()).sum();
+
+        if self.mount_num != mounts {
+            Some(format!("{} mounts but the groups place {}", self.mount_num, mounts))
+        } else if self.num != guns {
+            Some(format!("{} guns but its {} mounts hold {}", self.num, mounts, guns))
+        } else {
+            None
+        }
     }
 }
 
-// Inernals Output {{{2
-#[cfg(debug_assertions)]
+// Internals Output {{{2
 impl Battery {
-    pub fn internals(&self, hull: Hull, wgt_broad: f64) -> () {
-        eprintln!("units = {}", self.units);
-        eprintln!("num = {}", self.num);
-        eprintln!("diam = {}", self.diam);
-        eprintln!("len = {}", self.len);
-        eprintln!("year = {}", self.year);
-        eprintln!("shells = {}", self.shells);
-        eprintln!("kind = {}", self.kind);
-        eprintln!("mount_num = {}", self.mount_num);
-        eprintln!("mount_kind = {}", self.mount_kind);
-        eprintln!("armor_face = {}", self.armor_face);
-        eprintln!("armor_back = {}", self.armor_back);
-        eprintln!("armor_barb = {}", self.armor_barb);
-
-        eprintln!("broad_and_below() = {}", self.broad_and_below());
-        eprintln!("concentration() = {}", self.concentration(wgt_broad));
-        eprintln!("super_() = {}", self.super_(hull.clone()));
-        eprintln!("free() = {}", self.free(hull.clone()));
-        eprintln!("house_hgt() = {}", self.house_hgt());
-        eprintln!("armor_face_wgt() = {}", self.armor_face_wgt());
-        eprintln!("armor_back_wgt() = {}", self.armor_back_wgt());
-        eprintln!("armor_barb_wgt() = {}", self.armor_barb_wgt(hull.clone()));
-        eprintln!("armor_wgt() = {}", self.armor_wgt(hull.clone()));
-        eprintln!("wgt_adj() = {}", self.wgt_adj());
-        eprintln!("date_factor() = {}", self.date_factor());
-        eprintln!("shell_wgt() = {}", self.shell_wgt());
-        eprintln!("shell_wgt_est() = {}", self.shell_wgt_est());
-        eprintln!("gun_wgt() = {}", self.gun_wgt());
-        eprintln!("mount_wgt() = {}", self.mount_wgt());
-        eprintln!("broadside_wgt() = {}", self.broadside_wgt());
-        eprintln!("mag_wgt() = {}", self.mag_wgt());
-        eprintln!("");
+    // internals {{{3
+    /// Inputs and intermediate values of the battery by name.
+    ///
+    pub fn internals(&self, hull: Hull, wgt_broad: f64) -> Vec<(String, String)> {
+        let mut v: Vec<(String, String)> = vec![
+            ("num", self.num.to_string()),
+            ("diam", self.diam.to_string()),
+            ("len", self.len.to_string()),
+            ("year", self.year.to_string()),
+            ("shells", self.shells.to_string()),
+            ("kind", self.kind.to_string()),
+            ("mount_num", self.mount_num.to_string()),
+            ("mount_kind", self.mount_kind.to_string()),
+            ("armor_face", self.armor_face.to_string()),
+            ("armor_back", self.armor_back.to_string()),
+            ("armor_barb", self.armor_barb.to_string()),
+
+            ("broad_and_below", self.broad_and_below().to_string()),
+            ("concentration", self.concentration(wgt_broad).to_string()),
+            ("super", self.super_(hull.clone()).to_string()),
+            ("free", self.free(hull.clone()).to_string()),
+            ("house_hgt", self.house_hgt().to_string()),
+            ("armor_face_wgt", self.armor_face_wgt().to_string()),
+            ("armor_back_wgt", self.armor_back_wgt().to_string()),
+            ("armor_barb_wgt", self.armor_barb_wgt(hull.clone()).to_string()),
+            ("armor_wgt", self.armor_wgt(hull.clone()).to_string()),
+            ("wgt_adj", self.wgt_adj().to_string()),
+            ("date_factor", self.date_factor().to_string()),
+            ("shell_wgt", self.shell_wgt().to_pounds().to_string()),
+            ("shell_wgt_est", self.shell_wgt_est().to_string()),
+            ("gun_wgt", self.gun_wgt().to_string()),
+            ("mount_wgt", self.mount_wgt().to_string()),
+            ("broadside_wgt", self.broadside_wgt().to_string()),
+            ("mag_wgt", self.mag_wgt().to_string()),
+        ].into_iter().map(|(k, v)| (k.to_string(), v)).collect();
 
         for (i, g) in self.groups.iter().enumerate() {
-            eprintln!("Group {}", i);
-            eprintln!("--------");
-            g.internals(hull.clone(), self.diam);
+            for (k, val) in g.internals(hull.clone(), self.diam) {
+                v.push((format!("group[{}].{}", i, k), val));
+            }
         }
+
+        v
     }
 }
 
@@ -405,6 +770,34 @@ mod battery {
         broad_and_below_broadside_below:     (true, MountType::Broadside, 1),
     }
 
+    // Test deck_area {{{3
+    macro_rules! test_deck_area {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, mount_kind, above, on, below) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.diam = 10.0;
+                    btry.mount_kind = mount_kind;
+                    btry.groups[0].above = above;
+                    btry.groups[0].on = on;
+                    btry.groups[0].below = below;
+
+                    assert_eq!(expected, to_place(btry.deck_area(), 2));
+                }
+            )*
+        }
+    }
+    test_deck_area! {
+        // name:             (deck_area, mount_kind, above, on, below)
+        deck_area_deck:      (575.27, MountType::Deck, 1, 1, 0),
+        deck_area_hull:      (0.0, MountType::Deck, 0, 0, 2),
+        deck_area_broadside: (0.0, MountType::Broadside, 1, 1, 0),
+        deck_area_casemate:  (0.0, MountType::Casemate, 1, 1, 0),
+    }
+
     // Test concentration {{{3
     macro_rules! test_concentration {
         ($($name:ident: $value:expr,)*) => {
@@ -414,7 +807,7 @@ mod battery {
                     let (expected, shell_wgt, mount_kind, mount_num) = $value;
 
                     let mut btry = Battery::default();
-                    btry.set_shell_wgt(shell_wgt);
+                    btry.set_shell_wgt(Tons::pounds(shell_wgt));
                     btry.mount_kind = mount_kind;
                     btry.mount_num = mount_num;
                     btry.num = 10;
@@ -796,7 +1189,7 @@ mod battery {
                     let (expected, num) = $value;
 
                     let mut btry = Battery::default();
-                    btry.set_shell_wgt(10.0);
+                    btry.set_shell_wgt(Tons::pounds(10.0));
                     btry.num = num;
 
                     assert!(expected == btry.broadside_wgt());
@@ -820,7 +1213,7 @@ mod battery {
                     let mut btry = Battery::default();
                     btry.num = num;
                     btry.shells = shells;
-                    btry.set_shell_wgt(shell_wgt);
+                    btry.set_shell_wgt(Tons::pounds(shell_wgt));
 
                     assert!(to_place(expected, 2) == to_place(btry.mag_wgt(), 2));
                 }
@@ -832,12 +1225,331 @@ mod battery {
         mag_wgt_test_1: (5.56, 10, 10, 100.0),
         mag_wgt_test_2: (1.0+Battery::CORDITE_FACTOR, 1, 1, Ship::POUND2TON),
     }
+
+    // Test over_length {{{3
+    macro_rules! test_over_length {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year, len) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.num = 1;
+                    btry.year = year;
+                    btry.len = len;
+
+                    assert_eq!(expected, btry.over_length());
+                }
+            )*
+        }
+    }
+    test_over_length! {
+        // name:               (over_length, year, len)
+        over_length_1870:      (true, 1870, 35.0),
+        over_length_1890:      (false, 1890, 45.0),
+        over_length_1890_long: (true, 1890, 80.0),
+        over_length_1905:      (false, 1905, 50.0),
+        over_length_1915:      (true, 1915, 56.0),
+        over_length_1940:      (false, 1940, 70.0),
+    }
+
+    // Test training {{{3
+    macro_rules! test_training {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((wgt, power), training) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.num = 2;
+                    btry.diam = 12.0;
+                    btry.len = 45.0;
+                    btry.year = 1910;
+                    btry.mount_kind = MountType::ClosedBarbette;
+                    btry.mount_num = 1;
+                    btry.groups[0].on = 1;
+                    btry.training = training;
+
+                    assert_eq!(wgt, to_place(btry.training_wgt() / btry.mount_wgt(), 2));
+                    assert_eq!(power, to_place(btry.training_power() / btry.mount_wgt(), 2));
+                }
+            )*
+        }
+    }
+    test_training! {
+        // name:             ((wgt factor, power factor), training)
+        training_usual:      ((0.0, 0.33), None),
+        training_electric:   ((0.0, 0.33), Some(TrainingDrive::Electric)),
+        training_hydraulic:  ((0.01, 0.4), Some(TrainingDrive::Hydraulic)),
+        training_hand:       ((-0.02, 0.0), Some(TrainingDrive::Hand)),
+    }
+
+    // Test turret_wgt_hist {{{3
+    macro_rules! test_turret_wgt_hist {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, (num, mount_num, diam, year), mount_kind) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.num = num;
+                    btry.mount_num = mount_num;
+                    btry.diam = diam;
+                    btry.year = year;
+                    btry.mount_kind = mount_kind;
+
+                    assert_eq!(expected, btry.turret_wgt_hist().map(|(name, wgt)| (name, to_place(wgt, 0))));
+                }
+            )*
+        }
+    }
+    test_turret_wgt_hist! {
+        // name:                    (turret, (num, mounts, diam, year), mount_kind)
+        turret_hist_same:           (Some(("HMS Queen Elizabeth", 775.0)), (8, 4, 15.0, 1912), MountType::ClosedBarbette),
+        turret_hist_year:           (Some(("Bismarck", 1050.0)), (8, 4, 15.0, 1938), MountType::ClosedBarbette),
+        turret_hist_scaled:         (Some(("HMS Dreadnought", 313.0)), (8, 4, 10.0, 1905), MountType::ClosedBarbette),
+        turret_hist_largest:        (Some(("IJN Yamato", 2510.0)), (9, 3, 460.0, 1937), MountType::ClosedBarbette),
+        turret_hist_small:          (None, (8, 4, 3.0, 1940), MountType::DeckAndHoist),
+        turret_hist_single:         (None, (4, 4, 9.2, 1905), MountType::ClosedBarbette),
+        turret_hist_deck:           (None, (8, 4, 6.0, 1935), MountType::Deck),
+        turret_hist_unmounted:      (None, (8, 0, 15.0, 1912), MountType::ClosedBarbette),
+    }
+
+    // Test magazine split {{{3
+    macro_rules! test_mag_split {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((shells, propellant, hoists), mount_kind, diam) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.num = 1;
+                    btry.shells = 1;
+                    btry.diam = diam;
+                    btry.mount_kind = mount_kind;
+                    btry.set_shell_wgt(Tons::long(1.0));
+
+                    assert_eq!(shells, to_place(btry.mag_shell_wgt(), 4));
+                    assert_eq!(propellant, to_place(btry.mag_propellant_wgt(), 4));
+                    assert_eq!(hoists, to_place(btry.mag_hoist_wgt(), 4));
+                    assert_eq!(to_place(btry.mag_wgt(), 4), to_place(shells + propellant + hoists, 4));
+                }
+            )*
+        }
+    }
+    test_mag_split! {
+        // name:                ((shells, propellant, hoists), mount_kind, diam)
+        mag_split_deck:         ((1.0, 0.2383, 0.0061), MountType::Deck, 4.0),
+        mag_split_turret:       ((1.0, 0.1711, 0.0733), MountType::ClosedBarbette, 12.0),
+        mag_split_large_turret: ((1.0, 0.1344, 0.11), MountType::ClosedBarbette, 18.0),
+    }
+
+    // Test ammo {{{3
+    macro_rules! test_ammo {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (avg, rounds, ammo) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.num = 1;
+                    btry.shells = 100;
+                    btry.set_shell_wgt(Tons::pounds(100.0));
+                    btry.ammo = ammo;
+
+                    assert_eq!(avg, to_place(btry.shell_wgt_avg(), 2));
+                    assert_eq!(rounds, btry.rounds());
+                }
+            )*
+        }
+    }
+    test_ammo! {
+        // name:        (shell_wgt_avg, rounds, ammo)
+        ammo_none:      (100.0, Vec::<(AmmoType, u32)>::new(), None),
+        ammo_ap:        (100.0, vec![(AmmoType::AP, 100)], Some(AmmoMix { ap: 1.0, ..Default::default() })),
+        ammo_mixed:     (92.5, vec![(AmmoType::AP, 50), (AmmoType::HE, 50)], Some(AmmoMix { ap: 1.0, he: 1.0, ..Default::default() })),
+        ammo_percent:   (94.0,
+            vec![(AmmoType::AP, 40), (AmmoType::Common, 30), (AmmoType::HE, 30)],
+            Some(AmmoMix { ap: 40.0, common: 30.0, he: 30.0 })
+        ),
+        ammo_empty:     (100.0, vec![(AmmoType::AP, 100)], Some(AmmoMix::default())),
+    }
+
+    // Test set_layout {{{3
+    #[test]
+    fn set_layout() {
+        let mut btry = Battery::default();
+        btry.set_group(1, (1, 1, 0), GunLayoutType::Single, GunDistributionType::SidesEven);
+        assert_eq!((2, 2), (btry.num, btry.mount_num));
+
+        btry.set_layout((2, 2, 0), GunLayoutType::Triple, GunDistributionType::CenterlineEven);
+        assert_eq!((12, 4), (btry.num, btry.mount_num));
+        assert_eq!(0, btry.groups[1].num_mounts());
+        assert_eq!(None, btry.count_mismatch());
+
+        btry.set_group(1, (0, 0, 4), GunLayoutType::Twin, GunDistributionType::SidesEven);
+        assert_eq!((20, 8), (btry.num, btry.mount_num));
+        assert_eq!(None, btry.count_mismatch());
+    }
+
+    // Test infeasible_layouts {{{3
+    macro_rules! test_infeasible_layouts {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, diam, year, layout, mount_kind) = $value;
+
+                    let mut btry = Battery { diam, year, mount_kind, ..Default::default() };
+                    btry.set_layout((0, 2, 0), layout, GunDistributionType::CenterlineEven);
+
+                    assert_eq!(expected, btry.infeasible_layouts());
+                }
+            )*
+        }
+    }
+    test_infeasible_layouts! {
+        // name:                  (issues, diam, year, layout, mount_kind)
+        infeasible_single:        (Vec::<String>::new(), 20.0, 1870, GunLayoutType::Single, MountType::Broadside),
+        infeasible_twin:          (Vec::<String>::new(), 12.0, 1890, GunLayoutType::Twin, MountType::ClosedBarbette),
+        infeasible_twin_20in:     (vec!["Twin mounts were never built for guns over 18.1\""], 20.0, 1940, GunLayoutType::Twin, MountType::ClosedBarbette),
+        infeasible_early_triple:  (vec!["Triple mounts were not built until 1905"], 12.0, 1895, GunLayoutType::Triple, MountType::ClosedBarbette),
+        infeasible_quint_1890:    (vec!["quintuple mounts were not built until 1930"], 1.0, 1890, GunLayoutType::Quint, MountType::Deck),
+        infeasible_decuple_large: (vec!["2 row, decuple mounts were never built for guns over 2\""], 4.0, 1940, GunLayoutType::Dec2Row, MountType::Deck),
+        infeasible_pom_pom:       (Vec::<String>::new(), 1.6, 1935, GunLayoutType::Oct2Row, MountType::Deck),
+        infeasible_twin_broad:    (vec!["broadside mounts never held more than 1 gun"], 10.0, 1870, GunLayoutType::Twin, MountType::Broadside),
+        infeasible_triple_case:   (vec!["casemate mounts never held more than 2 guns"], 6.0, 1920, GunLayoutType::Triple, MountType::Casemate),
+    }
+
+    // Test count_mismatch {{{3
+    macro_rules! test_count_mismatch {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, num, mount_num) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.set_layout((1, 2, 0), GunLayoutType::Twin, GunDistributionType::CenterlineEven);
+                    btry.num = num;
+                    btry.mount_num = mount_num;
+
+                    assert_eq!(expected, btry.count_mismatch().as_deref());
+                }
+            )*
+        }
+    }
+    test_count_mismatch! {
+        // name:                 (mismatch, num, mount_num)
+        count_mismatch_none:     (None, 6, 3),
+        count_mismatch_guns:     (Some("5 guns but its 3 mounts hold 6"), 5, 3),
+        count_mismatch_mounts:   (Some("4 mounts but the groups place 3"), 8, 4),
+    }
+}
+
+// AmmoType {{{1
+/// Type of shell.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum AmmoType {
+    /// Armor piercing.
+    AP,
+    /// Common (semi-armor piercing).
+    Common,
+    /// High explosive.
+    HE,
+}
+
+impl fmt::Display for AmmoType { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::AP     => "AP",
+                Self::Common => "Common",
+                Self::HE     => "HE",
+            }
+        )
+    }
+}
+
+impl AmmoType { // {{{2
+    /// All shell types in report order.
+    pub const ALL: [AmmoType; 3] = [Self::AP, Self::Common, Self::HE];
+
+    // wgt_factor {{{3
+    /// Shell weight relative to an armor piercing shell.
+    ///
+    pub fn wgt_factor(&self) -> f64 {
+        match self {
+            Self::AP     => 1.0,
+            Self::Common => 0.95,
+            Self::HE     => 0.85,
+        }
+    }
+}
+
+// AmmoMix {{{1
+/// Proportion of each type of shell carried.
+///
+/// The proportions are relative to each other so they can be given as
+/// percentages, fractions or shells per gun.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AmmoMix {
+    /// Armor piercing shells.
+    #[serde(default)]
+    pub ap: f64,
+    /// Common shells.
+    #[serde(default)]
+    pub common: f64,
+    /// High explosive shells.
+    #[serde(default)]
+    pub he: f64,
+}
+
+impl AmmoMix { // {{{2
+    /// Year by which flash-tight cordite handling became standard.
+    pub const SAFE_CORDITE_YEAR: u32 = 1918;
+
+    // share {{{3
+    /// Fraction of the magazine holding a type of shell.
+    ///
+    /// An empty mix is treated as all armor piercing.
+    ///
+    pub fn share(&self, kind: AmmoType) -> f64 {
+        let total = self.ap + self.common + self.he;
+        if total <= 0.0 {
+            return if kind == AmmoType::AP { 1.0 } else { 0.0 };
+        }
+
+        let n = match kind {
+            AmmoType::AP     => self.ap,
+            AmmoType::Common => self.common,
+            AmmoType::HE     => self.he,
+        };
+
+        n / total
+    }
+
+    // wgt_factor {{{3
+    /// Average shell weight relative to an armor piercing shell.
+    ///
+    pub fn wgt_factor(&self) -> f64 {
+        AmmoType::ALL.iter()
+            .map(|t| self.share(*t) * t.wgt_factor())
+            .sum()
+    }
 }
 
 // GunType {{{1
 /// Type of gun
 ///
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(PartialEq, Serialize, Deserialize, Clone, Debug, Default)]
 pub enum GunType {
     MuzzleLoading,
     #[default]
@@ -856,6 +1568,7 @@ impl From<String> for GunType { // {{{2
 }
 
 impl From<&str> for GunType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::BreechLoading,
@@ -1059,6 +1772,7 @@ impl From<String> for MountType { // {{{2
 }
 
 impl From<&str> for MountType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::ColesTurret,
@@ -1087,8 +1801,21 @@ impl fmt::Display for MountType { // {{{2
     }
 }
 impl MountType { // {{{2
+    // max_guns {{{3
+    /// Most guns a single mount of this kind carried, or None if there
+    /// is no limit.
+    ///
+    pub fn max_guns(&self) -> Option<u32> {
+        match self {
+            Self::Broadside => Some(1),
+            Self::Casemate  => Some(2),
+            _               => None,
+        }
+    }
+
     // gunhouse_hgt_factor {{{3
-    /// XXX: I do not know what this does.
+    /// Multiplier for the height of the gunhouse. Coles turrets enclose
+    /// the whole gun and are twice as tall.
     ///
     pub fn gunhouse_hgt_factor(&self) -> f64 {
         match self {
@@ -1177,6 +1904,22 @@ impl MountType { // {{{2
         }
     }
 
+    // hoist_share {{{3
+    /// Share of the propellant and handling allowance taken up by the
+    /// hoists and handling gear of a 12" gun in this mount.
+    ///
+    pub fn hoist_share(&self) -> f64 {
+        match self {
+            Self::Broadside      => 0.05,
+            Self::ColesTurret    => 0.2,
+            Self::OpenBarbette   => 0.25,
+            Self::ClosedBarbette => 0.3,
+            Self::DeckAndHoist   => 0.2,
+            Self::Deck           => 0.05,
+            Self::Casemate       => 0.15,
+        }
+    }
+
     // wgt {{{3
     /// Multiplier for weight calculations.
     ///
@@ -1300,6 +2043,170 @@ mod mount_type {
 
 }
 
+// TrainingDrive {{{1
+/// How gun mounts are trained.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub enum TrainingDrive {
+    #[default]
+    Hand,
+    Hydraulic,
+    Electric,
+}
+
+impl From<String> for TrainingDrive { // {{{2
+    fn from(index: String) -> Self {
+        index.as_str().into()
+    }
+}
+
+impl From<&str> for TrainingDrive {
+    fn from(label: &str) -> Self {
+        Self::from_label(label).unwrap_or_default()
+    }
+}
+
+impl fmt::Display for TrainingDrive { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Hand      => "hand",
+                Self::Hydraulic => "hydraulic",
+                Self::Electric  => "electric",
+            }
+        )
+    }
+}
+
+impl TrainingDrive { // {{{2
+    /// Heaviest mount (t) that can be trained by hand.
+    pub const HAND_MAX_WGT: f64 = 50.0;
+
+    // for_mount {{{3
+    /// Usual drive for a mount of the given year and gun diameter.
+    ///
+    /// Guns in broadside, deck and casemate mounts and guns under 6"
+    /// were trained by hand. Heavier mounts used hydraulic power until
+    /// electric training became common.
+    ///
+    pub fn for_mount(year: u32, mount: &MountType, diam: f64) -> Self {
+        match mount {
+            MountType::Broadside | MountType::Deck | MountType::Casemate => Self::Hand,
+
+            _ if diam < 6.0   => Self::Hand,
+            _ if year < 1880  => Self::Hand,
+            _ if year < 1905  => Self::Hydraulic,
+            _                 => Self::Electric,
+        }
+    }
+
+    // wgt_factor {{{3
+    /// Weight of the training gear as a share of the mount weight.
+    ///
+    pub fn wgt_factor(&self) -> f64 {
+        match self {
+            Self::Hand      => 0.0,
+            Self::Hydraulic => 0.03,
+            Self::Electric  => 0.02,
+        }
+    }
+
+    // power_factor {{{3
+    /// Auxiliary power (hp) needed per ton of mount.
+    ///
+    pub fn power_factor(&self) -> f64 {
+        match self {
+            Self::Hand      => 0.0,
+            Self::Hydraulic => 0.4,
+            Self::Electric  => 0.33,
+        }
+    }
+}
+
+// Testing TrainingDrive {{{2
+#[cfg(test)]
+mod training_drive {
+    use super::*;
+
+    // Test for_mount {{{3
+    macro_rules! test_for_mount {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year, mount, diam) = $value;
+
+                    assert_eq!(expected, TrainingDrive::for_mount(year, &mount, diam));
+                }
+            )*
+        }
+    }
+
+    test_for_mount! {
+        // name:                (drive, year, mount, diam)
+        for_mount_deck:         (TrainingDrive::Hand, 1920, MountType::Deck, 12.0),
+        for_mount_small:        (TrainingDrive::Hand, 1920, MountType::ClosedBarbette, 5.0),
+        for_mount_early:        (TrainingDrive::Hand, 1870, MountType::ColesTurret, 12.0),
+        for_mount_hydraulic:    (TrainingDrive::Hydraulic, 1895, MountType::ClosedBarbette, 12.0),
+        for_mount_electric:     (TrainingDrive::Electric, 1910, MountType::ClosedBarbette, 12.0),
+    }
+
+    // Test from {{{3
+    macro_rules! test_from {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, label) = $value;
+
+                    assert_eq!(expected, TrainingDrive::from(label));
+                }
+            )*
+        }
+    }
+
+    test_from! {
+        // name:            (drive, label)
+        from_hydraulic:     (TrainingDrive::Hydraulic, "Hydraulic"),
+        from_electric:      (TrainingDrive::Electric, "electric"),
+        from_hand:          (TrainingDrive::Hand, "Hand"),
+        from_unknown:       (TrainingDrive::Hand, "2"),
+    }
+}
+
+// HistoricalTurret {{{1
+/// Revolving weight of a turret that was built.
+///
+struct HistoricalTurret {
+    name: &'static str,
+    year: u32,
+    /// Calibre (in).
+    diam: f64,
+    /// Guns in the turret.
+    guns: u32,
+    /// Revolving weight (t), approximate.
+    wgt: f64,
+}
+
+// Dataset {{{2
+const TURRETS: [HistoricalTurret; 15] = [
+    HistoricalTurret { name: "HMS Dreadnought",      year: 1906, diam: 12.0, guns: 2, wgt:  540.0 },
+    HistoricalTurret { name: "HMS Orion",            year: 1910, diam: 13.5, guns: 2, wgt:  600.0 },
+    HistoricalTurret { name: "HMS Queen Elizabeth",  year: 1913, diam: 15.0, guns: 2, wgt:  775.0 },
+    HistoricalTurret { name: "HMS Nelson",           year: 1922, diam: 16.0, guns: 3, wgt: 1500.0 },
+    HistoricalTurret { name: "HMS Kent",             year: 1926, diam:  8.0, guns: 2, wgt:  185.0 },
+    HistoricalTurret { name: "USS Northampton",      year: 1928, diam:  8.0, guns: 3, wgt:  270.0 },
+    HistoricalTurret { name: "HMS Leander",          year: 1931, diam:  6.0, guns: 2, wgt:   95.0 },
+    HistoricalTurret { name: "Scharnhorst",          year: 1934, diam: 11.0, guns: 3, wgt:  750.0 },
+    HistoricalTurret { name: "USS Brooklyn",         year: 1935, diam:  6.0, guns: 3, wgt:  170.0 },
+    HistoricalTurret { name: "Bismarck",             year: 1935, diam: 15.0, guns: 2, wgt: 1050.0 },
+    HistoricalTurret { name: "USS Porter",           year: 1935, diam:  5.0, guns: 2, wgt:   45.0 },
+    HistoricalTurret { name: "HMS Afridi",           year: 1936, diam:  4.7, guns: 2, wgt:   25.0 },
+    HistoricalTurret { name: "HMS King George V",    year: 1937, diam: 14.0, guns: 4, wgt: 1580.0 },
+    HistoricalTurret { name: "IJN Yamato",           year: 1937, diam: 18.1, guns: 3, wgt: 2510.0 },
+    HistoricalTurret { name: "USS Iowa",             year: 1939, diam: 16.0, guns: 3, wgt: 1700.0 },
+];
+
 // SubBattery {{{1
 /// Gun grouping within a battery.
 ///
@@ -1321,25 +2228,33 @@ pub struct SubBattery {
     pub two_mounts_up: bool,
     /// If mounts below the waterline are on the lower deck
     pub lower_deck: bool,
+
+    /// Explicit heights of the mounts above the waterline. Overrides
+    /// above, on and two_mounts_up in the topweight calculations.
+    #[serde(default)]
+    pub tiers: Option<MountTiers>,
 }
 
 // Internals Output {{{2
-#[cfg(debug_assertions)]
 impl SubBattery {
-    pub fn internals(&self, hull: Hull, diam: f64) -> () {
-        eprintln!("layout = {}", self.layout);
-        eprintln!("distribution = {}", self.distribution);
-        eprintln!("above = {}", self.above);
-        eprintln!("on = {}", self.on);
-        eprintln!("below = {}", self.below);
-        eprintln!("two_mounts_up = {}", self.two_mounts_up);
-        eprintln!("lower_deck = {}", self.lower_deck);
-        eprintln!("super_() = {}", self.super_());
-        eprintln!("num_mounts() = {}", self.num_mounts());
-        eprintln!("diameter_calc() = {}", self.diameter_calc(diam));
-        eprintln!("wgt_adj() = {}", self.wgt_adj());
-        eprintln!("free() = {}", self.free(hull.clone()));
-        eprintln!("");
+    // internals {{{3
+    /// Inputs and intermediate values of the group by name.
+    ///
+    pub fn internals(&self, hull: Hull, diam: f64) -> Vec<(&'static str, String)> {
+        vec![
+            ("layout", self.layout.to_string()),
+            ("distribution", self.distribution.to_string()),
+            ("above", self.above.to_string()),
+            ("on", self.on.to_string()),
+            ("below", self.below.to_string()),
+            ("two_mounts_up", self.two_mounts_up.to_string()),
+            ("lower_deck", self.lower_deck.to_string()),
+            ("super", self.super_().to_string()),
+            ("num_mounts", self.num_mounts().to_string()),
+            ("diameter_calc", self.diameter_calc(diam).to_string()),
+            ("wgt_adj", self.wgt_adj().to_string()),
+            ("free", self.free(hull).to_string()),
+        ]
     }
 }
 
@@ -1355,6 +2270,48 @@ impl SubBattery { // {{{2
         (above - below) * self.layout.guns_per() as i32
     }
 
+    // super_hgt {{{3
+    /// Sum of the height of each barrel above the deck (ft) given the
+    /// height of one superfiring tier. Barrels below the waterline count
+    /// as negative heights.
+    ///
+    pub fn super_hgt(&self, tier_hgt: f64) -> f64 {
+        match &self.tiers {
+            Some(t) => {
+                let below = (self.below * if self.lower_deck { 2 } else { 1 }) as f64 * tier_hgt;
+
+                (t.super_hgt(tier_hgt) - below) * self.layout.guns_per() as f64
+            },
+            None => self.super_() as f64 * tier_hgt,
+        }
+    }
+
+    // mount_tiers {{{3
+    /// Mount heights above the waterline, either as set or as implied by
+    /// above, on and two_mounts_up.
+    ///
+    pub fn mount_tiers(&self) -> MountTiers {
+        match &self.tiers {
+            Some(t) => t.clone(),
+            None => MountTiers {
+                deck: self.on,
+                super1: if self.two_mounts_up { 0 } else { self.above },
+                super2: if self.two_mounts_up { self.above } else { 0 },
+                ..Default::default()
+            },
+        }
+    }
+
+    // set_tiers {{{3
+    /// Set explicit mount heights and update the mount counts to match.
+    ///
+    pub fn set_tiers(&mut self, tiers: MountTiers) {
+        self.on = tiers.deck;
+        self.above = tiers.super1 + tiers.super2;
+        self.two_mounts_up = tiers.super2 > 0;
+        self.tiers = Some(tiers);
+    }
+
     // num_mounts {{{3
     /// Total number of gun mounts.
     ///
@@ -1362,8 +2319,15 @@ impl SubBattery { // {{{2
         self.above + self.on + self.below
     }
 
+    // num_guns {{{3
+    /// Total number of guns in the group's mounts.
+    ///
+    pub fn num_guns(&self) -> u32 {
+        self.num_mounts() * self.layout.guns_per()
+    }
+
     // diameter_calc {{{3
-    /// XXX: I do not know what this does.
+    /// Diameter (ft) of a mount of the group for guns of calibre diam.
     ///
     pub fn diameter_calc(&self, diam: f64) -> f64 {
         if diam == 0.0 { return 0.0; } // Catch divide by zero
@@ -1378,15 +2342,51 @@ impl SubBattery { // {{{2
         calc
     }
 
+    // arcs {{{3
+    /// Number of guns that bear ahead, on one broadside and astern.
+    ///
+    /// Deck mounts on the centerline bear on either broadside while
+    /// hull mounts and mounts on the sides only bear on their own side.
+    ///
+    pub fn arcs(&self, fwd_len: f64, hull_mounts: bool) -> (u32, u32, u32) {
+        let (deck, hull) = if hull_mounts {
+            (0, self.num_mounts())
+        } else {
+            (self.above + self.on, self.below)
+        };
+
+        let ahead = self.distribution.mounts_ahead(deck, fwd_len);
+        let astern = deck - ahead;
+        let broadside = deck.div_ceil(self.distribution.abreast()) + hull.div_ceil(2);
+
+        let guns = self.layout.guns_per();
+
+        (ahead * guns, broadside * guns, astern * guns)
+    }
+
+    // beam_req {{{3
+    /// Beam needed to fit the deck mounts of the group across the ship
+    /// for guns of calibre diam on barbettes barb (in) thick.
+    ///
+    /// Hull mounts are ignored.
+    ///
+    pub fn beam_req(&self, diam: f64, barb: f64) -> f64 {
+        if self.above + self.on == 0 { return 0.0; }
+
+        (self.diameter_calc(diam) + 2.0 * barb / 12.0) * self.distribution.abreast() as f64
+    }
+
     // wgt_adj {{{3
-    /// XXX: I do not know what this does.
+    /// Share of a full mount's weight the group's mounts weigh, summed
+    /// over them for Battery::wgt_adj() to average.
     ///
     pub fn wgt_adj(&self) -> f64 {
         self.layout.wgt_adj() * self.num_mounts() as f64
     }
 
     // free {{{3
-    /// XXX: I do not know what this does.
+    /// Freeboard (ft) at the group's mounts summed over them, for
+    /// Battery::free() to average.
     ///
     pub fn free(&self, hull: Hull) -> f64 {
         let free = self.distribution.free(self.num_mounts(), hull);
@@ -1434,6 +2434,44 @@ mod sub_battery {
         super_test_8: ( 1, 1, true, 1, false),
     }
 
+    // Test super_hgt {{{3
+    macro_rules! test_super_hgt {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, above, two_mounts_up, tiers) = $value;
+
+                    let mut sub_btry = SubBattery::default();
+                    sub_btry.layout = GunLayoutType::Twin;
+                    sub_btry.above = above;
+                    sub_btry.two_mounts_up = two_mounts_up;
+                    sub_btry.below = 1;
+                    if let Some(tiers) = tiers { sub_btry.set_tiers(tiers); }
+
+                    assert_eq!(expected, sub_btry.super_hgt(10.0));
+                }
+            )*
+        }
+    }
+    test_super_hgt! {
+        // name:               (super_hgt, above, two_mounts_up, tiers)
+        super_hgt_legacy:      (20.0, 2, false, None),
+        super_hgt_legacy_two:  (60.0, 2, true, None),
+        super_hgt_tiers:       (40.0, 0, false, Some(MountTiers { deck: 1, super1: 1, super2: 1, ..Default::default() })),
+        super_hgt_custom:      (10.0, 0, false, Some(MountTiers { super1: 1, super1_hgt: Some(15.0), ..Default::default() })),
+    }
+
+    // Test mount_tiers {{{3
+    #[test]
+    fn mount_tiers() {
+        let mut sub_btry = SubBattery { on: 2, above: 1, ..Default::default() };
+        assert_eq!(MountTiers { deck: 2, super1: 1, ..Default::default() }, sub_btry.mount_tiers());
+
+        sub_btry.set_tiers(MountTiers { deck: 1, super1: 1, super2: 1, ..Default::default() });
+        assert_eq!((1, 2, true), (sub_btry.on, sub_btry.above, sub_btry.two_mounts_up));
+    }
+
     // Test diameter_calc {{{3
     macro_rules! test_diameter_calc {
         ($($name:ident: $value:expr,)*) => {
@@ -1458,6 +2496,66 @@ mod sub_battery {
         diameter_calc_cal_sm:  (25.82, 0.5),
     }
 
+    // Test arcs {{{3
+    macro_rules! test_arcs {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, layout, distribution, above, on, below, hull_mounts) = $value;
+
+                    let mut sub_btry = SubBattery::default();
+                    sub_btry.layout = layout;
+                    sub_btry.distribution = distribution;
+                    sub_btry.above = above;
+                    sub_btry.on = on;
+                    sub_btry.below = below;
+
+                    assert_eq!(expected, sub_btry.arcs(0.5, hull_mounts));
+                }
+            )*
+        }
+    }
+    test_arcs! {
+        // name:              (arcs, layout, distribution, above, on, below, hull_mounts)
+        arcs_centerline_ends: ((4, 8, 4), GunLayoutType::Twin, GunDistributionType::CenterlineEndsFD, 2, 2, 0, false),
+        arcs_centerline_fd:   ((6, 6, 0), GunLayoutType::Triple, GunDistributionType::CenterlineFD, 1, 1, 0, false),
+        arcs_centerline_ad:   ((0, 2, 2), GunLayoutType::Twin, GunDistributionType::CenterlineADFwd, 0, 1, 0, false),
+        arcs_sides:           ((2, 2, 2), GunLayoutType::Single, GunDistributionType::SidesEven, 0, 4, 0, false),
+        arcs_hull:            ((0, 3, 0), GunLayoutType::Single, GunDistributionType::SidesEven, 0, 0, 6, false),
+        arcs_hull_mounts:     ((0, 2, 0), GunLayoutType::Single, GunDistributionType::CenterlineEven, 0, 4, 0, true),
+    }
+
+    // Test beam_req {{{3
+    macro_rules! test_beam_req {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, distribution, above, on, below, barb) = $value;
+
+                    let mut sub_btry = SubBattery::default();
+                    sub_btry.layout = GunLayoutType::Single;
+                    sub_btry.distribution = distribution;
+                    sub_btry.above = above;
+                    sub_btry.on = on;
+                    sub_btry.below = below;
+
+                    assert!(expected == to_place(sub_btry.beam_req(10.0, barb), 2));
+                }
+            )*
+        }
+    }
+    test_beam_req! {
+        // name:                (beam_req, distribution, above, on, below, barbette)
+        beam_req_centerline:    (19.14, GunDistributionType::CenterlineEven, 0, 2, 0, 0.0),
+        beam_req_sides:         (38.27, GunDistributionType::SidesEven, 0, 2, 0, 0.0),
+        beam_req_sides_raised:  (38.27, GunDistributionType::SidesFD, 1, 0, 0, 0.0),
+        beam_req_hull_only:     (0.0, GunDistributionType::SidesEven, 0, 0, 2, 0.0),
+        beam_req_barbette:      (21.14, GunDistributionType::CenterlineEven, 0, 2, 0, 12.0),
+        beam_req_sides_barbette: (42.27, GunDistributionType::SidesEven, 0, 2, 0, 12.0),
+    }
+
     // Test wgt_adj {{{3
     macro_rules! test_wgt_adj {
         ($($name:ident: $value:expr,)*) => {
@@ -1519,6 +2617,47 @@ mod sub_battery {
     }
 }
 
+// MountTiers {{{1
+/// Number of mounts at each height above the deck.
+///
+#[derive(PartialEq, Serialize, Deserialize, Clone, Debug, Default)]
+pub struct MountTiers {
+    /// Mounts on the deck.
+    #[serde(default)]
+    pub deck: u32,
+    /// Mounts superfiring over deck mounts.
+    #[serde(default)]
+    pub super1: u32,
+    /// Mounts superfiring over the first tier.
+    #[serde(default)]
+    pub super2: u32,
+
+    /// Height of the first tier above the deck (ft), if not the default.
+    #[serde(default)]
+    pub super1_hgt: Option<f64>,
+    /// Height of the second tier above the deck (ft), if not the default.
+    #[serde(default)]
+    pub super2_hgt: Option<f64>,
+}
+
+impl MountTiers { // {{{2
+    // super_hgt {{{3
+    /// Sum of the height of each mount above the deck (ft) given the
+    /// default height of one tier.
+    ///
+    pub fn super_hgt(&self, tier_hgt: f64) -> f64 {
+        self.super1 as f64 * self.super1_hgt.unwrap_or(tier_hgt) +
+        self.super2 as f64 * self.super2_hgt.unwrap_or(2.0 * tier_hgt)
+    }
+
+    // num_mounts {{{3
+    /// Total number of mounts.
+    ///
+    pub fn num_mounts(&self) -> u32 {
+        self.deck + self.super1 + self.super2
+    }
+}
+
 // GunDistributionType {{{1
 /// Distribution of gun mounts on the deck.
 ///
@@ -1552,6 +2691,7 @@ impl From<String> for GunDistributionType { // {{{2
 }
 
 impl From<&str> for GunDistributionType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::CenterlineEndsFD,
@@ -1622,7 +2762,7 @@ impl GunDistributionType { // {{{2
             Self::CenterlineEndsFD =>
                 if mounts == 1 {
                     "centreline forward"
-                } else if mounts % 2 == 0 {
+                } else if mounts.is_multiple_of(2) {
                     "centreline ends, evenly spread"
                 } else {
                     "centreline ends, majority forward"
@@ -1630,7 +2770,7 @@ impl GunDistributionType { // {{{2
             Self::CenterlineEndsAD =>
                 if mounts == 1 {
                     "centreline aft"
-                } else if mounts % 2 == 0 {
+                } else if mounts.is_multiple_of(2) {
                     "centrelineends, evenly spread"
                 } else {
                     "centreline ends, majority aft"
@@ -1660,7 +2800,7 @@ impl GunDistributionType { // {{{2
             Self::SidesEndsFD =>
                 if mounts < 3 {
                     "sides, forward"
-                } else if mounts % 4 == 0 {
+                } else if mounts.is_multiple_of(4) {
                     "side ends, evenly spread"
                 } else {
                     "side ends, majority forward"
@@ -1668,7 +2808,7 @@ impl GunDistributionType { // {{{2
             Self::SidesEndsAD =>
                 if mounts < 3 {
                     "sides aft"
-                } else if mounts % 4 == 0 {
+                } else if mounts.is_multiple_of(4) {
                     "side ends, evenly spread"
                 } else {
                     "side ends, majority aft"
@@ -1697,8 +2837,9 @@ impl GunDistributionType { // {{{2
     // super_aft {{{3
     /// True if the type would place guns aft.
     ///
+    #[allow(clippy::match_like_matches_macro)]
     pub fn super_aft(&self) -> bool {
-        let s = match self {
+        match self {
             Self::CenterlineEndsAD |
             Self::CenterlineADFwd |
             Self::CenterlineAD |
@@ -1709,9 +2850,53 @@ impl GunDistributionType { // {{{2
             Self::SidesADAft => true,
 
             _ => false,
-        };
+        }
+    }
 
-        s.into()
+    // amidships {{{3
+    /// Number of mounts placed on the centreline amidships, where they
+    /// compete with the boiler uptakes for deck.
+    ///
+    pub fn amidships(&self, mounts: u32) -> u32 {
+        match self {
+            Self::CenterlineEven =>
+                if mounts == 1 { 1 } else { mounts.saturating_sub(2) },
+            Self::CenterlineFDAft |
+            Self::CenterlineADFwd => mounts,
+
+            _ => 0,
+        }
+    }
+
+    // abreast {{{3
+    /// Number of mounts placed side by side across the deck.
+    ///
+    pub fn abreast(&self) -> u32 {
+        match self {
+            Self::SidesEven |
+            Self::SidesEndsFD |
+            Self::SidesEndsAD |
+            Self::SidesFDFwd |
+            Self::SidesFD |
+            Self::SidesFDAft |
+            Self::SidesADFwd |
+            Self::SidesAD |
+            Self::SidesADAft => 2,
+
+            _ => 1,
+        }
+    }
+
+    // mounts_ahead {{{3
+    /// Number of mounts that are able to fire ahead.
+    ///
+    // mounts_fwd() counts afterdeck forward mounts as forward
+    // which is not true for arcs of fire
+    fn mounts_ahead(&self, tot: u32, fwd_len: f64) -> u32 {
+        match self {
+            Self::CenterlineADFwd => 0,
+            _ => self.mounts_fwd(tot, fwd_len),
+        }
     }
 
     // mounts_fwd {{{3
@@ -1758,8 +2943,11 @@ impl GunDistributionType { // {{{2
     }
 
     // free {{{3
-    /// XXX: I do not know what this does
+    /// Average freeboard (ft) at num_mounts mounts placed by this
+    /// distribution.
     ///
+    /// Mounts at the ends, or forward or aft on one deck, are taken to
+    /// be between the middle and that end of their deck.
     pub fn free(&self, num_mounts: u32, hull: Hull) -> f64 {
 
         if num_mounts == 0 { return 0.0; } // catch divide by zero
@@ -1828,7 +3016,8 @@ impl GunDistributionType { // {{{2
     }
 
     // gun_position {{{3
-    /// XXX: I do not know what this does.
+    /// Where on their deck mounts placed on one deck sit, as a fraction
+    /// of the waterline, given the deck lengths as fractions of it.
     ///
     fn gun_position(&self, fd_len: f64, ad_len: f64) -> f64 {
         match self {
@@ -1849,7 +3038,8 @@ impl GunDistributionType { // {{{2
     }
 
     // g1_gun_position {{{3
-    /// XXX: I do not know what this does.
+    /// Position of a battery's first group for comparing it with the
+    /// second, or 1 if it is spread along the ship.
     ///
     pub fn g1_gun_position(&self, fd_len: f64, ad_len: f64) -> f64 {
         match self {
@@ -1863,7 +3053,8 @@ impl GunDistributionType { // {{{2
         }
     }
     // g2_gun_position {{{3
-    /// XXX: I do not know what this does.
+    /// Position of a battery's second group for comparing it with the
+    /// first, or 0 if it is spread along the ship.
     ///
     pub fn g2_gun_position(&self, fd_len: f64, ad_len: f64) -> f64 {
         match self {
@@ -1878,7 +3069,8 @@ impl GunDistributionType { // {{{2
     }
 
     // super_factor_long {{{3
-    /// XXX: I do not know what this does.
+    /// True if the distribution groups a battery's mounts together, which
+    /// gives Ship::super_factor_long() the smaller lever.
     ///
     pub fn super_factor_long(&self) -> bool {
         match self {
@@ -2052,7 +3244,7 @@ mod gun_dist_type {
 // GunLayoutType {{{1
 /// Layout of guns within a mount.
 ///
-#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[derive(PartialEq, Serialize, Deserialize, Clone, Debug, Default)]
 pub enum GunLayoutType {
     #[default]
     Single,
@@ -2079,6 +3271,7 @@ impl From<String> for GunLayoutType { // {{{2
 }
 
 impl From<&str> for GunLayoutType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::Twin2Row,
@@ -2193,6 +3386,49 @@ impl GunLayoutType { // {{{2
             Self::Dec2Row  => 1.0,
         }
     }
+
+    // limits {{{3
+    /// Year mounts of this layout were first built and the largest gun
+    /// (in) they ever carried.
+    ///
+    /// Mounts with guns in two rows or five to a mount were only built
+    /// for light automatic weapons.
+    ///
+    pub fn limits(&self) -> (u32, f64) {
+        match self {
+            Self::Single   => (0, f64::INFINITY),
+            Self::Twin     => (1860, 18.1),
+            Self::TwoGun   => (1860, 18.1),
+            Self::Triple   => (1905, 18.1),
+            Self::ThreeGun => (1905, 18.1),
+            Self::Quad     => (1912, 15.0),
+            Self::FourGun  => (1912, 15.0),
+            Self::Quint    => (1930, 2.0),
+            Self::FiveGun  => (1930, 2.0),
+            Self::Twin2Row => (1925, 2.0),
+            Self::Quad2Row => (1925, 2.0),
+            Self::Quad4Row => (1925, 2.0),
+            Self::Sex2Row  => (1925, 2.0),
+            Self::Oct2Row  => (1925, 2.0),
+            Self::Dec2Row  => (1925, 2.0),
+        }
+    }
+
+    // infeasible {{{3
+    /// Why mounts of this layout could not carry guns of this calibre
+    /// and year, or None if they could.
+    ///
+    pub fn infeasible(&self, diam: f64, year: u32) -> Option<String> {
+        let (first, max) = self.limits();
+
+        if diam > max {
+            Some(format!("{} mounts were never built for guns over {}\"", self, max))
+        } else if year < first {
+            Some(format!("{} mounts were not built until {}", self, first))
+        } else {
+            None
+        }
+    }
 }
 
 // Torpedoes {{{1
@@ -2200,8 +3436,11 @@ impl GunLayoutType { // {{{2
 ///
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Torpedoes {
-    /// Units
+    /// Units SpringSharp flagged the torpedoes as entered in.
+    #[deprecated(note = "only used by version 1 and 2 JSON reports, use Ship::units for how values are shown")]
+    #[serde(default, skip_serializing_if = "Units::is_imperial")]
     pub units: Units,
+
     /// Year torpedo was designed.
     pub year: u32,
 
@@ -2217,14 +3456,31 @@ pub struct Torpedoes {
     pub diam: f64,
     /// Torpedo length.
     pub len: f64,
+
+    /// Reloads are moved to the tubes by rails, davits and power hoists
+    /// rather than by hand.
+    #[serde(default)]
+    pub reload_gear: bool,
 }
 
 impl Torpedoes { // {{{2
+    /// Width of the rails and working space along each set of deck
+    /// reloads.
+    const RELOAD_LANE: f64 = 3.0;
+
+    /// Year torpedo directors with a deflection calculator came into use.
+    const DIRECTOR_CALCULATOR_YEAR: u32 = 1912;
+    /// Year directors were linked to the tubes by follow-the-pointer
+    /// transmitters.
+    const DIRECTOR_TRANSMITTER_YEAR: u32 = 1925;
+    /// Year directors gained a torpedo control computer.
+    const DIRECTOR_COMPUTER_YEAR: u32 = 1935;
+
     // wgt {{{3
-    /// Weight of all torpedoes and mounts in the set.
+    /// Weight of all torpedoes, mounts and reload gear in the set.
     ///
     pub fn wgt(&self) -> f64 {
-        self.wgt_weaps() + self.wgt_mounts()
+        self.wgt_weaps() + self.wgt_mounts() + self.wgt_reload_gear()
     }
 
     // wgt_weaps {{{3
@@ -2246,6 +3502,26 @@ impl Torpedoes { // {{{2
         self.mount_kind.wgt_factor() * self.wgt_weaps()
     }
 
+    // wgt_reload_gear {{{3
+    /// Weight of the gear handling the reloads in the set, over and above
+    /// their stowage.
+    ///
+    pub fn wgt_reload_gear(&self) -> f64 {
+        if !self.reload_gear { return 0.0; }
+
+        self.mount_kind.reload_gear_factor() * self.wgt_weaps()
+    }
+
+    // wgt_director {{{3
+    /// Weight of a torpedo director and its gear in a ship of this year.
+    ///
+    pub fn wgt_director(year: u32) -> f64 {
+             if year >= Self::DIRECTOR_COMPUTER_YEAR    { 5.0 }
+        else if year >= Self::DIRECTOR_TRANSMITTER_YEAR { 3.5 }
+        else if year >= Self::DIRECTOR_CALCULATOR_YEAR  { 2.0 }
+        else                                            { 1.0 }
+    }
+
     // hull_space {{{3
     /// Hull space taken up by the set.
     ///
@@ -2257,7 +3533,23 @@ impl Torpedoes { // {{{2
     /// Deck space taken up by the set.
     ///
     pub fn deck_space(&self, b: f64) -> f64 {
-        self.mount_kind.deck_space(b, self.num, self.len, self.diam, self.mounts)
+        if self.num == 0 { return 0.0; } // catch divide by zero in empty sets
+
+        self.mount_kind.deck_space(b, self.num, self.len, self.diam, self.mounts) +
+            self.deck_space_reload_gear()
+    }
+
+    // deck_space_reload_gear {{{3
+    /// Deck space taken up by the rails and working space of the reload
+    /// gear.
+    ///
+    pub fn deck_space_reload_gear(&self) -> f64 {
+        if !self.reload_gear || self.num == 0 { return 0.0; }
+
+        match self.mount_kind {
+            TorpedoMountType::DeckReloads => self.len * Self::RELOAD_LANE * self.mounts.max(1) as f64,
+            _ => 0.0,
+        }
     }
 }
 
@@ -2285,6 +3577,7 @@ impl From<String> for TorpedoMountType { // {{{2
 }
 
 impl From<&str> for TorpedoMountType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::DeckSideTubes,
@@ -2318,6 +3611,21 @@ impl TorpedoMountType { // {{{2
         }
     }
 
+    // reload_gear_factor {{{3
+    /// Multiplier used to determine the weight of gear handling reloads.
+    ///
+    /// Deck reloads need rails, davits and protected lockers. Reloads
+    /// below water are swung into the tubes by hoists from the flat
+    /// alongside.
+    ///
+    pub fn reload_gear_factor(&self) -> f64 {
+        match self {
+            Self::DeckReloads      => 0.3,
+            Self::SubmergedReloads => 0.2,
+            _                      => 0.0,
+        }
+    }
+
     // hull_space {{{3
     /// Hull space taken up by torpedo mounts.
     ///
@@ -2504,7 +3812,9 @@ mod torpedo_mount_type {
 ///
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct Mines {
-    /// Units
+    /// Units SpringSharp flagged the mines as entered in.
+    #[deprecated(note = "only used by version 1 and 2 JSON reports, use Ship::units for how values are shown")]
+    #[serde(default, skip_serializing_if = "Units::is_imperial")]
     pub units: Units,
 
     /// Year mines were designed.
@@ -2564,6 +3874,7 @@ impl From<String> for MineType { // {{{2
 }
 
 impl From<&str> for MineType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::BowTubes,
@@ -2634,7 +3945,9 @@ mod mine_type {
 ///
 #[derive(Serialize, Deserialize, Clone, Debug, Default)]
 pub struct ASW {
-    /// Units.
+    /// Units SpringSharp flagged the ASW gear as entered in.
+    #[deprecated(note = "only used by version 1 and 2 JSON reports, use Ship::units for how values are shown")]
+    #[serde(default, skip_serializing_if = "Units::is_imperial")]
     pub units: Units,
 
     /// Year ASW system was designed.
@@ -2677,6 +3990,7 @@ impl ASW { // {{{2
 
 // Testing Torpedoes, Mines and ASW {{{2
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod weapons {
     use super::*;
     use crate::test_support::*;
@@ -2978,6 +4292,53 @@ mod weapons {
         test_deck_space_submerged_tubes:     (0.0, TorpedoMountType::SubmergedSideTubes, 18.0, 21.0, 4, 2),
         test_deck_space_submerged_reloads:   (0.0, TorpedoMountType::SubmergedReloads,   18.0, 21.0, 4, 2),
     }
+
+    // Test torpedo_reload_gear {{{3
+    macro_rules! test_torpedo_reload_gear {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let ((wgt, space), kind, reload_gear) = $value;
+
+                    let mut torp = Torpedoes::default();
+                    torp.mount_kind = kind; torp.diam = 18.0; torp.len = 21.0; torp.num = 4; torp.mounts = 2; torp.year = 1940;
+                    torp.reload_gear = reload_gear;
+
+                    assert_eq!(wgt, to_place(torp.wgt_reload_gear(), 3));
+                    assert_eq!(space, to_place(torp.deck_space(10.0), 3));
+                }
+            )*
+        }
+    }
+    test_torpedo_reload_gear! {
+        // name:                       ((wgt, space), kind, reload_gear)
+        reload_gear_deck:              ((1.335, 378.0), TorpedoMountType::DeckReloads, true),
+        reload_gear_deck_by_hand:      ((0.0, 252.0), TorpedoMountType::DeckReloads, false),
+        reload_gear_submerged:         ((0.89, 0.0), TorpedoMountType::SubmergedReloads, true),
+        reload_gear_tubes:             ((0.0, 126.0), TorpedoMountType::FixedTubes, true),
+    }
+
+    // Test torpedo_wgt_director {{{3
+    macro_rules! test_torpedo_wgt_director {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, year) = $value;
+
+                    assert_eq!(expected, Torpedoes::wgt_director(year));
+                }
+            )*
+        }
+    }
+    test_torpedo_wgt_director! {
+        // name:                  (wgt, year)
+        wgt_director_sights:      (1.0, 1905),
+        wgt_director_calculator:  (2.0, 1912),
+        wgt_director_transmitter: (3.5, 1930),
+        wgt_director_computer:    (5.0, 1940),
+    }
 }
 
 // ASWType {{{1
@@ -2999,6 +4360,7 @@ impl From<String> for ASWType { // {{{2
 }
 
 impl From<&str> for ASWType {
+    #[allow(clippy::wildcard_in_or_patterns)]
     fn from(index: &str) -> Self {
         match index {
             "1" => Self::Throwers,
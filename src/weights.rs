@@ -13,16 +13,18 @@ pub struct MiscWgts {
     pub on: u32,
     /// Extra weight above the deck.
     pub above: u32,
-    /// Extra displacement given to void space.
-    pub void: u32,
+    /// Extra displacement given to void space. Estimated from the bulges
+    /// when not set.
+    pub void: Option<u32>,
 }
 
 impl MiscWgts { // {{{2
     // wgt {{{3
-    /// Total of miscellaneous weights.
+    /// Total of miscellaneous weights, not counting an estimated void
+    /// weight.
     ///
     pub fn wgt(&self) -> u32 {
-        self.vital + self.hull + self.on + self.above + self.void
+        self.vital + self.hull + self.on + self.above + self.void.unwrap_or(0)
     }
 }
 
@@ -40,11 +42,11 @@ mod misc_wgts {
                 fn $name() {
                     let (expected, vital, hull, on, above, void) = $value;
                     let misc_wgts = MiscWgts {
-                        vital: vital,
-                        hull: hull,
-                        on: on,
-                        above: above,
-                        void: void,
+                        vital,
+                        hull,
+                        on,
+                        above,
+                        void,
                     };
 
                     assert!(expected == misc_wgts.wgt());
@@ -54,7 +56,8 @@ mod misc_wgts {
     }
     test_wgt! {
         // name: (wgt, vital, hull, on, above, void)
-        wgt_sum: (11_111, 1, 10, 100, 1_000, 10_000),
+        wgt_sum:     (11_111, 1, 10, 100, 1_000, Some(10_000)),
+        wgt_no_void: (1_111, 1, 10, 100, 1_000, None),
     }
 
 }
@@ -0,0 +1,59 @@
+// Public API tests {{{1
+//
+// Every type named here is part of the public interface promised by the
+// crate root and the prelude. A change that breaks this file needs a new
+// major version.
+
+use std::any::type_name;
+
+// prelude {{{2
+#[test]
+fn prelude() {
+    use sharpie::prelude::*;
+
+    let names = [
+        type_name::<Ship>(), type_name::<Template>(), type_name::<ShipKind>(), type_name::<FileFormat>(),
+        type_name::<ReportOptions>(), type_name::<ReportFormat>(), type_name::<ReportSection>(),
+        type_name::<DesignIssue>(), type_name::<Severity>(),
+        type_name::<Units>(), type_name::<Tons>(),
+        type_name::<Hull>(), type_name::<Armor>(), type_name::<Engine>(), type_name::<Battery>(), type_name::<Torpedoes>(),
+        type_name::<GunType>(), type_name::<MountType>(), type_name::<GunLayoutType>(), type_name::<GunDistributionType>(),
+        type_name::<FuelType>(), type_name::<BoilerType>(), type_name::<DriveType>(),
+        type_name::<ScaleOptions>(), type_name::<FitTargets>(), type_name::<Condition>(),
+    ];
+
+    assert!(names.iter().all(|n| n.starts_with("sharpie::")));
+
+    // The parts of a ship can be named through the prelude
+    let ship = Ship::from_template(Template::Dreadnought1910);
+    let _: &Hull = &ship.hull;
+    let _: &Battery = &ship.batteries[0];
+    let _: &MountType = &ship.batteries[0].mount_kind;
+    let _: &GunDistributionType = &ship.batteries[0].groups[0].distribution;
+}
+
+// root {{{2
+#[test]
+fn root() {
+    use sharpie::*;
+
+    let names = [
+        type_name::<Hull>(), type_name::<BowType>(), type_name::<SternType>(), type_name::<Hump>(),
+        type_name::<Armor>(), type_name::<ArmorPlate>(), type_name::<Belt>(), type_name::<BeltType>(),
+        type_name::<BulkheadType>(), type_name::<Citadel>(), type_name::<CT>(), type_name::<Deck>(),
+        type_name::<DeckType>(), type_name::<SplinterDeck>(),
+        type_name::<Engine>(), type_name::<Propulsion>(), type_name::<FuelType>(), type_name::<BoilerType>(), type_name::<DriveType>(),
+        type_name::<Battery>(), type_name::<SubBattery>(), type_name::<MountTiers>(), type_name::<Torpedoes>(),
+        type_name::<Mines>(), type_name::<ASW>(),
+        type_name::<GunType>(), type_name::<MountType>(), type_name::<GunLayoutType>(), type_name::<GunDistributionType>(),
+        type_name::<TrainingDrive>(), type_name::<AmmoType>(), type_name::<AmmoMix>(),
+        type_name::<TorpedoMountType>(), type_name::<MineType>(), type_name::<ASWType>(),
+        type_name::<MiscWgts>(), type_name::<CrewQuality>(), type_name::<CrewModel>(), type_name::<SeaState>(),
+        type_name::<StabilityStandard>(), type_name::<StabilityCriterion>(), type_name::<Notes>(),
+        type_name::<Superstructure>(), type_name::<SuperstructureKind>(),
+        type_name::<Ship>(), type_name::<ShipComputed>(), type_name::<FileIssues>(),
+        type_name::<Fleet>(), type_name::<Treaty>(), type_name::<Conversion>(), type_name::<Interchange>(),
+    ];
+
+    assert!(names.iter().all(|n| n.starts_with("sharpie::")));
+}
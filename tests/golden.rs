@@ -0,0 +1,95 @@
+// Golden report tests {{{1
+//
+// Each ship in tests/fixtures has a matching .json file holding the
+// expected output of Ship::report_json(). Set UPDATE_GOLDEN=1 to
+// regenerate the expected reports after an intentional change.
+
+use sharpie::Ship;
+
+use serde_json::Value;
+
+use std::env;
+use std::fs;
+
+const FIXTURES: &str = "tests/fixtures";
+
+// Relative tolerance when comparing numbers.
+const TOLERANCE: f64 = 1e-9;
+
+// compare {{{2
+// Compare two JSON values, allowing for floating point noise,
+// and return the path of the first difference found.
+fn compare(path: &str, expected: &Value, actual: &Value) -> Result<(), String> {
+    match (expected, actual) {
+        (Value::Number(e), Value::Number(a)) => {
+            let (e, a) = (e.as_f64().unwrap(), a.as_f64().unwrap());
+            let scale = e.abs().max(a.abs()).max(1.0);
+
+            if (e - a).abs() / scale > TOLERANCE {
+                return Err(format!("{}: expected {}, got {}", path, e, a));
+            }
+        },
+        (Value::Array(e), Value::Array(a)) => {
+            if e.len() != a.len() {
+                return Err(format!("{}: expected {} items, got {}", path, e.len(), a.len()));
+            }
+            for (i, (e, a)) in e.iter().zip(a.iter()).enumerate() {
+                compare(&format!("{}[{}]", path, i), e, a)?;
+            }
+        },
+        (Value::Object(e), Value::Object(a)) => {
+            for k in e.keys().chain(a.keys()) {
+                match (e.get(k), a.get(k)) {
+                    (Some(e), Some(a)) => compare(&format!("{}.{}", path, k), e, a)?,
+                    (None, _) => return Err(format!("{}.{}: unexpected field", path, k)),
+                    (_, None) => return Err(format!("{}.{}: missing field", path, k)),
+                }
+            }
+        },
+        (e, a) => {
+            if e != a {
+                return Err(format!("{}: expected {}, got {}", path, e, a));
+            }
+        },
+    }
+
+    Ok(())
+}
+
+// golden {{{2
+fn golden(name: &str) {
+    let ship = Ship::load(format!("{}/{}.ship", FIXTURES, name)).unwrap();
+    let report = ship.report_json().unwrap();
+
+    let golden = format!("{}/{}.json", FIXTURES, name);
+    if env::var("UPDATE_GOLDEN").is_ok() {
+        fs::write(&golden, format!("{}\n", report)).unwrap();
+    }
+
+    let expected: Value = serde_json::from_str(&fs::read_to_string(&golden).unwrap()).unwrap();
+    let actual: Value = serde_json::from_str(&report).unwrap();
+
+    if let Err(e) = compare(name, &expected, &actual) {
+        panic!("{}", e);
+    }
+}
+
+// Tests {{{2
+macro_rules! test_golden {
+    ($($name:ident,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                golden(stringify!($name));
+            }
+        )*
+    }
+}
+
+test_golden! {
+    central_battery_1870,
+    protected_cruiser_1895,
+    dreadnought_1915,
+    destroyer_1935,
+    aa_cruiser_1942,
+}